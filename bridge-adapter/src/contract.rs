@@ -0,0 +1,196 @@
+use crate::storage;
+use crate::errors::BridgeAdapterError;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, vec, Address, Bytes, Env, IntoVal, Symbol, Val};
+use token::Client as TokenClient;
+
+#[contract]
+pub struct BridgeAdapterContract;
+
+#[contractclient(name="BridgeAdapterClient")]
+pub trait BridgeAdapter {
+
+    /// Initialize the bridge adapter
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    /// * `treasury` - The Address of the treasury, which tracks the supply bridged out
+    /// * `token` - The Address of the stablecoin bridged by the adapter
+    /// * `relayer` - The Address of the relayer delivering messages from other chains
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, relayer: Address);
+
+    /// Lock stablecoins to be issued on another chain, recording them as bridged out with the treasury
+    ///
+    /// ### Arguments
+    /// * `from` - The Address locking the stablecoins
+    /// * `amount` - The amount to lock
+    /// * `chain` - The chain the stablecoins are issued on
+    /// * `recipient` - The encoded address receiving the stablecoins on the other chain
+    ///
+    /// ### Panics
+    /// If the amount is not positive
+    fn bridge_out(e: Env, from: Address, amount: i128, chain: Symbol, recipient: Bytes);
+
+    /// (Relayer only) Return stablecoins burnt on another chain, releasing the locked stablecoins and minting
+    /// the remainder through the treasury, under the adapter's minter caps
+    ///
+    /// Returns the amount minted
+    ///
+    /// ### Arguments
+    /// * `nonce` - The nonce of the relayed message
+    /// * `to` - The Address receiving the stablecoins
+    /// * `amount` - The amount returned
+    ///
+    /// ### Panics
+    /// If the caller is not the relayer, the amount is not positive, the message was already processed, or
+    /// the remainder exceeds the adapter's mint allowance
+    fn bridge_in(e: Env, nonce: u64, to: Address, amount: i128) -> i128;
+
+    /// (Admin only) Set a new relayer
+    ///
+    /// ### Arguments
+    /// * `relayer` - The Address of the relayer
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_relayer(e: Env, relayer: Address);
+
+    /// (Admin only) Set a new admin
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The Address for the admin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// Fetch the stablecoins locked for other chains
+    fn get_locked(e: Env) -> i128;
+
+    /// Check if a relayed message was processed
+    ///
+    /// ### Arguments
+    /// * `nonce` - The nonce of the message
+    fn is_processed(e: Env, nonce: u64) -> bool;
+
+    /// Fetch the relayer delivering messages from other chains
+    fn get_relayer(e: Env) -> Address;
+
+    /// Fetch the treasury tracking the supply bridged out
+    fn get_treasury(e: Env) -> Address;
+
+    /// Fetch the stablecoin bridged by the adapter
+    fn get_token(e: Env) -> Address;
+}
+
+fn require_positive(e: &Env, amount: i128) {
+    if amount <= 0 {
+        panic_with_error!(e, BridgeAdapterError::InvalidAmount);
+    }
+}
+
+#[contractimpl]
+impl BridgeAdapter for BridgeAdapterContract {
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, relayer: Address) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, BridgeAdapterError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_treasury(&e, &treasury);
+        storage::set_token(&e, &token);
+        storage::set_relayer(&e, &relayer);
+    }
+
+    fn bridge_out(e: Env, from: Address, amount: i128, chain: Symbol, recipient: Bytes) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        require_positive(&e, amount);
+
+        let token = storage::get_token(&e);
+        TokenClient::new(&e, &token).transfer(&from, &e.current_contract_address(), &amount);
+        storage::set_locked(&e, storage::get_locked(&e) + amount);
+
+        let record_args = vec![&e, token.into_val(&e), amount.into_val(&e)];
+        e.invoke_contract::<Val>(&storage::get_treasury(&e), &Symbol::new(&e, "record_bridge_out"), record_args);
+
+        e.events().publish(("BridgeAdapter", Symbol::new(&e, "bridge_out")), (from, amount, chain, recipient));
+    }
+
+    fn bridge_in(e: Env, nonce: u64, to: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_relayer(&e).require_auth();
+        require_positive(&e, amount);
+        if storage::is_processed(&e, nonce) {
+            panic_with_error!(&e, BridgeAdapterError::NonceUsedError);
+        }
+        storage::set_processed(&e, nonce);
+
+        let token = storage::get_token(&e);
+        let treasury = storage::get_treasury(&e);
+        let locked = storage::get_locked(&e);
+        let released = amount.min(locked);
+        if released > 0 {
+            storage::set_locked(&e, locked - released);
+            let record_args = vec![&e, token.into_val(&e), released.into_val(&e)];
+            e.invoke_contract::<Val>(&treasury, &Symbol::new(&e, "record_bridge_in"), record_args);
+            TokenClient::new(&e, &token).transfer(&e.current_contract_address(), &to, &released);
+        }
+        let minted = amount - released;
+        if minted > 0 {
+            let mint_args = vec![
+                &e,
+                e.current_contract_address().into_val(&e),
+                to.into_val(&e),
+                minted.into_val(&e),
+            ];
+            e.invoke_contract::<Val>(&treasury, &Symbol::new(&e, "mint"), mint_args);
+        }
+
+        e.events().publish(("BridgeAdapter", Symbol::new(&e, "bridge_in")), (nonce, to, released, minted));
+        minted
+    }
+
+    fn set_relayer(e: Env, relayer: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_relayer(&e, &relayer);
+
+        e.events().publish(("BridgeAdapter", Symbol::new(&e, "set_relayer")), relayer);
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+
+        e.events().publish(("BridgeAdapter", Symbol::new(&e, "set_admin")), new_admin);
+    }
+
+    fn get_locked(e: Env) -> i128 {
+        storage::get_locked(&e)
+    }
+
+    fn is_processed(e: Env, nonce: u64) -> bool {
+        storage::is_processed(&e, nonce)
+    }
+
+    fn get_relayer(e: Env) -> Address {
+        storage::get_relayer(&e)
+    }
+
+    fn get_treasury(e: Env) -> Address {
+        storage::get_treasury(&e)
+    }
+
+    fn get_token(e: Env) -> Address {
+        storage::get_token(&e)
+    }
+}
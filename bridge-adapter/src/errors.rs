@@ -0,0 +1,18 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BridgeAdapterError {
+    /// not yet initialized
+    NotInitialized = 2101,
+
+    /// already initialized
+    AlreadyInitializedError = 2102,
+
+    /// amount is zero or negative
+    InvalidAmount = 2103,
+
+    /// the relayed message was already processed
+    NonceUsedError = 2104,
+}
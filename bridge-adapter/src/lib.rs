@@ -0,0 +1,8 @@
+#![no_std]
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod storage;
+mod contract;
+mod errors;
+pub use contract::*;
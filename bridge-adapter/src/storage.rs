@@ -0,0 +1,160 @@
+use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TREASURY,
+    TOKEN,
+    RELAYER,
+    LOCKED,
+    NONCE(u64), // mapping nonce of a relayed message to whether it was processed
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the treasury tracking the supply bridged out
+///
+/// ### Panics
+/// If the treasury does not exist
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TREASURY)
+        .unwrap_optimized()
+}
+
+/// Set the treasury tracking the supply bridged out
+///
+/// ### Arguments
+/// * `treasury` - The Address of the treasury
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TREASURY, treasury);
+}
+
+/// Fetch the stablecoin bridged by the adapter
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the stablecoin bridged by the adapter
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the relayer delivering messages from other chains
+///
+/// ### Panics
+/// If the relayer does not exist
+pub fn get_relayer(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::RELAYER)
+        .unwrap_optimized()
+}
+
+/// Set the relayer delivering messages from other chains
+///
+/// ### Arguments
+/// * `relayer` - The Address of the relayer
+pub fn set_relayer(e: &Env, relayer: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RELAYER, relayer);
+}
+
+/// Fetch the stablecoins locked for other chains
+pub fn get_locked(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::LOCKED)
+        .unwrap_or(0)
+}
+
+/// Set the stablecoins locked for other chains
+///
+/// ### Arguments
+/// * `locked` - The amount locked
+pub fn set_locked(e: &Env, locked: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::LOCKED, &locked);
+}
+
+/// Check if a relayed message was processed
+///
+/// ### Arguments
+/// * `nonce` - The nonce of the message
+pub fn is_processed(e: &Env, nonce: u64) -> bool {
+    let key = DataKey::NONCE(nonce);
+    let processed = e.storage().persistent().has(&key);
+    if processed {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    processed
+}
+
+/// Mark a relayed message as processed
+///
+/// ### Arguments
+/// * `nonce` - The nonce of the message
+pub fn set_processed(e: &Env, nonce: u64) {
+    let key = DataKey::NONCE(nonce);
+    e.storage()
+        .persistent()
+        .set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
@@ -0,0 +1,360 @@
+use crate::helper::{self, SCALAR_7, SCALAR_9, SCALAR_BPS};
+use crate::storage::{self, CDPConfig, OracleConfig, Position, RateIndex};
+use crate::dependencies::oracle;
+use crate::errors::CDPError;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env, Symbol};
+use soroban_sdk::unwrap::UnwrapOptimized;
+use token::Client as TokenClient;
+
+#[contract]
+pub struct CDPContract;
+
+#[contractclient(name="CDPClient")]
+pub trait CDP {
+
+    /// Initialize the CDP contract
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    /// * `treasury` - The Address of the treasury, which must register the CDP contract as a minter
+    /// * `token` - The Address of the stablecoin
+    /// * `collateral` - The Address of the collateral asset, e.g. XLM
+    /// * `oracle_config` - The oracle config pricing the collateral
+    /// * `config` - The CDP config
+    ///
+    /// ### Panics
+    /// If the contract is already initialized or the config is invalid
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, collateral: Address, oracle_config: OracleConfig, config: CDPConfig);
+
+    /// Open a position, locking collateral and minting stablecoins against it
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    /// * `collateral` - The amount of collateral to lock
+    /// * `debt` - The amount of stablecoins to mint
+    ///
+    /// ### Panics
+    /// If the user already has a position or it would be below the minimum collateral ratio
+    fn open_position(e: Env, user: Address, collateral: i128, debt: i128);
+
+    /// Adjust a position's collateral and debt
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    /// * `collateral_delta` - The collateral to lock if positive, or to withdraw if negative
+    /// * `debt_delta` - The stablecoins to mint if positive, or to repay if negative
+    ///
+    /// ### Panics
+    /// If the user has no position or withdrawing or minting leaves it below the minimum collateral ratio
+    fn adjust_position(e: Env, user: Address, collateral_delta: i128, debt_delta: i128);
+
+    /// Close a position, repaying its debt and withdrawing its collateral
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    ///
+    /// Returns the collateral withdrawn
+    ///
+    /// ### Panics
+    /// If the user has no position
+    fn close_position(e: Env, user: Address) -> i128;
+
+    /// Liquidate a position below the liquidation threshold, repaying its debt in exchange for its
+    /// collateral plus the liquidation bonus
+    ///
+    /// ### Arguments
+    /// * `liquidator` - The Address of the liquidator
+    /// * `user` - The Address of the user being liquidated
+    /// * `debt_amount` - The amount of debt to repay
+    ///
+    /// Returns the collateral seized
+    ///
+    /// ### Panics
+    /// If the user has no position or the position is at or above the liquidation threshold
+    fn liquidate(e: Env, liquidator: Address, user: Address, debt_amount: i128) -> i128;
+
+    /// (Admin only) Set the CDP config
+    ///
+    /// ### Arguments
+    /// * `config` - The CDP config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the config is invalid
+    fn set_config(e: Env, config: CDPConfig);
+
+    /// (Admin only) Set the oracle config
+    ///
+    /// ### Arguments
+    /// * `config` - The oracle config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_oracle(e: Env, config: OracleConfig);
+
+    /// Fetch the CDP config
+    fn get_config(e: Env) -> CDPConfig;
+
+    /// Fetch a user's position, if any, with its debt as of its last accrual
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    fn get_position(e: Env, user: Address) -> Option<Position>;
+
+    /// Fetch the debt of a user's position, including stability fees
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    fn get_debt(e: Env, user: Address) -> i128;
+
+    /// Fetch the collateral ratio of a user's position, in basis points
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    fn get_collateral_ratio(e: Env, user: Address) -> i128;
+
+    /// (Admin only) Set the pauser, e.g. the protocol sentinel, allowed to pause and unpause the CDP besides the admin
+    ///
+    /// ### Arguments
+    /// * `pauser` - The Address of the pauser
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_pauser(e: Env, pauser: Address);
+
+    /// (Admin or pauser only) Pause the CDP, blocking position changes and liquidations
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the pauser
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the pauser
+    fn pause(e: Env, caller: Address);
+
+    /// (Admin or pauser only) Unpause the CDP
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the pauser
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the pauser
+    fn unpause(e: Env, caller: Address);
+
+    /// Check if the CDP is paused
+    fn is_paused(e: Env) -> bool;
+}
+
+fn require_valid_config(e: &Env, config: &CDPConfig) {
+    if config.liquidation_threshold_bps > config.min_collateral_ratio_bps
+        || i128::from(config.liquidation_threshold_bps) < SCALAR_BPS
+    {
+        panic_with_error!(e, CDPError::InvalidAmount);
+    }
+}
+
+fn get_position_or_panic(e: &Env, user: &Address) -> Position {
+    match storage::get_position(e, user) {
+        Some(position) => position,
+        None => panic_with_error!(e, CDPError::PositionNotFoundError),
+    }
+}
+
+#[contractimpl]
+impl CDP for CDPContract {
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, collateral: Address, oracle_config: OracleConfig, config: CDPConfig) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, CDPError::AlreadyInitializedError);
+        }
+        require_valid_config(&e, &config);
+
+        storage::set_admin(&e, &admin);
+        storage::set_treasury(&e, &treasury);
+        storage::set_token(&e, &token);
+        storage::set_collateral(&e, &collateral);
+        storage::set_oracle_config(&e, &oracle_config);
+        storage::set_config(&e, &config);
+        storage::set_rate_index(&e, &RateIndex { index: SCALAR_9, last_update: e.ledger().timestamp() });
+    }
+
+    fn open_position(e: Env, user: Address, collateral: i128, debt: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        user.require_auth();
+        if storage::get_position(&e, &user).is_some() {
+            panic_with_error!(&e, CDPError::PositionExistsError);
+        }
+        if collateral <= 0 || debt < 0 {
+            panic_with_error!(&e, CDPError::InvalidAmount);
+        }
+
+        let index = helper::accrue(&e);
+        let mut position = Position { collateral, debt: 0, principal: 0, index };
+        TokenClient::new(&e, &storage::get_collateral(&e)).transfer(&user, &e.current_contract_address(), &collateral);
+        if debt > 0 {
+            helper::borrow(&e, &mut position, &user, debt, index);
+            helper::require_healthy(&e, &position, index);
+        }
+        storage::set_position(&e, &user, &position);
+
+        e.events().publish(("CDP", Symbol::new(&e, "open_position")), (user, collateral, debt));
+    }
+
+    fn adjust_position(e: Env, user: Address, collateral_delta: i128, debt_delta: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        user.require_auth();
+        let mut position = get_position_or_panic(&e, &user);
+
+        let index = helper::accrue(&e);
+        if collateral_delta > 0 {
+            TokenClient::new(&e, &storage::get_collateral(&e)).transfer(&user, &e.current_contract_address(), &collateral_delta);
+            position.collateral += collateral_delta;
+        }
+        if debt_delta < 0 {
+            helper::repay(&e, &mut position, &user, -debt_delta, index);
+        }
+        if debt_delta > 0 {
+            helper::borrow(&e, &mut position, &user, debt_delta, index);
+        }
+        if collateral_delta < 0 {
+            if -collateral_delta > position.collateral {
+                panic_with_error!(&e, CDPError::InvalidAmount);
+            }
+            position.collateral += collateral_delta;
+            TokenClient::new(&e, &storage::get_collateral(&e)).transfer(&e.current_contract_address(), &user, &-collateral_delta);
+        }
+        if collateral_delta < 0 || debt_delta > 0 {
+            helper::require_healthy(&e, &position, index);
+        }
+        storage::set_position(&e, &user, &position);
+
+        e.events().publish(("CDP", Symbol::new(&e, "adjust_position")), (user, collateral_delta, debt_delta));
+    }
+
+    fn close_position(e: Env, user: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        user.require_auth();
+        let mut position = get_position_or_panic(&e, &user);
+
+        let index = helper::accrue(&e);
+        let debt = helper::calc_debt(&position, index);
+        helper::repay(&e, &mut position, &user, debt, index);
+        storage::del_position(&e, &user);
+        if position.collateral > 0 {
+            TokenClient::new(&e, &storage::get_collateral(&e)).transfer(&e.current_contract_address(), &user, &position.collateral);
+        }
+
+        e.events().publish(("CDP", Symbol::new(&e, "close_position")), (user, position.collateral, debt));
+        position.collateral
+    }
+
+    fn liquidate(e: Env, liquidator: Address, user: Address, debt_amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        liquidator.require_auth();
+        let mut position = get_position_or_panic(&e, &user);
+        if debt_amount <= 0 {
+            panic_with_error!(&e, CDPError::InvalidAmount);
+        }
+
+        let index = helper::accrue(&e);
+        let config = storage::get_config(&e);
+        let ratio = helper::calc_collateral_ratio(&e, position.collateral, helper::calc_debt(&position, index));
+        if ratio >= i128::from(config.liquidation_threshold_bps) {
+            panic_with_error!(&e, CDPError::HealthyPositionError);
+        }
+
+        let repaid = helper::repay(&e, &mut position, &liquidator, debt_amount, index);
+        let price = oracle::get_price(&e, &storage::get_oracle_config(&e), &storage::get_collateral(&e));
+        let seized = repaid
+            .fixed_mul_floor(SCALAR_BPS + i128::from(config.liquidation_bonus_bps), SCALAR_BPS)
+            .unwrap_optimized()
+            .fixed_div_floor(price, SCALAR_7)
+            .unwrap_optimized()
+            .min(position.collateral);
+        position.collateral -= seized;
+        storage::set_position(&e, &user, &position);
+        TokenClient::new(&e, &storage::get_collateral(&e)).transfer(&e.current_contract_address(), &liquidator, &seized);
+
+        e.events().publish(("CDP", Symbol::new(&e, "liquidate")), (liquidator, user, repaid, seized));
+        seized
+    }
+
+    fn set_config(e: Env, config: CDPConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        require_valid_config(&e, &config);
+        // accrue at the previous stability fee up to now
+        helper::accrue(&e);
+        storage::set_config(&e, &config);
+
+        e.events().publish(("CDP", Symbol::new(&e, "set_config")), config);
+    }
+
+    fn set_oracle(e: Env, config: OracleConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_oracle_config(&e, &config);
+
+        e.events().publish(("CDP", Symbol::new(&e, "set_oracle")), (config.oracle, config.max_age));
+    }
+
+    fn get_config(e: Env) -> CDPConfig {
+        storage::get_config(&e)
+    }
+
+    fn get_position(e: Env, user: Address) -> Option<Position> {
+        storage::get_position(&e, &user)
+    }
+
+    fn get_debt(e: Env, user: Address) -> i128 {
+        match storage::get_position(&e, &user) {
+            Some(position) => helper::calc_debt(&position, helper::calc_rate_index(&e).index),
+            None => 0,
+        }
+    }
+
+    fn get_collateral_ratio(e: Env, user: Address) -> i128 {
+        let position = get_position_or_panic(&e, &user);
+        let debt = helper::calc_debt(&position, helper::calc_rate_index(&e).index);
+        helper::calc_collateral_ratio(&e, position.collateral, debt)
+    }
+
+    fn set_pauser(e: Env, pauser: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_pauser(&e, &pauser);
+
+        e.events().publish(("CDP", Symbol::new(&e, "set_pauser")), pauser);
+    }
+
+    fn pause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_pauser(&e, &caller);
+
+        storage::set_paused(&e, true);
+
+        e.events().publish(("CDP", Symbol::new(&e, "pause")), caller);
+    }
+
+    fn unpause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_pauser(&e, &caller);
+
+        storage::set_paused(&e, false);
+
+        e.events().publish(("CDP", Symbol::new(&e, "unpause")), caller);
+    }
+
+    fn is_paused(e: Env) -> bool {
+        storage::is_paused(&e)
+    }
+}
@@ -0,0 +1,36 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CDPError {
+    /// not yet initialized
+    NotInitialized = 1401,
+
+    /// already initialized
+    AlreadyInitializedError = 1402,
+
+    /// amount is zero or negative, or a config value is out of range
+    InvalidAmount = 1403,
+
+    /// the user already has an open position
+    PositionExistsError = 1404,
+
+    /// the user has no open position
+    PositionNotFoundError = 1405,
+
+    /// the position would be below the minimum collateral ratio
+    UndercollateralizedError = 1406,
+
+    /// the position is above the liquidation threshold
+    HealthyPositionError = 1407,
+
+    /// the oracle price is missing or older than the max age
+    StaleOracleError = 1408,
+
+    /// the contract is paused
+    Paused = 1409,
+
+    /// the caller is neither the admin nor the pauser
+    UnauthorizedError = 1410,
+}
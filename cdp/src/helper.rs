@@ -0,0 +1,198 @@
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{panic_with_error, token, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+use crate::dependencies::oracle;
+use crate::errors::CDPError;
+use crate::storage::{self, Position, RateIndex};
+
+pub(crate) const SCALAR_7: i128 = 1_0000000;
+pub(crate) const SCALAR_9: i128 = 1_000_000_000;
+pub(crate) const SCALAR_BPS: i128 = 10_000;
+pub(crate) const SECONDS_PER_YEAR: i128 = 31_536_000;
+
+/// Calculate the stability fee rate index as of the current ledger
+pub fn calc_rate_index(e: &Env) -> RateIndex {
+    let mut rate_index = storage::get_rate_index(e);
+    let now = e.ledger().timestamp();
+    if now > rate_index.last_update {
+        let elapsed = i128::from(now - rate_index.last_update);
+        let fee_bps = i128::from(storage::get_config(e).stability_fee_bps);
+        let accrued = rate_index.index
+            .fixed_mul_ceil(fee_bps * elapsed, SCALAR_BPS * SECONDS_PER_YEAR)
+            .unwrap_optimized();
+        rate_index.index += accrued;
+        rate_index.last_update = now;
+    }
+    rate_index
+}
+
+/// Accrue the stability fee and store the updated rate index
+///
+/// Returns the rate index
+pub fn accrue(e: &Env) -> i128 {
+    let rate_index = calc_rate_index(e);
+    storage::set_rate_index(e, &rate_index);
+    rate_index.index
+}
+
+/// Calculate the debt of a position, including stability fees
+///
+/// ### Arguments
+/// * `position` - The position
+/// * `index` - The stability fee rate index
+pub fn calc_debt(position: &Position, index: i128) -> i128 {
+    if position.debt == 0 || index == position.index {
+        return position.debt;
+    }
+    position.debt.fixed_mul_ceil(index, position.index).unwrap_optimized()
+}
+
+/// Accrue the stability fees of a position up to the rate index
+///
+/// ### Arguments
+/// * `position` - The position
+/// * `index` - The stability fee rate index
+pub fn accrue_position(position: &mut Position, index: i128) {
+    position.debt = calc_debt(position, index);
+    position.index = index;
+}
+
+/// Calculate the collateral ratio of a position, in basis points
+///
+/// ### Arguments
+/// * `collateral` - The collateral locked in the position
+/// * `debt` - The debt of the position
+pub fn calc_collateral_ratio(e: &Env, collateral: i128, debt: i128) -> i128 {
+    if debt <= 0 {
+        return i128::MAX;
+    }
+    let price = oracle::get_price(e, &storage::get_oracle_config(e), &storage::get_collateral(e));
+    let value = collateral.fixed_mul_floor(price, SCALAR_7).unwrap_optimized();
+    value.fixed_div_floor(debt, SCALAR_BPS).unwrap_optimized()
+}
+
+/// Require a position to be at or above the minimum collateral ratio
+///
+/// ### Arguments
+/// * `position` - The position
+/// * `index` - The stability fee rate index
+///
+/// ### Panics
+/// If the position is below the minimum collateral ratio
+pub fn require_healthy(e: &Env, position: &Position, index: i128) {
+    let min_ratio = i128::from(storage::get_config(e).min_collateral_ratio_bps);
+    if calc_collateral_ratio(e, position.collateral, calc_debt(position, index)) < min_ratio {
+        panic_with_error!(e, CDPError::UndercollateralizedError);
+    }
+}
+
+/// Mint stablecoins against a position through the treasury
+///
+/// ### Arguments
+/// * `position` - The position borrowing
+/// * `to` - The Address receiving the stablecoins
+/// * `amount` - The amount to borrow
+/// * `index` - The stability fee rate index
+pub fn borrow(e: &Env, position: &mut Position, to: &Address, amount: i128, index: i128) {
+    accrue_position(position, index);
+    position.debt += amount;
+    position.principal += amount;
+    let args: Vec<Val> = vec![
+        e,
+        e.current_contract_address().into_val(e),
+        to.into_val(e),
+        amount.into_val(e),
+    ];
+    e.invoke_contract::<()>(&storage::get_treasury(e), &Symbol::new(e, "mint"), args);
+}
+
+/// Repay the debt of a position
+///
+/// Stability fees are paid first and deposited into the treasury surplus buffer, the
+/// remainder repays the principal and is burnt through the treasury.
+///
+/// ### Arguments
+/// * `position` - The position being repaid
+/// * `from` - The Address repaying
+/// * `amount` - The amount to repay, capped at the debt of the position
+/// * `index` - The stability fee rate index
+///
+/// Returns the amount repaid
+pub fn repay(e: &Env, position: &mut Position, from: &Address, amount: i128, index: i128) -> i128 {
+    accrue_position(position, index);
+    let debt = position.debt;
+    let amount = amount.min(debt);
+    if amount <= 0 {
+        return 0;
+    }
+    let token = storage::get_token(e);
+    let treasury = storage::get_treasury(e);
+    token::Client::new(e, &token).transfer(from, &e.current_contract_address(), &amount);
+
+    let fees_paid = amount.min((debt - position.principal).max(0));
+    let principal_paid = amount - fees_paid;
+    position.debt -= amount;
+    position.principal -= principal_paid;
+
+    if principal_paid > 0 {
+        authorize_token_fn(e, &token, "burn", vec![e, e.current_contract_address().into_val(e), principal_paid.into_val(e)]);
+        let args: Vec<Val> = vec![
+            e,
+            e.current_contract_address().into_val(e),
+            principal_paid.into_val(e),
+        ];
+        e.invoke_contract::<()>(&treasury, &Symbol::new(e, "burn"), args);
+    }
+    if fees_paid > 0 {
+        authorize_token_fn(e, &token, "transfer", vec![e, e.current_contract_address().into_val(e), treasury.into_val(e), fees_paid.into_val(e)]);
+        let args: Vec<Val> = vec![
+            e,
+            e.current_contract_address().into_val(e),
+            token.into_val(e),
+            fees_paid.into_val(e),
+        ];
+        e.invoke_contract::<()>(&treasury, &Symbol::new(e, "deposit_surplus"), args);
+    }
+    amount
+}
+
+/// Authorize a token function invoked by the treasury on behalf of the CDP contract
+fn authorize_token_fn(e: &Env, token: &Address, fn_name: &str, args: Vec<Val>) {
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(e, fn_name),
+                args,
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+}
+
+/// Require the contract not to be paused
+///
+/// ### Panics
+/// If the contract is paused
+pub fn require_not_paused(e: &Env) {
+    if storage::is_paused(e) {
+        panic_with_error!(e, CDPError::Paused);
+    }
+}
+
+/// Require the caller to be the admin or the pauser, and to authorize the call
+///
+/// ### Arguments
+/// * `caller` - The Address of the caller
+///
+/// ### Panics
+/// If the caller is neither the admin nor the pauser
+pub fn require_pauser(e: &Env, caller: &Address) {
+    caller.require_auth();
+    if *caller != storage::get_admin(e) && Some(caller.clone()) != storage::get_pauser(e) {
+        panic_with_error!(e, CDPError::UnauthorizedError);
+    }
+}
@@ -0,0 +1,294 @@
+use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TREASURY,
+    TOKEN,
+    COLLATERAL,
+    CONFIG,
+    ORACLE,
+    RATE,
+    POSITION(Address), // mapping user address to their position
+    PAUSER,
+    PAUSED,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct CDPConfig {
+    pub min_collateral_ratio_bps: u32,  // minimum collateral ratio to open or adjust a position, in basis points
+    pub liquidation_threshold_bps: u32, // collateral ratio below which a position can be liquidated, in basis points
+    pub liquidation_bonus_bps: u32,     // bonus collateral paid to liquidators, in basis points
+    pub stability_fee_bps: u32,         // yearly fee charged on debt, in basis points
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OracleConfig {
+    pub oracle: Address, // SEP-40 oracle reporting USD prices
+    pub max_age: u64,    // maximum age of a price, in seconds
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct RateIndex {
+    pub index: i128,      // accumulated stability fee index, in 9 decimals
+    pub last_update: u64, // timestamp the index was last updated at
+}
+
+/// A user's position, packed under a single persistent key so each operation reads and writes
+/// one ledger entry per user
+///
+/// Stability fees accrue lazily: the debt is stored as of the rate index it was last accrued at,
+/// and is only brought up to date when the position is touched.
+#[derive(Clone)]
+#[contracttype]
+pub struct Position {
+    pub collateral: i128, // collateral locked in the position
+    pub debt: i128,       // debt as of the last accrual, including stability fees
+    pub principal: i128,  // stablecoins minted and not yet repaid, excluding stability fees
+    pub index: i128,      // rate index the debt was last accrued at, in 9 decimals
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the treasury minting and burning the stablecoin
+///
+/// ### Panics
+/// If the treasury does not exist
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TREASURY)
+        .unwrap_optimized()
+}
+
+/// Set the treasury minting and burning the stablecoin
+///
+/// ### Arguments
+/// * `treasury` - The Address of the treasury
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TREASURY, treasury);
+}
+
+/// Fetch the stablecoin
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the stablecoin
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the collateral asset
+///
+/// ### Panics
+/// If the collateral asset does not exist
+pub fn get_collateral(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::COLLATERAL)
+        .unwrap_optimized()
+}
+
+/// Set the collateral asset
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral asset
+pub fn set_collateral(e: &Env, collateral: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::COLLATERAL, collateral);
+}
+
+/// Fetch the CDP config
+///
+/// ### Panics
+/// If the config does not exist
+pub fn get_config(e: &Env) -> CDPConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::CONFIG)
+        .unwrap_optimized()
+}
+
+/// Set the CDP config
+///
+/// ### Arguments
+/// * `config` - The CDP config
+pub fn set_config(e: &Env, config: &CDPConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::CONFIG, config);
+}
+
+/// Fetch the oracle config
+///
+/// ### Panics
+/// If the oracle config does not exist
+pub fn get_oracle_config(e: &Env) -> OracleConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::ORACLE)
+        .unwrap_optimized()
+}
+
+/// Set the oracle config
+///
+/// ### Arguments
+/// * `config` - The oracle config
+pub fn set_oracle_config(e: &Env, config: &OracleConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ORACLE, config);
+}
+
+/// Fetch the stability fee rate index
+///
+/// ### Panics
+/// If the rate index does not exist
+pub fn get_rate_index(e: &Env) -> RateIndex {
+    e.storage()
+        .instance()
+        .get(&DataKey::RATE)
+        .unwrap_optimized()
+}
+
+/// Set the stability fee rate index
+///
+/// ### Arguments
+/// * `rate_index` - The rate index
+pub fn set_rate_index(e: &Env, rate_index: &RateIndex) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RATE, rate_index);
+}
+
+/// Fetch a user's position, if any
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+pub fn get_position(e: &Env, user: &Address) -> Option<Position> {
+    let key = DataKey::POSITION(user.clone());
+    let position = e.storage().persistent().get::<DataKey, Position>(&key);
+    if position.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    position
+}
+
+/// Set a user's position
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+/// * `position` - The user's position
+pub fn set_position(e: &Env, user: &Address, position: &Position) {
+    let key = DataKey::POSITION(user.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, Position>(&key, position);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove a user's position
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+pub fn del_position(e: &Env, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::POSITION(user.clone()));
+}
+
+/// Fetch the pauser, e.g. the protocol sentinel, if any
+pub fn get_pauser(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PAUSER)
+}
+
+/// Set the pauser
+///
+/// ### Arguments
+/// * `pauser` - The Address allowed to pause and unpause the contract besides the admin
+pub fn set_pauser(e: &Env, pauser: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PAUSER, pauser);
+}
+
+/// Check if the contract is paused
+pub fn is_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::PAUSED)
+        .unwrap_or(false)
+}
+
+/// Set whether the contract is paused
+///
+/// ### Arguments
+/// * `paused` - Whether the contract is paused
+pub fn set_paused(e: &Env, paused: bool) {
+    if paused {
+        e.storage().instance().set::<DataKey, bool>(&DataKey::PAUSED, &true);
+    } else {
+        e.storage().instance().remove(&DataKey::PAUSED);
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use keeper::error::{KeeperError, Result};
+use keeper::tx::{self, Submitter};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    ContractExecutable, ContractIdPreimage, ContractIdPreimageFromAddress, CreateContractArgs, Hash, HostFunction,
+    ScVal, Uint256,
+};
+
+/// Print a decoded contract result as JSON
+fn print(label: &str, value: &ScVal) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|err| KeeperError::Rpc(err.to_string()))?;
+    println!("{label}: {json}");
+    Ok(())
+}
+
+/// Upload a contract wasm and deploy an instance of it, printing the contract address
+///
+/// Returns the address of the deployed contract
+///
+/// ### Arguments
+/// * `wasm` - The path of the contract wasm
+/// * `salt` - The hex encoded salt of the contract address, random if not set
+pub fn deploy(submitter: &Submitter, wasm: &Path, salt: Option<&str>) -> Result<String> {
+    let code = fs::read(wasm).map_err(|err| KeeperError::Config(format!("{}: {err}", wasm.display())))?;
+    let wasm_hash = match submitter.submit_host_function(HostFunction::UploadContractWasm(code.try_into()?))? {
+        ScVal::Bytes(hash) => Hash(hash.to_vec().try_into().map_err(|_| KeeperError::Rpc("invalid wasm hash".to_string()))?),
+        _ => return Err(KeeperError::Rpc("expected a wasm hash".to_string())),
+    };
+    println!("wasm hash: {}", hex::encode(wasm_hash.0));
+
+    let salt = match salt {
+        Some(salt) => hex::decode(salt)
+            .ok()
+            .and_then(|salt| <[u8; 32]>::try_from(salt).ok())
+            .ok_or_else(|| KeeperError::Config("salt must be 32 hex encoded bytes".to_string()))?,
+        None => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            Sha256::digest([wasm_hash.0.as_slice(), &now.as_nanos().to_be_bytes()].concat()).into()
+        }
+    };
+    let create = HostFunction::CreateContract(CreateContractArgs {
+        contract_id_preimage: ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+            address: submitter.address(),
+            salt: Uint256(salt),
+        }),
+        executable: ContractExecutable::Wasm(wasm_hash),
+    });
+    match submitter.submit_host_function(create)? {
+        ScVal::Address(contract) => {
+            println!("contract: {contract}");
+            Ok(contract.to_string())
+        }
+        _ => Err(KeeperError::Rpc("expected a contract address".to_string())),
+    }
+}
+
+/// Set the default flash loan fee of the treasury. The signer must hold the fee manager role.
+///
+/// ### Arguments
+/// * `treasury` - The address of the treasury
+/// * `fee_bps` - The fee charged on the borrowed amount, in basis points
+pub fn set_fee(submitter: &Submitter, treasury: &str, fee_bps: u32) -> Result<()> {
+    let args = vec![ScVal::Address(submitter.address()), ScVal::U32(fee_bps)];
+    submitter.submit(&tx::contract_address(treasury)?, "set_fee_bps", args)?;
+    println!("flash loan fee set to {fee_bps} bps");
+    Ok(())
+}
+
+/// Print the config of the treasury, its lifetime counters and, for each stablecoin, its
+/// position across blend pools
+///
+/// ### Arguments
+/// * `treasury` - The address of the treasury
+/// * `tokens` - The addresses of the stablecoins
+pub fn status(submitter: &Submitter, treasury: &str, tokens: &[String]) -> Result<()> {
+    let treasury = tx::contract_address(treasury)?;
+    print("config", &submitter.simulate(&treasury, "get_config", vec![])?)?;
+    print("stats", &submitter.simulate(&treasury, "get_stats", vec![])?)?;
+    for token in tokens {
+        let args = vec![ScVal::Address(tx::contract_address(token)?)];
+        print(&format!("position {token}"), &submitter.simulate(&treasury, "get_total_position", args)?)?;
+    }
+    Ok(())
+}
+
+/// Print the quote of a flash loan to a receiver, without executing it
+///
+/// ### Arguments
+/// * `treasury` - The address of the treasury
+/// * `receiver` - The address of the flash loan receiver
+/// * `amount` - The amount of the flash loan
+pub fn simulate_flash_loan(submitter: &Submitter, treasury: &str, receiver: &str, amount: i128) -> Result<()> {
+    let args = vec![ScVal::Address(tx::address(receiver)?), ScVal::from(amount)];
+    print("quote", &submitter.simulate(&tx::contract_address(treasury)?, "simulate_flash_loan", args)?)
+}
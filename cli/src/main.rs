@@ -0,0 +1,140 @@
+//! The `orbit` command line tool for deploying and operating the Orbit protocol
+//!
+//! Commands build, sign and submit transactions through a soroban RPC endpoint, signing with the
+//! secret key of the operator account. Read-only commands only simulate transactions. Contract
+//! addresses default to the network's deployment in the deployments file.
+
+mod commands;
+mod network;
+
+use std::path::PathBuf;
+use std::process;
+
+use clap::{Parser, Subcommand};
+use keeper::deployment::Deployments;
+use keeper::error::{KeeperError, Result};
+use keeper::rpc::RpcClient;
+use keeper::tx::Submitter;
+use network::Network;
+
+#[derive(Parser)]
+#[command(name = "orbit", about = "Deploy and operate the Orbit protocol")]
+struct Cli {
+    /// The network to target
+    #[arg(long, value_enum, default_value = "testnet", global = true)]
+    network: Network,
+    /// The deployments file recording the contracts deployed on each network
+    #[arg(long, env = "ORBIT_DEPLOYMENTS", default_value = "deployments.toml", global = true)]
+    deployments: PathBuf,
+    /// The RPC endpoint, required for mainnet unless the deployments file sets one
+    #[arg(long, env = "ORBIT_RPC_URL", global = true)]
+    rpc_url: Option<String>,
+    /// The secret key of the operator account, e.g. S...
+    #[arg(long, env = "ORBIT_SECRET_KEY", hide_env_values = true, global = true)]
+    secret_key: String,
+    /// The inclusion fee of a transaction, in stroops
+    #[arg(long, default_value_t = 100, global = true)]
+    base_fee: u32,
+    /// The maximum fee a fee bump can raise a transaction to, in stroops
+    #[arg(long, default_value_t = 10_000_000, global = true)]
+    max_fee: i64,
+    /// The attempts to land a transaction before giving up
+    #[arg(long, default_value_t = 5, global = true)]
+    max_retries: u32,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Upload a contract wasm and deploy an instance of it
+    Deploy {
+        /// The path of the contract wasm
+        wasm: PathBuf,
+        /// The hex encoded 32 byte salt of the contract address, random if not set
+        #[arg(long)]
+        salt: Option<String>,
+        /// The name of the contract to record the address as in the deployments file, e.g. treasury
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Set the default flash loan fee of the treasury
+    SetFee {
+        /// The address of the treasury, defaults to the deployed treasury
+        #[arg(long, env = "ORBIT_TREASURY")]
+        treasury: Option<String>,
+        /// The fee charged on the borrowed amount, in basis points
+        fee_bps: u32,
+    },
+    /// Print the state of the treasury
+    Status {
+        /// The address of the treasury, defaults to the deployed treasury
+        #[arg(long, env = "ORBIT_TREASURY")]
+        treasury: Option<String>,
+        /// The stablecoins whose positions are printed, as addresses or deployed token symbols
+        #[arg(long = "token")]
+        tokens: Vec<String>,
+    },
+    /// Quote a flash loan to a receiver without executing it
+    SimulateFlashloan {
+        /// The address of the treasury, defaults to the deployed treasury
+        #[arg(long, env = "ORBIT_TREASURY")]
+        treasury: Option<String>,
+        /// The address of the flash loan receiver
+        receiver: String,
+        /// The amount of the flash loan
+        amount: i128,
+    },
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let mut deployments = Deployments::load(&cli.deployments)?;
+    let deployment = deployments.0.get(cli.network.name()).cloned().unwrap_or_default();
+    let treasury = |treasury: Option<String>| {
+        treasury
+            .or_else(|| deployment.contracts.treasury.clone())
+            .ok_or_else(|| KeeperError::Config("--treasury is required, no treasury is deployed".to_string()))
+    };
+
+    let rpc_url = cli
+        .rpc_url
+        .clone()
+        .or_else(|| deployment.rpc_url.clone())
+        .or(cli.network.default_rpc_url().map(str::to_string))
+        .ok_or_else(|| KeeperError::Config("--rpc-url is required for this network".to_string()))?;
+    let submitter = Submitter::new(
+        RpcClient::new(&rpc_url),
+        &cli.secret_key,
+        cli.network.passphrase(),
+        cli.base_fee,
+        cli.max_fee,
+        cli.max_retries,
+    )?;
+    match cli.command {
+        Command::Deploy { wasm, salt, name } => {
+            let contract = commands::deploy(&submitter, &wasm, salt.as_deref())?;
+            if let Some(name) = name {
+                deployments.network_mut(cli.network.name()).contracts.set(&name, contract)?;
+                deployments.save(&cli.deployments)?;
+                println!("recorded as {name} in {}", cli.deployments.display());
+            }
+            Ok(())
+        }
+        Command::SetFee { treasury: address, fee_bps } => commands::set_fee(&submitter, &treasury(address)?, fee_bps),
+        Command::Status { treasury: address, tokens } => {
+            let tokens: Vec<String> =
+                tokens.into_iter().map(|token| deployment.tokens.get(&token).cloned().unwrap_or(token)).collect();
+            commands::status(&submitter, &treasury(address)?, &tokens)
+        }
+        Command::SimulateFlashloan { treasury: address, receiver, amount } => {
+            commands::simulate_flash_loan(&submitter, &treasury(address)?, &receiver, amount)
+        }
+    }
+}
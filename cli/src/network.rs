@@ -0,0 +1,33 @@
+use clap::ValueEnum;
+
+/// A stellar network the CLI can target
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Network {
+    Testnet,
+    Mainnet,
+}
+
+impl Network {
+    /// The name of the network in the deployments file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Testnet => "testnet",
+            Network::Mainnet => "mainnet",
+        }
+    }
+
+    pub fn passphrase(&self) -> &'static str {
+        match self {
+            Network::Testnet => "Test SDF Network ; September 2015",
+            Network::Mainnet => "Public Global Stellar Network ; September 2015",
+        }
+    }
+
+    /// The public RPC endpoint of the network, if one exists. Mainnet requires a provider's endpoint.
+    pub fn default_rpc_url(&self) -> Option<&'static str> {
+        match self {
+            Network::Testnet => Some("https://soroban-testnet.stellar.org"),
+            Network::Mainnet => None,
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::storage::{self, Auction, Lot};
+use crate::errors::AuctionError;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, vec, Address, Env, IntoVal, Symbol, Val};
+use soroban_sdk::unwrap::UnwrapOptimized;
+use token::Client as TokenClient;
+
+const SCALAR_7: i128 = 1_0000000;
+
+#[contract]
+pub struct CollateralAuctionContract;
+
+#[contractclient(name="CollateralAuctionClient")]
+pub trait CollateralAuction {
+
+    /// Initialize the auction house
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    /// * `treasury` - The Address of the treasury, receiving the proceeds into its surplus buffer
+    /// * `token` - The Address of the stablecoin lots are paid in
+    /// * `duration` - The number of ledgers the price of an auction declines over
+    ///
+    /// ### Panics
+    /// If the contract is already initialized or the duration is zero
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, duration: u32);
+
+    /// (Admin only) Allow or disallow an address, e.g. the pegkeeper, to start auctions
+    ///
+    /// ### Arguments
+    /// * `seller` - The Address of the seller
+    /// * `enabled` - Whether the seller can start auctions
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_seller(e: Env, seller: Address, enabled: bool);
+
+    /// (Admin only) Set the number of ledgers the price of new auctions declines over
+    ///
+    /// ### Arguments
+    /// * `duration` - The number of ledgers
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the duration is zero
+    fn set_duration(e: Env, duration: u32);
+
+    /// (Seller only) Start a Dutch auction of a lot, transferring the lot to the auction house. The
+    /// price declines linearly from the lot's start price to its end price over the auction duration,
+    /// then stays at the end price until the lot is taken or the auction is cancelled.
+    ///
+    /// Returns the id of the auction
+    ///
+    /// ### Arguments
+    /// * `seller` - The Address of the seller
+    /// * `lot` - The collateral sold and its start and end prices
+    ///
+    /// ### Panics
+    /// If the seller is not allowed, the amount or prices are not positive, or the end price is above the start price
+    fn start_auction(e: Env, seller: Address, lot: Lot) -> u32;
+
+    /// Buy part or all of an auction's lot at the current price, paying the proceeds into the
+    /// treasury's surplus buffer
+    ///
+    /// Returns the amount of the stablecoin paid
+    ///
+    /// ### Arguments
+    /// * `taker` - The Address buying the collateral
+    /// * `auction_id` - The id of the auction
+    /// * `amount` - The amount of the collateral to buy
+    /// * `max_price` - The highest price the taker accepts, in 7 decimals
+    ///
+    /// ### Panics
+    /// If the auction does not exist, the amount is not positive or exceeds the lot, or the price is above `max_price`
+    fn take(e: Env, taker: Address, auction_id: u32, amount: i128, max_price: i128) -> i128;
+
+    /// (Seller or admin only) Cancel an auction, returning the rest of the lot to the seller
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the auction's seller or the admin
+    /// * `auction_id` - The id of the auction
+    ///
+    /// ### Panics
+    /// If the auction does not exist or the caller is neither its seller nor the admin
+    fn cancel_auction(e: Env, caller: Address, auction_id: u32);
+
+    /// (Admin only) Set a new admin
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The Address for the admin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// Fetch an auction
+    ///
+    /// ### Arguments
+    /// * `auction_id` - The id of the auction
+    ///
+    /// ### Panics
+    /// If the auction does not exist
+    fn get_auction(e: Env, auction_id: u32) -> Auction;
+
+    /// Fetch the current price of an auction's collateral in the stablecoin, in 7 decimals
+    ///
+    /// ### Arguments
+    /// * `auction_id` - The id of the auction
+    ///
+    /// ### Panics
+    /// If the auction does not exist
+    fn get_price(e: Env, auction_id: u32) -> i128;
+
+    /// Check if an address can start auctions
+    ///
+    /// ### Arguments
+    /// * `seller` - The Address of the seller
+    fn is_seller(e: Env, seller: Address) -> bool;
+}
+
+fn require_auction(e: &Env, auction_id: u32) -> Auction {
+    match storage::get_auction(e, auction_id) {
+        Some(auction) => auction,
+        None => panic_with_error!(e, AuctionError::AuctionNotFound),
+    }
+}
+
+/// Calculate the price of an auction at the current ledger, declining linearly from the start price
+/// to the end price over the auction's duration
+fn calc_price(e: &Env, auction: &Auction) -> i128 {
+    let elapsed = e.ledger().sequence().saturating_sub(auction.start_ledger);
+    if elapsed >= auction.duration {
+        return auction.lot.end_price;
+    }
+    let decline = (auction.lot.start_price - auction.lot.end_price)
+        .fixed_mul_floor(i128::from(elapsed), i128::from(auction.duration))
+        .unwrap_optimized();
+    auction.lot.start_price - decline
+}
+
+#[contractimpl]
+impl CollateralAuction for CollateralAuctionContract {
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, duration: u32) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, AuctionError::AlreadyInitializedError);
+        }
+        if duration == 0 {
+            panic_with_error!(&e, AuctionError::InvalidAmount);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_treasury(&e, &treasury);
+        storage::set_token(&e, &token);
+        storage::set_duration(&e, duration);
+    }
+
+    fn set_seller(e: Env, seller: Address, enabled: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_seller(&e, &seller, enabled);
+
+        e.events().publish(("CollateralAuction", Symbol::new(&e, "set_seller")), (seller, enabled));
+    }
+
+    fn set_duration(e: Env, duration: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if duration == 0 {
+            panic_with_error!(&e, AuctionError::InvalidAmount);
+        }
+
+        storage::set_duration(&e, duration);
+
+        e.events().publish(("CollateralAuction", Symbol::new(&e, "set_duration")), duration);
+    }
+
+    fn start_auction(e: Env, seller: Address, lot: Lot) -> u32 {
+        storage::extend_instance(&e);
+        seller.require_auth();
+        if !storage::is_seller(&e, &seller) {
+            panic_with_error!(&e, AuctionError::UnauthorizedError);
+        }
+        if lot.amount <= 0 || lot.end_price <= 0 || lot.end_price > lot.start_price {
+            panic_with_error!(&e, AuctionError::InvalidAmount);
+        }
+
+        TokenClient::new(&e, &lot.asset).transfer(&seller, &e.current_contract_address(), &lot.amount);
+
+        let auction_id = storage::next_auction_id(&e);
+        let auction = Auction {
+            seller: seller.clone(),
+            lot: lot.clone(),
+            start_ledger: e.ledger().sequence(),
+            duration: storage::get_duration(&e),
+        };
+        storage::set_auction(&e, auction_id, &auction);
+
+        e.events().publish(("CollateralAuction", Symbol::new(&e, "start_auction")), (auction_id, seller, lot.asset, lot.amount));
+        auction_id
+    }
+
+    fn take(e: Env, taker: Address, auction_id: u32, amount: i128, max_price: i128) -> i128 {
+        storage::extend_instance(&e);
+        taker.require_auth();
+
+        let mut auction = require_auction(&e, auction_id);
+        if amount <= 0 || amount > auction.lot.amount {
+            panic_with_error!(&e, AuctionError::InvalidAmount);
+        }
+        let price = calc_price(&e, &auction);
+        if price > max_price {
+            panic_with_error!(&e, AuctionError::PriceExceeded);
+        }
+        let cost = amount.fixed_mul_ceil(price, SCALAR_7).unwrap_optimized();
+
+        let deposit_args = vec![
+            &e,
+            taker.into_val(&e),
+            storage::get_token(&e).into_val(&e),
+            cost.into_val(&e),
+        ];
+        e.invoke_contract::<Val>(&storage::get_treasury(&e), &Symbol::new(&e, "deposit_surplus"), deposit_args);
+        TokenClient::new(&e, &auction.lot.asset).transfer(&e.current_contract_address(), &taker, &amount);
+
+        auction.lot.amount -= amount;
+        if auction.lot.amount == 0 {
+            storage::del_auction(&e, auction_id);
+        } else {
+            storage::set_auction(&e, auction_id, &auction);
+        }
+
+        e.events().publish(("CollateralAuction", Symbol::new(&e, "take")), (auction_id, taker, amount, cost));
+        cost
+    }
+
+    fn cancel_auction(e: Env, caller: Address, auction_id: u32) {
+        storage::extend_instance(&e);
+        caller.require_auth();
+        let auction = require_auction(&e, auction_id);
+        if caller != auction.seller && caller != storage::get_admin(&e) {
+            panic_with_error!(&e, AuctionError::UnauthorizedError);
+        }
+
+        TokenClient::new(&e, &auction.lot.asset).transfer(&e.current_contract_address(), &auction.seller, &auction.lot.amount);
+        storage::del_auction(&e, auction_id);
+
+        e.events().publish(("CollateralAuction", Symbol::new(&e, "cancel_auction")), (auction_id, auction.lot.amount));
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+
+        e.events().publish(("CollateralAuction", Symbol::new(&e, "set_admin")), new_admin);
+    }
+
+    fn get_auction(e: Env, auction_id: u32) -> Auction {
+        require_auction(&e, auction_id)
+    }
+
+    fn get_price(e: Env, auction_id: u32) -> i128 {
+        calc_price(&e, &require_auction(&e, auction_id))
+    }
+
+    fn is_seller(e: Env, seller: Address) -> bool {
+        storage::is_seller(&e, &seller)
+    }
+}
@@ -0,0 +1,24 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    /// not yet initialized
+    NotInitialized = 1901,
+
+    /// already initialized
+    AlreadyInitializedError = 1902,
+
+    /// amount or price is zero or negative, the lot is exceeded, or the end price is above the start price
+    InvalidAmount = 1903,
+
+    /// the auction does not exist or has been fully taken
+    AuctionNotFound = 1904,
+
+    /// the current price is above the taker's maximum price
+    PriceExceeded = 1905,
+
+    /// the caller is not an allowed seller, or neither the seller nor the admin of the auction
+    UnauthorizedError = 1906,
+}
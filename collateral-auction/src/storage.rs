@@ -0,0 +1,211 @@
+use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TREASURY,
+    TOKEN,
+    DURATION,
+    SELLER(Address), // mapping address to whether it can start auctions
+    AUCTIONID,
+    AUCTION(u32), // mapping auction id to the auction
+}
+
+/// Collateral offered for sale by a seller
+#[derive(Clone)]
+#[contracttype]
+pub struct Lot {
+    pub asset: Address,    // collateral sold
+    pub amount: i128,      // amount of the collateral sold
+    pub start_price: i128, // price of the collateral in the stablecoin when the auction starts, in 7 decimals
+    pub end_price: i128,   // price of the collateral in the stablecoin the auction declines to, in 7 decimals
+}
+
+/// A Dutch auction of a lot, its price declining linearly from the start price to the end price
+#[derive(Clone)]
+#[contracttype]
+pub struct Auction {
+    pub seller: Address,   // address that started the auction, receiving the lot back if it is cancelled
+    pub lot: Lot,          // collateral sold, its amount reduced as it is taken
+    pub start_ledger: u32, // ledger sequence the auction started in
+    pub duration: u32,     // number of ledgers the price declines over
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the treasury receiving the proceeds
+///
+/// ### Panics
+/// If the treasury does not exist
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TREASURY)
+        .unwrap_optimized()
+}
+
+/// Set the treasury receiving the proceeds
+///
+/// ### Arguments
+/// * `treasury` - The Address of the treasury
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TREASURY, treasury);
+}
+
+/// Fetch the stablecoin lots are paid in
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the stablecoin lots are paid in
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the number of ledgers the price of new auctions declines over
+///
+/// ### Panics
+/// If the duration does not exist
+pub fn get_duration(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::DURATION)
+        .unwrap_optimized()
+}
+
+/// Set the number of ledgers the price of new auctions declines over
+///
+/// ### Arguments
+/// * `duration` - The number of ledgers
+pub fn set_duration(e: &Env, duration: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::DURATION, &duration);
+}
+
+/// Check if an address can start auctions
+///
+/// ### Arguments
+/// * `seller` - The Address of the seller
+pub fn is_seller(e: &Env, seller: &Address) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::SELLER(seller.clone()))
+        .unwrap_or(false)
+}
+
+/// Allow or disallow an address to start auctions
+///
+/// ### Arguments
+/// * `seller` - The Address of the seller
+/// * `enabled` - Whether the seller can start auctions
+pub fn set_seller(e: &Env, seller: &Address, enabled: bool) {
+    let key = DataKey::SELLER(seller.clone());
+    if enabled {
+        e.storage().instance().set::<DataKey, bool>(&key, &true);
+    } else {
+        e.storage().instance().remove(&key);
+    }
+}
+
+/// Fetch the next auction id and increment it
+pub fn next_auction_id(e: &Env) -> u32 {
+    let id = e.storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::AUCTIONID)
+        .unwrap_or(0);
+    e.storage()
+        .instance()
+        .set::<DataKey, u32>(&DataKey::AUCTIONID, &(id + 1));
+    id
+}
+
+/// Fetch an auction, if any
+///
+/// ### Arguments
+/// * `id` - The id of the auction
+pub fn get_auction(e: &Env, id: u32) -> Option<Auction> {
+    let key = DataKey::AUCTION(id);
+    let auction = e.storage().persistent().get::<DataKey, Auction>(&key);
+    if auction.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    auction
+}
+
+/// Set an auction
+///
+/// ### Arguments
+/// * `id` - The id of the auction
+/// * `auction` - The auction
+pub fn set_auction(e: &Env, id: u32, auction: &Auction) {
+    let key = DataKey::AUCTION(id);
+    e.storage()
+        .persistent()
+        .set::<DataKey, Auction>(&key, auction);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove an auction
+///
+/// ### Arguments
+/// * `id` - The id of the auction
+pub fn del_auction(e: &Env, id: u32) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::AUCTION(id));
+}
@@ -0,0 +1,175 @@
+use crate::errors::FactoryError;
+use crate::helper;
+use crate::storage::{self, OrbitConfig, OrbitDeployment, WasmHashes};
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, vec, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+use token::StellarAssetClient;
+
+#[contract]
+pub struct FactoryContract;
+
+#[contractclient(name="FactoryClient")]
+pub trait Factory {
+
+    /// Initialize the factory
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    /// * `wasm_hashes` - The wasm hashes of the treasury, pegkeeper and bridge oracle
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address, wasm_hashes: WasmHashes);
+
+    /// (Admin only) Deploy and wire a treasury, pegkeeper and bridge oracle for a stablecoin
+    ///
+    /// The treasury is initialized with the factory as its admin so it can deploy the stablecoin,
+    /// then handed to `config.admin`, who must authorize taking it over. The stablecoin admin must
+    /// authorize handing its admin to the treasury.
+    ///
+    /// ### Arguments
+    /// * `salt` - The salt the contract addresses are derived from
+    /// * `config` - The deployment config
+    ///
+    /// Returns the deployment
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the stablecoin already has a deployment
+    fn deploy_orbit(e: Env, salt: BytesN<32>, config: OrbitConfig) -> OrbitDeployment;
+
+    /// (Admin only) Set the wasm hashes used for new deployments
+    ///
+    /// ### Arguments
+    /// * `wasm_hashes` - The wasm hashes of the treasury, pegkeeper and bridge oracle
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_wasm_hashes(e: Env, wasm_hashes: WasmHashes);
+
+    /// (Admin only) Set a new address as the admin of the factory
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The new admin address
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// Fetch the deployment of a stablecoin, if any
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_deployment(e: Env, token: Address) -> Option<OrbitDeployment>;
+
+    /// Fetch every deployment, in deployment order
+    fn get_deployments(e: Env) -> Vec<OrbitDeployment>;
+}
+
+#[contractimpl]
+impl Factory for FactoryContract {
+    fn initialize(e: Env, admin: Address, wasm_hashes: WasmHashes) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, FactoryError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_wasm_hashes(&e, &wasm_hashes);
+    }
+
+    fn deploy_orbit(e: Env, salt: BytesN<32>, config: OrbitConfig) -> OrbitDeployment {
+        storage::extend_instance(&e);
+        if !storage::is_init(&e) {
+            panic_with_error!(&e, FactoryError::NotInitialized);
+        }
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if storage::get_deployment(&e, &config.token).is_some() {
+            panic_with_error!(&e, FactoryError::AlreadyDeployedError);
+        }
+
+        let wasm_hashes = storage::get_wasm_hashes(&e);
+        let treasury = helper::deploy(&e, &wasm_hashes.treasury, &salt, 0);
+        let pegkeeper = helper::deploy(&e, &wasm_hashes.pegkeeper, &salt, 1);
+        let bridge_oracle = helper::deploy(&e, &wasm_hashes.bridge_oracle, &salt, 2);
+        let factory = e.current_contract_address();
+
+        let bridge_oracle_args: Vec<Val> = vec![&e, treasury.into_val(&e), config.oracle.into_val(&e)];
+        e.invoke_contract::<()>(&bridge_oracle, &Symbol::new(&e, "initialize"), bridge_oracle_args);
+
+        let pegkeeper_args: Vec<Val> = vec![&e, treasury.into_val(&e), config.router.into_val(&e)];
+        e.invoke_contract::<()>(&pegkeeper, &Symbol::new(&e, "initialize"), pegkeeper_args);
+
+        let treasury_args: Vec<Val> = vec![
+            &e,
+            factory.into_val(&e),
+            bridge_oracle.into_val(&e),
+            pegkeeper.into_val(&e),
+        ];
+        e.invoke_contract::<()>(&treasury, &Symbol::new(&e, "initialize"), treasury_args);
+
+        StellarAssetClient::new(&e, &config.token).set_admin(&treasury);
+
+        let deploy_args: Vec<Val> = vec![
+            &e,
+            config.token.into_val(&e),
+            config.asset.into_val(&e),
+            config.blend_pool.into_val(&e),
+        ];
+        e.invoke_contract::<()>(&treasury, &Symbol::new(&e, "deploy_stablecoin"), deploy_args);
+        let currency_args: Vec<Val> = vec![&e, config.token.into_val(&e), config.currency.into_val(&e)];
+        e.invoke_contract::<()>(&treasury, &Symbol::new(&e, "set_currency"), currency_args);
+        e.invoke_contract::<()>(&treasury, &Symbol::new(&e, "set_admin"), vec![&e, config.admin.into_val(&e)]);
+
+        let deployment = OrbitDeployment {
+            token: config.token.clone(),
+            treasury,
+            pegkeeper,
+            bridge_oracle,
+        };
+        storage::set_deployment(&e, &deployment);
+        let mut tokens = storage::get_tokens(&e);
+        tokens.push_back(config.token.clone());
+        storage::set_tokens(&e, &tokens);
+
+        e.events().publish(
+            ("Factory", Symbol::new(&e, "deploy_orbit")),
+            (config.token, deployment.treasury.clone(), deployment.pegkeeper.clone(), deployment.bridge_oracle.clone()),
+        );
+        deployment
+    }
+
+    fn set_wasm_hashes(e: Env, wasm_hashes: WasmHashes) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_wasm_hashes(&e, &wasm_hashes);
+
+        e.events().publish(("Factory", Symbol::new(&e, "set_wasm_hashes")), admin);
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        new_admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+
+        e.events().publish(("Factory", Symbol::new(&e, "set_admin")), new_admin);
+    }
+
+    fn get_deployment(e: Env, token: Address) -> Option<OrbitDeployment> {
+        storage::get_deployment(&e, &token)
+    }
+
+    fn get_deployments(e: Env) -> Vec<OrbitDeployment> {
+        let mut deployments = Vec::new(&e);
+        for token in storage::get_tokens(&e).iter() {
+            if let Some(deployment) = storage::get_deployment(&e, &token) {
+                deployments.push_back(deployment);
+            }
+        }
+        deployments
+    }
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    /// not yet initialized
+    NotInitialized = 1601,
+
+    /// already initialized
+    AlreadyInitializedError = 1602,
+
+    /// the stablecoin already has a deployment
+    AlreadyDeployedError = 1603,
+}
@@ -0,0 +1,17 @@
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+
+/// Deploy a contract from the factory, deriving a distinct salt for each contract deployed
+/// under the same deployment salt
+///
+/// ### Arguments
+/// * `wasm_hash` - The wasm hash of the contract
+/// * `salt` - The deployment salt
+/// * `index` - The index of the contract within the deployment
+pub fn deploy(e: &Env, wasm_hash: &BytesN<32>, salt: &BytesN<32>, index: u8) -> Address {
+    let mut salt_bytes = Bytes::from_array(e, &salt.to_array());
+    salt_bytes.push_back(index);
+    let contract_salt = e.crypto().sha256(&salt_bytes);
+    e.deployer()
+        .with_current_contract(contract_salt)
+        .deploy(wasm_hash.clone())
+}
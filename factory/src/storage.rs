@@ -0,0 +1,146 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::{Address, BytesN, contracttype, Env, Symbol, Vec};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    WASMHASHES,
+    DEPLOYMENT(Address), // mapping token address to its deployed treasury and pegkeeper
+    TOKENS,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct WasmHashes {
+    pub treasury: BytesN<32>,
+    pub pegkeeper: BytesN<32>,
+    pub bridge_oracle: BytesN<32>,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OrbitConfig {
+    pub admin: Address,      // admin of the deployed treasury
+    pub token: Address,      // the stablecoin SAC, whose admin is handed to the treasury
+    pub asset: Asset,        // the asset the stablecoin is pegged to
+    pub currency: Symbol,    // the fiat currency the stablecoin is pegged to, e.g. EUR
+    pub oracle: Address,     // the price oracle wrapped by the bridge oracle
+    pub router: Address,     // the soroswap router used by the pegkeeper
+    pub blend_pool: Address, // the blend pool the treasury supplies the stablecoin to
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OrbitDeployment {
+    pub token: Address,
+    pub treasury: Address,
+    pub pegkeeper: Address,
+    pub bridge_oracle: Address,
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the wasm hashes of the deployed contracts
+///
+/// ### Panics
+/// If the wasm hashes do not exist
+pub fn get_wasm_hashes(e: &Env) -> WasmHashes {
+    e.storage()
+        .instance()
+        .get(&DataKey::WASMHASHES)
+        .unwrap_optimized()
+}
+
+/// Set the wasm hashes of the deployed contracts
+///
+/// ### Arguments
+/// * `wasm_hashes` - The wasm hashes
+pub fn set_wasm_hashes(e: &Env, wasm_hashes: &WasmHashes) {
+    e.storage()
+        .instance()
+        .set(&DataKey::WASMHASHES, wasm_hashes);
+}
+
+/// Fetch the deployment of a stablecoin
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn get_deployment(e: &Env, token: &Address) -> Option<OrbitDeployment> {
+    let key = DataKey::DEPLOYMENT(token.clone());
+    let deployment = e.storage().persistent().get::<DataKey, OrbitDeployment>(&key);
+    if deployment.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    deployment
+}
+
+/// Record the deployment of a stablecoin
+///
+/// ### Arguments
+/// * `deployment` - The deployment
+pub fn set_deployment(e: &Env, deployment: &OrbitDeployment) {
+    let key = DataKey::DEPLOYMENT(deployment.token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, OrbitDeployment>(&key, deployment);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the stablecoins deployed by the factory, in deployment order
+pub fn get_tokens(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKENS)
+        .unwrap_or(Vec::new(e))
+}
+
+/// Set the stablecoins deployed by the factory
+///
+/// ### Arguments
+/// * `tokens` - The Addresses of the stablecoins
+pub fn set_tokens(e: &Env, tokens: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKENS, tokens);
+}
@@ -0,0 +1,282 @@
+use crate::helper;
+use crate::storage::{self, Call, GovConfig, Proposal, ProposalStatus, Stake};
+use crate::errors::GovernanceError;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env, String, Symbol, Vec};
+use token::Client as TokenClient;
+
+#[contract]
+pub struct GovernanceContract;
+
+#[contractclient(name="GovernanceClient")]
+pub trait Governance {
+
+    /// Initialize the governance contract
+    ///
+    /// The governance contract should then be set as the admin of the treasury, which in turn
+    /// administers the pegkeeper.
+    ///
+    /// ### Arguments
+    /// * `config` - The governance config
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, config: GovConfig);
+
+    /// Stake tokens for voting power
+    ///
+    /// ### Arguments
+    /// * `from` - The Address staking
+    /// * `amount` - The amount to stake
+    ///
+    /// ### Panics
+    /// If the amount is not positive
+    fn stake(e: Env, from: Address, amount: i128);
+
+    /// Unstake tokens
+    ///
+    /// ### Arguments
+    /// * `from` - The Address unstaking
+    /// * `amount` - The amount to unstake
+    ///
+    /// ### Panics
+    /// If the amount is not positive or exceeds the stake, or the stake is locked by an active vote
+    fn unstake(e: Env, from: Address, amount: i128);
+
+    /// Create a proposal
+    ///
+    /// ### Arguments
+    /// * `proposer` - The Address of the proposer
+    /// * `description` - The description of the proposal
+    /// * `calls` - The calls executed if the proposal passes
+    ///
+    /// Returns the proposal id
+    ///
+    /// ### Panics
+    /// If the proposer's stake is below the proposal threshold
+    fn propose(e: Env, proposer: Address, description: String, calls: Vec<Call>) -> u32;
+
+    /// Vote on a proposal with the voter's full stake
+    ///
+    /// ### Arguments
+    /// * `voter` - The Address of the voter
+    /// * `proposal_id` - The proposal id
+    /// * `support` - Whether the vote is for the proposal
+    ///
+    /// ### Panics
+    /// If the vote has ended, the voter already voted, or the voter has no stake
+    fn vote(e: Env, voter: Address, proposal_id: u32, support: bool);
+
+    /// Queue a passed proposal for execution after the timelock
+    ///
+    /// ### Arguments
+    /// * `proposal_id` - The proposal id
+    ///
+    /// ### Panics
+    /// If the vote has not ended or the proposal did not pass
+    fn queue(e: Env, proposal_id: u32);
+
+    /// Execute a queued proposal
+    ///
+    /// ### Arguments
+    /// * `proposal_id` - The proposal id
+    ///
+    /// ### Panics
+    /// If the proposal is not queued or the timelock has not passed
+    fn execute(e: Env, proposal_id: u32);
+
+    /// (Proposer only) Cancel a proposal that has not been executed
+    ///
+    /// ### Arguments
+    /// * `proposal_id` - The proposal id
+    ///
+    /// ### Panics
+    /// If the proposal was already executed or canceled
+    fn cancel(e: Env, proposal_id: u32);
+
+    /// Fetch a proposal
+    ///
+    /// ### Arguments
+    /// * `proposal_id` - The proposal id
+    fn get_proposal(e: Env, proposal_id: u32) -> Option<Proposal>;
+
+    /// Fetch a user's stake
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user
+    fn get_stake(e: Env, user: Address) -> Stake;
+
+    /// Fetch the governance config
+    fn get_config(e: Env) -> GovConfig;
+}
+
+#[contractimpl]
+impl Governance for GovernanceContract {
+    fn initialize(e: Env, config: GovConfig) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, GovernanceError::AlreadyInitializedError);
+        }
+
+        storage::set_config(&e, &config);
+    }
+
+    fn stake(e: Env, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, GovernanceError::InvalidAmount);
+        }
+
+        let config = storage::get_config(&e);
+        TokenClient::new(&e, &config.vote_token).transfer(&from, &e.current_contract_address(), &amount);
+        let mut stake = storage::get_stake(&e, &from);
+        stake.amount += amount;
+        storage::set_stake(&e, &from, &stake);
+
+        e.events().publish(("Governance", Symbol::new(&e, "stake")), (from, amount));
+    }
+
+    fn unstake(e: Env, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        let mut stake = storage::get_stake(&e, &from);
+        if amount <= 0 {
+            panic_with_error!(&e, GovernanceError::InvalidAmount);
+        }
+        if amount > stake.amount {
+            panic_with_error!(&e, GovernanceError::InsufficientBalanceError);
+        }
+        // stake that voted can not be moved until the vote ends, to prevent double voting
+        if stake.locked_until > e.ledger().timestamp() {
+            panic_with_error!(&e, GovernanceError::StakeLockedError);
+        }
+
+        stake.amount -= amount;
+        storage::set_stake(&e, &from, &stake);
+        let config = storage::get_config(&e);
+        TokenClient::new(&e, &config.vote_token).transfer(&e.current_contract_address(), &from, &amount);
+
+        e.events().publish(("Governance", Symbol::new(&e, "unstake")), (from, amount));
+    }
+
+    fn propose(e: Env, proposer: Address, description: String, calls: Vec<Call>) -> u32 {
+        storage::extend_instance(&e);
+        proposer.require_auth();
+
+        let config = storage::get_config(&e);
+        if storage::get_stake(&e, &proposer).amount < config.proposal_threshold {
+            panic_with_error!(&e, GovernanceError::InsufficientBalanceError);
+        }
+
+        let proposal_id = storage::get_next_proposal_id(&e);
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            description,
+            calls,
+            vote_end: e.ledger().timestamp() + config.voting_period,
+            for_votes: 0,
+            against_votes: 0,
+            status: ProposalStatus::Active,
+            eta: 0,
+        };
+        storage::set_proposal(&e, proposal_id, &proposal);
+        storage::set_next_proposal_id(&e, proposal_id + 1);
+
+        e.events().publish(("Governance", Symbol::new(&e, "propose")), (proposal_id, proposer));
+        proposal_id
+    }
+
+    fn vote(e: Env, voter: Address, proposal_id: u32, support: bool) {
+        storage::extend_instance(&e);
+        voter.require_auth();
+
+        let mut proposal = helper::get_proposal(&e, proposal_id);
+        if proposal.status != ProposalStatus::Active || e.ledger().timestamp() >= proposal.vote_end {
+            panic_with_error!(&e, GovernanceError::InvalidProposalStatus);
+        }
+        if storage::has_voted(&e, proposal_id, &voter) {
+            panic_with_error!(&e, GovernanceError::AlreadyVotedError);
+        }
+        let mut stake = storage::get_stake(&e, &voter);
+        if stake.amount <= 0 {
+            panic_with_error!(&e, GovernanceError::InsufficientBalanceError);
+        }
+
+        if support {
+            proposal.for_votes += stake.amount;
+        } else {
+            proposal.against_votes += stake.amount;
+        }
+        storage::set_proposal(&e, proposal_id, &proposal);
+        storage::set_voted(&e, proposal_id, &voter);
+        stake.locked_until = stake.locked_until.max(proposal.vote_end);
+        storage::set_stake(&e, &voter, &stake);
+
+        e.events().publish(("Governance", Symbol::new(&e, "vote")), (proposal_id, voter, support, stake.amount));
+    }
+
+    fn queue(e: Env, proposal_id: u32) {
+        storage::extend_instance(&e);
+
+        let mut proposal = helper::get_proposal(&e, proposal_id);
+        if proposal.status != ProposalStatus::Active || e.ledger().timestamp() < proposal.vote_end {
+            panic_with_error!(&e, GovernanceError::InvalidProposalStatus);
+        }
+        if !helper::is_passed(&e, &proposal) {
+            panic_with_error!(&e, GovernanceError::ProposalDefeatedError);
+        }
+
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = e.ledger().timestamp() + storage::get_config(&e).timelock;
+        storage::set_proposal(&e, proposal_id, &proposal);
+
+        e.events().publish(("Governance", Symbol::new(&e, "queue")), (proposal_id, proposal.eta));
+    }
+
+    fn execute(e: Env, proposal_id: u32) {
+        storage::extend_instance(&e);
+
+        let mut proposal = helper::get_proposal(&e, proposal_id);
+        if proposal.status != ProposalStatus::Queued {
+            panic_with_error!(&e, GovernanceError::InvalidProposalStatus);
+        }
+        if e.ledger().timestamp() < proposal.eta {
+            panic_with_error!(&e, GovernanceError::TimelockError);
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        storage::set_proposal(&e, proposal_id, &proposal);
+        for call in proposal.calls.iter() {
+            helper::execute_call(&e, &call);
+        }
+
+        e.events().publish(("Governance", Symbol::new(&e, "execute")), proposal_id);
+    }
+
+    fn cancel(e: Env, proposal_id: u32) {
+        storage::extend_instance(&e);
+
+        let mut proposal = helper::get_proposal(&e, proposal_id);
+        proposal.proposer.require_auth();
+        if proposal.status == ProposalStatus::Executed || proposal.status == ProposalStatus::Canceled {
+            panic_with_error!(&e, GovernanceError::InvalidProposalStatus);
+        }
+
+        proposal.status = ProposalStatus::Canceled;
+        storage::set_proposal(&e, proposal_id, &proposal);
+
+        e.events().publish(("Governance", Symbol::new(&e, "cancel")), proposal_id);
+    }
+
+    fn get_proposal(e: Env, proposal_id: u32) -> Option<Proposal> {
+        storage::get_proposal(&e, proposal_id)
+    }
+
+    fn get_stake(e: Env, user: Address) -> Stake {
+        storage::get_stake(&e, &user)
+    }
+
+    fn get_config(e: Env) -> GovConfig {
+        storage::get_config(&e)
+    }
+}
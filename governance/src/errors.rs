@@ -0,0 +1,36 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    /// not yet initialized
+    NotInitialized = 1501,
+
+    /// already initialized
+    AlreadyInitializedError = 1502,
+
+    /// amount is zero or negative
+    InvalidAmount = 1503,
+
+    /// not enough stake to execute the operation
+    InsufficientBalanceError = 1504,
+
+    /// the proposal does not exist
+    ProposalNotFoundError = 1505,
+
+    /// the proposal is not in the required status or voting period
+    InvalidProposalStatus = 1506,
+
+    /// the voter already voted on the proposal
+    AlreadyVotedError = 1507,
+
+    /// the proposal did not reach quorum or a majority
+    ProposalDefeatedError = 1508,
+
+    /// the proposal's timelock has not passed
+    TimelockError = 1509,
+
+    /// the stake is locked by an active vote
+    StakeLockedError = 1510,
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{panic_with_error, Env, IntoVal, Symbol, Val};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+use crate::errors::GovernanceError;
+use crate::storage::{self, Call, GovConfig, Proposal};
+
+/// Fetch a proposal
+///
+/// ### Arguments
+/// * `proposal_id` - The proposal id
+///
+/// ### Panics
+/// If the proposal does not exist
+pub fn get_proposal(e: &Env, proposal_id: u32) -> Proposal {
+    match storage::get_proposal(e, proposal_id) {
+        Some(proposal) => proposal,
+        None => panic_with_error!(e, GovernanceError::ProposalNotFoundError),
+    }
+}
+
+/// Check if a proposal reached quorum and a majority
+///
+/// ### Arguments
+/// * `proposal` - The proposal
+pub fn is_passed(e: &Env, proposal: &Proposal) -> bool {
+    let config = storage::get_config(e);
+    proposal.for_votes + proposal.against_votes >= config.quorum && proposal.for_votes > proposal.against_votes
+}
+
+/// Execute a call of a proposal
+///
+/// Calls to the governance contract itself are handled internally, as a contract can not
+/// invoke itself. Only `set_config` is supported.
+///
+/// ### Arguments
+/// * `call` - The call to execute
+pub fn execute_call(e: &Env, call: &Call) {
+    if call.contract == e.current_contract_address() {
+        if call.fn_name != Symbol::new(e, "set_config") {
+            panic_with_error!(e, GovernanceError::InvalidProposalStatus);
+        }
+        let config: GovConfig = call.args.get(0).unwrap_optimized().into_val(e);
+        storage::set_config(e, &config);
+        return;
+    }
+    e.invoke_contract::<Val>(&call.contract, &call.fn_name, call.args.clone());
+}
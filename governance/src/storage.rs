@@ -0,0 +1,210 @@
+use soroban_sdk::{Address, contracttype, Env, String, Symbol, Val, Vec};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    CONFIG,
+    PROPOSALID,
+    PROPOSAL(u32), // mapping proposal id to the proposal
+    VOTE(VoteKey), // mapping proposal id and voter to whether they voted
+    STAKE(Address), // mapping user address to their stake
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct VoteKey {
+    pub proposal_id: u32,
+    pub voter: Address,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct GovConfig {
+    pub vote_token: Address,       // token staked for voting power, e.g. a governance token or OUSD
+    pub voting_period: u64,        // duration of the vote, in seconds
+    pub timelock: u64,             // delay between queueing and executing a proposal, in seconds
+    pub quorum: i128,              // minimum votes cast for a proposal to pass
+    pub proposal_threshold: i128,  // minimum stake required to create a proposal
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Call {
+    pub contract: Address, // contract invoked, e.g. the treasury
+    pub fn_name: Symbol,   // function invoked
+    pub args: Vec<Val>,    // arguments of the function
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum ProposalStatus {
+    Active = 0,
+    Queued = 1,
+    Executed = 2,
+    Canceled = 3,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Proposal {
+    pub proposer: Address,
+    pub description: String,
+    pub calls: Vec<Call>,
+    pub vote_end: u64,       // timestamp the vote ends at
+    pub for_votes: i128,
+    pub against_votes: i128,
+    pub status: ProposalStatus,
+    pub eta: u64,            // timestamp the proposal can be executed at, once queued
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Stake {
+    pub amount: i128,       // tokens staked for voting power
+    pub locked_until: u64,  // timestamp the stake is locked until by the user's votes
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::CONFIG) }
+
+/// Fetch the governance config
+///
+/// ### Panics
+/// If the config does not exist
+pub fn get_config(e: &Env) -> GovConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::CONFIG)
+        .unwrap_optimized()
+}
+
+/// Set the governance config
+///
+/// ### Arguments
+/// * `config` - The governance config
+pub fn set_config(e: &Env, config: &GovConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::CONFIG, config);
+}
+
+/// Fetch the next proposal id
+pub fn get_next_proposal_id(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::PROPOSALID)
+        .unwrap_or(0)
+}
+
+/// Set the next proposal id
+///
+/// ### Arguments
+/// * `proposal_id` - The next proposal id
+pub fn set_next_proposal_id(e: &Env, proposal_id: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PROPOSALID, &proposal_id);
+}
+
+/// Fetch a proposal, if it exists
+///
+/// ### Arguments
+/// * `proposal_id` - The proposal id
+pub fn get_proposal(e: &Env, proposal_id: u32) -> Option<Proposal> {
+    let key = DataKey::PROPOSAL(proposal_id);
+    let proposal = e.storage().persistent().get::<DataKey, Proposal>(&key);
+    if proposal.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    proposal
+}
+
+/// Set a proposal
+///
+/// ### Arguments
+/// * `proposal_id` - The proposal id
+/// * `proposal` - The proposal
+pub fn set_proposal(e: &Env, proposal_id: u32, proposal: &Proposal) {
+    let key = DataKey::PROPOSAL(proposal_id);
+    e.storage()
+        .persistent()
+        .set::<DataKey, Proposal>(&key, proposal);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Check if a voter has voted on a proposal
+///
+/// ### Arguments
+/// * `proposal_id` - The proposal id
+/// * `voter` - The Address of the voter
+pub fn has_voted(e: &Env, proposal_id: u32, voter: &Address) -> bool {
+    e.storage()
+        .persistent()
+        .has(&DataKey::VOTE(VoteKey { proposal_id, voter: voter.clone() }))
+}
+
+/// Record a voter's vote on a proposal
+///
+/// ### Arguments
+/// * `proposal_id` - The proposal id
+/// * `voter` - The Address of the voter
+pub fn set_voted(e: &Env, proposal_id: u32, voter: &Address) {
+    let key = DataKey::VOTE(VoteKey { proposal_id, voter: voter.clone() });
+    e.storage()
+        .persistent()
+        .set::<DataKey, bool>(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch a user's stake
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+pub fn get_stake(e: &Env, user: &Address) -> Stake {
+    let key = DataKey::STAKE(user.clone());
+    match e.storage().persistent().get::<DataKey, Stake>(&key) {
+        Some(stake) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            stake
+        }
+        None => Stake { amount: 0, locked_until: 0 },
+    }
+}
+
+/// Set a user's stake
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+/// * `stake` - The user's stake
+pub fn set_stake(e: &Env, user: &Address, stake: &Stake) {
+    let key = DataKey::STAKE(user.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, Stake>(&key, stake);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
@@ -0,0 +1,162 @@
+use crate::storage;
+use crate::errors::InsuranceFundError;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env, Symbol};
+use token::Client as TokenClient;
+
+#[contract]
+pub struct InsuranceFundContract;
+
+#[contractclient(name="InsuranceFundClient")]
+pub trait InsuranceFund {
+
+    /// Initialize the insurance fund
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    /// * `treasury` - The Address of the treasury, which forwards a share of its fees to the fund
+    /// * `token` - The Address of the token held by the fund
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address);
+
+    /// Deposit tokens into the fund
+    ///
+    /// ### Arguments
+    /// * `from` - The Address depositing the tokens
+    /// * `amount` - The amount to deposit
+    ///
+    /// ### Panics
+    /// If the amount is not positive
+    fn deposit(e: Env, from: Address, amount: i128);
+
+    /// (Admin only) Withdraw tokens from the fund
+    ///
+    /// ### Arguments
+    /// * `to` - The Address receiving the tokens
+    /// * `amount` - The amount to withdraw
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the amount is not positive, or the amount exceeds the fund's balance
+    fn withdraw(e: Env, to: Address, amount: i128);
+
+    /// (Treasury only) Cover bad debt left by a liquidation, transferring up to the amount to the treasury
+    ///
+    /// Returns the amount covered, which is less than the amount if the fund's balance does not cover it
+    ///
+    /// ### Arguments
+    /// * `amount` - The shortfall to cover
+    ///
+    /// ### Panics
+    /// If the caller is not the treasury or the amount is not positive
+    fn cover_shortfall(e: Env, amount: i128) -> i128;
+
+    /// (Admin only) Set a new admin
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The Address for the admin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// Fetch the fund's balance of its token
+    fn get_balance(e: Env) -> i128;
+
+    /// Fetch the total shortfalls covered by the fund
+    fn get_covered(e: Env) -> i128;
+
+    /// Fetch the treasury whose shortfalls the fund covers
+    fn get_treasury(e: Env) -> Address;
+
+    /// Fetch the token held by the fund
+    fn get_token(e: Env) -> Address;
+}
+
+fn require_positive(e: &Env, amount: i128) {
+    if amount <= 0 {
+        panic_with_error!(e, InsuranceFundError::InvalidAmount);
+    }
+}
+
+#[contractimpl]
+impl InsuranceFund for InsuranceFundContract {
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, InsuranceFundError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_treasury(&e, &treasury);
+        storage::set_token(&e, &token);
+    }
+
+    fn deposit(e: Env, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        require_positive(&e, amount);
+
+        TokenClient::new(&e, &storage::get_token(&e)).transfer(&from, &e.current_contract_address(), &amount);
+
+        e.events().publish(("InsuranceFund", Symbol::new(&e, "deposit")), (from, amount));
+    }
+
+    fn withdraw(e: Env, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        require_positive(&e, amount);
+
+        let token_client = TokenClient::new(&e, &storage::get_token(&e));
+        if amount > token_client.balance(&e.current_contract_address()) {
+            panic_with_error!(&e, InsuranceFundError::InsufficientBalanceError);
+        }
+        token_client.transfer(&e.current_contract_address(), &to, &amount);
+
+        e.events().publish(("InsuranceFund", Symbol::new(&e, "withdraw")), (to, amount));
+    }
+
+    fn cover_shortfall(e: Env, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        let treasury = storage::get_treasury(&e);
+        treasury.require_auth();
+        require_positive(&e, amount);
+
+        let token_client = TokenClient::new(&e, &storage::get_token(&e));
+        let covered = amount.min(token_client.balance(&e.current_contract_address()));
+        if covered > 0 {
+            token_client.transfer(&e.current_contract_address(), &treasury, &covered);
+            storage::set_covered(&e, storage::get_covered(&e) + covered);
+        }
+
+        e.events().publish(("InsuranceFund", Symbol::new(&e, "cover_shortfall")), (amount, covered));
+        covered
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+
+        e.events().publish(("InsuranceFund", Symbol::new(&e, "set_admin")), new_admin);
+    }
+
+    fn get_balance(e: Env) -> i128 {
+        TokenClient::new(&e, &storage::get_token(&e)).balance(&e.current_contract_address())
+    }
+
+    fn get_covered(e: Env) -> i128 {
+        storage::get_covered(&e)
+    }
+
+    fn get_treasury(e: Env) -> Address {
+        storage::get_treasury(&e)
+    }
+
+    fn get_token(e: Env) -> Address {
+        storage::get_token(&e)
+    }
+}
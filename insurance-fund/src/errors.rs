@@ -0,0 +1,18 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InsuranceFundError {
+    /// not yet initialized
+    NotInitialized = 1801,
+
+    /// already initialized
+    AlreadyInitializedError = 1802,
+
+    /// amount is zero or negative
+    InvalidAmount = 1803,
+
+    /// not enough tokens in the fund to execute the operation
+    InsufficientBalanceError = 1804,
+}
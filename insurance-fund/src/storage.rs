@@ -0,0 +1,105 @@
+use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TREASURY,
+    TOKEN,
+    COVERED,
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the treasury whose shortfalls the fund covers
+///
+/// ### Panics
+/// If the treasury does not exist
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TREASURY)
+        .unwrap_optimized()
+}
+
+/// Set the treasury whose shortfalls the fund covers
+///
+/// ### Arguments
+/// * `treasury` - The Address of the treasury
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TREASURY, treasury);
+}
+
+/// Fetch the token held by the fund
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the token held by the fund
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the total shortfalls covered by the fund
+pub fn get_covered(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::COVERED)
+        .unwrap_or(0)
+}
+
+/// Set the total shortfalls covered by the fund
+///
+/// ### Arguments
+/// * `covered` - The total amount covered
+pub fn set_covered(e: &Env, covered: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::COVERED, &covered);
+}
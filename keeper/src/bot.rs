@@ -0,0 +1,105 @@
+use stellar_xdr::curr::{ScAddress, ScVal};
+
+use keeper::error::{KeeperError, Result};
+use keeper::tx::{self, Submitter};
+
+use crate::config::Config;
+
+const LIQUIDATION_AUCTION: u32 = 0; // blend auction type of a user liquidation
+const LIQUIDATION_PERCENT: u64 = 100; // percent of a position auctioned when a liquidation is created
+
+/// Watches the peg and blend positions and submits the transactions that maintain them
+pub struct Keeper {
+    submitter: Submitter,
+    pegkeeper: ScAddress,
+    treasury: ScAddress,
+    token: ScAddress,
+    collaterals: Vec<ScAddress>,
+    blend_pool: Option<ScAddress>,
+    users: Vec<ScAddress>,
+    threshold_bps: i128,
+}
+
+impl Keeper {
+    pub fn new(config: &Config, submitter: Submitter) -> Result<Keeper> {
+        Ok(Keeper {
+            submitter,
+            pegkeeper: tx::contract_address(&config.pegkeeper)?,
+            treasury: tx::contract_address(&config.treasury)?,
+            token: tx::contract_address(&config.token)?,
+            collaterals: config.collaterals.iter().map(|c| tx::contract_address(c)).collect::<Result<_>>()?,
+            blend_pool: config.blend_pool.as_deref().map(tx::contract_address).transpose()?,
+            users: config.users.iter().map(|u| tx::address(u)).collect::<Result<_>>()?,
+            threshold_bps: config.threshold_bps,
+        })
+    }
+
+    /// Run a single round over every peg collateral and watched user
+    ///
+    /// Errors are logged and do not stop the round, so one failing pair or position does not block the others
+    pub fn run_once(&self) {
+        for collateral in &self.collaterals {
+            if let Err(err) = self.check_peg(collateral) {
+                eprintln!("peg {collateral}: {err}");
+            }
+        }
+        if let Some(blend_pool) = &self.blend_pool {
+            for user in &self.users {
+                if let Err(err) = self.check_position(blend_pool, user) {
+                    eprintln!("position {user}: {err}");
+                }
+            }
+        }
+    }
+
+    /// Rebalance the pair of a peg collateral if the stablecoin deviates from its peg by more than the threshold
+    fn check_peg(&self, collateral: &ScAddress) -> Result<()> {
+        let args = vec![ScVal::Address(collateral.clone())];
+        let deviation = to_i128(self.submitter.simulate(&self.pegkeeper, "check_peg", args.clone())?)?;
+        if deviation.abs() <= self.threshold_bps {
+            return Ok(());
+        }
+        println!("peg {collateral}: deviation of {deviation} bps, rebalancing");
+        let amount = to_i128(self.submitter.submit(&self.pegkeeper, "maybe_rebalance", args)?)?;
+        println!("peg {collateral}: rebalanced {amount}");
+        Ok(())
+    }
+
+    /// Fill the liquidation auction of a user, or create one if the position can be liquidated
+    fn check_position(&self, blend_pool: &ScAddress, user: &ScAddress) -> Result<()> {
+        let auction_args = vec![ScVal::U32(LIQUIDATION_AUCTION), ScVal::Address(user.clone())];
+        match self.submitter.simulate(blend_pool, "get_auction", auction_args) {
+            Ok(_) => {
+                // the pegkeeper scales the bid on-chain and panics if the fill is not yet profitable. The
+                // keeper's account must hold the keeper role on the treasury.
+                let args = vec![
+                    ScVal::Address(self.submitter.address()),
+                    ScVal::Address(self.token.clone()),
+                    ScVal::U32(LIQUIDATION_AUCTION),
+                    ScVal::Address(user.clone()),
+                ];
+                if self.submitter.simulate(&self.treasury, "fill_auction", args.clone()).is_err() {
+                    return Ok(());
+                }
+                println!("position {user}: filling auction");
+                self.submitter.submit(&self.treasury, "fill_auction", args)?;
+                println!("position {user}: liquidated");
+            }
+            // the pool panics if the user has no auction
+            Err(KeeperError::Simulation(_)) => {
+                let args = vec![ScVal::Address(user.clone()), ScVal::U64(LIQUIDATION_PERCENT)];
+                // the pool panics if the position is healthy, so only submit auctions that simulate
+                if self.submitter.simulate(blend_pool, "new_liquidation_auction", args.clone()).is_ok() {
+                    println!("position {user}: creating liquidation auction");
+                    self.submitter.submit(blend_pool, "new_liquidation_auction", args)?;
+                }
+            }
+            Err(err) => return Err(err),
+        }
+        Ok(())
+    }
+}
+
+fn to_i128(value: ScVal) -> Result<i128> {
+    i128::try_from(value).map_err(|_| KeeperError::Rpc("expected an i128 result".to_string()))
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::time::Duration;
+
+use std::path::Path;
+
+use keeper::deployment::{Deployment, Deployments};
+use keeper::error::{KeeperError, Result};
+
+/// The keeper configuration, read from `ORBIT_*` environment variables
+///
+/// Addresses and network settings that are not set fall back to the deployment of `ORBIT_NETWORK`
+/// (default `testnet`) in the deployments file at `ORBIT_DEPLOYMENTS` (default `deployments.toml`).
+/// Tokens and collaterals can be given as addresses or as symbols of the deployment's tokens.
+pub struct Config {
+    pub rpc_url: String,            // soroban rpc endpoint
+    pub network_passphrase: String, // passphrase of the network the rpc serves
+    pub secret_key: String,         // secret key of the keeper account, e.g. S...
+    pub pegkeeper: String,          // pegkeeper contract, e.g. C...
+    pub treasury: String,           // treasury contract
+    pub token: String,              // stablecoin defended by the keeper
+    pub collaterals: Vec<String>,   // peg collaterals whose pairs are rebalanced
+    pub blend_pool: Option<String>, // blend pool whose positions are liquidated
+    pub users: Vec<String>,         // blend users whose positions are watched
+    pub threshold_bps: i128,        // peg deviation, in basis points, before rebalancing
+    pub poll_interval: Duration,    // time between two rounds
+    pub max_retries: u32,           // attempts to land a transaction before giving up
+    pub base_fee: u32,              // inclusion fee of a transaction, in stroops
+    pub max_fee: i64,               // maximum fee a fee bump can raise a transaction to, in stroops
+}
+
+fn required(name: &str, fallback: Option<&String>) -> Result<String> {
+    optional(name)
+        .or_else(|| fallback.cloned())
+        .ok_or_else(|| KeeperError::Config(format!("{name} is not set")))
+}
+
+/// Resolve a token symbol of the deployment to its address, or return the value if it is not a symbol
+fn token(deployment: &Deployment, value: String) -> String {
+    deployment.tokens.get(&value).cloned().unwrap_or(value)
+}
+
+fn optional(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn list(name: &str) -> Vec<String> {
+    optional(name)
+        .map(|value| value.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+fn number<T: std::str::FromStr>(name: &str, default: T) -> Result<T> {
+    match optional(name) {
+        Some(value) => value.parse().map_err(|_| KeeperError::Config(format!("{name} is not a number"))),
+        None => Ok(default),
+    }
+}
+
+impl Config {
+    /// Read the configuration from the environment and the deployments file
+    ///
+    /// ### Errors
+    /// If a required value is missing, a number can not be parsed, or the deployments file is invalid
+    pub fn from_env() -> Result<Config> {
+        let deployments = Deployments::load(Path::new(&optional("ORBIT_DEPLOYMENTS").unwrap_or("deployments.toml".to_string())))?;
+        let network = optional("ORBIT_NETWORK").unwrap_or("testnet".to_string());
+        let deployment = deployments.0.get(&network).cloned().unwrap_or_default();
+
+        let token_symbol = optional("ORBIT_TOKEN").unwrap_or("OUSD".to_string());
+        let blend_pool = optional("ORBIT_BLEND_POOL").or_else(|| deployment.blend_pools.get(&token_symbol).cloned());
+        Ok(Config {
+            rpc_url: required("ORBIT_RPC_URL", deployment.rpc_url.as_ref())?,
+            network_passphrase: required("ORBIT_NETWORK_PASSPHRASE", deployment.network_passphrase.as_ref())?,
+            secret_key: required("ORBIT_SECRET_KEY", None)?,
+            pegkeeper: required("ORBIT_PEGKEEPER", deployment.contracts.pegkeeper.as_ref())?,
+            treasury: required("ORBIT_TREASURY", deployment.contracts.treasury.as_ref())?,
+            token: token(&deployment, token_symbol),
+            collaterals: list("ORBIT_COLLATERALS").into_iter().map(|collateral| token(&deployment, collateral)).collect(),
+            blend_pool,
+            users: list("ORBIT_USERS"),
+            threshold_bps: number("ORBIT_THRESHOLD_BPS", 50)?,
+            poll_interval: Duration::from_secs(number("ORBIT_POLL_SECS", 30)?),
+            max_retries: number("ORBIT_MAX_RETRIES", 5)?,
+            base_fee: number("ORBIT_BASE_FEE", 100)?,
+            max_fee: number("ORBIT_MAX_FEE", 10_000_000)?,
+        })
+    }
+}
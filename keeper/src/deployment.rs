@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{KeeperError, Result};
+use crate::tx;
+
+/// The addresses of the protocol deployed on each network, keyed by network name, e.g. `testnet`.
+/// Parsed from a TOML file such as the repository's `deployments.toml`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Deployments(pub BTreeMap<String, Deployment>);
+
+/// The protocol deployed on a network
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Deployment {
+    pub rpc_url: Option<String>,               // soroban rpc endpoint of the network
+    pub network_passphrase: Option<String>,    // passphrase of the network
+    #[serde(default)]
+    pub contracts: Contracts,                  // orbit contracts
+    #[serde(default)]
+    pub tokens: BTreeMap<String, String>,      // token addresses, keyed by symbol, e.g. OUSD
+    #[serde(default)]
+    pub blend_pools: BTreeMap<String, String>, // blend pool addresses, keyed by the symbol of the stablecoin they lend
+    #[serde(default)]
+    pub pairs: BTreeMap<String, String>,       // soroswap pair addresses, keyed by `<token>-<token>` symbols
+}
+
+/// The orbit contracts deployed on a network
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Contracts {
+    pub treasury: Option<String>,
+    pub pegkeeper: Option<String>,
+    pub bridge_oracle: Option<String>,
+    pub factory: Option<String>,
+    pub psm: Option<String>,
+    pub stability_pool: Option<String>,
+    pub savings: Option<String>,
+    pub governance: Option<String>,
+}
+
+impl Deployments {
+    /// Read the deployments from a TOML file. A missing file has no deployments.
+    ///
+    /// ### Errors
+    /// If the file can not be read or does not match the schema
+    pub fn load(path: &Path) -> Result<Deployments> {
+        if !path.exists() {
+            return Ok(Deployments::default());
+        }
+        let contents = fs::read_to_string(path).map_err(|err| KeeperError::Config(format!("{}: {err}", path.display())))?;
+        Deployments::parse(&contents).map_err(|err| KeeperError::Config(format!("{}: {err}", path.display())))
+    }
+
+    /// Parse the deployments from TOML
+    ///
+    /// ### Errors
+    /// If the TOML does not match the schema
+    pub fn parse(contents: &str) -> std::result::Result<Deployments, String> {
+        toml::from_str(contents).map_err(|err| err.to_string())
+    }
+
+    /// Write the deployments to a TOML file
+    ///
+    /// ### Errors
+    /// If the file can not be written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self).map_err(|err| KeeperError::Config(err.to_string()))?;
+        fs::write(path, contents).map_err(|err| KeeperError::Config(format!("{}: {err}", path.display())))
+    }
+
+    /// Check that every recorded address is a valid account or contract address
+    ///
+    /// ### Errors
+    /// If an address is invalid
+    pub fn validate(&self) -> Result<()> {
+        for (network, deployment) in &self.0 {
+            let addresses = deployment
+                .contracts
+                .iter()
+                .map(|(name, address)| (name as &str, address))
+                .chain(deployment.tokens.iter().map(|(name, address)| (name.as_str(), address)))
+                .chain(deployment.blend_pools.iter().map(|(name, address)| (name.as_str(), address)))
+                .chain(deployment.pairs.iter().map(|(name, address)| (name.as_str(), address)));
+            for (name, address) in addresses {
+                tx::address(address).map_err(|_| KeeperError::Config(format!("{network}.{name}: invalid address {address}")))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch the deployment on a network
+    ///
+    /// ### Errors
+    /// If nothing is deployed on the network
+    pub fn network(&self, network: &str) -> Result<&Deployment> {
+        self.0.get(network).ok_or_else(|| KeeperError::Config(format!("no deployment for network {network}")))
+    }
+
+    /// Fetch the deployment on a network, creating an empty one if nothing is deployed on it
+    pub fn network_mut(&mut self, network: &str) -> &mut Deployment {
+        self.0.entry(network.to_string()).or_default()
+    }
+}
+
+impl Contracts {
+    /// Fetch a contract address by name, e.g. `treasury`
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.iter().find(|(contract, _)| *contract == name).map(|(_, address)| address)
+    }
+
+    /// Record a contract address by name
+    ///
+    /// ### Errors
+    /// If the name is not an orbit contract
+    pub fn set(&mut self, name: &str, address: String) -> Result<()> {
+        let slot = self.slot_mut(name).ok_or_else(|| KeeperError::Config(format!("unknown contract {name}")))?;
+        *slot = Some(address);
+        Ok(())
+    }
+
+    fn slot_mut(&mut self, name: &str) -> Option<&mut Option<String>> {
+        match name {
+            "treasury" => Some(&mut self.treasury),
+            "pegkeeper" => Some(&mut self.pegkeeper),
+            "bridge_oracle" => Some(&mut self.bridge_oracle),
+            "factory" => Some(&mut self.factory),
+            "psm" => Some(&mut self.psm),
+            "stability_pool" => Some(&mut self.stability_pool),
+            "savings" => Some(&mut self.savings),
+            "governance" => Some(&mut self.governance),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the recorded contracts as `(name, address)`
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &String)> {
+        [
+            ("treasury", &self.treasury),
+            ("pegkeeper", &self.pegkeeper),
+            ("bridge_oracle", &self.bridge_oracle),
+            ("factory", &self.factory),
+            ("psm", &self.psm),
+            ("stability_pool", &self.stability_pool),
+            ("savings", &self.savings),
+            ("governance", &self.governance),
+        ]
+        .into_iter()
+        .filter_map(|(name, address)| address.as_ref().map(|address| (name, address)))
+    }
+}
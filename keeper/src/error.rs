@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// An error raised while watching the protocol or submitting a transaction
+#[derive(Debug)]
+pub enum KeeperError {
+    /// the configuration is missing or invalid
+    Config(String),
+    /// the RPC request failed or returned an unexpected response
+    Rpc(String),
+    /// the transaction could not be encoded or decoded
+    Xdr(stellar_xdr::curr::Error),
+    /// the simulation of the transaction failed, e.g. because the contract panicked
+    Simulation(String),
+    /// the transaction was rejected or failed on-chain
+    Transaction(String),
+}
+
+impl fmt::Display for KeeperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeeperError::Config(msg) => write!(f, "invalid config: {msg}"),
+            KeeperError::Rpc(msg) => write!(f, "rpc error: {msg}"),
+            KeeperError::Xdr(err) => write!(f, "xdr error: {err}"),
+            KeeperError::Simulation(msg) => write!(f, "simulation failed: {msg}"),
+            KeeperError::Transaction(msg) => write!(f, "transaction failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for KeeperError {}
+
+impl From<stellar_xdr::curr::Error> for KeeperError {
+    fn from(err: stellar_xdr::curr::Error) -> Self {
+        KeeperError::Xdr(err)
+    }
+}
+
+impl From<reqwest::Error> for KeeperError {
+    fn from(err: reqwest::Error) -> Self {
+        KeeperError::Rpc(err.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, KeeperError>;
@@ -0,0 +1,7 @@
+//! Off-chain clients for the Orbit protocol: a soroban RPC client, a transaction submitter that
+//! signs, fee bumps and retries contract invocations, and the deployment config shared by the tools
+
+pub mod deployment;
+pub mod error;
+pub mod rpc;
+pub mod tx;
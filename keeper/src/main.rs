@@ -0,0 +1,44 @@
+//! A reference keeper bot for the Orbit protocol
+//!
+//! The keeper polls the pegkeeper for the peg deviation of each collateral pair and rebalances pairs
+//! that deviate by more than a threshold. It also watches blend positions, creating liquidation
+//! auctions for unhealthy users and filling them through the treasury.
+//!
+//! The keeper is configured through `ORBIT_*` environment variables and the deployments file, see
+//! [`config::Config`].
+
+mod bot;
+mod config;
+
+use std::process;
+use std::thread;
+
+use bot::Keeper;
+use config::Config;
+use keeper::error;
+use keeper::rpc::RpcClient;
+use keeper::tx::Submitter;
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run() -> error::Result<()> {
+    let config = Config::from_env()?;
+    let submitter = Submitter::new(
+        RpcClient::new(&config.rpc_url),
+        &config.secret_key,
+        &config.network_passphrase,
+        config.base_fee,
+        config.max_fee,
+        config.max_retries,
+    )?;
+    let keeper = Keeper::new(&config, submitter)?;
+    loop {
+        keeper.run_once();
+        thread::sleep(config.poll_interval);
+    }
+}
@@ -0,0 +1,141 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use stellar_xdr::curr::{
+    AccountId, LedgerEntry, LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, ReadXdr, ScVal,
+    SorobanAuthorizationEntry, SorobanTransactionData, TransactionEnvelope, WriteXdr,
+};
+
+use crate::error::{KeeperError, Result};
+
+/// The result of simulating a transaction
+pub struct Simulation {
+    pub transaction_data: SorobanTransactionData, // footprint and resources of the transaction
+    pub min_resource_fee: i64,                    // resource fee the transaction must pay, in stroops
+    pub auth: Vec<SorobanAuthorizationEntry>,     // authorizations recorded during the simulation
+    pub result: ScVal,                            // return value of the invoked function
+}
+
+/// The status of a submitted transaction
+pub enum SendStatus {
+    Pending(String), // accepted, carries the transaction hash
+    Duplicate(String),
+    TryAgainLater,
+    Error(String), // rejected, carries the base64 transaction result
+}
+
+/// The status of a transaction that was accepted by the RPC
+pub enum TxStatus {
+    Success,
+    Failed(String),
+    NotFound,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSimulation {
+    error: Option<String>,
+    transaction_data: Option<String>,
+    min_resource_fee: Option<String>,
+    results: Option<Vec<RawSimulationResult>>,
+}
+
+#[derive(Deserialize)]
+struct RawSimulationResult {
+    auth: Vec<String>,
+    xdr: String,
+}
+
+/// A blocking client for the soroban JSON-RPC interface
+pub struct RpcClient {
+    url: String,
+    http: reqwest::blocking::Client,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> RpcClient {
+        RpcClient { url: url.to_string(), http: reqwest::blocking::Client::new() }
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self.http.post(&self.url).json(&body).send()?.error_for_status()?.json()?;
+        if let Some(error) = response.get("error") {
+            return Err(KeeperError::Rpc(format!("{method}: {error}")));
+        }
+        response
+            .get_mut("result")
+            .map(Value::take)
+            .ok_or_else(|| KeeperError::Rpc(format!("{method}: missing result")))
+    }
+
+    /// Fetch the current sequence number of an account
+    ///
+    /// ### Errors
+    /// If the account does not exist
+    pub fn get_sequence(&self, account: &AccountId) -> Result<i64> {
+        let key = LedgerKey::Account(LedgerKeyAccount { account_id: account.clone() });
+        let result = self.request("getLedgerEntries", json!({ "keys": [key.to_xdr_base64(Limits::none())?] }))?;
+        let entry = result["entries"]
+            .get(0)
+            .and_then(|entry| entry["xdr"].as_str())
+            .ok_or_else(|| KeeperError::Rpc("account not found".to_string()))?;
+        match LedgerEntryData::from_xdr_base64(entry, Limits::none()) {
+            Ok(LedgerEntryData::Account(account)) => Ok(account.seq_num.0),
+            // older RPC versions return the full ledger entry
+            _ => match LedgerEntry::from_xdr_base64(entry, Limits::none())?.data {
+                LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+                _ => Err(KeeperError::Rpc("unexpected ledger entry".to_string())),
+            },
+        }
+    }
+
+    /// Simulate a transaction containing a single host function invocation
+    ///
+    /// ### Errors
+    /// If the invocation fails, e.g. because the contract panicked
+    pub fn simulate(&self, tx: &TransactionEnvelope) -> Result<Simulation> {
+        let result = self.request("simulateTransaction", json!({ "transaction": tx.to_xdr_base64(Limits::none())? }))?;
+        let raw: RawSimulation = serde_json::from_value(result).map_err(|err| KeeperError::Rpc(err.to_string()))?;
+        if let Some(error) = raw.error {
+            return Err(KeeperError::Simulation(error));
+        }
+        let missing = |field: &str| KeeperError::Rpc(format!("simulateTransaction: missing {field}"));
+        let transaction_data = raw.transaction_data.ok_or_else(|| missing("transactionData"))?;
+        let min_resource_fee = raw.min_resource_fee.ok_or_else(|| missing("minResourceFee"))?;
+        let result = raw.results.and_then(|mut results| results.pop()).ok_or_else(|| missing("results"))?;
+        Ok(Simulation {
+            transaction_data: SorobanTransactionData::from_xdr_base64(transaction_data, Limits::none())?,
+            min_resource_fee: min_resource_fee.parse().map_err(|_| missing("minResourceFee"))?,
+            auth: result
+                .auth
+                .iter()
+                .map(|entry| SorobanAuthorizationEntry::from_xdr_base64(entry, Limits::none()))
+                .collect::<std::result::Result<_, _>>()?,
+            result: ScVal::from_xdr_base64(result.xdr, Limits::none())?,
+        })
+    }
+
+    /// Submit a signed transaction
+    pub fn send(&self, tx: &TransactionEnvelope) -> Result<SendStatus> {
+        let result = self.request("sendTransaction", json!({ "transaction": tx.to_xdr_base64(Limits::none())? }))?;
+        let hash = result["hash"].as_str().unwrap_or_default().to_string();
+        match result["status"].as_str() {
+            Some("PENDING") => Ok(SendStatus::Pending(hash)),
+            Some("DUPLICATE") => Ok(SendStatus::Duplicate(hash)),
+            Some("TRY_AGAIN_LATER") => Ok(SendStatus::TryAgainLater),
+            Some("ERROR") => Ok(SendStatus::Error(result["errorResultXdr"].as_str().unwrap_or_default().to_string())),
+            status => Err(KeeperError::Rpc(format!("sendTransaction: unexpected status {status:?}"))),
+        }
+    }
+
+    /// Fetch the status of a submitted transaction
+    pub fn get_transaction(&self, hash: &str) -> Result<TxStatus> {
+        let result = self.request("getTransaction", json!({ "hash": hash }))?;
+        match result["status"].as_str() {
+            Some("SUCCESS") => Ok(TxStatus::Success),
+            Some("FAILED") => Ok(TxStatus::Failed(result["resultXdr"].as_str().unwrap_or_default().to_string())),
+            Some("NOT_FOUND") => Ok(TxStatus::NotFound),
+            status => Err(KeeperError::Rpc(format!("getTransaction: unexpected status {status:?}"))),
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::thread;
+use std::time::Duration;
+
+use ed25519_dalek::{Signer as _, SigningKey};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    AccountId, DecoratedSignature, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+    MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ReadXdr, ScAddress, ScSymbol, ScVal,
+    SequenceNumber, Signature, SignatureHint, Transaction, TransactionEnvelope, TransactionExt, TransactionResult,
+    TransactionResultResult, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
+
+use crate::error::{KeeperError, Result};
+use crate::rpc::{RpcClient, SendStatus, Simulation, TxStatus};
+
+const POLL_ATTEMPTS: u32 = 30; // attempts to fetch the status of a pending transaction
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const BACKOFF: Duration = Duration::from_secs(3); // initial delay before retrying a transaction
+
+/// Parse a contract address, e.g. C...
+pub fn contract_address(address: &str) -> Result<ScAddress> {
+    let contract = stellar_strkey::Contract::from_string(address)
+        .map_err(|_| KeeperError::Config(format!("invalid contract address {address}")))?;
+    Ok(ScAddress::Contract(Hash(contract.0)))
+}
+
+/// Parse an account or contract address, e.g. G... or C...
+pub fn address(address: &str) -> Result<ScAddress> {
+    match stellar_strkey::ed25519::PublicKey::from_string(address) {
+        Ok(key) => Ok(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0))))),
+        Err(_) => contract_address(address),
+    }
+}
+
+/// Build the host function invoking a contract function
+pub fn invoke_contract(contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<HostFunction> {
+    Ok(HostFunction::InvokeContract(InvokeContractArgs {
+        contract_address: contract.clone(),
+        function_name: ScSymbol(function.try_into()?),
+        args: args.try_into()?,
+    }))
+}
+
+/// Signs and submits transactions on behalf of the keeper account
+pub struct Submitter {
+    rpc: RpcClient,
+    key: SigningKey,
+    network_id: Hash,
+    base_fee: u32,
+    max_fee: i64,
+    max_retries: u32,
+}
+
+/// Why a submission attempt failed, and how the next attempt should react
+enum Attempt {
+    Retry,        // a transient error, resubmit the same transaction
+    BumpFee,      // the fee is too low for the network, resubmit with a fee bump
+    Rebuild,      // the sequence number is stale, rebuild the transaction
+    Fatal(String), // the transaction can not succeed
+}
+
+impl Submitter {
+    /// Create a submitter for the account of a secret key, e.g. S...
+    ///
+    /// ### Errors
+    /// If the secret key is invalid
+    pub fn new(
+        rpc: RpcClient,
+        secret_key: &str,
+        network_passphrase: &str,
+        base_fee: u32,
+        max_fee: i64,
+        max_retries: u32,
+    ) -> Result<Submitter> {
+        let secret = stellar_strkey::ed25519::PrivateKey::from_string(secret_key)
+            .map_err(|_| KeeperError::Config("invalid secret key".to_string()))?;
+        Ok(Submitter {
+            rpc,
+            key: SigningKey::from_bytes(&secret.0),
+            network_id: Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+            base_fee,
+            max_fee,
+            max_retries,
+        })
+    }
+
+    fn account_id(&self) -> AccountId {
+        AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(self.key.verifying_key().to_bytes())))
+    }
+
+    fn muxed_account(&self) -> MuxedAccount {
+        MuxedAccount::Ed25519(Uint256(self.key.verifying_key().to_bytes()))
+    }
+
+    /// The address of the keeper account, which signs and pays for transactions
+    pub fn address(&self) -> ScAddress {
+        ScAddress::Account(self.account_id())
+    }
+
+    fn build(&self, host_function: HostFunction) -> Result<Transaction> {
+        let sequence = self.rpc.get_sequence(&self.account_id())?;
+        let operation = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function,
+                auth: VecM::default(),
+            }),
+        };
+        Ok(Transaction {
+            source_account: self.muxed_account(),
+            fee: self.base_fee,
+            seq_num: SequenceNumber(sequence + 1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![operation].try_into()?,
+            ext: TransactionExt::V0,
+        })
+    }
+
+    fn unsigned(tx: Transaction) -> TransactionEnvelope {
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures: VecM::default() })
+    }
+
+    fn sign(&self, tagged_transaction: TransactionSignaturePayloadTaggedTransaction) -> Result<VecM<DecoratedSignature, 20>> {
+        let payload = TransactionSignaturePayload { network_id: self.network_id.clone(), tagged_transaction };
+        let hash = Sha256::digest(payload.to_xdr(Limits::none())?);
+        let public_key = self.key.verifying_key().to_bytes();
+        let signature = DecoratedSignature {
+            hint: SignatureHint(public_key[28..].try_into().expect("public key is 32 bytes")),
+            signature: Signature(self.key.sign(&hash).to_bytes().to_vec().try_into()?),
+        };
+        Ok(vec![signature].try_into()?)
+    }
+
+    /// Assemble a simulated transaction with its resources, fee and authorizations, then sign it
+    fn assemble(&self, mut tx: Transaction, simulation: &Simulation) -> Result<TransactionEnvelope> {
+        let fee = i64::from(self.base_fee) + simulation.min_resource_fee;
+        tx.fee = u32::try_from(fee).map_err(|_| KeeperError::Transaction(format!("fee {fee} is too large")))?;
+        tx.ext = TransactionExt::V1(simulation.transaction_data.clone());
+        if let Some(Operation { body: OperationBody::InvokeHostFunction(op), .. }) = tx.operations.first().cloned() {
+            let op = InvokeHostFunctionOp { host_function: op.host_function, auth: simulation.auth.clone().try_into()? };
+            tx.operations = vec![Operation { source_account: None, body: OperationBody::InvokeHostFunction(op) }].try_into()?;
+        }
+        let signatures = self.sign(TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()))?;
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }))
+    }
+
+    /// Wrap a signed transaction in a fee bump paying `fee` stroops, signed by the keeper account
+    fn fee_bump(&self, inner: &TransactionEnvelope, fee: i64) -> Result<TransactionEnvelope> {
+        let inner = match inner {
+            TransactionEnvelope::Tx(inner) => inner.clone(),
+            TransactionEnvelope::TxFeeBump(bump) => match &bump.tx.inner_tx {
+                FeeBumpTransactionInnerTx::Tx(inner) => inner.clone(),
+            },
+            TransactionEnvelope::TxV0(_) => return Err(KeeperError::Transaction("unsupported envelope".to_string())),
+        };
+        let tx = FeeBumpTransaction {
+            fee_source: self.muxed_account(),
+            fee,
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+            ext: FeeBumpTransactionExt::V0,
+        };
+        let signatures = self.sign(TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone()))?;
+        Ok(TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, signatures }))
+    }
+
+    /// Simulate a contract invocation and return its result, without submitting it
+    ///
+    /// ### Errors
+    /// If the invocation fails
+    pub fn simulate(&self, contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<ScVal> {
+        self.simulate_host_function(invoke_contract(contract, function, args)?)
+    }
+
+    /// Simulate a host function and return its result, without submitting it
+    ///
+    /// ### Errors
+    /// If the host function fails
+    pub fn simulate_host_function(&self, host_function: HostFunction) -> Result<ScVal> {
+        let tx = self.build(host_function)?;
+        Ok(self.rpc.simulate(&Self::unsigned(tx))?.result)
+    }
+
+    /// Simulate, sign and submit a contract invocation, then wait for it to be applied
+    ///
+    /// Transient errors are retried with an exponential backoff. If the network rejects the fee, the
+    /// transaction is resubmitted in a fee bump that doubles the fee each attempt, up to the max fee.
+    /// If the sequence number is stale, the transaction is rebuilt.
+    ///
+    /// ### Errors
+    /// If the simulation fails, the transaction fails on-chain, or the retries are exhausted
+    pub fn submit(&self, contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<ScVal> {
+        self.submit_host_function(invoke_contract(contract, function, args)?)
+    }
+
+    /// Simulate, sign and submit a host function, then wait for it to be applied. See [`Submitter::submit`].
+    ///
+    /// ### Errors
+    /// If the simulation fails, the transaction fails on-chain, or the retries are exhausted
+    pub fn submit_host_function(&self, host_function: HostFunction) -> Result<ScVal> {
+        let mut attempt = 0;
+        let mut backoff = BACKOFF;
+        'rebuild: loop {
+            let tx = self.build(host_function.clone())?;
+            let simulation = self.rpc.simulate(&Self::unsigned(tx.clone()))?;
+            let mut envelope = self.assemble(tx, &simulation)?;
+            let mut fee = i64::from(self.base_fee) + simulation.min_resource_fee;
+            loop {
+                attempt += 1;
+                if attempt > self.max_retries {
+                    return Err(KeeperError::Transaction("retries exhausted".to_string()));
+                }
+                match self.send_and_wait(&envelope) {
+                    Ok(()) => return Ok(simulation.result),
+                    Err(Attempt::Fatal(msg)) => return Err(KeeperError::Transaction(msg)),
+                    Err(Attempt::Rebuild) => continue 'rebuild,
+                    Err(Attempt::Retry) => {}
+                    Err(Attempt::BumpFee) => {
+                        if fee >= self.max_fee {
+                            return Err(KeeperError::Transaction("fee exceeds the max fee".to_string()));
+                        }
+                        fee = (fee * 2).min(self.max_fee);
+                        envelope = self.fee_bump(&envelope, fee)?;
+                    }
+                }
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+        }
+    }
+
+    fn send_and_wait(&self, envelope: &TransactionEnvelope) -> std::result::Result<(), Attempt> {
+        let hash = match self.rpc.send(envelope) {
+            Ok(SendStatus::Pending(hash)) | Ok(SendStatus::Duplicate(hash)) => hash,
+            Ok(SendStatus::TryAgainLater) => return Err(Attempt::BumpFee),
+            Ok(SendStatus::Error(result)) => return Err(Self::classify(&result)),
+            Err(_) => return Err(Attempt::Retry),
+        };
+        for _ in 0..POLL_ATTEMPTS {
+            thread::sleep(POLL_INTERVAL);
+            match self.rpc.get_transaction(&hash) {
+                Ok(TxStatus::Success) => return Ok(()),
+                Ok(TxStatus::Failed(result)) => return Err(Self::classify(&result)),
+                Ok(TxStatus::NotFound) | Err(_) => {}
+            }
+        }
+        Err(Attempt::Retry)
+    }
+
+    fn classify(result: &str) -> Attempt {
+        match TransactionResult::from_xdr_base64(result, Limits::none()).map(|result| result.result) {
+            Ok(TransactionResultResult::TxInsufficientFee) => Attempt::BumpFee,
+            Ok(TransactionResultResult::TxBadSeq) => Attempt::Rebuild,
+            Ok(TransactionResultResult::TxTooLate) | Ok(TransactionResultResult::TxInternalError) => Attempt::Retry,
+            Ok(result) => Attempt::Fatal(result.name().to_string()),
+            Err(_) => Attempt::Fatal(result.to_string()),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::helper::{self, SCALAR_7};
+use crate::storage;
+use crate::errors::SavingsError;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env, Symbol};
+use soroban_sdk::unwrap::UnwrapOptimized;
+use token::Client as TokenClient;
+use token::StellarAssetClient as TokenAdminClient;
+
+#[contract]
+pub struct SavingsContract;
+
+#[contractclient(name="SavingsClient")]
+pub trait Savings {
+
+    /// Initialize the savings vault
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin (the treasury)
+    /// * `token` - The Address of the stablecoin deposited into the vault
+    /// * `share_token` - The Address of the share token, administered by the vault
+    /// * `rate_bps` - The savings rate, in basis points per year
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address, token: Address, share_token: Address, rate_bps: u32);
+
+    /// Deposit stablecoins into the vault and mint shares
+    ///
+    /// ### Arguments
+    /// * `from` - The Address of the depositor
+    /// * `amount` - The amount of stablecoins to deposit
+    ///
+    /// Returns the shares minted to the depositor
+    ///
+    /// ### Panics
+    /// If the amount is not positive
+    fn deposit(e: Env, from: Address, amount: i128) -> i128;
+
+    /// Burn shares and withdraw the stablecoins backing them
+    ///
+    /// ### Arguments
+    /// * `from` - The Address of the depositor
+    /// * `shares` - The amount of shares to burn
+    ///
+    /// Returns the stablecoins withdrawn
+    ///
+    /// ### Panics
+    /// If the shares are not positive or exceed the vault's shares
+    fn withdraw(e: Env, from: Address, shares: i128) -> i128;
+
+    /// (Admin only) Add treasury yield to the vault, accrued to the shares at the savings rate
+    ///
+    /// ### Arguments
+    /// * `amount` - The amount of stablecoins
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the amount is not positive
+    fn distribute_yield(e: Env, amount: i128);
+
+    /// (Admin only) Set the savings rate
+    ///
+    /// ### Arguments
+    /// * `rate_bps` - The savings rate, in basis points per year
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_savings_rate(e: Env, rate_bps: u32);
+
+    /// Fetch the savings rate, in basis points per year
+    fn get_savings_rate(e: Env) -> u32;
+
+    /// Fetch the stablecoins backing one share, in 7 decimals
+    fn get_exchange_rate(e: Env) -> i128;
+
+    /// Fetch the stablecoins backing all shares
+    fn get_total_assets(e: Env) -> i128;
+
+    /// Fetch the yield received and not yet accrued to the shares
+    fn get_buffer(e: Env) -> i128;
+}
+
+#[contractimpl]
+impl Savings for SavingsContract {
+    fn initialize(e: Env, admin: Address, token: Address, share_token: Address, rate_bps: u32) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, SavingsError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_token(&e, &token);
+        storage::set_share_token(&e, &share_token);
+        storage::set_rate(&e, rate_bps);
+        storage::set_last_accrual(&e, e.ledger().timestamp());
+    }
+
+    fn deposit(e: Env, from: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, SavingsError::InvalidAmount);
+        }
+
+        helper::accrue(&e);
+        let shares = helper::to_shares(&e, amount, false);
+        if shares <= 0 {
+            panic_with_error!(&e, SavingsError::InvalidAmount);
+        }
+
+        TokenClient::new(&e, &storage::get_token(&e)).transfer(&from, &e.current_contract_address(), &amount);
+        TokenAdminClient::new(&e, &storage::get_share_token(&e)).mint(&from, &shares);
+        storage::set_total_assets(&e, storage::get_total_assets(&e) + amount);
+        storage::set_total_shares(&e, storage::get_total_shares(&e) + shares);
+
+        e.events().publish(("Savings", Symbol::new(&e, "deposit")), (from, amount, shares));
+        shares
+    }
+
+    fn withdraw(e: Env, from: Address, shares: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+        let total_shares = storage::get_total_shares(&e);
+        if shares <= 0 {
+            panic_with_error!(&e, SavingsError::InvalidAmount);
+        }
+        if shares > total_shares {
+            panic_with_error!(&e, SavingsError::InsufficientBalanceError);
+        }
+
+        helper::accrue(&e);
+        let amount = helper::to_assets(&e, shares);
+
+        TokenClient::new(&e, &storage::get_share_token(&e)).burn(&from, &shares);
+        storage::set_total_assets(&e, storage::get_total_assets(&e) - amount);
+        storage::set_total_shares(&e, total_shares - shares);
+        TokenClient::new(&e, &storage::get_token(&e)).transfer(&e.current_contract_address(), &from, &amount);
+
+        e.events().publish(("Savings", Symbol::new(&e, "withdraw")), (from, amount, shares));
+        amount
+    }
+
+    fn distribute_yield(e: Env, amount: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, SavingsError::InvalidAmount);
+        }
+
+        helper::accrue(&e);
+        TokenClient::new(&e, &storage::get_token(&e)).transfer(&admin, &e.current_contract_address(), &amount);
+        storage::set_buffer(&e, storage::get_buffer(&e) + amount);
+
+        e.events().publish(("Savings", Symbol::new(&e, "distribute_yield")), amount);
+    }
+
+    fn set_savings_rate(e: Env, rate_bps: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        // accrue at the previous rate up to now
+        helper::accrue(&e);
+        storage::set_rate(&e, rate_bps);
+
+        e.events().publish(("Savings", Symbol::new(&e, "set_savings_rate")), rate_bps);
+    }
+
+    fn get_savings_rate(e: Env) -> u32 {
+        storage::get_rate(&e)
+    }
+
+    fn get_exchange_rate(e: Env) -> i128 {
+        let total_shares = storage::get_total_shares(&e);
+        if total_shares == 0 {
+            return SCALAR_7;
+        }
+        let total_assets = storage::get_total_assets(&e) + helper::calc_accrued_yield(&e);
+        total_assets.fixed_div_floor(total_shares, SCALAR_7).unwrap_optimized()
+    }
+
+    fn get_total_assets(e: Env) -> i128 {
+        storage::get_total_assets(&e) + helper::calc_accrued_yield(&e)
+    }
+
+    fn get_buffer(e: Env) -> i128 {
+        storage::get_buffer(&e) - helper::calc_accrued_yield(&e)
+    }
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SavingsError {
+    /// not yet initialized
+    NotInitialized = 1201,
+
+    /// already initialized
+    AlreadyInitializedError = 1202,
+
+    /// amount is zero or negative
+    InvalidAmount = 1203,
+
+    /// not enough shares to execute the operation
+    InsufficientBalanceError = 1204,
+}
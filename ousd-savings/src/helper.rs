@@ -0,0 +1,68 @@
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::Env;
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+use crate::storage;
+
+pub(crate) const SCALAR_7: i128 = 1_0000000;
+pub(crate) const SCALAR_BPS: i128 = 10_000;
+pub(crate) const SECONDS_PER_YEAR: i128 = 31_536_000;
+
+/// Calculate the yield accrued to the shares since the last accrual
+///
+/// Yield is released from the buffer at most at the savings rate on the total assets.
+///
+/// Returns the yield accrued
+pub fn calc_accrued_yield(e: &Env) -> i128 {
+    let buffer = storage::get_buffer(e);
+    let last_accrual = storage::get_last_accrual(e);
+    let now = e.ledger().timestamp();
+    if buffer <= 0 || now <= last_accrual {
+        return 0;
+    }
+    let elapsed = i128::from(now - last_accrual);
+    let max_yield = storage::get_total_assets(e)
+        .fixed_mul_floor(i128::from(storage::get_rate(e)) * elapsed, SCALAR_BPS * SECONDS_PER_YEAR)
+        .unwrap_optimized();
+    max_yield.min(buffer)
+}
+
+/// Move the accrued yield from the buffer into the assets backing the shares
+pub fn accrue(e: &Env) {
+    let accrued = calc_accrued_yield(e);
+    if accrued > 0 {
+        storage::set_buffer(e, storage::get_buffer(e) - accrued);
+        storage::set_total_assets(e, storage::get_total_assets(e) + accrued);
+    }
+    storage::set_last_accrual(e, e.ledger().timestamp());
+}
+
+/// Convert an amount of stablecoins to shares
+///
+/// ### Arguments
+/// * `amount` - The amount of stablecoins
+/// * `round_up` - Whether to round the shares up
+pub fn to_shares(e: &Env, amount: i128, round_up: bool) -> i128 {
+    let total_shares = storage::get_total_shares(e);
+    let total_assets = storage::get_total_assets(e);
+    if total_shares == 0 || total_assets == 0 {
+        return amount;
+    }
+    if round_up {
+        amount.fixed_mul_ceil(total_shares, total_assets).unwrap_optimized()
+    } else {
+        amount.fixed_mul_floor(total_shares, total_assets).unwrap_optimized()
+    }
+}
+
+/// Convert an amount of shares to stablecoins, rounding down
+///
+/// ### Arguments
+/// * `shares` - The amount of shares
+pub fn to_assets(e: &Env, shares: i128) -> i128 {
+    let total_shares = storage::get_total_shares(e);
+    if total_shares == 0 {
+        return shares;
+    }
+    shares.fixed_mul_floor(storage::get_total_assets(e), total_shares).unwrap_optimized()
+}
@@ -0,0 +1,9 @@
+#![no_std]
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod storage;
+mod contract;
+mod errors;
+mod helper;
+pub use contract::*;
@@ -0,0 +1,181 @@
+use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TOKEN,
+    SHARETOKEN,
+    TOTALSHARES,
+    TOTALASSETS,
+    BUFFER,
+    RATE,
+    LASTACCRUAL,
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the stablecoin deposited into the vault
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the stablecoin deposited into the vault
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the share token minted to depositors
+///
+/// ### Panics
+/// If the share token does not exist
+pub fn get_share_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::SHARETOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the share token minted to depositors
+///
+/// ### Arguments
+/// * `share_token` - The Address of the share token
+pub fn set_share_token(e: &Env, share_token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SHARETOKEN, share_token);
+}
+
+/// Fetch the total shares minted by the vault
+pub fn get_total_shares(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOTALSHARES)
+        .unwrap_or(0)
+}
+
+/// Set the total shares minted by the vault
+///
+/// ### Arguments
+/// * `total_shares` - The total shares
+pub fn set_total_shares(e: &Env, total_shares: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOTALSHARES, &total_shares);
+}
+
+/// Fetch the stablecoins backing the shares
+pub fn get_total_assets(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOTALASSETS)
+        .unwrap_or(0)
+}
+
+/// Set the stablecoins backing the shares
+///
+/// ### Arguments
+/// * `total_assets` - The total assets
+pub fn set_total_assets(e: &Env, total_assets: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOTALASSETS, &total_assets);
+}
+
+/// Fetch the yield received from the treasury and not yet accrued to the shares
+pub fn get_buffer(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::BUFFER)
+        .unwrap_or(0)
+}
+
+/// Set the yield received from the treasury and not yet accrued to the shares
+///
+/// ### Arguments
+/// * `buffer` - The undistributed yield
+pub fn set_buffer(e: &Env, buffer: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BUFFER, &buffer);
+}
+
+/// Fetch the savings rate, in basis points per year
+pub fn get_rate(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::RATE)
+        .unwrap_or(0)
+}
+
+/// Set the savings rate
+///
+/// ### Arguments
+/// * `rate_bps` - The savings rate, in basis points per year
+pub fn set_rate(e: &Env, rate_bps: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RATE, &rate_bps);
+}
+
+/// Fetch the timestamp yield was last accrued at
+pub fn get_last_accrual(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::LASTACCRUAL)
+        .unwrap_or(0)
+}
+
+/// Set the timestamp yield was last accrued at
+///
+/// ### Arguments
+/// * `timestamp` - The accrual timestamp
+pub fn set_last_accrual(e: &Env, timestamp: u64) {
+    e.storage()
+        .instance()
+        .set(&DataKey::LASTACCRUAL, &timestamp);
+}
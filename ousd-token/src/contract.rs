@@ -0,0 +1,294 @@
+use crate::errors::TokenError;
+use crate::storage::{self, AllowanceValue, TokenMetadata};
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env, String, Symbol};
+
+#[contract]
+pub struct OusdTokenContract;
+
+#[contractclient(name="OusdTokenClient")]
+pub trait OusdToken {
+
+    /// Initialize the token
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin, the only address allowed to mint (the treasury)
+    /// * `decimal` - The decimals of the token
+    /// * `name` - The name of the token
+    /// * `symbol` - The symbol of the token
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address, decimal: u32, name: String, symbol: String);
+
+    /// (Admin only) Mint tokens to an account
+    ///
+    /// ### Arguments
+    /// * `to` - The Address receiving the tokens
+    /// * `amount` - The amount to mint
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the amount is negative, or the account is frozen
+    fn mint(e: Env, to: Address, amount: i128);
+
+    /// (Admin only) Set a new address as the admin of the token
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The new admin address
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// Fetch the admin of the token
+    fn admin(e: Env) -> Address;
+
+    /// Set or remove the compliance authority allowed to freeze accounts. Freezing is disabled
+    /// while no compliance authority is set.
+    ///
+    /// Requires the authorization of the current compliance authority, or of the admin while none is set.
+    ///
+    /// ### Arguments
+    /// * `compliance` - The Address of the compliance authority, or None to disable freezing
+    fn set_compliance(e: Env, compliance: Option<Address>);
+
+    /// Fetch the compliance authority, if any
+    fn compliance(e: Env) -> Option<Address>;
+
+    /// (Compliance authority only) Freeze or unfreeze an account. A frozen account can not send,
+    /// receive, approve or burn tokens.
+    ///
+    /// ### Arguments
+    /// * `account` - The Address of the account
+    /// * `frozen` - Whether the account is frozen
+    ///
+    /// ### Panics
+    /// If no compliance authority is set or the caller is not the compliance authority
+    fn set_frozen(e: Env, account: Address, frozen: bool);
+
+    /// Check if an account is frozen
+    ///
+    /// ### Arguments
+    /// * `account` - The Address of the account
+    fn is_frozen(e: Env, account: Address) -> bool;
+
+    /// Fetch the total supply of the token, the amount minted less the amount burnt
+    fn total_supply(e: Env) -> i128;
+}
+
+fn require_nonnegative(e: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(e, TokenError::InvalidAmount);
+    }
+}
+
+fn require_not_frozen(e: &Env, account: &Address) {
+    if storage::is_frozen(e, account) {
+        panic_with_error!(e, TokenError::FrozenError);
+    }
+}
+
+fn spend_balance(e: &Env, from: &Address, amount: i128) {
+    let balance = storage::get_balance(e, from);
+    if balance < amount {
+        panic_with_error!(e, TokenError::InsufficientBalanceError);
+    }
+    storage::set_balance(e, from, balance - amount);
+}
+
+fn receive_balance(e: &Env, to: &Address, amount: i128) {
+    storage::set_balance(e, to, storage::get_balance(e, to) + amount);
+}
+
+fn spend_allowance(e: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = storage::get_allowance(e, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(e, TokenError::InsufficientAllowanceError);
+    }
+    if amount > 0 {
+        storage::set_allowance(
+            e,
+            from,
+            spender,
+            &AllowanceValue { amount: allowance.amount - amount, expiration_ledger: allowance.expiration_ledger },
+        );
+    }
+}
+
+#[contractimpl]
+impl OusdToken for OusdTokenContract {
+    fn initialize(e: Env, admin: Address, decimal: u32, name: String, symbol: String) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, TokenError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_metadata(&e, &TokenMetadata { decimal, name, symbol });
+    }
+
+    fn mint(e: Env, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        require_nonnegative(&e, amount);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        require_not_frozen(&e, &to);
+
+        receive_balance(&e, &to, amount);
+        storage::set_total_supply(&e, storage::get_total_supply(&e) + amount);
+
+        e.events().publish((Symbol::new(&e, "mint"), admin, to), amount);
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+
+        e.events().publish((Symbol::new(&e, "set_admin"), admin), new_admin);
+    }
+
+    fn admin(e: Env) -> Address {
+        storage::extend_instance(&e);
+        storage::get_admin(&e)
+    }
+
+    fn set_compliance(e: Env, compliance: Option<Address>) {
+        storage::extend_instance(&e);
+        match storage::get_compliance(&e) {
+            Some(current) => current.require_auth(),
+            None => storage::get_admin(&e).require_auth(),
+        }
+
+        match compliance.clone() {
+            Some(compliance) => storage::set_compliance(&e, &compliance),
+            None => storage::del_compliance(&e),
+        }
+
+        e.events().publish((Symbol::new(&e, "set_compliance"),), compliance);
+    }
+
+    fn compliance(e: Env) -> Option<Address> {
+        storage::extend_instance(&e);
+        storage::get_compliance(&e)
+    }
+
+    fn set_frozen(e: Env, account: Address, frozen: bool) {
+        storage::extend_instance(&e);
+        match storage::get_compliance(&e) {
+            Some(compliance) => compliance.require_auth(),
+            None => panic_with_error!(&e, TokenError::FreezeDisabledError),
+        }
+
+        storage::set_frozen(&e, &account, frozen);
+
+        e.events().publish((Symbol::new(&e, "set_frozen"), account), frozen);
+    }
+
+    fn is_frozen(e: Env, account: Address) -> bool {
+        storage::extend_instance(&e);
+        storage::is_frozen(&e, &account)
+    }
+
+    fn total_supply(e: Env) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_total_supply(&e)
+    }
+}
+
+#[contractimpl]
+impl token::Interface for OusdTokenContract {
+    fn allowance(e: Env, from: Address, spender: Address) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_allowance(&e, &from, &spender).amount
+    }
+
+    fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        require_nonnegative(&e, amount);
+        require_not_frozen(&e, &from);
+        if amount > 0 && expiration_ledger < e.ledger().sequence() {
+            panic_with_error!(&e, TokenError::InvalidExpirationError);
+        }
+
+        storage::set_allowance(&e, &from, &spender, &AllowanceValue { amount, expiration_ledger });
+
+        e.events().publish((Symbol::new(&e, "approve"), from, spender), (amount, expiration_ledger));
+    }
+
+    fn balance(e: Env, id: Address) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_balance(&e, &id)
+    }
+
+    fn transfer(e: Env, from: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        require_nonnegative(&e, amount);
+        require_not_frozen(&e, &from);
+        require_not_frozen(&e, &to);
+
+        spend_balance(&e, &from, amount);
+        receive_balance(&e, &to, amount);
+
+        e.events().publish((Symbol::new(&e, "transfer"), from, to), amount);
+    }
+
+    fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        spender.require_auth();
+        require_nonnegative(&e, amount);
+        require_not_frozen(&e, &spender);
+        require_not_frozen(&e, &from);
+        require_not_frozen(&e, &to);
+
+        spend_allowance(&e, &from, &spender, amount);
+        spend_balance(&e, &from, amount);
+        receive_balance(&e, &to, amount);
+
+        e.events().publish((Symbol::new(&e, "transfer"), from, to), amount);
+    }
+
+    fn burn(e: Env, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        require_nonnegative(&e, amount);
+        require_not_frozen(&e, &from);
+
+        spend_balance(&e, &from, amount);
+        storage::set_total_supply(&e, storage::get_total_supply(&e) - amount);
+
+        e.events().publish((Symbol::new(&e, "burn"), from), amount);
+    }
+
+    fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        spender.require_auth();
+        require_nonnegative(&e, amount);
+        require_not_frozen(&e, &spender);
+        require_not_frozen(&e, &from);
+
+        spend_allowance(&e, &from, &spender, amount);
+        spend_balance(&e, &from, amount);
+        storage::set_total_supply(&e, storage::get_total_supply(&e) - amount);
+
+        e.events().publish((Symbol::new(&e, "burn"), from), amount);
+    }
+
+    fn decimals(e: Env) -> u32 {
+        storage::extend_instance(&e);
+        storage::get_metadata(&e).decimal
+    }
+
+    fn name(e: Env) -> String {
+        storage::extend_instance(&e);
+        storage::get_metadata(&e).name
+    }
+
+    fn symbol(e: Env) -> String {
+        storage::extend_instance(&e);
+        storage::get_metadata(&e).symbol
+    }
+}
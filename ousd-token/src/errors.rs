@@ -0,0 +1,30 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenError {
+    /// not yet initialized
+    NotInitialized = 1701,
+
+    /// already initialized
+    AlreadyInitializedError = 1702,
+
+    /// amount is negative
+    InvalidAmount = 1703,
+
+    /// not enough balance to execute the operation
+    InsufficientBalanceError = 1704,
+
+    /// not enough allowance to execute the operation
+    InsufficientAllowanceError = 1705,
+
+    /// the expiration ledger of an allowance is in the past
+    InvalidExpirationError = 1706,
+
+    /// the account is frozen
+    FrozenError = 1707,
+
+    /// no compliance authority is set
+    FreezeDisabledError = 1708,
+}
@@ -0,0 +1,233 @@
+use soroban_sdk::{contracttype, Address, Env, String};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    COMPLIANCE,
+    METADATA,
+    BALANCE(Address), // mapping account address to its balance
+    ALLOWANCE(AllowanceKey), // mapping owner and spender to the allowance
+    FROZEN(Address), // mapping account address to whether it is frozen
+    SUPPLY,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct AllowanceKey {
+    pub from: Address,
+    pub spender: Address,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct TokenMetadata {
+    pub decimal: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address, the mint authority
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the compliance authority, if any
+pub fn get_compliance(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::COMPLIANCE)
+}
+
+/// Set the compliance authority
+///
+/// ### Arguments
+/// * `compliance` - The Address of the compliance authority
+pub fn set_compliance(e: &Env, compliance: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::COMPLIANCE, compliance);
+}
+
+/// Remove the compliance authority
+pub fn del_compliance(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::COMPLIANCE);
+}
+
+/// Fetch the token metadata
+///
+/// ### Panics
+/// If the metadata does not exist
+pub fn get_metadata(e: &Env) -> TokenMetadata {
+    e.storage()
+        .instance()
+        .get(&DataKey::METADATA)
+        .unwrap_optimized()
+}
+
+/// Set the token metadata
+///
+/// ### Arguments
+/// * `metadata` - The token metadata
+pub fn set_metadata(e: &Env, metadata: &TokenMetadata) {
+    e.storage()
+        .instance()
+        .set(&DataKey::METADATA, metadata);
+}
+
+/// Fetch the balance of an account
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+pub fn get_balance(e: &Env, account: &Address) -> i128 {
+    let key = DataKey::BALANCE(account.clone());
+    match e.storage().persistent().get::<DataKey, i128>(&key) {
+        Some(balance) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            balance
+        }
+        None => 0,
+    }
+}
+
+/// Set the balance of an account
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+/// * `balance` - The balance
+pub fn set_balance(e: &Env, account: &Address, balance: i128) {
+    let key = DataKey::BALANCE(account.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &balance);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the allowance of a spender over the balance of an owner. An expired allowance is 0.
+///
+/// ### Arguments
+/// * `from` - The Address of the owner
+/// * `spender` - The Address of the spender
+pub fn get_allowance(e: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let key = DataKey::ALLOWANCE(AllowanceKey { from: from.clone(), spender: spender.clone() });
+    match e.storage().temporary().get::<DataKey, AllowanceValue>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= e.ledger().sequence() => allowance,
+        Some(allowance) => AllowanceValue { amount: 0, expiration_ledger: allowance.expiration_ledger },
+        None => AllowanceValue { amount: 0, expiration_ledger: 0 },
+    }
+}
+
+/// Set the allowance of a spender over the balance of an owner, kept until its expiration ledger
+///
+/// ### Arguments
+/// * `from` - The Address of the owner
+/// * `spender` - The Address of the spender
+/// * `allowance` - The allowance
+pub fn set_allowance(e: &Env, from: &Address, spender: &Address, allowance: &AllowanceValue) {
+    let key = DataKey::ALLOWANCE(AllowanceKey { from: from.clone(), spender: spender.clone() });
+    e.storage()
+        .temporary()
+        .set::<DataKey, AllowanceValue>(&key, allowance);
+    if allowance.amount > 0 {
+        let live_for = allowance.expiration_ledger.saturating_sub(e.ledger().sequence());
+        e.storage()
+            .temporary()
+            .extend_ttl(&key, live_for, live_for);
+    }
+}
+
+/// Check if an account is frozen
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+pub fn is_frozen(e: &Env, account: &Address) -> bool {
+    e.storage()
+        .persistent()
+        .get::<DataKey, bool>(&DataKey::FROZEN(account.clone()))
+        .unwrap_or(false)
+}
+
+/// Freeze or unfreeze an account
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+/// * `frozen` - Whether the account is frozen
+pub fn set_frozen(e: &Env, account: &Address, frozen: bool) {
+    let key = DataKey::FROZEN(account.clone());
+    if frozen {
+        e.storage()
+            .persistent()
+            .set::<DataKey, bool>(&key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage()
+            .persistent()
+            .remove(&key);
+    }
+}
+
+/// Fetch the total supply of the token
+pub fn get_total_supply(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::SUPPLY)
+        .unwrap_or(0)
+}
+
+/// Set the total supply of the token
+///
+/// ### Arguments
+/// * `supply` - The total supply
+pub fn set_total_supply(e: &Env, supply: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SUPPLY, &supply);
+}
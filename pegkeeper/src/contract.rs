@@ -1,9 +1,9 @@
-use soroban_sdk::{contract, contractclient, contractimpl, log, panic_with_error, token, Address, Env, vec, Val, Vec, IntoVal, Symbol};
-use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
-use crate::{errors::PegkeeperError, storage, helper};
+use soroban_sdk::{contract, contractclient, contractimpl, log, panic_with_error, token, xdr::FromXdr, Address, Bytes, Env, vec, Val, Vec, IntoVal, Symbol};
+use crate::{errors::PegkeeperError, storage::{self, AuctionConfig, BackstopAuctionConfig, DexConfig, FlashLoanOp, FxConfig, OracleConfig, PegConfig, RebalanceQuote, Strategy, StrategyConfig, StrategyLimits, TwapConfig}, helper};
 use crate::dependencies::{
     router::{Client as RouterClient},
     pool::{Client as PoolClient, Request},
+    oracle::{self, SCALAR_7},
 };
 #[contract]
 pub struct PegkeeperContract;
@@ -17,18 +17,422 @@ pub trait Pegkeeper {
     /// * `maximum_duration` - The maximum_duration for swap transaction
     fn initialize(e: Env, admin: Address, router: Address);
 
-    /// Execute operation
+    /// (Admin only) Flash loan callback
+    ///
+    /// Executes the operation encoded in the calldata with the loaned tokens, then approves the
+    /// treasury to pull the loan plus the fee. Any surplus is kept by the pegkeeper as profit.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the loaned token
+    /// * `amount` - The loaned amount
+    /// * `data` - The XDR encoded `FlashLoanOp` to execute
+    /// * `fee` - Flash loan fee owed to the treasury on top of the loaned amount
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the calldata can not be decoded, or the operation realizes less than the minimum profit
+    fn fl_receive(e: Env, token: Address, amount: i128, data: Bytes, fee: i128);
+
+    /// (Admin only) Register a strategy contract under an id, or replace the contract of an id.
+    /// Strategies implement `execute(token, amount, params)` and are executed with `execute_strategy`
+    /// or with a `FlashLoanOp::Strategy` flash loan.
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the strategy
+    /// * `contract` - The Address of the strategy contract
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn register_strategy(e: Env, id: u32, contract: Address);
+
+    /// (Admin only) Enable or disable a registered strategy
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the strategy
+    /// * `enabled` - Whether the strategy can be executed
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the strategy is not registered
+    fn set_strategy_enabled(e: Env, id: u32, enabled: bool);
+
+    /// Fetch a registered strategy, if any
     ///
     /// ### Arguments
-    /// * `pair` - Soroswap address
-    /// * `auction_creator` - Auction creator address
-    /// * `token_a` - Token A address
-    /// * `token_a_bid_amount` - Token A bid amount
-    /// * `token_b` - Token B address
-    /// * `token_b_lot_amount` - Token B lot amount
-    /// * `blend_pool` - Blend pool address
-    /// * `liq_amount` - Liquidate amount
-    fn fl_receive(e: Env, pair: Address, auction_creator: Address, token_a: Address, token_a_bid_amount: i128, token_b: Address, token_b_lot_amount: i128, blend_pool: Address, liq_amount: i128);
+    /// * `id` - The id of the strategy
+    fn get_strategy(e: Env, id: u32) -> Option<StrategyConfig>;
+
+    /// (Admin only) Set or remove the risk limits of a strategy, enforced each time it is executed
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the strategy
+    /// * `limits` - The risk limits, or None to remove them
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the limits are negative
+    fn set_strategy_limits(e: Env, id: u32, limits: Option<StrategyLimits>);
+
+    /// Fetch the risk limits of a strategy, if any
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the strategy
+    fn get_strategy_limits(e: Env, id: u32) -> Option<StrategyLimits>;
+
+    /// (Admin only) Execute a registered strategy with tokens held by the pegkeeper
+    ///
+    /// Returns the profit realized in the token
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the strategy
+    /// * `token` - The Address of the token provided to the strategy
+    /// * `amount` - The amount of the token provided to the strategy
+    /// * `params` - The parameters passed to the strategy
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the strategy is not registered or is disabled, the execution exceeds
+    /// the strategy's risk limits, the strategy returns less of the token than it was provided,
+    /// or the profit is below the minimum profit
+    fn execute_strategy(e: Env, id: u32, token: Address, amount: i128, params: Bytes) -> i128;
+
+    /// Forward profits held by the pegkeeper to the treasury's surplus buffer
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to forward
+    fn forward_profit(e: Env, token: Address, amount: i128);
+
+    /// (Admin only) Transfer tokens accidentally sent to the pegkeeper. The treasury, as the admin, only sweeps
+    /// tokens its accounting does not track.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `to` - The Address receiving the tokens
+    /// * `amount` - The amount to transfer
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the amount is not positive, or the amount exceeds the pegkeeper's balance
+    fn sweep(e: Env, token: Address, to: Address, amount: i128);
+
+    /// (Admin only) Set the soroswap path used to sell a collateral for the stablecoin
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the collateral
+    /// * `path` - The swap path, starting at the collateral and ending at the stablecoin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the path does not start at the collateral
+    fn set_swap_path(e: Env, collateral: Address, path: Vec<Address>);
+
+    /// (Admin only) Remove the swap path of a collateral, falling back to the direct pair
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the collateral
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn remove_swap_path(e: Env, collateral: Address);
+
+    /// Fetch the swap path used to sell a collateral, if any
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the collateral
+    fn get_swap_path(e: Env, collateral: Address) -> Option<Vec<Address>>;
+
+    /// (Admin only) Set the dex used to sell a token
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `dex` - The dex config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_dex(e: Env, token: Address, dex: DexConfig);
+
+    /// Fetch the dex used to sell a token
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    fn get_dex(e: Env, token: Address) -> DexConfig;
+
+    /// (Admin only) Set the stablecoin and threshold used to defend the peg. A pegkeeper defends the
+    /// peg of one stablecoin; its strategies can still flash loan any stablecoin of the treasury.
+    ///
+    /// ### Arguments
+    /// * `config` - The peg config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_peg_config(e: Env, config: PegConfig);
+
+    /// (Admin only) Set the collaterals a strategy is allowed to use
+    ///
+    /// ### Arguments
+    /// * `strategy` - The strategy
+    /// * `collaterals` - The Addresses of the collaterals
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_collaterals(e: Env, strategy: Strategy, collaterals: Vec<Address>);
+
+    /// Fetch the collaterals a strategy is allowed to use
+    ///
+    /// ### Arguments
+    /// * `strategy` - The strategy
+    fn get_collaterals(e: Env, strategy: Strategy) -> Vec<Address>;
+
+    /// (Admin only) Set the oracle used to price the stablecoin and collateral
+    ///
+    /// ### Arguments
+    /// * `config` - The oracle config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_oracle(e: Env, config: OracleConfig);
+
+    /// (Admin only) Set the max slippage from the oracle price allowed on every swap.
+    /// Requires the oracle to be set.
+    ///
+    /// ### Arguments
+    /// * `max_slippage_bps` - The max slippage, in basis points
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the slippage is above 100%
+    fn set_max_slippage(e: Env, max_slippage_bps: u32);
+
+    /// (Admin only) Set the minimum profit every flash loan and strategy execution must realize after
+    /// repaying the loan and its fee. Auctioned liquidations are exempt, as their collateral is sold later.
+    ///
+    /// ### Arguments
+    /// * `min_profit` - The minimum profit, in the token received
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the minimum profit is negative
+    fn set_min_profit(e: Env, min_profit: i128);
+
+    /// Fetch the minimum profit every flash loan and strategy execution must realize
+    fn get_min_profit(e: Env) -> i128;
+
+    /// (Admin only) Peg a stablecoin to a fiat currency other than USD, tracked through an FX oracle
+    ///
+    /// ### Arguments
+    /// * `stablecoin` - The Address of the stablecoin
+    /// * `config` - The FX config, or None to peg the stablecoin to USD
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_fx_config(e: Env, stablecoin: Address, config: Option<FxConfig>);
+
+    /// Fetch the FX config of a stablecoin with a non-USD peg, if any
+    ///
+    /// ### Arguments
+    /// * `stablecoin` - The Address of the stablecoin
+    fn get_fx_config(e: Env, stablecoin: Address) -> Option<FxConfig>;
+
+    /// Fetch the target price of a stablecoin in USD, scaled to 7 decimals
+    ///
+    /// ### Arguments
+    /// * `stablecoin` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the FX oracle price is missing or stale
+    fn get_peg_target(e: Env, stablecoin: Address) -> i128;
+
+    /// Fetch the USD price of an asset from the oracle, scaled to 7 decimals
+    ///
+    /// ### Arguments
+    /// * `asset` - The Address of the asset
+    ///
+    /// ### Panics
+    /// If the oracle has no price for the asset or the price is stale
+    fn get_price(e: Env, asset: Address) -> i128;
+
+    /// Fetch the current deviation of the stablecoin from its peg against a peg collateral, in basis points.
+    /// A positive deviation means the stablecoin trades above the peg.
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the peg collateral
+    ///
+    /// ### Panics
+    /// If the collateral is not whitelisted for the peg strategy
+    fn check_peg(e: Env, collateral: Address) -> i128;
+
+    /// (Admin only) Set or remove the TWAP guard. When set, peg operations refuse to act on a pair
+    /// price that deviates from the pair's TWAP by more than the max deviation.
+    ///
+    /// ### Arguments
+    /// * `config` - The TWAP guard config, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the window is zero
+    fn set_twap_config(e: Env, config: Option<TwapConfig>);
+
+    /// Fetch the TWAP guard config, if any
+    fn get_twap_config(e: Env) -> Option<TwapConfig>;
+
+    /// (Admin only) Set or remove the collateral auction config. When set, collateral seized by
+    /// `Liquidate` flash loan operations that is listed in the config is sold through Dutch auctions
+    /// in the auction house instead of being swapped on a DEX, with the proceeds flowing to the
+    /// treasury. The flash loan is then repaid from the stablecoins held by the pegkeeper.
+    ///
+    /// The pegkeeper must be allowed as a seller by the auction house.
+    ///
+    /// ### Arguments
+    /// * `config` - The collateral auction config, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the floor discount is 100% or more
+    fn set_auction_config(e: Env, config: Option<AuctionConfig>);
+
+    /// Fetch the collateral auction config, if any
+    fn get_auction_config(e: Env) -> Option<AuctionConfig>;
+
+    /// (Admin only) Set or remove the backstop auction config, required to fill blend bad debt and
+    /// interest auctions, whose lots and bids are backstop tokens
+    ///
+    /// ### Arguments
+    /// * `config` - The backstop auction config, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the buffer is above 100%
+    fn set_backstop_auction_config(e: Env, config: Option<BackstopAuctionConfig>);
+
+    /// Fetch the backstop auction config, if any
+    fn get_backstop_auction_config(e: Env) -> Option<BackstopAuctionConfig>;
+
+    /// Quote the stablecoins to flash loan to fill a blend auction in full at the current ledger
+    /// with a `FillAuction` operation, with the bid scaled by the blocks the auction has run for
+    ///
+    /// ### Arguments
+    /// * `blend_pool` - The Address of the blend pool
+    /// * `token` - The Address of the stablecoin
+    /// * `auction_type` - The blend auction type, 0 for user liquidations, 1 for bad debt, 2 for interest
+    /// * `user` - The Address the auction was created for, the backstop for bad debt and interest auctions
+    ///
+    /// ### Panics
+    /// If the auction does not exist, its type is unknown, or it bids an asset the pegkeeper can not pay
+    fn quote_auction(e: Env, blend_pool: Address, token: Address, auction_type: u32, user: Address) -> i128;
+
+    /// Record the current price of the pair of the stablecoin against a peg collateral, building
+    /// the price history the TWAP guard is computed from
+    ///
+    /// Returns the TWAP of the pair, or 0 if too little price history is recorded
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the peg collateral
+    ///
+    /// ### Panics
+    /// If the collateral is not whitelisted for the peg strategy or the TWAP guard is not set
+    fn record_price(e: Env, collateral: Address) -> i128;
+
+    /// Rebalance the pair of the stablecoin against a peg collateral if the deviation exceeds the threshold,
+    /// minting stablecoins within the pegkeeper's mint allowance above the peg, or spending its collateral below it
+    ///
+    /// Returns the deviation from the peg, in basis points, before rebalancing
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the peg collateral
+    ///
+    /// ### Panics
+    /// If the collateral is not whitelisted for the peg strategy
+    /// If the TWAP guard is set and the pair price deviates from its TWAP, or too little price history is recorded
+    fn maybe_rebalance(e: Env, collateral: Address) -> i128;
+
+    /// (Admin or keeper only) Mint stablecoins through the treasury's minter facility and sell them for a
+    /// peg collateral while the stablecoin trades above the peg, never below the peg target
+    ///
+    /// Returns the profit realized in the peg collateral, above the value of the stablecoins at the peg target
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the keeper
+    /// * `collateral` - The Address of the peg collateral
+    /// * `amount` - The amount of stablecoins to mint and sell
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the keeper
+    /// If the collateral is not whitelisted for the peg strategy
+    /// If the TWAP guard is set and the pair price deviates from its TWAP, or too little price history is recorded
+    /// If the stablecoin is not above the peg by more than the threshold
+    /// If the pegkeeper is not a treasury minter, or the amount exceeds its mint allowance
+    fn expand_peg(e: Env, caller: Address, collateral: Address, amount: i128) -> i128;
+
+    /// (Admin or keeper only) Buy stablecoins back with a peg collateral held by the pegkeeper while the
+    /// stablecoin trades below the peg, never above the peg target, and burn them through the treasury's
+    /// minter facility up to the peg debt
+    ///
+    /// Returns the profit realized in the stablecoin, above the value of the collateral at the peg target
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the keeper
+    /// * `collateral` - The Address of the peg collateral
+    /// * `amount` - The amount of the peg collateral to spend
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the keeper
+    /// If the collateral is not whitelisted for the peg strategy
+    /// If the TWAP guard is set and the pair price deviates from its TWAP, or too little price history is recorded
+    /// If the stablecoin is not below the peg by more than the threshold, or the pegkeeper balance is too low
+    fn contract_peg(e: Env, caller: Address, collateral: Address, amount: i128) -> i128;
+
+    /// Fetch the total profit realized by peg operations
+    ///
+    /// Returns (expansion profit in the peg asset, contraction profit in the stablecoin)
+    fn get_peg_profits(e: Env) -> (i128, i128);
+
+    /// Fetch the stablecoins minted by expanding the peg and not yet burnt by contracting it
+    fn get_peg_debt(e: Env) -> i128;
+
+    /// Simulate the peg operation `expand_peg` or `contract_peg` would execute for an amount at the
+    /// current pair reserves, without executing it, so bots can check off-chain whether it clears
+    /// the profit threshold. The direction follows the deviation of the stablecoin from the peg.
+    ///
+    /// Returns the expected output, profit and price impact of selling the amount of stablecoins for
+    /// the collateral above the peg, or of the collateral for stablecoins below it
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the peg collateral
+    /// * `amount` - The amount to sell
+    ///
+    /// ### Panics
+    /// If the collateral is not whitelisted for the peg strategy or the amount is not positive
+    /// If the stablecoin is within the threshold of the peg
+    fn simulate_rebalance(e: Env, collateral: Address, amount: i128) -> RebalanceQuote;
+
+    /// (Admin only) Set the pauser, e.g. the protocol sentinel, allowed to pause and unpause the pegkeeper besides the admin
+    ///
+    /// ### Arguments
+    /// * `pauser` - The Address of the pauser
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_pauser(e: Env, pauser: Address);
+
+    /// (Admin only) Set the keeper, e.g. the protocol keeper bot, allowed to run `expand_peg` and `contract_peg` besides the admin
+    ///
+    /// ### Arguments
+    /// * `keeper` - The Address of the keeper
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_keeper(e: Env, keeper: Address);
+
+    /// (Admin or pauser only) Pause the pegkeeper, blocking flash loan operations, strategies and peg operations
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the pauser
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the pauser
+    fn pause(e: Env, caller: Address);
+
+    /// (Admin or pauser only) Unpause the pegkeeper
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the pauser
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the pauser
+    fn unpause(e: Env, caller: Address);
+
+    /// Check if the pegkeeper is paused
+    fn is_paused(e: Env) -> bool;
 }
 
 #[contractimpl]
@@ -43,37 +447,540 @@ impl Pegkeeper for PegkeeperContract {
         storage::set_router(&e, &router);
         storage::set_admin(&e, &admin);
     }
-    fn fl_receive(e: Env, pair: Address, auction_creator: Address, token_a: Address, token_a_bid_amount: i128, token_b: Address, token_b_lot_amount: i128, blend_pool: Address, liq_amount: i128) {
+    fn fl_receive(e: Env, token: Address, amount: i128, data: Bytes, fee: i128) {
         log!(&e, "================================= Real: Pegkeeper Function Start ================================");
         storage::extend_instance(&e);
+        helper::require_not_paused(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
-        log!(&e, "================================= Real: Pegkeeper Function Passed Auth ================================");
-        let token_client = token::Client::new(&e, &token_a);
-        let balance_before = token_client.balance(&e.current_contract_address());
-
-        log!(&e, "================================= Real: Pegkeeper Function Prepare for liquidation ================================");
-        helper::liquidate(&e, auction_creator, token_a.clone(), token_a_bid_amount.clone(), token_b.clone(), token_b_lot_amount.clone(), blend_pool.clone(), liq_amount.clone());
-        log!(&e, "================================= Real: Pegkeeper Function Passed Liquidation ================================");
-        helper::swap(&e, pair, token_b.clone(), token_a.clone(), token_b_lot_amount.clone(), 0);
-        log!(&e, "================================= Real: Pegkeeper Function Passed Swap ================================");
 
-        let balance_after = token_client.balance(&e.current_contract_address());
-        let profit = balance_after - balance_before;
-        
-        // send this profit to speicfic address or caller
+        let op = FlashLoanOp::from_xdr(&e, &data)
+            .unwrap_or_else(|_| panic_with_error!(&e, PegkeeperError::InvalidCalldata));
 
-        log!(&e, "================================= Real: Profit {} {} {} ================================", profit, balance_after, balance_before);
-        //transfer profit to caller
-        // token_client.transfer(&e.current_contract_address(), &caller, &profit);
+        let token_client = token::Client::new(&e, &token);
+        // the balance includes the loaned amount, so only the fee has to be earned on top of it
+        let balance_before = token_client.balance(&e.current_contract_address());
+        let mut auctioned = false;
+        let liquidation = match op {
+            FlashLoanOp::Auction(op) => {
+                helper::require_collateral(&e, Strategy::Auction, &op.lot_token);
+                if op.path.len() < 2 || op.path.get_unchecked(0) != op.lot_token || op.path.get_unchecked(op.path.len() - 1) != token {
+                    panic_with_error!(&e, PegkeeperError::InvalidSwapPath);
+                }
+                // the bid repayment and the lot sale are authorized in a single batch
+                let swap = helper::plan_swap_path(&e, &op.path, op.lot_amount, op.min_out);
+                e.authorize_as_current_contract(vec![
+                    &e,
+                    helper::transfer_auth(&e, &token, &op.blend_pool, amount),
+                    helper::transfer_auth(&e, &op.lot_token, &swap.first_pair, op.lot_amount),
+                ]);
+                helper::liquidate(&e, op.auction_creator.clone(), token.clone(), amount, op.lot_token, op.lot_amount, op.blend_pool.clone(), op.liq_amount);
+                helper::execute_swap_path(&e, &op.path, op.lot_amount, &swap);
+                Some((Symbol::new(&e, "auction"), op.blend_pool, op.auction_creator))
+            }
+            FlashLoanOp::Liquidate(op) => {
+                let auctions = helper::fill_liquidation(&e, &token, &op.blend_pool, &op.user, amount);
+                if auctions > 0 {
+                    // auctioned collateral is sold later, so the loan is repaid from the pegkeeper's own stablecoins
+                    if token_client.balance(&e.current_contract_address()) < amount + fee {
+                        panic_with_error!(&e, PegkeeperError::InsufficientBalanceError);
+                    }
+                    e.events().publish(
+                        ("Pegkeeper", Symbol::new(&e, "liquidation_auctioned")),
+                        (op.blend_pool, op.user, token.clone(), amount, auctions),
+                    );
+                    auctioned = true;
+                    None
+                } else {
+                    Some((Symbol::new(&e, "liquidate"), op.blend_pool, op.user))
+                }
+            }
+            FlashLoanOp::FillAuction(op) => {
+                helper::fill_auction(&e, &token, amount, &op);
+                Some((Symbol::new(&e, "fill_auction"), op.blend_pool, op.user))
+            }
+            FlashLoanOp::Strategy(op) => {
+                helper::execute_strategy(&e, op.id, &token, amount, op.params);
+                let profit = token_client.balance(&e.current_contract_address()) - balance_before - fee;
+                helper::require_strategy_profit(&e, op.id, profit);
+                e.events().publish(("Pegkeeper", Symbol::new(&e, "strategy")), (op.id, token.clone(), amount));
+                None
+            }
+        };
+        let profit = token_client.balance(&e.current_contract_address()) - balance_before - fee;
+        if !auctioned {
+            if profit < 0 {
+                panic_with_error!(&e, PegkeeperError::InsufficientBalanceError);
+            }
+            helper::require_min_profit(&e, profit);
+        }
+        if let Some((kind, blend_pool, user)) = liquidation {
+            storage::set_liquidation_profit(&e, storage::get_liquidation_profit(&e) + profit);
+            e.events().publish(
+                ("Pegkeeper", Symbol::new(&e, "liquidation")),
+                (kind, blend_pool, user, token.clone(), amount, fee, profit),
+            );
+        }
+        log!(&e, "================================= Real: Profit {} ================================", profit);
 
         token_client.approve(
             &e.current_contract_address(),
             &admin,
-            &token_a_bid_amount,
+            &(amount + fee),
             &(e.ledger().sequence() + 1),
         );
 
         log!(&e, "================================= Real: Pegkeeper Function End ================================");
     }
+
+    fn register_strategy(e: Env, id: u32, contract: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_strategy(&e, id, &StrategyConfig { contract: contract.clone(), enabled: true });
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "register_strategy")), (id, contract));
+    }
+
+    fn set_strategy_enabled(e: Env, id: u32, enabled: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let mut config = match storage::get_strategy(&e, id) {
+            Some(config) => config,
+            None => panic_with_error!(&e, PegkeeperError::StrategyNotAllowed),
+        };
+        config.enabled = enabled;
+        storage::set_strategy(&e, id, &config);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_strategy_enabled")), (id, enabled));
+    }
+
+    fn get_strategy(e: Env, id: u32) -> Option<StrategyConfig> {
+        storage::extend_instance(&e);
+        storage::get_strategy(&e, id)
+    }
+
+    fn set_strategy_limits(e: Env, id: u32, limits: Option<StrategyLimits>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        match limits.clone() {
+            Some(limits) => {
+                if limits.max_notional < 0 || limits.max_daily_volume < 0 || limits.min_profit < 0 {
+                    panic_with_error!(&e, PegkeeperError::InvalidValue);
+                }
+                storage::set_strategy_limits(&e, id, &limits);
+            }
+            None => storage::del_strategy_limits(&e, id),
+        }
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_strategy_limits")), (id, limits));
+    }
+
+    fn get_strategy_limits(e: Env, id: u32) -> Option<StrategyLimits> {
+        storage::extend_instance(&e);
+        storage::get_strategy_limits(&e, id)
+    }
+
+    fn execute_strategy(e: Env, id: u32, token: Address, amount: i128, params: Bytes) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if amount < 0 {
+            panic_with_error!(&e, PegkeeperError::InvalidValue);
+        }
+
+        let token_client = token::Client::new(&e, &token);
+        let balance_before = token_client.balance(&e.current_contract_address());
+        helper::execute_strategy(&e, id, &token, amount, params);
+        let profit = token_client.balance(&e.current_contract_address()) - balance_before;
+        if profit < 0 {
+            panic_with_error!(&e, PegkeeperError::InsufficientBalanceError);
+        }
+        helper::require_strategy_profit(&e, id, profit);
+        helper::require_min_profit(&e, profit);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "strategy")), (id, token, amount));
+        profit
+    }
+
+    fn forward_profit(e: Env, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        // the admin is the treasury
+        let treasury = storage::get_admin(&e);
+
+        e.authorize_as_current_contract(vec![&e, helper::transfer_auth(&e, &token, &treasury, amount)]);
+        let deposit_args = vec![
+            &e,
+            e.current_contract_address().into_val(&e),
+            token.into_val(&e),
+            amount.into_val(&e),
+        ];
+        e.invoke_contract::<Val>(&treasury, &Symbol::new(&e, "deposit_surplus"), deposit_args);
+    }
+
+    fn sweep(e: Env, token: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&e, PegkeeperError::InvalidValue);
+        }
+        let token_client = token::Client::new(&e, &token);
+        if amount > token_client.balance(&e.current_contract_address()) {
+            panic_with_error!(&e, PegkeeperError::InsufficientBalanceError);
+        }
+        token_client.transfer(&e.current_contract_address(), &to, &amount);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "sweep")), (token, to, amount));
+    }
+
+    fn set_swap_path(e: Env, collateral: Address, path: Vec<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if path.len() < 2 || path.get_unchecked(0) != collateral {
+            panic_with_error!(&e, PegkeeperError::InvalidSwapPath);
+        }
+        storage::set_swap_path(&e, &collateral, &path);
+    }
+
+    fn remove_swap_path(e: Env, collateral: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::del_swap_path(&e, &collateral);
+    }
+
+    fn get_swap_path(e: Env, collateral: Address) -> Option<Vec<Address>> {
+        storage::extend_instance(&e);
+        storage::get_swap_path(&e, &collateral)
+    }
+
+    fn set_dex(e: Env, token: Address, dex: DexConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_dex(&e, &token, &dex);
+    }
+
+    fn get_dex(e: Env, token: Address) -> DexConfig {
+        storage::extend_instance(&e);
+        storage::get_dex(&e, &token)
+    }
+
+    fn set_peg_config(e: Env, config: PegConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_peg_config(&e, &config);
+    }
+
+    fn set_collaterals(e: Env, strategy: Strategy, collaterals: Vec<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_collaterals(&e, strategy, &collaterals);
+    }
+
+    fn get_collaterals(e: Env, strategy: Strategy) -> Vec<Address> {
+        storage::extend_instance(&e);
+        storage::get_collaterals(&e, strategy)
+    }
+
+    fn set_oracle(e: Env, config: OracleConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_oracle_config(&e, &config);
+    }
+
+    fn set_max_slippage(e: Env, max_slippage_bps: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if i128::from(max_slippage_bps) > helper::SCALAR_BPS {
+            panic_with_error!(&e, PegkeeperError::InvalidValue);
+        }
+        storage::set_max_slippage(&e, max_slippage_bps);
+    }
+
+    fn set_min_profit(e: Env, min_profit: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if min_profit < 0 {
+            panic_with_error!(&e, PegkeeperError::InvalidValue);
+        }
+        storage::set_min_profit(&e, min_profit);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_min_profit")), min_profit);
+    }
+
+    fn get_min_profit(e: Env) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_min_profit(&e)
+    }
+
+    fn set_fx_config(e: Env, stablecoin: Address, config: Option<FxConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        match config {
+            Some(config) => storage::set_fx_config(&e, &stablecoin, &config),
+            None => storage::del_fx_config(&e, &stablecoin),
+        }
+    }
+
+    fn get_fx_config(e: Env, stablecoin: Address) -> Option<FxConfig> {
+        storage::extend_instance(&e);
+        storage::get_fx_config(&e, &stablecoin)
+    }
+
+    fn get_peg_target(e: Env, stablecoin: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::get_peg_target(&e, &stablecoin)
+    }
+
+    fn get_price(e: Env, asset: Address) -> i128 {
+        storage::extend_instance(&e);
+        oracle::get_price(&e, &storage::get_oracle_config(&e), &asset)
+    }
+
+    fn check_peg(e: Env, collateral: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_collateral(&e, Strategy::Peg, &collateral);
+        let config = storage::get_peg_config(&e);
+        let (stable_reserve, peg_reserve, _) = helper::get_peg_reserves(&e, &config, &collateral);
+        helper::calc_deviation_bps(stable_reserve, peg_reserve, helper::get_peg_target(&e, &config.stablecoin))
+    }
+
+    fn set_twap_config(e: Env, config: Option<TwapConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        match config.clone() {
+            Some(config) => {
+                if config.window == 0 {
+                    panic_with_error!(&e, PegkeeperError::InvalidValue);
+                }
+                storage::set_twap_config(&e, &config);
+            }
+            None => storage::del_twap_config(&e),
+        }
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_twap_config")), config);
+    }
+
+    fn get_twap_config(e: Env) -> Option<TwapConfig> {
+        storage::extend_instance(&e);
+        storage::get_twap_config(&e)
+    }
+
+    fn set_auction_config(e: Env, config: Option<AuctionConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        match config.clone() {
+            Some(config) => {
+                if i128::from(config.floor_discount_bps) >= helper::SCALAR_BPS {
+                    panic_with_error!(&e, PegkeeperError::InvalidValue);
+                }
+                storage::set_auction_config(&e, &config);
+            }
+            None => storage::del_auction_config(&e),
+        }
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_auction_config")), config);
+    }
+
+    fn get_auction_config(e: Env) -> Option<AuctionConfig> {
+        storage::extend_instance(&e);
+        storage::get_auction_config(&e)
+    }
+
+    fn set_backstop_auction_config(e: Env, config: Option<BackstopAuctionConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        match config.clone() {
+            Some(config) => {
+                if i128::from(config.buffer_bps) > helper::SCALAR_BPS {
+                    panic_with_error!(&e, PegkeeperError::InvalidValue);
+                }
+                storage::set_backstop_auction_config(&e, &config);
+            }
+            None => storage::del_backstop_auction_config(&e),
+        }
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_backstop_auction_config")), config);
+    }
+
+    fn get_backstop_auction_config(e: Env) -> Option<BackstopAuctionConfig> {
+        storage::extend_instance(&e);
+        storage::get_backstop_auction_config(&e)
+    }
+
+    fn quote_auction(e: Env, blend_pool: Address, token: Address, auction_type: u32, user: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::calc_auction_cost(&e, &blend_pool, &token, auction_type, &user)
+    }
+
+    fn record_price(e: Env, collateral: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_collateral(&e, Strategy::Peg, &collateral);
+        let twap_config = match storage::get_twap_config(&e) {
+            Some(twap_config) => twap_config,
+            None => panic_with_error!(&e, PegkeeperError::TwapUnavailable),
+        };
+        let config = storage::get_peg_config(&e);
+        let (stable_reserve, peg_reserve, pair) = helper::get_peg_reserves(&e, &config, &collateral);
+        let acc = helper::record_price(&e, &pair, helper::calc_pair_price(stable_reserve, peg_reserve), twap_config.window);
+        helper::calc_twap(&acc, twap_config.window).unwrap_or(0)
+    }
+
+    fn maybe_rebalance(e: Env, collateral: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_collateral(&e, Strategy::Peg, &collateral);
+        let config = storage::get_peg_config(&e);
+        let (stable_reserve, peg_reserve, pair) = helper::get_peg_reserves(&e, &config, &collateral);
+        let target = helper::get_peg_target(&e, &config.stablecoin);
+        let deviation = helper::calc_deviation_bps(stable_reserve, peg_reserve, target);
+        if deviation.abs() <= i128::from(config.threshold_bps) {
+            return deviation;
+        }
+        helper::require_twap(&e, &pair, stable_reserve, peg_reserve);
+
+        // sell the over-priced side of the pair until the reserves are balanced
+        if deviation > 0 {
+            let amount = helper::calc_amount_to_peg(stable_reserve, peg_reserve * SCALAR_7 / target).min(helper::get_mint_allowance(&e));
+            if amount > 0 {
+                helper::expand_peg(&e, &config, &pair, &collateral, amount);
+            }
+        } else {
+            let balance = token::Client::new(&e, &collateral).balance(&e.current_contract_address());
+            let amount = helper::calc_amount_to_peg(peg_reserve, stable_reserve * target / SCALAR_7).min(balance);
+            if amount > 0 {
+                helper::contract_peg(&e, &config, &pair, &collateral, amount);
+            }
+        }
+        deviation
+    }
+
+    fn expand_peg(e: Env, caller: Address, collateral: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_keeper(&e, &caller);
+        helper::require_not_paused(&e);
+        helper::require_collateral(&e, Strategy::Peg, &collateral);
+        let config = storage::get_peg_config(&e);
+        let (stable_reserve, peg_reserve, pair) = helper::get_peg_reserves(&e, &config, &collateral);
+        if helper::calc_deviation_bps(stable_reserve, peg_reserve, helper::get_peg_target(&e, &config.stablecoin)) <= i128::from(config.threshold_bps) {
+            panic_with_error!(&e, PegkeeperError::PegDirectionError);
+        }
+        helper::require_twap(&e, &pair, stable_reserve, peg_reserve);
+        helper::expand_peg(&e, &config, &pair, &collateral, amount)
+    }
+
+    fn contract_peg(e: Env, caller: Address, collateral: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_keeper(&e, &caller);
+        helper::require_not_paused(&e);
+        helper::require_collateral(&e, Strategy::Peg, &collateral);
+        let config = storage::get_peg_config(&e);
+        let (stable_reserve, peg_reserve, pair) = helper::get_peg_reserves(&e, &config, &collateral);
+        if helper::calc_deviation_bps(stable_reserve, peg_reserve, helper::get_peg_target(&e, &config.stablecoin)) >= -i128::from(config.threshold_bps) {
+            panic_with_error!(&e, PegkeeperError::PegDirectionError);
+        }
+        helper::require_twap(&e, &pair, stable_reserve, peg_reserve);
+        helper::contract_peg(&e, &config, &pair, &collateral, amount)
+    }
+
+    fn get_peg_profits(e: Env) -> (i128, i128) {
+        storage::extend_instance(&e);
+        (storage::get_expand_profit(&e), storage::get_contract_profit(&e))
+    }
+
+    fn get_peg_debt(e: Env) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_peg_debt(&e)
+    }
+
+    fn simulate_rebalance(e: Env, collateral: Address, amount: i128) -> RebalanceQuote {
+        helper::require_collateral(&e, Strategy::Peg, &collateral);
+        if amount <= 0 {
+            panic_with_error!(&e, PegkeeperError::InvalidValue);
+        }
+        let config = storage::get_peg_config(&e);
+        let (stable_reserve, peg_reserve, _) = helper::get_peg_reserves(&e, &config, &collateral);
+        let target = helper::get_peg_target(&e, &config.stablecoin);
+        let deviation = helper::calc_deviation_bps(stable_reserve, peg_reserve, target);
+        if deviation > i128::from(config.threshold_bps) {
+            helper::calc_rebalance_quote(amount, amount * target / SCALAR_7, stable_reserve, peg_reserve)
+        } else if deviation < -i128::from(config.threshold_bps) {
+            helper::calc_rebalance_quote(amount, amount * SCALAR_7 / target, peg_reserve, stable_reserve)
+        } else {
+            panic_with_error!(&e, PegkeeperError::PegDirectionError)
+        }
+    }
+
+    fn set_pauser(e: Env, pauser: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_pauser(&e, &pauser);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_pauser")), pauser);
+    }
+
+    fn set_keeper(e: Env, keeper: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_keeper(&e, &keeper);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "set_keeper")), keeper);
+    }
+
+    fn pause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_pauser(&e, &caller);
+
+        storage::set_paused(&e, true);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "pause")), caller);
+    }
+
+    fn unpause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_pauser(&e, &caller);
+
+        storage::set_paused(&e, false);
+
+        e.events().publish(("Pegkeeper", Symbol::new(&e, "unpause")), caller);
+    }
+
+    fn is_paused(e: Env) -> bool {
+        storage::is_paused(&e)
+    }
 }
@@ -0,0 +1,11 @@
+use soroban_sdk::{contracttype, Address};
+
+/// Collateral offered for sale in the collateral auction house, mirroring its `Lot` type
+#[derive(Clone)]
+#[contracttype]
+pub struct Lot {
+    pub asset: Address,    // collateral sold
+    pub amount: i128,      // amount of the collateral sold
+    pub start_price: i128, // price of the collateral in the stablecoin when the auction starts, in 7 decimals
+    pub end_price: i128,   // price of the collateral in the stablecoin the auction declines to, in 7 decimals
+}
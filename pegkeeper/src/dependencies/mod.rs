@@ -1,2 +1,6 @@
 pub mod pool;
-pub mod router;
\ No newline at end of file
+pub mod router;
+pub mod pair;
+pub mod oracle;
+pub mod comet;
+pub mod auction;
\ No newline at end of file
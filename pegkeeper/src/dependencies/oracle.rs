@@ -0,0 +1,50 @@
+use sep_40_oracle::{Asset, PriceFeedClient};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::errors::PegkeeperError;
+use crate::storage::{FxConfig, OracleConfig};
+
+pub(crate) const SCALAR_7: i128 = 1_0000000;
+
+/// Fetch the USD price of an asset from the oracle, scaled to 7 decimals
+///
+/// ### Arguments
+/// * `config` - The oracle config
+/// * `asset` - The Address of the asset
+///
+/// ### Panics
+/// If the oracle has no price for the asset or the price is older than the max age
+pub fn get_price(e: &Env, config: &OracleConfig, asset: &Address) -> i128 {
+    let oracle_client = PriceFeedClient::new(e, &config.oracle);
+    let price_data = match oracle_client.lastprice(&Asset::Stellar(asset.clone())) {
+        Some(price_data) => price_data,
+        None => panic_with_error!(e, PegkeeperError::StaleOracleError),
+    };
+    if price_data.timestamp + config.max_age < e.ledger().timestamp() {
+        panic_with_error!(e, PegkeeperError::StaleOracleError);
+    }
+
+    let decimals = oracle_client.decimals();
+    price_data.price * SCALAR_7 / 10i128.pow(decimals)
+}
+
+/// Fetch the USD price of a fiat currency from the FX oracle, scaled to 7 decimals
+///
+/// ### Arguments
+/// * `config` - The FX config
+///
+/// ### Panics
+/// If the FX oracle has no price for the currency or the price is older than the max age
+pub fn get_fx_price(e: &Env, config: &FxConfig) -> i128 {
+    let oracle_client = PriceFeedClient::new(e, &config.oracle);
+    let price_data = match oracle_client.lastprice(&Asset::Other(config.currency.clone())) {
+        Some(price_data) => price_data,
+        None => panic_with_error!(e, PegkeeperError::StaleOracleError),
+    };
+    if price_data.timestamp + config.max_age < e.ledger().timestamp() {
+        panic_with_error!(e, PegkeeperError::StaleOracleError);
+    }
+
+    let decimals = oracle_client.decimals();
+    price_data.price * SCALAR_7 / 10i128.pow(decimals)
+}
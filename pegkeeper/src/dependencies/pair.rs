@@ -0,0 +1,2 @@
+use soroban_sdk::contractimport;
+contractimport!(file = "../wasm/soroswap/pair.wasm");
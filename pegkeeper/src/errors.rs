@@ -9,4 +9,49 @@ pub enum PegkeeperError {
 
     /// already initialized
     AlreadyInitializedError = 102,
-}
\ No newline at end of file
+
+    /// the peg is not deviated in the direction of the operation
+    PegDirectionError = 103,
+
+    /// not enough balance to execute the operation
+    InsufficientBalanceError = 104,
+
+    /// the oracle price is missing or stale
+    StaleOracleError = 105,
+
+    /// the swap path is invalid
+    InvalidSwapPath = 106,
+
+    /// the value is outside of the allowed range
+    InvalidValue = 107,
+
+    /// the flash loan calldata can not be decoded
+    InvalidCalldata = 108,
+
+    /// the collateral is not whitelisted for the strategy
+    CollateralNotAllowed = 109,
+
+    /// the strategy is not registered or is disabled
+    StrategyNotAllowed = 110,
+
+    /// the strategy exceeds its notional or daily volume limit, or misses its profit threshold
+    StrategyLimitExceeded = 111,
+
+    /// too little price history is recorded for the pair to compute its TWAP
+    TwapUnavailable = 112,
+
+    /// the price of the pair deviates from its TWAP by more than the max deviation
+    TwapDeviationExceeded = 113,
+
+    /// the contract is paused
+    Paused = 114,
+
+    /// the caller is neither the admin nor the pauser or keeper the operation requires
+    UnauthorizedError = 115,
+
+    /// the operation realized less than the minimum profit
+    ProfitTooLow = 116,
+
+    /// the auction type is unknown, or the auction bids an asset other than the loaned stablecoin
+    AuctionNotFillable = 117,
+}
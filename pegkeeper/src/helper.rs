@@ -1,12 +1,29 @@
-use soroban_sdk::{log, Address, Env, vec, Val, Vec, IntoVal, Symbol};
+use soroban_sdk::{log, panic_with_error, token, Address, Bytes, Env, vec, Val, Vec, IntoVal, Symbol};
 use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
 
 use crate::dependencies::{
     router::{Client as RouterClient},
-    pool::{Client as PoolClient, Request},
+    pool::{AuctionData, Client as PoolClient, Request, Reserve},
+    comet::Client as CometClient,
+    pair::{Client as PairClient},
+    oracle,
+    auction::Lot,
 };
-use crate::storage;
+use crate::errors::PegkeeperError;
+use crate::swap_adapter;
+use crate::storage::{self, AuctionConfig, BackstopAuctionConfig, FillAuctionOp, PegConfig, PriceAccumulator, RebalanceQuote, Strategy, StrategyVolume};
 
+pub(crate) const SCALAR_BPS: i128 = 10_000;
+pub(crate) const SCALAR_9: i128 = 1_000_000_000;
+
+pub(crate) const USER_LIQUIDATION_AUCTION: u32 = 0;
+pub(crate) const BAD_DEBT_AUCTION: u32 = 1;
+pub(crate) const INTEREST_AUCTION: u32 = 2;
+
+/// Fill a blend liquidation auction by repaying its bid and withdrawing its lot
+///
+/// The transfer of the bid to the blend pool must be pre-authorized with `transfer_auth`, so it can
+/// be batched with the authorization of the swap that sells the lot.
 pub fn liquidate(e: &Env, auction_creator: Address, token_a: Address, token_a_bid_amount: i128, token_b: Address, token_b_lot_amount: i128, blend_pool: Address, liq_amount: i128) {
   log!(e, "================================= RealPegkeeper  liquidation Function ================================");
   storage::extend_instance(e);
@@ -30,24 +47,6 @@ pub fn liquidate(e: &Env, auction_creator: Address, token_a: Address, token_a_bi
       },
   ];
 
-  let args: Vec<Val> = vec![
-      e,
-      e.current_contract_address().into_val(e),
-      blend_pool.into_val(e),
-      token_a_bid_amount.into_val(e),
-  ];
-  e.authorize_as_current_contract(vec![
-      e,
-      InvokerContractAuthEntry::Contract(SubContractInvocation {
-          context: ContractContext {
-              contract: token_a.clone(),
-              fn_name: Symbol::new(e, "transfer"),
-              args: args.clone(),
-          },
-          sub_invocations: vec![e],
-      })
-  ]);
-
   log!(e, "================================= RealPegkeeper  Fill Request ================================");
   PoolClient::new(e, &blend_pool).submit(&e.current_contract_address(), &e.current_contract_address(), &e.current_contract_address(), &fill_requests);
 
@@ -60,29 +59,939 @@ pub fn swap(e: &Env, pair: Address, token_a: Address, token_b: Address, amount_a
 
   let router = storage::get_router(e);
   let router_client = RouterClient::new(e, &router);
+  let amount_b = calc_min_out(e, &token_a, &token_b, amount_a, amount_b);
 
   let path = vec![
       e,
       token_a.clone(),
       token_b.clone(),
   ];
-  let args: Vec<Val> = vec![
-      e,
-      e.current_contract_address().into_val(e),
-      pair.into_val(e),
-      amount_a.into_val(e),
+  e.authorize_as_current_contract(vec![e, transfer_auth(e, &token_a, &pair, amount_a)]);
+  let out_client = token::Client::new(e, &token_b);
+  let balance_before = out_client.balance(&e.current_contract_address());
+  router_client.swap_exact_tokens_for_tokens(&amount_a, &amount_b, &path, &e.current_contract_address(), &u64::MAX);
+  let amount_out = out_client.balance(&e.current_contract_address()) - balance_before;
+  e.events().publish(("Pegkeeper", Symbol::new(e, "swap")), (token_a, token_b, amount_a, amount_out));
+  log!(e, "================================= RealPegkeeper  Swap End ================================");
+}
+
+/// Calculate the minimum output of a swap
+///
+/// If a max slippage is set, the minimum output is raised so the realized price can not
+/// deviate from the oracle price by more than the max slippage.
+///
+/// ### Arguments
+/// * `token_in` - The Address of the input token
+/// * `token_out` - The Address of the output token
+/// * `amount_in` - The amount of the input token
+/// * `amount_out_min` - The minimum output requested by the caller
+pub fn calc_min_out(e: &Env, token_in: &Address, token_out: &Address, amount_in: i128, amount_out_min: i128) -> i128 {
+  match storage::get_max_slippage(e) {
+    Some(max_slippage_bps) => {
+      let oracle_config = storage::get_oracle_config(e);
+      let price_in = oracle::get_price(e, &oracle_config, token_in);
+      let price_out = oracle::get_price(e, &oracle_config, token_out);
+      let expected_out = amount_in * price_in / price_out;
+      let oracle_min_out = expected_out * (SCALAR_BPS - i128::from(max_slippage_bps)) / SCALAR_BPS;
+      amount_out_min.max(oracle_min_out)
+    }
+    None => amount_out_min,
+  }
+}
+
+/// Execute a registered strategy with tokens held by the pegkeeper
+///
+/// The tokens are transferred to the strategy contract before invoking its `execute` function,
+/// and the strategy must transfer the proceeds back to the pegkeeper.
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+/// * `token` - The Address of the token provided to the strategy
+/// * `amount` - The amount of the token provided to the strategy
+/// * `params` - The parameters passed to the strategy
+///
+/// ### Panics
+/// If the strategy is not registered or is disabled, or the amount exceeds its risk limits
+pub fn execute_strategy(e: &Env, id: u32, token: &Address, amount: i128, params: Bytes) {
+  let config = match storage::get_strategy(e, id) {
+    Some(config) if config.enabled => config,
+    _ => panic_with_error!(e, PegkeeperError::StrategyNotAllowed),
+  };
+  require_strategy_volume(e, id, amount);
+  if amount > 0 {
+    token::Client::new(e, token).transfer(&e.current_contract_address(), &config.contract, &amount);
+  }
+  let args: Vec<Val> = vec![e, token.into_val(e), amount.into_val(e), params.into_val(e)];
+  e.invoke_contract::<Val>(&config.contract, &Symbol::new(e, "execute"), args);
+}
+
+/// Record the amount provided to a strategy against its notional and daily volume limits
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+/// * `amount` - The amount provided to the strategy
+///
+/// ### Panics
+/// If the amount exceeds the max notional, or the daily volume including the amount exceeds the max daily volume
+pub fn require_strategy_volume(e: &Env, id: u32, amount: i128) {
+  let limits = match storage::get_strategy_limits(e, id) {
+    Some(limits) => limits,
+    None => return,
+  };
+  if amount > limits.max_notional {
+    panic_with_error!(e, PegkeeperError::StrategyLimitExceeded);
+  }
+  let day = e.ledger().timestamp() / 86400;
+  let volume = match storage::get_strategy_volume(e, id) {
+    Some(volume) if volume.day == day => volume.volume + amount,
+    _ => amount,
+  };
+  if volume > limits.max_daily_volume {
+    panic_with_error!(e, PegkeeperError::StrategyLimitExceeded);
+  }
+  storage::set_strategy_volume(e, id, &StrategyVolume { day, volume });
+}
+
+/// Require a strategy execution to meet the profit threshold of the strategy
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+/// * `profit` - The profit realized by the execution
+///
+/// ### Panics
+/// If the profit is below the min profit of the strategy
+pub fn require_strategy_profit(e: &Env, id: u32, profit: i128) {
+  if let Some(limits) = storage::get_strategy_limits(e, id) {
+    if profit < limits.min_profit {
+      panic_with_error!(e, PegkeeperError::StrategyLimitExceeded);
+    }
+  }
+}
+
+/// Require an operation to realize at least the minimum profit, so break-even churn is rejected
+///
+/// ### Arguments
+/// * `profit` - The profit realized after repaying the flash loan, if any
+///
+/// ### Panics
+/// If the profit is below the minimum profit
+pub fn require_min_profit(e: &Env, profit: i128) {
+  if profit < storage::get_min_profit(e) {
+    panic_with_error!(e, PegkeeperError::ProfitTooLow);
+  }
+}
+
+/// A swap along a soroswap path, resolved before its input transfer is authorized
+pub struct SwapPlan {
+  pub first_pair: Address,  // pair the router transfers the input token to
+  pub amount_out_min: i128, // minimum output, including the oracle slippage bound
+}
+
+/// Resolve the first pair and the minimum output of a swap along a multi-hop soroswap path
+///
+/// ### Arguments
+/// * `path` - The swap path, from the input token to the output token
+/// * `amount_in` - The amount of the input token to swap
+/// * `amount_out_min` - The minimum amount of the output token to receive
+///
+/// ### Panics
+/// If the path has less than two tokens
+pub fn plan_swap_path(e: &Env, path: &Vec<Address>, amount_in: i128, amount_out_min: i128) -> SwapPlan {
+  if path.len() < 2 {
+    panic_with_error!(e, PegkeeperError::InvalidSwapPath);
+  }
+  let token_in = path.get_unchecked(0);
+  let token_out = path.get_unchecked(path.len() - 1);
+  SwapPlan {
+    // the router transfers the input token to the first pair of the path
+    first_pair: RouterClient::new(e, &storage::get_router(e)).router_pair_for(&token_in, &path.get_unchecked(1)),
+    amount_out_min: calc_min_out(e, &token_in, &token_out, amount_in, amount_out_min),
+  }
+}
+
+/// Swap an exact amount along a multi-hop soroswap path
+///
+/// ### Arguments
+/// * `path` - The swap path, from the input token to the output token
+/// * `amount_in` - The amount of the input token to swap
+/// * `amount_out_min` - The minimum amount of the output token to receive
+pub fn swap_path(e: &Env, path: &Vec<Address>, amount_in: i128, amount_out_min: i128) {
+  let plan = plan_swap_path(e, path, amount_in, amount_out_min);
+  e.authorize_as_current_contract(vec![e, transfer_auth(e, &path.get_unchecked(0), &plan.first_pair, amount_in)]);
+  execute_swap_path(e, path, amount_in, &plan);
+}
+
+/// Execute a planned swap along a multi-hop soroswap path. The transfer of the input token to the
+/// first pair must be pre-authorized with `transfer_auth`.
+///
+/// ### Arguments
+/// * `path` - The swap path, from the input token to the output token
+/// * `amount_in` - The amount of the input token to swap
+/// * `plan` - The planned swap
+pub fn execute_swap_path(e: &Env, path: &Vec<Address>, amount_in: i128, plan: &SwapPlan) {
+  let token_in = path.get_unchecked(0);
+  let token_out = path.get_unchecked(path.len() - 1);
+  let out_client = token::Client::new(e, &token_out);
+  let balance_before = out_client.balance(&e.current_contract_address());
+  RouterClient::new(e, &storage::get_router(e)).swap_exact_tokens_for_tokens(&amount_in, &plan.amount_out_min, path, &e.current_contract_address(), &u64::MAX);
+  let amount_out = out_client.balance(&e.current_contract_address()) - balance_before;
+  e.events().publish(("Pegkeeper", Symbol::new(e, "swap")), (token_in, token_out, amount_in, amount_out));
+}
+
+/// Build the authorization of a transfer from the pegkeeper, for `authorize_as_current_contract`
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `to` - The Address receiving the tokens
+/// * `amount` - The amount transferred
+pub fn transfer_auth(e: &Env, token: &Address, to: &Address, amount: i128) -> InvokerContractAuthEntry {
+  InvokerContractAuthEntry::Contract(SubContractInvocation {
+    context: ContractContext {
+      contract: token.clone(),
+      fn_name: Symbol::new(e, "transfer"),
+      args: vec![e, e.current_contract_address().into_val(e), to.into_val(e), amount.into_val(e)],
+    },
+    sub_invocations: vec![e],
+  })
+}
+
+/// Build the authorization of a burn of the pegkeeper's tokens, for `authorize_as_current_contract`
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `amount` - The amount burnt
+pub fn burn_auth(e: &Env, token: &Address, amount: i128) -> InvokerContractAuthEntry {
+  InvokerContractAuthEntry::Contract(SubContractInvocation {
+    context: ContractContext {
+      contract: token.clone(),
+      fn_name: Symbol::new(e, "burn"),
+      args: vec![e, e.current_contract_address().into_val(e), amount.into_val(e)],
+    },
+    sub_invocations: vec![e],
+  })
+}
+
+/// Require a collateral to be whitelisted for a strategy
+///
+/// ### Arguments
+/// * `strategy` - The strategy
+/// * `collateral` - The Address of the collateral
+///
+/// ### Panics
+/// If the collateral is not whitelisted for the strategy
+pub fn require_collateral(e: &Env, strategy: Strategy, collateral: &Address) {
+  if !storage::get_collaterals(e, strategy).contains(collateral) {
+    panic_with_error!(e, PegkeeperError::CollateralNotAllowed);
+  }
+}
+
+/// Fetch the soroswap pair of the stablecoin against a peg collateral and its reserves
+///
+/// Returns (stablecoin reserve, peg collateral reserve, pair address)
+///
+/// ### Arguments
+/// * `config` - The peg config
+/// * `collateral` - The Address of the peg collateral
+pub fn get_peg_reserves(e: &Env, config: &PegConfig, collateral: &Address) -> (i128, i128, Address) {
+  let pair = RouterClient::new(e, &storage::get_router(e)).router_pair_for(&config.stablecoin, collateral);
+  let pair_client = PairClient::new(e, &pair);
+  let (reserve_0, reserve_1) = pair_client.get_reserves();
+  if pair_client.token_0() == config.stablecoin {
+    (reserve_0, reserve_1, pair)
+  } else {
+    (reserve_1, reserve_0, pair)
+  }
+}
+
+/// Fetch the target price of a stablecoin in units of the peg collateral, scaled to 7 decimals
+///
+/// Peg collaterals are USD stablecoins, so a non-USD peg targets the USD price of its currency
+/// reported by the stablecoin's FX oracle. A USD peg targets 1.
+///
+/// ### Arguments
+/// * `stablecoin` - The Address of the stablecoin
+///
+/// ### Panics
+/// If the FX oracle price is missing or stale
+pub fn get_peg_target(e: &Env, stablecoin: &Address) -> i128 {
+  match storage::get_fx_config(e, stablecoin) {
+    Some(config) => oracle::get_fx_price(e, &config),
+    None => oracle::SCALAR_7,
+  }
+}
+
+/// Calculate the deviation of the stablecoin price from the peg target, in basis points
+///
+/// Assumes both pair assets use the same decimals. A positive deviation means the
+/// stablecoin trades above the peg.
+///
+/// ### Arguments
+/// * `stable_reserve` - The stablecoin reserve of the pair
+/// * `peg_reserve` - The peg collateral reserve of the pair
+/// * `target` - The target price of the stablecoin in the peg collateral, scaled to 7 decimals
+pub fn calc_deviation_bps(stable_reserve: i128, peg_reserve: i128, target: i128) -> i128 {
+  if stable_reserve == 0 || target <= 0 {
+    return 0;
+  }
+  let target_reserve = stable_reserve * target / oracle::SCALAR_7;
+  if target_reserve == 0 {
+    return 0;
+  }
+  (peg_reserve - target_reserve) * SCALAR_BPS / target_reserve
+}
+
+/// Calculate the price of the stablecoin in the peg collateral implied by the pair reserves, scaled to 7 decimals
+///
+/// ### Arguments
+/// * `stable_reserve` - The stablecoin reserve of the pair
+/// * `peg_reserve` - The peg collateral reserve of the pair
+pub fn calc_pair_price(stable_reserve: i128, peg_reserve: i128) -> i128 {
+  if stable_reserve == 0 {
+    return 0;
+  }
+  peg_reserve * oracle::SCALAR_7 / stable_reserve
+}
+
+/// Record the current price of a pair in its price accumulator
+///
+/// The previous price is accumulated over the ledgers it was held for, so a price recorded in the current
+/// ledger does not weigh on the TWAP until a later ledger. A new checkpoint is taken once the latest one
+/// is `window` ledgers old, so the TWAP always spans between `window` and twice `window` ledgers.
+///
+/// ### Arguments
+/// * `pair` - The Address of the pair
+/// * `price` - The current price of the pair
+/// * `window` - The minimum number of ledgers the TWAP is computed over
+pub fn record_price(e: &Env, pair: &Address, price: i128, window: u32) -> PriceAccumulator {
+  let ledger = e.ledger().sequence();
+  let mut acc = match storage::get_price_accumulator(e, pair) {
+    Some(acc) => acc,
+    None => PriceAccumulator {
+      ledger,
+      price,
+      cumulative: 0,
+      mid_ledger: ledger,
+      mid_cumulative: 0,
+      anchor_ledger: ledger,
+      anchor_cumulative: 0,
+    },
+  };
+  acc.cumulative += acc.price * i128::from(ledger - acc.ledger);
+  acc.ledger = ledger;
+  acc.price = price;
+  if ledger - acc.mid_ledger >= window {
+    acc.anchor_ledger = acc.mid_ledger;
+    acc.anchor_cumulative = acc.mid_cumulative;
+    acc.mid_ledger = ledger;
+    acc.mid_cumulative = acc.cumulative;
+  }
+  storage::set_price_accumulator(e, pair, &acc);
+  acc
+}
+
+/// Calculate the TWAP of a price accumulator, if it spans at least `window` ledgers
+///
+/// ### Arguments
+/// * `acc` - The price accumulator
+/// * `window` - The minimum number of ledgers the TWAP is computed over
+pub fn calc_twap(acc: &PriceAccumulator, window: u32) -> Option<i128> {
+  let period = acc.ledger - acc.anchor_ledger;
+  if period == 0 || period < window {
+    return None;
+  }
+  Some((acc.cumulative - acc.anchor_cumulative) / i128::from(period))
+}
+
+/// Record the current price of a peg pair and require it to be close to the pair's TWAP, if a TWAP guard is set
+///
+/// ### Arguments
+/// * `pair` - The Address of the pair
+/// * `stable_reserve` - The stablecoin reserve of the pair
+/// * `peg_reserve` - The peg collateral reserve of the pair
+///
+/// ### Panics
+/// If too little price history is recorded for the pair, or the price deviates from the TWAP by more than the max deviation
+pub fn require_twap(e: &Env, pair: &Address, stable_reserve: i128, peg_reserve: i128) {
+  let config = match storage::get_twap_config(e) {
+    Some(config) => config,
+    None => return,
+  };
+  let price = calc_pair_price(stable_reserve, peg_reserve);
+  let acc = record_price(e, pair, price, config.window);
+  let twap = match calc_twap(&acc, config.window) {
+    Some(twap) if twap > 0 => twap,
+    _ => panic_with_error!(e, PegkeeperError::TwapUnavailable),
+  };
+  if (price - twap).abs() * SCALAR_BPS / twap > i128::from(config.max_deviation_bps) {
+    panic_with_error!(e, PegkeeperError::TwapDeviationExceeded);
+  }
+}
+
+/// Record the price of a peg pair after a peg operation moved it, if a TWAP guard is set
+///
+/// ### Arguments
+/// * `config` - The peg config
+/// * `collateral` - The Address of the peg collateral
+pub fn record_peg_price(e: &Env, config: &PegConfig, collateral: &Address) {
+  if let Some(twap_config) = storage::get_twap_config(e) {
+    let (stable_reserve, peg_reserve, pair) = get_peg_reserves(e, config, collateral);
+    record_price(e, &pair, calc_pair_price(stable_reserve, peg_reserve), twap_config.window);
+  }
+}
+
+/// Calculate the amount of `reserve_in` to swap so that both reserves of a constant product pair end equal
+///
+/// Scale `reserve_out` into units of `reserve_in` to balance the pair at a price other than 1.
+pub fn calc_amount_to_peg(reserve_in: i128, reserve_out: i128) -> i128 {
+  let target = match reserve_in.checked_mul(reserve_out) {
+    Some(k) => sqrt(k),
+    None => sqrt(reserve_in) * sqrt(reserve_out),
+  };
+  (target - reserve_in).max(0)
+}
+
+/// Calculate the output of a soroswap swap at the given reserves, after the 0.3% swap fee
+///
+/// ### Arguments
+/// * `amount_in` - The amount of the input token
+/// * `reserve_in` - The input token reserve of the pair
+/// * `reserve_out` - The output token reserve of the pair
+pub fn calc_amount_out(amount_in: i128, reserve_in: i128, reserve_out: i128) -> i128 {
+  let amount_in_with_fee = amount_in * 997;
+  let denominator = reserve_in * 1000 + amount_in_with_fee;
+  if denominator == 0 {
+    return 0;
+  }
+  amount_in_with_fee * reserve_out / denominator
+}
+
+/// Quote a peg operation selling an amount into a pair, without executing it
+///
+/// ### Arguments
+/// * `amount_in` - The amount sold
+/// * `value_in` - The value of the amount sold at the peg target, in the bought token
+/// * `reserve_in` - The reserve of the sold token
+/// * `reserve_out` - The reserve of the bought token
+pub fn calc_rebalance_quote(amount_in: i128, value_in: i128, reserve_in: i128, reserve_out: i128) -> RebalanceQuote {
+  let expected_out = calc_amount_out(amount_in, reserve_in, reserve_out);
+  let spot_out = if reserve_in == 0 { 0 } else { amount_in * reserve_out / reserve_in };
+  let price_impact_bps = if spot_out == 0 { 0 } else { (spot_out - expected_out) * SCALAR_BPS / spot_out };
+  RebalanceQuote {
+    expected_out,
+    expected_profit: expected_out - value_in,
+    price_impact_bps,
+  }
+}
+
+/// Integer square root, rounded down
+pub fn sqrt(x: i128) -> i128 {
+  if x <= 1 {
+    return x.max(0);
+  }
+  let mut z = x;
+  let mut y = x / 2 + 1;
+  while y < z {
+    z = y;
+    y = (x / y + y) / 2;
+  }
+  z
+}
+
+/// Mint stablecoins through the treasury's minter facility and sell them into the peg pair while the
+/// stablecoin trades above the peg. The minted stablecoins are recorded as peg debt.
+///
+/// The stablecoins are never sold below the peg target. Returns the profit realized in the peg asset,
+/// i.e. the proceeds above the value of the stablecoins sold at the peg target.
+///
+/// ### Panics
+/// If the pegkeeper is not a treasury minter, or the amount exceeds its mint allowance
+pub fn expand_peg(e: &Env, config: &PegConfig, pair: &Address, peg_asset: &Address, amount: i128) -> i128 {
+  let peg_value = amount * get_peg_target(e, &config.stablecoin) / oracle::SCALAR_7;
+  mint_stablecoin(e, amount);
+
+  let peg_client = token::Client::new(e, peg_asset);
+  let balance_before = peg_client.balance(&e.current_contract_address());
+  swap(e, pair.clone(), config.stablecoin.clone(), peg_asset.clone(), amount, peg_value);
+  let amount_out = peg_client.balance(&e.current_contract_address()) - balance_before;
+  let profit = amount_out - peg_value;
+
+  storage::set_expand_profit(e, storage::get_expand_profit(e) + profit);
+  record_peg_price(e, config, peg_asset);
+  e.events().publish(
+    ("Pegkeeper", Symbol::new(e, "rebalance")),
+    (peg_asset.clone(), Symbol::new(e, "expand"), amount, amount_out, profit),
+  );
+  profit
+}
+
+/// Buy stablecoins back from the peg pair with the peg asset held by the pegkeeper while the stablecoin
+/// trades below the peg, and burn them through the treasury's minter facility up to the peg debt.
+///
+/// The stablecoins are never bought above the peg target. Returns the profit realized in the stablecoin,
+/// i.e. the stablecoins bought above the value of the peg asset spent at the peg target. The profit is
+/// kept by the pegkeeper rather than burnt.
+///
+/// ### Panics
+/// If the pegkeeper balance of the peg asset is too low
+pub fn contract_peg(e: &Env, config: &PegConfig, pair: &Address, peg_asset: &Address, amount: i128) -> i128 {
+  let balance = token::Client::new(e, peg_asset).balance(&e.current_contract_address());
+  if amount > balance {
+    panic_with_error!(e, PegkeeperError::InsufficientBalanceError);
+  }
+  let peg_value = amount * oracle::SCALAR_7 / get_peg_target(e, &config.stablecoin);
+
+  let stable_client = token::Client::new(e, &config.stablecoin);
+  let balance_before = stable_client.balance(&e.current_contract_address());
+  swap(e, pair.clone(), peg_asset.clone(), config.stablecoin.clone(), amount, peg_value);
+  let amount_out = stable_client.balance(&e.current_contract_address()) - balance_before;
+  let profit = amount_out - peg_value;
+
+  // retire the supply minted by expansions the peg asset was earned with
+  let burn_amount = peg_value.min(storage::get_peg_debt(e));
+  if burn_amount > 0 {
+    burn_stablecoin(e, &config.stablecoin, burn_amount);
+  }
+
+  storage::set_contract_profit(e, storage::get_contract_profit(e) + profit);
+  record_peg_price(e, config, peg_asset);
+  e.events().publish(
+    ("Pegkeeper", Symbol::new(e, "rebalance")),
+    (peg_asset.clone(), Symbol::new(e, "contract"), amount, amount_out, profit),
+  );
+  profit
+}
+
+/// Fetch the amount of stablecoins the pegkeeper can still mint through the treasury today
+///
+/// Returns 0 if the pegkeeper is not a treasury minter
+pub fn get_mint_allowance(e: &Env) -> i128 {
+  // the admin is the treasury
+  let args = vec![e, e.current_contract_address().into_val(e)];
+  e.invoke_contract::<i128>(&storage::get_admin(e), &Symbol::new(e, "get_mint_allowance"), args)
+}
+
+/// Mint stablecoins to the pegkeeper through the treasury's minter facility and add them to the peg debt
+///
+/// ### Arguments
+/// * `amount` - The amount to mint
+pub fn mint_stablecoin(e: &Env, amount: i128) {
+  let self_address = e.current_contract_address();
+  let args = vec![e, self_address.into_val(e), self_address.into_val(e), amount.into_val(e)];
+  e.invoke_contract::<Val>(&storage::get_admin(e), &Symbol::new(e, "mint"), args);
+  storage::set_peg_debt(e, storage::get_peg_debt(e) + amount);
+}
+
+/// Burn stablecoins held by the pegkeeper through the treasury's minter facility and repay them from the peg debt
+///
+/// ### Arguments
+/// * `stablecoin` - The Address of the stablecoin
+/// * `amount` - The amount to burn
+pub fn burn_stablecoin(e: &Env, stablecoin: &Address, amount: i128) {
+  e.authorize_as_current_contract(vec![e, burn_auth(e, stablecoin, amount)]);
+  let args = vec![e, e.current_contract_address().into_val(e), amount.into_val(e)];
+  e.invoke_contract::<Val>(&storage::get_admin(e), &Symbol::new(e, "burn"), args);
+  storage::set_peg_debt(e, storage::get_peg_debt(e) - amount);
+}
+
+/// Fill a blend liquidation auction with stablecoins held by the pegkeeper and sell the seized collateral
+///
+/// Seized collateral listed in the auction config is sold through the collateral auction house
+/// instead of being swapped, with the proceeds flowing to the treasury's surplus buffer.
+///
+/// Returns the number of collateral auctions started
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `blend_pool` - The Address of the blend pool
+/// * `user` - The Address of the user being liquidated
+/// * `debt_amount` - The amount of stablecoin debt to repay
+pub fn fill_liquidation(e: &Env, token: &Address, blend_pool: &Address, user: &Address, debt_amount: i128) -> u32 {
+  let pool_client = PoolClient::new(e, blend_pool);
+  let auction = pool_client.get_auction(&0, user);
+  let bid_amount = auction.bid.get(token.clone()).unwrap_or(0);
+  if bid_amount <= 0 {
+    panic_with_error!(e, PegkeeperError::InsufficientBalanceError);
+  }
+  // fill the percentage of the auction covered by the debt amount
+  let fill_pct = ((debt_amount * 100 + bid_amount - 1) / bid_amount).min(100);
+
+  let mut fill_requests = vec![
+    e,
+    Request {
+      request_type: 6 as u32, // liquidationAuction
+      address: user.clone(),
+      amount: fill_pct,
+    },
+    Request {
+      request_type: 5 as u32, // Repay
+      address: token.clone(),
+      amount: debt_amount,
+    },
   ];
-  e.authorize_as_current_contract(vec![
-      e,
-      InvokerContractAuthEntry::Contract( SubContractInvocation {
+  let mut lot_balances: Vec<(Address, i128)> = vec![e];
+  for (asset, lot_amount) in auction.lot.iter() {
+    if asset != *token {
+      require_collateral(e, Strategy::Liquidate, &asset);
+    }
+    fill_requests.push_back(Request {
+      request_type: 3 as u32, // Withdraw
+      address: asset.clone(),
+      amount: lot_amount * fill_pct / 100,
+    });
+    let balance = token::Client::new(e, &asset).balance(&e.current_contract_address());
+    lot_balances.push_back((asset, balance));
+  }
+
+  e.authorize_as_current_contract(vec![e, transfer_auth(e, token, blend_pool, debt_amount)]);
+  pool_client.submit(&e.current_contract_address(), &e.current_contract_address(), &e.current_contract_address(), &fill_requests);
+
+  // sell the seized collateral for stablecoins
+  let auction_config = storage::get_auction_config(e);
+  let mut auctions = 0;
+  for (asset, balance_before) in lot_balances.iter() {
+    if asset == *token {
+      continue;
+    }
+    let seized = token::Client::new(e, &asset).balance(&e.current_contract_address()) - balance_before;
+    if seized <= 0 {
+      continue;
+    }
+    match &auction_config {
+      Some(config) if config.collaterals.contains(&asset) => {
+        start_collateral_auction(e, config, token, &asset, seized);
+        auctions += 1;
+      }
+      _ => {
+        swap_adapter::swap_exact_in(e, &asset, token, seized, 0);
+      }
+    }
+  }
+  auctions
+}
+
+/// Start a Dutch auction of seized collateral in the auction house, priced off the oracle
+///
+/// The auction starts at the oracle price of the collateral in the stablecoin plus the start
+/// premium and declines to the oracle price less the floor discount.
+///
+/// ### Arguments
+/// * `config` - The collateral auction config
+/// * `token` - The Address of the stablecoin
+/// * `asset` - The Address of the seized collateral
+/// * `amount` - The amount of the seized collateral
+pub fn start_collateral_auction(e: &Env, config: &AuctionConfig, token: &Address, asset: &Address, amount: i128) -> u32 {
+  let oracle_config = storage::get_oracle_config(e);
+  let price = oracle::get_price(e, &oracle_config, asset) * oracle::SCALAR_7 / oracle::get_price(e, &oracle_config, token);
+  let start_price = price * (SCALAR_BPS + i128::from(config.start_premium_bps)) / SCALAR_BPS;
+  let end_price = price * (SCALAR_BPS - i128::from(config.floor_discount_bps)) / SCALAR_BPS;
+  let lot = Lot {
+    asset: asset.clone(),
+    amount,
+    start_price,
+    end_price,
+  };
+
+  e.authorize_as_current_contract(vec![e, transfer_auth(e, asset, &config.auction_house, amount)]);
+  let args: Vec<Val> = vec![e, e.current_contract_address().into_val(e), lot.into_val(e)];
+  let auction_id = e.invoke_contract::<u32>(&config.auction_house, &Symbol::new(e, "start_auction"), args);
+  e.events().publish(
+    ("Pegkeeper", Symbol::new(e, "start_auction")),
+    (asset.clone(), amount, start_price, end_price, auction_id),
+  );
+  auction_id
+}
+
+/// Calculate the modifiers the blend pool scales the bid and lot of an auction by at the current ledger
+///
+/// The lot grows from nothing to its full size over the first 200 blocks of the auction, then the
+/// bid shrinks from its full size to nothing over the next 200 blocks.
+///
+/// Returns the bid and lot modifiers, in 7 decimals
+///
+/// ### Arguments
+/// * `block` - The ledger sequence the auction started at
+pub fn calc_auction_modifiers(e: &Env, block: u32) -> (i128, i128) {
+  let elapsed = i128::from(e.ledger().sequence().saturating_sub(block));
+  if elapsed <= 200 {
+    (oracle::SCALAR_7, elapsed * oracle::SCALAR_7 / 200)
+  } else {
+    (((400 - elapsed) * oracle::SCALAR_7 / 200).max(0), oracle::SCALAR_7)
+  }
+}
+
+/// Require an auction type to be one the pegkeeper can fill
+///
+/// ### Panics
+/// If the auction type is not a user liquidation, bad debt or interest auction
+pub fn require_auction_type(e: &Env, auction_type: u32) {
+  if auction_type > INTEREST_AUCTION {
+    panic_with_error!(e, PegkeeperError::AuctionNotFillable);
+  }
+}
+
+/// Calculate the bid of an auction filled in full at the current ledger, rounded up as the pool does
+///
+/// ### Arguments
+/// * `auction` - The auction
+/// * `asset` - The Address of the asset bid
+///
+/// ### Panics
+/// If the auction bids any other asset
+pub fn calc_auction_bid(e: &Env, auction: &AuctionData, asset: &Address) -> i128 {
+  if auction.bid.keys().iter().any(|bid_asset| bid_asset != *asset) {
+    panic_with_error!(e, PegkeeperError::AuctionNotFillable);
+  }
+  let (bid_modifier, _) = calc_auction_modifiers(e, auction.block);
+  let bid = auction.bid.get(asset.clone()).unwrap_or(0);
+  (bid * bid_modifier + oracle::SCALAR_7 - 1) / oracle::SCALAR_7
+}
+
+/// Calculate the lot of an auction filled in full at the current ledger, rounded down as the pool does
+///
+/// ### Arguments
+/// * `auction` - The auction
+/// * `asset` - The Address of the asset in the lot
+pub fn calc_auction_lot(e: &Env, auction: &AuctionData, asset: &Address) -> i128 {
+  let (_, lot_modifier) = calc_auction_modifiers(e, auction.block);
+  auction.lot.get(asset.clone()).unwrap_or(0) * lot_modifier / oracle::SCALAR_7
+}
+
+/// Fetch the reserve of an asset from the blend pool
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the reserve asset
+pub fn get_reserve(e: &Env, blend_pool: &Address, asset: &Address) -> Reserve {
+  e.invoke_contract::<Reserve>(blend_pool, &Symbol::new(e, "get_reserve"), vec![e, asset.into_val(e)])
+}
+
+/// Require the backstop auction config to be set
+///
+/// ### Panics
+/// If the backstop auction config is not set
+pub fn require_backstop_auction_config(e: &Env) -> BackstopAuctionConfig {
+  match storage::get_backstop_auction_config(e) {
+    Some(config) => config,
+    None => panic_with_error!(e, PegkeeperError::AuctionNotFillable),
+  }
+}
+
+/// Calculate the stablecoins needed to mint backstop tokens with the deposit asset, at the spot
+/// price of the comet pool and the oracle price of the deposit asset, plus the configured buffer
+///
+/// A single sided deposit of a fraction of the pool's supply costs that fraction of the pool's
+/// value, which is the balance of the deposit asset divided by its normalized weight.
+///
+/// ### Arguments
+/// * `config` - The backstop auction config
+/// * `token` - The Address of the stablecoin
+/// * `amount` - The amount of backstop tokens
+pub fn calc_backstop_token_cost(e: &Env, config: &BackstopAuctionConfig, token: &Address, amount: i128) -> i128 {
+  let comet = CometClient::new(e, &config.backstop_token);
+  // comet weights are normalized to 7 decimals
+  let deposit = amount * comet.get_balance(&config.deposit_asset) / comet.get_total_supply() * oracle::SCALAR_7
+    / comet.get_normalized_weight(&config.deposit_asset);
+  let oracle_config = storage::get_oracle_config(e);
+  let cost = deposit * oracle::get_price(e, &oracle_config, &config.deposit_asset) / oracle::get_price(e, &oracle_config, token);
+  cost * (SCALAR_BPS + i128::from(config.buffer_bps)) / SCALAR_BPS
+}
+
+/// Calculate the stablecoins needed to fill a blend auction in full at the current ledger
+///
+/// User liquidation and bad debt auctions bid the stablecoin debt the filler takes on and repays,
+/// in dTokens. Interest auctions bid backstop tokens, minted with the stablecoin swapped into the
+/// deposit asset of the backstop auction config.
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the stablecoin
+/// * `auction_type` - The blend auction type
+/// * `user` - The Address the auction was created for
+///
+/// ### Panics
+/// If the auction type is unknown or the auction bids an asset other than the stablecoin, or
+/// the backstop token for interest auctions
+pub fn calc_auction_cost(e: &Env, blend_pool: &Address, token: &Address, auction_type: u32, user: &Address) -> i128 {
+  require_auction_type(e, auction_type);
+  let auction = PoolClient::new(e, blend_pool).get_auction(&auction_type, user);
+  match auction_type {
+    USER_LIQUIDATION_AUCTION | BAD_DEBT_AUCTION => {
+      let d_tokens = calc_auction_bid(e, &auction, token);
+      let d_rate = get_reserve(e, blend_pool, token).d_rate;
+      (d_tokens * d_rate + SCALAR_9 - 1) / SCALAR_9
+    }
+    INTEREST_AUCTION => {
+      let config = require_backstop_auction_config(e);
+      let backstop_tokens = calc_auction_bid(e, &auction, &config.backstop_token);
+      calc_backstop_token_cost(e, &config, token, backstop_tokens)
+    }
+    _ => panic_with_error!(e, PegkeeperError::AuctionNotFillable),
+  }
+}
+
+/// Fill a blend auction in full with flash loaned stablecoins and sell its lot for the stablecoin
+///
+/// User liquidation lots are withdrawn as collateral and bad debt lots are redeemed from backstop
+/// tokens for the deposit asset. Interest auctions are paid for with backstop tokens minted from
+/// the loaned stablecoins, and any backstop tokens minted beyond the bid are redeemed. Lot assets
+/// other than the stablecoin must be whitelisted for the liquidate strategy.
+///
+/// ### Arguments
+/// * `token` - The Address of the loaned stablecoin
+/// * `amount` - The loaned amount
+/// * `op` - The auction to fill
+///
+/// ### Panics
+/// If the auction type is unknown, the auction bids an unexpected asset, or a lot asset is not whitelisted
+pub fn fill_auction(e: &Env, token: &Address, amount: i128, op: &FillAuctionOp) {
+  require_auction_type(e, op.auction_type);
+  let self_address = e.current_contract_address();
+  let pool_client = PoolClient::new(e, &op.blend_pool);
+  let auction = pool_client.get_auction(&op.auction_type, &op.user);
+  let mut fill_requests = vec![
+    e,
+    Request {
+      request_type: 6 + op.auction_type, // fillUserLiquidationAuction, fillBadDebtAuction or fillInterestAuction
+      address: op.user.clone(),
+      amount: 100,
+    },
+  ];
+  let mut lot_balances: Vec<(Address, i128)> = vec![e];
+  let mut excess_backstop_tokens = 0;
+  match op.auction_type {
+    USER_LIQUIDATION_AUCTION | BAD_DEBT_AUCTION => {
+      if op.auction_type == BAD_DEBT_AUCTION {
+        require_backstop_auction_config(e);
+      }
+      calc_auction_bid(e, &auction, token);
+      fill_requests.push_back(Request {
+        request_type: 5 as u32, // Repay, the excess over the debt is refunded
+        address: token.clone(),
+        amount,
+      });
+      e.authorize_as_current_contract(vec![e, transfer_auth(e, token, &op.blend_pool, amount)]);
+    }
+    INTEREST_AUCTION => {
+      let config = require_backstop_auction_config(e);
+      let bid = calc_auction_bid(e, &auction, &config.backstop_token);
+      // mint at least the backstop tokens bid with all the loaned stablecoins
+      let deposit = if config.deposit_asset == *token {
+        amount
+      } else {
+        let deposit_client = token::Client::new(e, &config.deposit_asset);
+        let deposit_before = deposit_client.balance(&self_address);
+        swap_adapter::swap_exact_in(e, token, &config.deposit_asset, amount, 0);
+        deposit_client.balance(&self_address) - deposit_before
+      };
+      e.authorize_as_current_contract(vec![e, transfer_auth(e, &config.deposit_asset, &config.backstop_token, deposit)]);
+      let minted = CometClient::new(e, &config.backstop_token).dep_tokn_amt_in_get_lp_tokns_out(&config.deposit_asset, &deposit, &bid, &self_address);
+      excess_backstop_tokens = minted - bid;
+      // the pool pulls the bid into the backstop as a donation
+      e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
           context: ContractContext {
-              contract: token_a.clone(),
-              fn_name: Symbol::new(e, "transfer"),
-              args: args.clone(),
+            contract: config.backstop.clone(),
+            fn_name: Symbol::new(e, "donate"),
+            args: vec![e, self_address.into_val(e), op.blend_pool.into_val(e), bid.into_val(e)],
           },
-          sub_invocations: vec![e]
-      })
-  ]);
-  router_client.swap_exact_tokens_for_tokens(&amount_a, &amount_b, &path, &e.current_contract_address(), &u64::MAX);
-  log!(e, "================================= RealPegkeeper  Swap End ================================");
-}
\ No newline at end of file
+          sub_invocations: vec![e, transfer_auth(e, &config.backstop_token, &config.backstop, bid)],
+        }),
+      ]);
+    }
+    _ => panic_with_error!(e, PegkeeperError::AuctionNotFillable),
+  }
+  if op.auction_type == USER_LIQUIDATION_AUCTION {
+    for (asset, _) in auction.lot.iter() {
+      let b_tokens = calc_auction_lot(e, &auction, &asset);
+      let b_rate = get_reserve(e, &op.blend_pool, &asset).b_rate;
+      fill_requests.push_back(Request {
+        request_type: 3 as u32, // Withdraw
+        address: asset.clone(),
+        amount: b_tokens * b_rate / SCALAR_9,
+      });
+    }
+  }
+  for (asset, _) in auction.lot.iter() {
+    let balance = token::Client::new(e, &asset).balance(&self_address);
+    lot_balances.push_back((asset, balance));
+  }
+  pool_client.submit(&self_address, &self_address, &self_address, &fill_requests);
+
+  // sell the lot for stablecoins
+  let backstop_config = storage::get_backstop_auction_config(e);
+  for (asset, balance_before) in lot_balances.iter() {
+    let received = token::Client::new(e, &asset).balance(&self_address) - balance_before;
+    if asset == *token || received <= 0 {
+      continue;
+    }
+    match &backstop_config {
+      Some(config) if asset == config.backstop_token => {
+        redeem_backstop_tokens(e, config, token, received);
+      }
+      _ => {
+        require_collateral(e, Strategy::Liquidate, &asset);
+        swap_adapter::swap_exact_in(e, &asset, token, received, 0);
+      }
+    }
+  }
+  // redeem the backstop tokens minted beyond the bid
+  if let (Some(config), true) = (&backstop_config, excess_backstop_tokens > 0) {
+    redeem_backstop_tokens(e, config, token, excess_backstop_tokens);
+  }
+  e.events().publish(
+    ("Pegkeeper", Symbol::new(e, "fill_auction")),
+    (op.blend_pool.clone(), op.auction_type, op.user.clone(), token.clone(), amount),
+  );
+}
+
+/// Redeem backstop tokens for the deposit asset and sell it for the stablecoin
+///
+/// ### Arguments
+/// * `config` - The backstop auction config
+/// * `token` - The Address of the stablecoin
+/// * `amount` - The amount of backstop tokens to redeem
+pub fn redeem_backstop_tokens(e: &Env, config: &BackstopAuctionConfig, token: &Address, amount: i128) {
+  let out = CometClient::new(e, &config.backstop_token).wdr_tokn_amt_in_get_lp_tokns_out(&config.deposit_asset, &amount, &0, &e.current_contract_address());
+  if config.deposit_asset != *token {
+    swap_adapter::swap_exact_in(e, &config.deposit_asset, token, out, 0);
+  }
+}
+
+/// Require the contract not to be paused
+///
+/// ### Panics
+/// If the contract is paused
+pub fn require_not_paused(e: &Env) {
+  if storage::is_paused(e) {
+    panic_with_error!(e, PegkeeperError::Paused);
+  }
+}
+
+/// Require the caller to be the admin or the pauser, and to authorize the call
+///
+/// ### Arguments
+/// * `caller` - The Address of the caller
+///
+/// ### Panics
+/// If the caller is neither the admin nor the pauser
+pub fn require_pauser(e: &Env, caller: &Address) {
+  caller.require_auth();
+  if *caller != storage::get_admin(e) && Some(caller.clone()) != storage::get_pauser(e) {
+    panic_with_error!(e, PegkeeperError::UnauthorizedError);
+  }
+}
+
+/// Require the caller to be the admin or the keeper, and to authorize the call
+///
+/// ### Arguments
+/// * `caller` - The Address of the caller
+///
+/// ### Panics
+/// If the caller is neither the admin nor the keeper
+pub fn require_keeper(e: &Env, caller: &Address) {
+  caller.require_auth();
+  if *caller != storage::get_admin(e) && Some(caller.clone()) != storage::get_keeper(e) {
+    panic_with_error!(e, PegkeeperError::UnauthorizedError);
+  }
+}
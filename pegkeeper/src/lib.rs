@@ -6,5 +6,6 @@ mod storage;
 mod contract;
 mod errors;
 mod helper;
+mod swap_adapter;
 mod dependencies;
 pub use contract::*;
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, Env, unwrap::UnwrapOptimized, contracttype};
+use soroban_sdk::{Address, Bytes, Env, Symbol, Vec, unwrap::UnwrapOptimized, contracttype};
 
 const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
 
@@ -10,6 +10,181 @@ const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; /
 pub enum DataKey {
     ADMIN,
     ROUTER,
+    PEG,
+    EXPANDPROFIT,
+    CONTRACTPROFIT,
+    ORACLE,
+    LIQPROFIT,
+    SWAPPATH(Address), // mapping collateral address to the swap path used to sell it
+    MAXSLIPPAGE,
+    DEX(Address), // mapping token address to the dex used to sell it
+    COLLATERALS(Strategy), // mapping strategy to the collaterals it is allowed to use
+    FX(Address), // mapping stablecoin address to the FX config of its non-USD peg
+    STRATEGY(u32), // mapping strategy id to its registered strategy contract
+    STRATEGYLIMITS(u32), // mapping strategy id to its risk limits
+    STRATEGYVOLUME(u32), // mapping strategy id to the volume it executed today
+    TWAP,
+    PRICEACC(Address), // mapping pair address to its price accumulator
+    AUCTIONCONFIG,
+    PAUSER,
+    PAUSED,
+    MINPROFIT,
+    BACKSTOPAUCTION,
+    PEGDEBT,
+    KEEPER,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DexConfig {
+    Soroswap,
+    Comet(Address), // comet pool address
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OracleConfig {
+    pub oracle: Address, // SEP-40 oracle reporting USD prices
+    pub max_age: u64,    // maximum age of a price, in seconds
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FxConfig {
+    pub oracle: Address,  // SEP-40 oracle reporting the USD price of fiat currencies, e.g. Reflector FX
+    pub currency: Symbol, // currency the stablecoin is pegged to, e.g. EUR
+    pub max_age: u64,     // maximum age of a price, in seconds
+}
+
+/// A pegkeeper strategy, each with its own collateral whitelist
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[contracttype]
+pub enum Strategy {
+    Peg,       // collaterals the stablecoin is rebalanced against, each pegged to the same unit
+    Auction,   // collaterals received from filling liquidation auctions with `keep_peg`
+    Liquidate, // collaterals seized by liquidating blend users or received from blend auction lots
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct PegConfig {
+    pub stablecoin: Address, // stablecoin address
+    pub threshold_bps: u32,  // deviation from the peg, in basis points, before rebalancing
+}
+
+/// The expected outcome of a peg operation, as simulated before executing it
+#[derive(Clone)]
+#[contracttype]
+pub struct RebalanceQuote {
+    pub expected_out: i128,     // output of the swap at the current reserves
+    pub expected_profit: i128,  // output less the value of the amount sold at the peg target, as realized by the peg operation
+    pub price_impact_bps: i128, // shortfall of the output from the output at the spot price, in basis points
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct TwapConfig {
+    pub window: u32,            // minimum number of ledgers the TWAP is computed over
+    pub max_deviation_bps: u32, // maximum deviation of the pair price from its TWAP, in basis points
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct AuctionConfig {
+    pub auction_house: Address,    // collateral auction contract selling seized collateral
+    pub collaterals: Vec<Address>, // collaterals auctioned instead of swapped, e.g. ones with thin DEX liquidity
+    pub start_premium_bps: u32,    // premium over the oracle price auctions start at, in basis points
+    pub floor_discount_bps: u32,   // discount to the oracle price auctions decline to, in basis points
+}
+
+/// How the pegkeeper trades the backstop tokens paid for interest auctions and received from bad
+/// debt auctions
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopAuctionConfig {
+    pub backstop: Address,       // blend backstop of the pool, receiving the bids of interest auctions
+    pub backstop_token: Address, // comet pool whose LP tokens are the backstop token, e.g. BLND:USDC
+    pub deposit_asset: Address,  // comet asset backstop tokens are minted and redeemed with, e.g. USDC
+    pub buffer_bps: u32,         // margin over the spot cost of the backstop tokens bid, in basis points
+}
+
+/// Cumulative price of a pair, with the checkpoints its TWAP is computed from
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceAccumulator {
+    pub ledger: u32,            // ledger of the last recorded price
+    pub price: i128,            // last recorded price of the stablecoin in the peg collateral, scaled to 7 decimals
+    pub cumulative: i128,       // sum of the recorded prices weighted by the ledgers they were held for
+    pub mid_ledger: u32,        // ledger of the most recent checkpoint
+    pub mid_cumulative: i128,   // cumulative price at the most recent checkpoint
+    pub anchor_ledger: u32,     // ledger of the checkpoint the TWAP is computed from
+    pub anchor_cumulative: i128, // cumulative price at the checkpoint the TWAP is computed from
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct StrategyConfig {
+    pub contract: Address, // strategy contract implementing `execute(token, amount, params)`
+    pub enabled: bool,     // whether the strategy can be executed
+}
+
+/// The operation a flash loan is used for, encoded as XDR in the calldata of `fl_receive`
+#[derive(Clone)]
+#[contracttype]
+pub struct StrategyLimits {
+    pub max_notional: i128,     // maximum amount provided to the strategy in a single execution
+    pub max_daily_volume: i128, // maximum amount provided to the strategy in a day
+    pub min_profit: i128,       // minimum profit an execution has to realize, net of flash loan fees
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct StrategyVolume {
+    pub day: u64,     // the day the volume was executed in, as days since the unix epoch
+    pub volume: i128, // the amount provided to the strategy during the day
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum FlashLoanOp {
+    Auction(AuctionOp),
+    Liquidate(LiquidateOp),
+    Strategy(StrategyOp),
+    FillAuction(FillAuctionOp),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct AuctionOp {
+    pub blend_pool: Address,      // blend pool of the auction
+    pub auction_creator: Address, // user the liquidation auction was created for
+    pub liq_amount: i128,         // percentage of the auction to fill
+    pub lot_token: Address,       // token received from the auction lot
+    pub lot_amount: i128,         // amount of the lot token to withdraw and sell
+    pub path: Vec<Address>,       // swap path, from the lot token to the loaned token
+    pub min_out: i128,            // minimum amount of the loaned token to receive from the swap
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct StrategyOp {
+    pub id: u32,       // id of the registered strategy
+    pub params: Bytes, // parameters passed to the strategy
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct LiquidateOp {
+    pub blend_pool: Address, // blend pool of the user
+    pub user: Address,       // user being liquidated
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FillAuctionOp {
+    pub blend_pool: Address, // blend pool of the auction
+    pub auction_type: u32,   // blend auction type, 0 for user liquidations, 1 for bad debt, 2 for interest
+    pub user: Address,       // user the auction was created for, the backstop for bad debt and interest auctions
 }
 /// Bump the instance rent for the contract
 pub fn extend_instance(e: &Env) {
@@ -62,3 +237,478 @@ pub fn set_router(e: &Env, new_router: &Address) {
         .instance()
         .set(&DataKey::ROUTER, new_router);
 }
+
+/// Fetch the peg config
+///
+/// ### Panics
+/// If the peg config does not exist
+pub fn get_peg_config(e: &Env) -> PegConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::PEG)
+        .unwrap_optimized()
+}
+
+/// Set the peg config
+///
+/// ### Arguments
+/// * `config` - The peg config
+pub fn set_peg_config(e: &Env, config: &PegConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PEG, config);
+}
+
+/// Fetch the collaterals a strategy is allowed to use
+///
+/// ### Arguments
+/// * `strategy` - The strategy
+pub fn get_collaterals(e: &Env, strategy: Strategy) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::COLLATERALS(strategy))
+        .unwrap_or(Vec::new(e))
+}
+
+/// Set the collaterals a strategy is allowed to use
+///
+/// ### Arguments
+/// * `strategy` - The strategy
+/// * `collaterals` - The Addresses of the collaterals
+pub fn set_collaterals(e: &Env, strategy: Strategy, collaterals: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::COLLATERALS(strategy), collaterals);
+}
+
+/// Fetch the profit realized by expanding the peg, in the peg asset
+pub fn get_expand_profit(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::EXPANDPROFIT)
+        .unwrap_or(0)
+}
+
+/// Set the profit realized by expanding the peg
+///
+/// ### Arguments
+/// * `profit` - The total profit, in the peg asset
+pub fn set_expand_profit(e: &Env, profit: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::EXPANDPROFIT, &profit);
+}
+
+/// Fetch the profit realized by contracting the peg, in the stablecoin
+pub fn get_contract_profit(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::CONTRACTPROFIT)
+        .unwrap_or(0)
+}
+
+/// Set the profit realized by contracting the peg
+///
+/// ### Arguments
+/// * `profit` - The total profit, in the stablecoin
+pub fn set_contract_profit(e: &Env, profit: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::CONTRACTPROFIT, &profit);
+}
+
+/// Fetch the stablecoins minted by expanding the peg and not yet burnt by contracting it
+pub fn get_peg_debt(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::PEGDEBT)
+        .unwrap_or(0)
+}
+
+/// Set the stablecoins minted by expanding the peg and not yet burnt by contracting it
+///
+/// ### Arguments
+/// * `debt` - The outstanding amount, in the stablecoin
+pub fn set_peg_debt(e: &Env, debt: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PEGDEBT, &debt);
+}
+
+/// Fetch the oracle config
+///
+/// ### Panics
+/// If the oracle config does not exist
+pub fn get_oracle_config(e: &Env) -> OracleConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::ORACLE)
+        .unwrap_optimized()
+}
+
+/// Set the oracle config
+///
+/// ### Arguments
+/// * `config` - The oracle config
+pub fn set_oracle_config(e: &Env, config: &OracleConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ORACLE, config);
+}
+
+/// Fetch the profit realized by filling liquidations, in the stablecoin
+pub fn get_liquidation_profit(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::LIQPROFIT)
+        .unwrap_or(0)
+}
+
+/// Set the profit realized by filling liquidations
+///
+/// ### Arguments
+/// * `profit` - The total profit, in the stablecoin
+pub fn set_liquidation_profit(e: &Env, profit: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::LIQPROFIT, &profit);
+}
+
+/// Fetch the swap path used to sell a collateral, if any
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+pub fn get_swap_path(e: &Env, collateral: &Address) -> Option<Vec<Address>> {
+    e.storage()
+        .instance()
+        .get(&DataKey::SWAPPATH(collateral.clone()))
+}
+
+/// Set the swap path used to sell a collateral
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+/// * `path` - The swap path, starting at the collateral
+pub fn set_swap_path(e: &Env, collateral: &Address, path: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SWAPPATH(collateral.clone()), path);
+}
+
+/// Remove the swap path used to sell a collateral
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+pub fn del_swap_path(e: &Env, collateral: &Address) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::SWAPPATH(collateral.clone()));
+}
+
+/// Fetch the max slippage from the oracle price allowed on swaps, in basis points, if any
+pub fn get_max_slippage(e: &Env) -> Option<u32> {
+    e.storage()
+        .instance()
+        .get(&DataKey::MAXSLIPPAGE)
+}
+
+/// Set the max slippage from the oracle price allowed on swaps
+///
+/// ### Arguments
+/// * `max_slippage_bps` - The max slippage, in basis points
+pub fn set_max_slippage(e: &Env, max_slippage_bps: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::MAXSLIPPAGE, &max_slippage_bps);
+}
+
+/// Fetch the minimum profit every flash loan and strategy execution must realize, defaulting to 0
+pub fn get_min_profit(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::MINPROFIT)
+        .unwrap_or(0)
+}
+
+/// Set the minimum profit every flash loan and strategy execution must realize
+///
+/// ### Arguments
+/// * `min_profit` - The minimum profit, in the token received
+pub fn set_min_profit(e: &Env, min_profit: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::MINPROFIT, &min_profit);
+}
+
+/// Fetch the dex used to sell a token, defaulting to soroswap
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_dex(e: &Env, token: &Address) -> DexConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::DEX(token.clone()))
+        .unwrap_or(DexConfig::Soroswap)
+}
+
+/// Set the dex used to sell a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `dex` - The dex config
+pub fn set_dex(e: &Env, token: &Address, dex: &DexConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::DEX(token.clone()), dex);
+}
+
+/// Fetch the FX config of a stablecoin with a non-USD peg, if any
+///
+/// ### Arguments
+/// * `stablecoin` - The Address of the stablecoin
+pub fn get_fx_config(e: &Env, stablecoin: &Address) -> Option<FxConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::FX(stablecoin.clone()))
+}
+
+/// Set the FX config of a stablecoin with a non-USD peg
+///
+/// ### Arguments
+/// * `stablecoin` - The Address of the stablecoin
+/// * `config` - The FX config
+pub fn set_fx_config(e: &Env, stablecoin: &Address, config: &FxConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FX(stablecoin.clone()), config);
+}
+
+/// Remove the FX config of a stablecoin, pegging it to USD
+///
+/// ### Arguments
+/// * `stablecoin` - The Address of the stablecoin
+pub fn del_fx_config(e: &Env, stablecoin: &Address) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::FX(stablecoin.clone()));
+}
+
+/// Fetch a registered strategy, if any
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+pub fn get_strategy(e: &Env, id: u32) -> Option<StrategyConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::STRATEGY(id))
+}
+
+/// Register a strategy
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+/// * `config` - The strategy config
+pub fn set_strategy(e: &Env, id: u32, config: &StrategyConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::STRATEGY(id), config);
+}
+
+/// Fetch the risk limits of a strategy, if any
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+pub fn get_strategy_limits(e: &Env, id: u32) -> Option<StrategyLimits> {
+    e.storage()
+        .instance()
+        .get(&DataKey::STRATEGYLIMITS(id))
+}
+
+/// Set the risk limits of a strategy
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+/// * `limits` - The risk limits
+pub fn set_strategy_limits(e: &Env, id: u32, limits: &StrategyLimits) {
+    e.storage()
+        .instance()
+        .set(&DataKey::STRATEGYLIMITS(id), limits);
+}
+
+/// Remove the risk limits of a strategy
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+pub fn del_strategy_limits(e: &Env, id: u32) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::STRATEGYLIMITS(id));
+}
+
+/// Fetch the volume a strategy executed, if any
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+pub fn get_strategy_volume(e: &Env, id: u32) -> Option<StrategyVolume> {
+    e.storage()
+        .instance()
+        .get(&DataKey::STRATEGYVOLUME(id))
+}
+
+/// Set the volume a strategy executed
+///
+/// ### Arguments
+/// * `id` - The id of the strategy
+/// * `volume` - The executed volume
+pub fn set_strategy_volume(e: &Env, id: u32, volume: &StrategyVolume) {
+    e.storage()
+        .instance()
+        .set(&DataKey::STRATEGYVOLUME(id), volume);
+}
+
+/// Fetch the TWAP guard config, if any
+pub fn get_twap_config(e: &Env) -> Option<TwapConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::TWAP)
+}
+
+/// Set the TWAP guard config
+///
+/// ### Arguments
+/// * `config` - The TWAP guard config
+pub fn set_twap_config(e: &Env, config: &TwapConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TWAP, config);
+}
+
+/// Remove the TWAP guard config
+pub fn del_twap_config(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::TWAP);
+}
+
+/// Fetch the collateral auction config, if any
+pub fn get_auction_config(e: &Env) -> Option<AuctionConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::AUCTIONCONFIG)
+}
+
+/// Set the collateral auction config
+///
+/// ### Arguments
+/// * `config` - The collateral auction config
+pub fn set_auction_config(e: &Env, config: &AuctionConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::AUCTIONCONFIG, config);
+}
+
+/// Remove the collateral auction config
+pub fn del_auction_config(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::AUCTIONCONFIG);
+}
+
+/// Fetch the backstop auction config, if any
+pub fn get_backstop_auction_config(e: &Env) -> Option<BackstopAuctionConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::BACKSTOPAUCTION)
+}
+
+/// Set the backstop auction config
+///
+/// ### Arguments
+/// * `config` - The backstop auction config
+pub fn set_backstop_auction_config(e: &Env, config: &BackstopAuctionConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BACKSTOPAUCTION, config);
+}
+
+/// Remove the backstop auction config
+pub fn del_backstop_auction_config(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::BACKSTOPAUCTION);
+}
+
+/// Fetch the price accumulator of a pair, if any
+///
+/// ### Arguments
+/// * `pair` - The Address of the pair
+pub fn get_price_accumulator(e: &Env, pair: &Address) -> Option<PriceAccumulator> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PRICEACC(pair.clone()))
+}
+
+/// Set the price accumulator of a pair
+///
+/// ### Arguments
+/// * `pair` - The Address of the pair
+/// * `accumulator` - The price accumulator
+pub fn set_price_accumulator(e: &Env, pair: &Address, accumulator: &PriceAccumulator) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PRICEACC(pair.clone()), accumulator);
+}
+
+/// Fetch the pauser, e.g. the protocol sentinel, if any
+pub fn get_pauser(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PAUSER)
+}
+
+/// Set the pauser
+///
+/// ### Arguments
+/// * `pauser` - The Address allowed to pause and unpause the contract besides the admin
+pub fn set_pauser(e: &Env, pauser: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PAUSER, pauser);
+}
+
+/// Fetch the keeper allowed to run peg operations besides the admin, if any
+pub fn get_keeper(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::KEEPER)
+}
+
+/// Set the keeper
+///
+/// ### Arguments
+/// * `keeper` - The Address allowed to run peg operations besides the admin
+pub fn set_keeper(e: &Env, keeper: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::KEEPER, keeper);
+}
+
+/// Check if the contract is paused
+pub fn is_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::PAUSED)
+        .unwrap_or(false)
+}
+
+/// Set whether the contract is paused
+///
+/// ### Arguments
+/// * `paused` - Whether the contract is paused
+pub fn set_paused(e: &Env, paused: bool) {
+    if paused {
+        e.storage().instance().set::<DataKey, bool>(&DataKey::PAUSED, &true);
+    } else {
+        e.storage().instance().remove(&DataKey::PAUSED);
+    }
+}
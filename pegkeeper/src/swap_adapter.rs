@@ -0,0 +1,72 @@
+use soroban_sdk::{token, vec, Address, Env, Symbol};
+
+use crate::dependencies::comet::Client as CometClient;
+use crate::helper;
+use crate::storage::{self, DexConfig};
+
+/// A DEX the pegkeeper can swap through
+pub trait SwapAdapter {
+    /// Swap an exact amount of `token_in` for at least `amount_out_min` of `token_out`
+    ///
+    /// ### Arguments
+    /// * `token_in` - The Address of the input token
+    /// * `token_out` - The Address of the output token
+    /// * `amount_in` - The amount of the input token to swap
+    /// * `amount_out_min` - The minimum amount of the output token to receive
+    fn swap_exact_in(&self, e: &Env, token_in: &Address, token_out: &Address, amount_in: i128, amount_out_min: i128);
+}
+
+/// Swaps through the soroswap router, using the configured swap path of the input token if it ends at the output token
+pub struct SoroswapAdapter;
+
+impl SwapAdapter for SoroswapAdapter {
+    fn swap_exact_in(&self, e: &Env, token_in: &Address, token_out: &Address, amount_in: i128, amount_out_min: i128) {
+        let path = match storage::get_swap_path(e, token_in) {
+            Some(path) if path.last() == Some(token_out.clone()) => path,
+            _ => vec![e, token_in.clone(), token_out.clone()],
+        };
+        helper::swap_path(e, &path, amount_in, amount_out_min);
+    }
+}
+
+/// Swaps through a comet weighted pool
+pub struct CometAdapter {
+    pub pool: Address,
+}
+
+impl SwapAdapter for CometAdapter {
+    fn swap_exact_in(&self, e: &Env, token_in: &Address, token_out: &Address, amount_in: i128, amount_out_min: i128) {
+        let amount_out_min = helper::calc_min_out(e, token_in, token_out, amount_in, amount_out_min);
+
+        e.authorize_as_current_contract(vec![e, helper::transfer_auth(e, token_in, &self.pool, amount_in)]);
+        let out_client = token::Client::new(e, token_out);
+        let balance_before = out_client.balance(&e.current_contract_address());
+        CometClient::new(e, &self.pool).swap_exact_amount_in(
+            token_in,
+            &amount_in,
+            token_out,
+            &amount_out_min,
+            &i128::MAX,
+            &e.current_contract_address(),
+        );
+        let amount_out = out_client.balance(&e.current_contract_address()) - balance_before;
+        e.events().publish(
+            ("Pegkeeper", Symbol::new(e, "swap")),
+            (token_in.clone(), token_out.clone(), amount_in, amount_out),
+        );
+    }
+}
+
+/// Swap an exact amount of `token_in` through the DEX configured for it
+///
+/// ### Arguments
+/// * `token_in` - The Address of the input token
+/// * `token_out` - The Address of the output token
+/// * `amount_in` - The amount of the input token to swap
+/// * `amount_out_min` - The minimum amount of the output token to receive
+pub fn swap_exact_in(e: &Env, token_in: &Address, token_out: &Address, amount_in: i128, amount_out_min: i128) {
+    match storage::get_dex(e, token_in) {
+        DexConfig::Soroswap => SoroswapAdapter.swap_exact_in(e, token_in, token_out, amount_in, amount_out_min),
+        DexConfig::Comet(pool) => CometAdapter { pool }.swap_exact_in(e, token_in, token_out, amount_in, amount_out_min),
+    }
+}
@@ -0,0 +1,312 @@
+use crate::helper::{self, SCALAR_BPS};
+use crate::storage::{self, PSMConfig};
+use crate::errors::PSMError;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env, Symbol};
+use token::Client as TokenClient;
+
+#[contract]
+pub struct PSMContract;
+
+#[contractclient(name="PSMClient")]
+pub trait PSM {
+
+    /// Initialize the PSM
+    ///
+    /// The reserve asset and the stablecoin are swapped 1:1, so both must use the same decimals.
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    /// * `treasury` - The Address of the treasury, which must register the PSM as a minter
+    /// * `token` - The Address of the stablecoin
+    /// * `reserve_asset` - The Address of the reserve asset, e.g. USDC
+    /// * `config` - The PSM config
+    ///
+    /// ### Panics
+    /// If the contract is already initialized or the config is invalid
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, reserve_asset: Address, config: PSMConfig);
+
+    /// Swap the reserve asset for stablecoins minted 1:1, minus the fee
+    ///
+    /// ### Arguments
+    /// * `from` - The Address swapping
+    /// * `amount` - The amount of the reserve asset to swap
+    /// * `deadline` - The last ledger sequence the swap can execute in, or None for no deadline
+    /// * `referrer` - The Address of the integrating front-end sharing the fee, or None for no referrer
+    ///
+    /// Returns the stablecoins received
+    ///
+    /// ### Panics
+    /// If the sender is denied or is the referrer, the amount is not positive, the reserve would exceed its cap,
+    /// or the deadline has passed
+    fn swap_in(e: Env, from: Address, amount: i128, deadline: Option<u64>, referrer: Option<Address>) -> i128;
+
+    /// Swap stablecoins for the reserve asset 1:1, minus the fee, burning the stablecoins
+    ///
+    /// ### Arguments
+    /// * `from` - The Address swapping
+    /// * `amount` - The amount of stablecoins to swap
+    /// * `deadline` - The last ledger sequence the swap can execute in, or None for no deadline
+    /// * `referrer` - The Address of the integrating front-end sharing the fee, or None for no referrer
+    ///
+    /// Returns the reserve asset received
+    ///
+    /// ### Panics
+    /// If the sender is denied or is the referrer, the amount is not positive, exceeds the reserve, or the
+    /// deadline has passed
+    fn swap_out(e: Env, from: Address, amount: i128, deadline: Option<u64>, referrer: Option<Address>) -> i128;
+
+    /// (Admin only) Set the PSM config
+    ///
+    /// ### Arguments
+    /// * `config` - The PSM config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the config is invalid
+    fn set_config(e: Env, config: PSMConfig);
+
+    /// (Admin only) Withdraw the fees collected in the reserve asset
+    ///
+    /// ### Arguments
+    /// * `to` - The Address receiving the fees
+    ///
+    /// Returns the fees withdrawn
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn withdraw_fees(e: Env, to: Address) -> i128;
+
+    /// Claim the swap fees accrued to a referrer
+    ///
+    /// ### Arguments
+    /// * `referrer` - The Address of the referrer
+    ///
+    /// Returns the fees claimed, in the reserve asset
+    fn claim_referral_fees(e: Env, referrer: Address) -> i128;
+
+    /// Fetch the PSM config
+    fn get_config(e: Env) -> PSMConfig;
+
+    /// Fetch the reserve asset backing the minted stablecoins
+    fn get_reserve(e: Env) -> i128;
+
+    /// Fetch the fees collected in the reserve asset, excluding those accrued to referrers
+    fn get_fees(e: Env) -> i128;
+
+    /// Fetch the swap fees accrued to a referrer in the reserve asset
+    ///
+    /// ### Arguments
+    /// * `referrer` - The Address of the referrer
+    fn get_referral_fees(e: Env, referrer: Address) -> i128;
+
+    /// (Admin only) Set the pauser, e.g. the protocol sentinel, allowed to pause and unpause the PSM besides the admin
+    ///
+    /// ### Arguments
+    /// * `pauser` - The Address of the pauser
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_pauser(e: Env, pauser: Address);
+
+    /// (Admin or pauser only) Pause the PSM, blocking its swaps
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the pauser
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the pauser
+    fn pause(e: Env, caller: Address);
+
+    /// (Admin or pauser only) Unpause the PSM
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or the pauser
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor the pauser
+    fn unpause(e: Env, caller: Address);
+
+    /// Check if the PSM is paused
+    fn is_paused(e: Env) -> bool;
+}
+
+fn require_valid_config(e: &Env, config: &PSMConfig) {
+    if i128::from(config.fee_in_bps) > SCALAR_BPS
+        || i128::from(config.fee_out_bps) > SCALAR_BPS
+        || i128::from(config.referral_bps) > SCALAR_BPS
+        || config.reserve_cap < 0
+    {
+        panic_with_error!(e, PSMError::InvalidAmount);
+    }
+}
+
+#[contractimpl]
+impl PSM for PSMContract {
+    fn initialize(e: Env, admin: Address, treasury: Address, token: Address, reserve_asset: Address, config: PSMConfig) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, PSMError::AlreadyInitializedError);
+        }
+        require_valid_config(&e, &config);
+
+        storage::set_admin(&e, &admin);
+        storage::set_treasury(&e, &treasury);
+        storage::set_token(&e, &token);
+        storage::set_reserve_asset(&e, &reserve_asset);
+        storage::set_config(&e, &config);
+    }
+
+    fn swap_in(e: Env, from: Address, amount: i128, deadline: Option<u64>, referrer: Option<Address>) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+        from.require_auth();
+        helper::require_not_denied(&e, &from);
+        helper::require_valid_referrer(&e, &from, &referrer);
+        if amount <= 0 {
+            panic_with_error!(&e, PSMError::InvalidAmount);
+        }
+
+        let config = storage::get_config(&e);
+        let fee = helper::calc_fee(amount, config.fee_in_bps);
+        let amount_out = amount - fee;
+        let reserve = storage::get_reserve(&e) + amount_out;
+        if reserve > config.reserve_cap {
+            panic_with_error!(&e, PSMError::ReserveCapExceeded);
+        }
+
+        TokenClient::new(&e, &storage::get_reserve_asset(&e)).transfer(&from, &e.current_contract_address(), &amount);
+        storage::set_reserve(&e, reserve);
+        let referral_fee = helper::collect_fee(&e, fee, &referrer, config.referral_bps);
+        if amount_out > 0 {
+            helper::mint(&e, &from, amount_out);
+        }
+
+        e.events().publish(("PSM", Symbol::new(&e, "swap_in")), (from, amount, amount_out, fee));
+        if let Some(referrer) = referrer {
+            e.events().publish(("PSM", Symbol::new(&e, "referral_fee")), (referrer, referral_fee));
+        }
+        amount_out
+    }
+
+    fn swap_out(e: Env, from: Address, amount: i128, deadline: Option<u64>, referrer: Option<Address>) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+        from.require_auth();
+        helper::require_not_denied(&e, &from);
+        helper::require_valid_referrer(&e, &from, &referrer);
+        if amount <= 0 {
+            panic_with_error!(&e, PSMError::InvalidAmount);
+        }
+
+        let reserve = storage::get_reserve(&e);
+        if amount > reserve {
+            panic_with_error!(&e, PSMError::InsufficientBalanceError);
+        }
+        let config = storage::get_config(&e);
+        let fee = helper::calc_fee(amount, config.fee_out_bps);
+        let amount_out = amount - fee;
+
+        TokenClient::new(&e, &storage::get_token(&e)).transfer(&from, &e.current_contract_address(), &amount);
+        helper::burn(&e, amount);
+        storage::set_reserve(&e, reserve - amount);
+        let referral_fee = helper::collect_fee(&e, fee, &referrer, config.referral_bps);
+        if amount_out > 0 {
+            TokenClient::new(&e, &storage::get_reserve_asset(&e)).transfer(&e.current_contract_address(), &from, &amount_out);
+        }
+
+        e.events().publish(("PSM", Symbol::new(&e, "swap_out")), (from, amount, amount_out, fee));
+        if let Some(referrer) = referrer {
+            e.events().publish(("PSM", Symbol::new(&e, "referral_fee")), (referrer, referral_fee));
+        }
+        amount_out
+    }
+
+    fn set_config(e: Env, config: PSMConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        require_valid_config(&e, &config);
+        storage::set_config(&e, &config);
+
+        e.events().publish(("PSM", Symbol::new(&e, "set_config")), (config.fee_in_bps, config.fee_out_bps, config.referral_bps, config.reserve_cap));
+    }
+
+    fn withdraw_fees(e: Env, to: Address) -> i128 {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let fees = storage::get_fees(&e);
+        if fees > 0 {
+            storage::set_fees(&e, 0);
+            TokenClient::new(&e, &storage::get_reserve_asset(&e)).transfer(&e.current_contract_address(), &to, &fees);
+        }
+
+        e.events().publish(("PSM", Symbol::new(&e, "withdraw_fees")), (to, fees));
+        fees
+    }
+
+    fn claim_referral_fees(e: Env, referrer: Address) -> i128 {
+        storage::extend_instance(&e);
+        referrer.require_auth();
+
+        let fees = storage::get_referral_fees(&e, &referrer);
+        if fees > 0 {
+            storage::set_referral_fees(&e, &referrer, 0);
+            TokenClient::new(&e, &storage::get_reserve_asset(&e)).transfer(&e.current_contract_address(), &referrer, &fees);
+        }
+
+        e.events().publish(("PSM", Symbol::new(&e, "claim_referral_fees")), (referrer, fees));
+        fees
+    }
+
+    fn get_config(e: Env) -> PSMConfig {
+        storage::get_config(&e)
+    }
+
+    fn get_reserve(e: Env) -> i128 {
+        storage::get_reserve(&e)
+    }
+
+    fn get_fees(e: Env) -> i128 {
+        storage::get_fees(&e)
+    }
+
+    fn get_referral_fees(e: Env, referrer: Address) -> i128 {
+        storage::get_referral_fees(&e, &referrer)
+    }
+
+    fn set_pauser(e: Env, pauser: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_pauser(&e, &pauser);
+
+        e.events().publish(("PSM", Symbol::new(&e, "set_pauser")), pauser);
+    }
+
+    fn pause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_pauser(&e, &caller);
+
+        storage::set_paused(&e, true);
+
+        e.events().publish(("PSM", Symbol::new(&e, "pause")), caller);
+    }
+
+    fn unpause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_pauser(&e, &caller);
+
+        storage::set_paused(&e, false);
+
+        e.events().publish(("PSM", Symbol::new(&e, "unpause")), caller);
+    }
+
+    fn is_paused(e: Env) -> bool {
+        storage::is_paused(&e)
+    }
+}
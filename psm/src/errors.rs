@@ -0,0 +1,36 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PSMError {
+    /// not yet initialized
+    NotInitialized = 1301,
+
+    /// already initialized
+    AlreadyInitializedError = 1302,
+
+    /// amount is zero or negative, or the fee is above 100%
+    InvalidAmount = 1303,
+
+    /// the swap would take the reserve above its cap
+    ReserveCapExceeded = 1304,
+
+    /// not enough reserve or fees to execute the operation
+    InsufficientBalanceError = 1305,
+
+    /// the current ledger sequence is past the deadline of the call
+    DeadlineExpired = 1306,
+
+    /// the account is on the treasury's deny-list
+    AccountDenied = 1307,
+
+    /// the contract is paused
+    Paused = 1308,
+
+    /// the caller is neither the admin nor the pauser
+    UnauthorizedError = 1309,
+
+    /// the referrer of a swap is its sender
+    InvalidReferrer = 1310,
+}
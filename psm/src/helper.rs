@@ -0,0 +1,151 @@
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{panic_with_error, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+use crate::errors::PSMError;
+use crate::storage;
+
+pub(crate) const SCALAR_BPS: i128 = 10_000;
+
+/// Calculate the fee charged on a swap, rounding up
+///
+/// ### Arguments
+/// * `amount` - The amount swapped
+/// * `fee_bps` - The fee, in basis points
+pub fn calc_fee(amount: i128, fee_bps: u32) -> i128 {
+    amount
+        .fixed_mul_ceil(i128::from(fee_bps), SCALAR_BPS)
+        .unwrap_optimized()
+}
+
+/// Collect the fee of a swap, accruing the referrer's share to the referrer, if any, and the rest to the PSM
+///
+/// Returns the referrer's share
+///
+/// ### Arguments
+/// * `fee` - The fee charged on the swap, in the reserve asset
+/// * `referrer` - The Address of the referrer of the swap, if any
+/// * `referral_bps` - The referrer's share of the fee, in basis points
+pub fn collect_fee(e: &Env, fee: i128, referrer: &Option<Address>, referral_bps: u32) -> i128 {
+    let share = match referrer {
+        Some(referrer) => {
+            let share = fee
+                .fixed_mul_floor(i128::from(referral_bps), SCALAR_BPS)
+                .unwrap_optimized();
+            if share > 0 {
+                storage::set_referral_fees(e, referrer, storage::get_referral_fees(e, referrer) + share);
+            }
+            share
+        }
+        None => 0,
+    };
+    storage::set_fees(e, storage::get_fees(e) + fee - share);
+    share
+}
+
+/// Require the referrer of a swap, if any, not to be its sender, who would otherwise get a fee rebate
+///
+/// ### Arguments
+/// * `from` - The Address swapping
+/// * `referrer` - The Address of the referrer of the swap, if any
+///
+/// ### Panics
+/// If the referrer is the sender
+pub fn require_valid_referrer(e: &Env, from: &Address, referrer: &Option<Address>) {
+    if referrer.as_ref() == Some(from) {
+        panic_with_error!(e, PSMError::InvalidReferrer);
+    }
+}
+
+/// Require the current ledger sequence to be at or before a deadline, if any
+///
+/// ### Arguments
+/// * `deadline` - The last ledger sequence the swap can execute in, or None for no deadline
+///
+/// ### Panics
+/// If the current ledger sequence is past the deadline
+pub fn require_before_deadline(e: &Env, deadline: Option<u64>) {
+    if let Some(deadline) = deadline {
+        if u64::from(e.ledger().sequence()) > deadline {
+            panic_with_error!(e, PSMError::DeadlineExpired);
+        }
+    }
+}
+
+/// Require an account not to be on the treasury's deny-list
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+///
+/// ### Panics
+/// If the account is denied
+pub fn require_not_denied(e: &Env, account: &Address) {
+    let args: Vec<Val> = vec![e, account.into_val(e)];
+    if e.invoke_contract::<bool>(&storage::get_treasury(e), &Symbol::new(e, "is_denied"), args) {
+        panic_with_error!(e, PSMError::AccountDenied);
+    }
+}
+
+/// Mint stablecoins through the treasury
+///
+/// ### Arguments
+/// * `to` - The Address receiving the stablecoins
+/// * `amount` - The amount to mint
+pub fn mint(e: &Env, to: &Address, amount: i128) {
+    let args: Vec<Val> = vec![
+        e,
+        e.current_contract_address().into_val(e),
+        to.into_val(e),
+        amount.into_val(e),
+    ];
+    e.invoke_contract::<()>(&storage::get_treasury(e), &Symbol::new(e, "mint"), args);
+}
+
+/// Burn stablecoins held by the PSM through the treasury
+///
+/// ### Arguments
+/// * `amount` - The amount to burn
+pub fn burn(e: &Env, amount: i128) {
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: storage::get_token(e),
+                fn_name: Symbol::new(e, "burn"),
+                args: vec![e, e.current_contract_address().into_val(e), amount.into_val(e)],
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    let args: Vec<Val> = vec![
+        e,
+        e.current_contract_address().into_val(e),
+        amount.into_val(e),
+    ];
+    e.invoke_contract::<()>(&storage::get_treasury(e), &Symbol::new(e, "burn"), args);
+}
+
+/// Require the contract not to be paused
+///
+/// ### Panics
+/// If the contract is paused
+pub fn require_not_paused(e: &Env) {
+    if storage::is_paused(e) {
+        panic_with_error!(e, PSMError::Paused);
+    }
+}
+
+/// Require the caller to be the admin or the pauser, and to authorize the call
+///
+/// ### Arguments
+/// * `caller` - The Address of the caller
+///
+/// ### Panics
+/// If the caller is neither the admin nor the pauser
+pub fn require_pauser(e: &Env, caller: &Address) {
+    caller.require_auth();
+    if *caller != storage::get_admin(e) && Some(caller.clone()) != storage::get_pauser(e) {
+        panic_with_error!(e, PSMError::UnauthorizedError);
+    }
+}
@@ -0,0 +1,257 @@
+use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TREASURY,
+    TOKEN,
+    RESERVEASSET,
+    CONFIG,
+    RESERVE,
+    FEES,
+    PAUSER,
+    PAUSED,
+    REFERRAL(Address), // mapping referrer address to its accrued share of the swap fees
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct PSMConfig {
+    pub fee_in_bps: u32,   // fee charged swapping the reserve asset in for stablecoins, in basis points
+    pub fee_out_bps: u32,  // fee charged swapping stablecoins out for the reserve asset, in basis points
+    pub referral_bps: u32, // share of the swap fee accrued to the referrer of a swap, in basis points
+    pub reserve_cap: i128, // maximum amount of the reserve asset backing the minted stablecoins
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the treasury minting and burning the stablecoin
+///
+/// ### Panics
+/// If the treasury does not exist
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TREASURY)
+        .unwrap_optimized()
+}
+
+/// Set the treasury minting and burning the stablecoin
+///
+/// ### Arguments
+/// * `treasury` - The Address of the treasury
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TREASURY, treasury);
+}
+
+/// Fetch the stablecoin
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the stablecoin
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the reserve asset swapped 1:1 for the stablecoin
+///
+/// ### Panics
+/// If the reserve asset does not exist
+pub fn get_reserve_asset(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::RESERVEASSET)
+        .unwrap_optimized()
+}
+
+/// Set the reserve asset swapped 1:1 for the stablecoin
+///
+/// ### Arguments
+/// * `reserve_asset` - The Address of the reserve asset
+pub fn set_reserve_asset(e: &Env, reserve_asset: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RESERVEASSET, reserve_asset);
+}
+
+/// Fetch the PSM config
+///
+/// ### Panics
+/// If the config does not exist
+pub fn get_config(e: &Env) -> PSMConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::CONFIG)
+        .unwrap_optimized()
+}
+
+/// Set the PSM config
+///
+/// ### Arguments
+/// * `config` - The PSM config
+pub fn set_config(e: &Env, config: &PSMConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::CONFIG, config);
+}
+
+/// Fetch the reserve asset backing the minted stablecoins
+pub fn get_reserve(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::RESERVE)
+        .unwrap_or(0)
+}
+
+/// Set the reserve asset backing the minted stablecoins
+///
+/// ### Arguments
+/// * `reserve` - The reserve amount
+pub fn set_reserve(e: &Env, reserve: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RESERVE, &reserve);
+}
+
+/// Fetch the fees collected in the reserve asset
+pub fn get_fees(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::FEES)
+        .unwrap_or(0)
+}
+
+/// Set the fees collected in the reserve asset
+///
+/// ### Arguments
+/// * `fees` - The fees collected
+pub fn set_fees(e: &Env, fees: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FEES, &fees);
+}
+
+/// Fetch the swap fees accrued to a referrer in the reserve asset
+///
+/// ### Arguments
+/// * `referrer` - The Address of the referrer
+pub fn get_referral_fees(e: &Env, referrer: &Address) -> i128 {
+    let key = DataKey::REFERRAL(referrer.clone());
+    let fees = e.storage().persistent().get::<DataKey, i128>(&key);
+    match fees {
+        Some(fees) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            fees
+        }
+        None => 0,
+    }
+}
+
+/// Set the swap fees accrued to a referrer in the reserve asset
+///
+/// ### Arguments
+/// * `referrer` - The Address of the referrer
+/// * `fees` - The fees accrued
+pub fn set_referral_fees(e: &Env, referrer: &Address, fees: i128) {
+    let key = DataKey::REFERRAL(referrer.clone());
+    if fees == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &fees);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the pauser, e.g. the protocol sentinel, if any
+pub fn get_pauser(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PAUSER)
+}
+
+/// Set the pauser
+///
+/// ### Arguments
+/// * `pauser` - The Address allowed to pause and unpause the contract besides the admin
+pub fn set_pauser(e: &Env, pauser: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PAUSER, pauser);
+}
+
+/// Check if the contract is paused
+pub fn is_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::PAUSED)
+        .unwrap_or(false)
+}
+
+/// Set whether the contract is paused
+///
+/// ### Arguments
+/// * `paused` - Whether the contract is paused
+pub fn set_paused(e: &Env, paused: bool) {
+    if paused {
+        e.storage().instance().set::<DataKey, bool>(&DataKey::PAUSED, &true);
+    } else {
+        e.storage().instance().remove(&DataKey::PAUSED);
+    }
+}
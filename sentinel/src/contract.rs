@@ -0,0 +1,265 @@
+use crate::storage;
+use crate::errors::SentinelError;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, vec, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec};
+
+const MAX_TARGETS: u32 = 10;
+
+#[contract]
+pub struct SentinelContract;
+
+#[contractclient(name="SentinelClient")]
+pub trait Sentinel {
+
+    /// Initialize the sentinel
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address);
+
+    /// (Admin only) Allow or disallow an incident responder to pause the protocol
+    ///
+    /// ### Arguments
+    /// * `guardian` - The Address of the guardian
+    /// * `enabled` - Whether the address can pause the protocol
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_guardian(e: Env, guardian: Address, enabled: bool);
+
+    /// (Admin only) Add a contract the sentinel pauses, e.g. the treasury, pegkeeper, PSM or a CDP.
+    /// The sentinel must be allowed to pause the contract, as its pauser or, for the treasury, with the
+    /// pauser role.
+    ///
+    /// ### Arguments
+    /// * `target` - The Address of the contract
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the contract is already a target, or there are `MAX_TARGETS` targets
+    fn add_target(e: Env, target: Address);
+
+    /// (Admin only) Remove a contract the sentinel pauses
+    ///
+    /// ### Arguments
+    /// * `target` - The Address of the contract
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the contract is not a target
+    fn remove_target(e: Env, target: Address);
+
+    /// (Admin or guardian only) Pause every target with a single transaction
+    ///
+    /// A target that fails to pause is reported with a `pause_failed` event instead of blocking the
+    /// other targets from being paused.
+    ///
+    /// Returns the number of targets paused
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or a guardian
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor a guardian
+    fn pause_all(e: Env, caller: Address) -> u32;
+
+    /// (Admin only) Unpause every target
+    ///
+    /// A target that fails to unpause is reported with an `unpause_failed` event.
+    ///
+    /// Returns the number of targets unpaused
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn unpause_all(e: Env) -> u32;
+
+    /// (Admin or guardian only) Pause a single target
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the admin or a guardian
+    /// * `target` - The Address of the contract
+    ///
+    /// ### Panics
+    /// If the caller is neither the admin nor a guardian, the contract is not a target, or it fails to pause
+    fn pause(e: Env, caller: Address, target: Address);
+
+    /// (Admin only) Unpause a single target
+    ///
+    /// ### Arguments
+    /// * `target` - The Address of the contract
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the contract is not a target, or it fails to unpause
+    fn unpause(e: Env, target: Address);
+
+    /// (Admin only) Set a new admin
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The Address for the admin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// Fetch the contracts the sentinel pauses
+    fn get_targets(e: Env) -> Vec<Address>;
+
+    /// Check if an address is a guardian
+    ///
+    /// ### Arguments
+    /// * `guardian` - The Address of the guardian
+    fn is_guardian(e: Env, guardian: Address) -> bool;
+}
+
+fn require_guardian(e: &Env, caller: &Address) {
+    caller.require_auth();
+    if *caller != storage::get_admin(e) && !storage::is_guardian(e, caller) {
+        panic_with_error!(e, SentinelError::UnauthorizedError);
+    }
+}
+
+fn require_target(e: &Env, target: &Address) {
+    if !storage::get_targets(e).contains(target) {
+        panic_with_error!(e, SentinelError::TargetNotFound);
+    }
+}
+
+/// Invoke `fn_name` on a target with the sentinel as the caller, which every target authorizes
+/// implicitly as the direct invoker
+fn invoke_target(e: &Env, target: &Address, fn_name: &Symbol) -> bool {
+    let args: Vec<Val> = vec![e, e.current_contract_address().into_val(e)];
+    e.try_invoke_contract::<Val, InvokeError>(target, fn_name, args).is_ok()
+}
+
+#[contractimpl]
+impl Sentinel for SentinelContract {
+    fn initialize(e: Env, admin: Address) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, SentinelError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+    }
+
+    fn set_guardian(e: Env, guardian: Address, enabled: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_guardian(&e, &guardian, enabled);
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "set_guardian")), (guardian, enabled));
+    }
+
+    fn add_target(e: Env, target: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let mut targets = storage::get_targets(&e);
+        if targets.contains(&target) {
+            panic_with_error!(&e, SentinelError::TargetExists);
+        }
+        if targets.len() >= MAX_TARGETS {
+            panic_with_error!(&e, SentinelError::TooManyTargets);
+        }
+        targets.push_back(target.clone());
+        storage::set_targets(&e, &targets);
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "add_target")), target);
+    }
+
+    fn remove_target(e: Env, target: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let mut targets = storage::get_targets(&e);
+        match targets.first_index_of(&target) {
+            Some(index) => targets.remove(index),
+            None => panic_with_error!(&e, SentinelError::TargetNotFound),
+        };
+        storage::set_targets(&e, &targets);
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "remove_target")), target);
+    }
+
+    fn pause_all(e: Env, caller: Address) -> u32 {
+        storage::extend_instance(&e);
+        require_guardian(&e, &caller);
+
+        let fn_name = Symbol::new(&e, "pause");
+        let mut paused = 0;
+        for target in storage::get_targets(&e).iter() {
+            if invoke_target(&e, &target, &fn_name) {
+                paused += 1;
+            } else {
+                e.events().publish(("Sentinel", Symbol::new(&e, "pause_failed")), target);
+            }
+        }
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "pause_all")), (caller, paused));
+        paused
+    }
+
+    fn unpause_all(e: Env) -> u32 {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let fn_name = Symbol::new(&e, "unpause");
+        let mut unpaused = 0;
+        for target in storage::get_targets(&e).iter() {
+            if invoke_target(&e, &target, &fn_name) {
+                unpaused += 1;
+            } else {
+                e.events().publish(("Sentinel", Symbol::new(&e, "unpause_failed")), target);
+            }
+        }
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "unpause_all")), unpaused);
+        unpaused
+    }
+
+    fn pause(e: Env, caller: Address, target: Address) {
+        storage::extend_instance(&e);
+        require_guardian(&e, &caller);
+        require_target(&e, &target);
+
+        let args: Vec<Val> = vec![&e, e.current_contract_address().into_val(&e)];
+        e.invoke_contract::<Val>(&target, &Symbol::new(&e, "pause"), args);
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "pause")), (caller, target));
+    }
+
+    fn unpause(e: Env, target: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        require_target(&e, &target);
+
+        let args: Vec<Val> = vec![&e, e.current_contract_address().into_val(&e)];
+        e.invoke_contract::<Val>(&target, &Symbol::new(&e, "unpause"), args);
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "unpause")), target);
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+
+        e.events().publish(("Sentinel", Symbol::new(&e, "set_admin")), new_admin);
+    }
+
+    fn get_targets(e: Env) -> Vec<Address> {
+        storage::get_targets(&e)
+    }
+
+    fn is_guardian(e: Env, guardian: Address) -> bool {
+        storage::is_guardian(&e, &guardian)
+    }
+}
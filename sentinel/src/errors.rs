@@ -0,0 +1,24 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SentinelError {
+    /// not yet initialized
+    NotInitialized = 2001,
+
+    /// already initialized
+    AlreadyInitializedError = 2002,
+
+    /// the caller is not the admin or a guardian
+    UnauthorizedError = 2003,
+
+    /// the contract is not a pause target
+    TargetNotFound = 2004,
+
+    /// the contract is already a pause target
+    TargetExists = 2005,
+
+    /// there are more than the maximum number of pause targets
+    TooManyTargets = 2006,
+}
@@ -0,0 +1,87 @@
+use soroban_sdk::{Address, contracttype, Env, Vec};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    GUARDIAN(Address), // mapping address to whether it can pause the protocol
+    TARGETS,
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Check if an address is a guardian
+///
+/// ### Arguments
+/// * `guardian` - The Address of the guardian
+pub fn is_guardian(e: &Env, guardian: &Address) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::GUARDIAN(guardian.clone()))
+        .unwrap_or(false)
+}
+
+/// Allow or disallow an address to pause the protocol
+///
+/// ### Arguments
+/// * `guardian` - The Address of the guardian
+/// * `enabled` - Whether the address is a guardian
+pub fn set_guardian(e: &Env, guardian: &Address, enabled: bool) {
+    let key = DataKey::GUARDIAN(guardian.clone());
+    if enabled {
+        e.storage().instance().set::<DataKey, bool>(&key, &true);
+    } else {
+        e.storage().instance().remove(&key);
+    }
+}
+
+/// Fetch the contracts the sentinel pauses
+pub fn get_targets(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::TARGETS)
+        .unwrap_or(Vec::new(e))
+}
+
+/// Set the contracts the sentinel pauses
+///
+/// ### Arguments
+/// * `targets` - The Addresses of the contracts
+pub fn set_targets(e: &Env, targets: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TARGETS, targets);
+}
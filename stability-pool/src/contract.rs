@@ -0,0 +1,260 @@
+use crate::helper;
+use crate::storage;
+use crate::errors::StabilityPoolError;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, vec, Address, Env, Symbol, Vec};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+#[contract]
+pub struct StabilityPoolContract;
+
+#[contractclient(name="StabilityPoolClient")]
+pub trait StabilityPool {
+
+    /// Initialize the stability pool
+    ///
+    /// ### Arguments
+    /// * `admin` - The Address for the admin (the treasury)
+    /// * `token` - The Address of the stablecoin deposited into the pool
+    /// * `blend_pool` - The Address of the blend pool liquidations are filled on
+    ///
+    /// ### Panics
+    /// If the contract is already initialized
+    fn initialize(e: Env, admin: Address, token: Address, blend_pool: Address);
+
+    /// Deposit stablecoins into the pool
+    ///
+    /// ### Arguments
+    /// * `from` - The Address of the depositor
+    /// * `amount` - The amount of stablecoins to deposit
+    ///
+    /// Returns the shares minted to the depositor
+    ///
+    /// ### Panics
+    /// If the amount is not positive
+    fn deposit(e: Env, from: Address, amount: i128) -> i128;
+
+    /// Withdraw stablecoins from the pool
+    ///
+    /// ### Arguments
+    /// * `from` - The Address of the depositor
+    /// * `amount` - The amount of stablecoins to withdraw
+    ///
+    /// Returns the shares burnt from the depositor
+    ///
+    /// ### Panics
+    /// If the amount is not positive or exceeds the depositor's balance
+    fn withdraw(e: Env, from: Address, amount: i128) -> i128;
+
+    /// Claim the collateral a depositor has received from liquidations
+    ///
+    /// ### Arguments
+    /// * `from` - The Address of the depositor
+    ///
+    /// Returns the amount claimed of each collateral asset
+    fn claim_collateral(e: Env, from: Address) -> Vec<(Address, i128)>;
+
+    /// Claim the rewards a depositor has earned
+    ///
+    /// ### Arguments
+    /// * `from` - The Address of the depositor
+    ///
+    /// Returns the amount claimed of each reward asset
+    fn claim_rewards(e: Env, from: Address) -> Vec<(Address, i128)>;
+
+    /// Distribute rewards to all depositors pro-rata to their shares
+    ///
+    /// ### Arguments
+    /// * `from` - The Address funding the rewards
+    /// * `asset` - The Address of the reward asset
+    /// * `amount` - The amount of rewards
+    ///
+    /// ### Panics
+    /// If the amount is not positive or the pool has no deposits
+    fn add_rewards(e: Env, from: Address, asset: Address, amount: i128);
+
+    /// (Admin only) Fill a blend liquidation auction with the pool's deposits and distribute
+    /// the seized collateral to depositors
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user being liquidated
+    /// * `debt_amount` - The amount of stablecoin debt repaid
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, or the pool can not cover the debt amount
+    fn liquidate(e: Env, user: Address, debt_amount: i128);
+
+    /// Fetch the total stablecoin deposits of the pool
+    fn get_total_deposits(e: Env) -> i128;
+
+    /// Fetch the stablecoin balance of a depositor
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the depositor
+    fn get_balance(e: Env, user: Address) -> i128;
+
+    /// Fetch the unclaimed collateral and rewards of a depositor
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the depositor
+    fn get_pending(e: Env, user: Address) -> Vec<(Address, i128)>;
+}
+
+#[contractimpl]
+impl StabilityPool for StabilityPoolContract {
+    fn initialize(e: Env, admin: Address, token: Address, blend_pool: Address) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, StabilityPoolError::AlreadyInitializedError);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_token(&e, &token);
+        storage::set_blend_pool(&e, &blend_pool);
+    }
+
+    fn deposit(e: Env, from: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, StabilityPoolError::InvalidAmount);
+        }
+
+        let total_shares = storage::get_total_shares(&e);
+        let total_deposits = storage::get_total_deposits(&e);
+        let shares = if total_shares == 0 {
+            amount
+        } else {
+            amount.fixed_mul_floor(total_shares, total_deposits).unwrap_optimized()
+        };
+        if shares <= 0 {
+            panic_with_error!(&e, StabilityPoolError::InvalidAmount);
+        }
+
+        let mut deposit = storage::get_deposit(&e, &from);
+        helper::settle(&e, &mut deposit);
+        token::Client::new(&e, &storage::get_token(&e)).transfer(&from, &e.current_contract_address(), &amount);
+
+        deposit.shares += shares;
+        storage::set_deposit(&e, &from, &deposit);
+        storage::set_total_shares(&e, total_shares + shares);
+        storage::set_total_deposits(&e, total_deposits + amount);
+
+        e.events().publish(("StabilityPool", Symbol::new(&e, "deposit")), (from, amount, shares));
+        shares
+    }
+
+    fn withdraw(e: Env, from: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, StabilityPoolError::InvalidAmount);
+        }
+
+        let total_shares = storage::get_total_shares(&e);
+        let total_deposits = storage::get_total_deposits(&e);
+        let mut deposit = storage::get_deposit(&e, &from);
+        if total_deposits <= 0 {
+            panic_with_error!(&e, StabilityPoolError::InsufficientBalanceError);
+        }
+        let shares = amount.fixed_mul_ceil(total_shares, total_deposits).unwrap_optimized();
+        if shares > deposit.shares {
+            panic_with_error!(&e, StabilityPoolError::InsufficientBalanceError);
+        }
+
+        helper::settle(&e, &mut deposit);
+        deposit.shares -= shares;
+        storage::set_deposit(&e, &from, &deposit);
+        storage::set_total_shares(&e, total_shares - shares);
+        storage::set_total_deposits(&e, total_deposits - amount);
+        token::Client::new(&e, &storage::get_token(&e)).transfer(&e.current_contract_address(), &from, &amount);
+
+        e.events().publish(("StabilityPool", Symbol::new(&e, "withdraw")), (from, amount, shares));
+        shares
+    }
+
+    fn claim_collateral(e: Env, from: Address) -> Vec<(Address, i128)> {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let mut deposit = storage::get_deposit(&e, &from);
+        helper::settle(&e, &mut deposit);
+        let claimed = helper::claim(&e, &from, &mut deposit, &storage::get_collaterals(&e));
+        storage::set_deposit(&e, &from, &deposit);
+
+        e.events().publish(("StabilityPool", Symbol::new(&e, "claim_collateral")), (from, claimed.clone()));
+        claimed
+    }
+
+    fn claim_rewards(e: Env, from: Address) -> Vec<(Address, i128)> {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let mut deposit = storage::get_deposit(&e, &from);
+        helper::settle(&e, &mut deposit);
+        let claimed = helper::claim(&e, &from, &mut deposit, &storage::get_rewards(&e));
+        storage::set_deposit(&e, &from, &deposit);
+
+        e.events().publish(("StabilityPool", Symbol::new(&e, "claim_rewards")), (from, claimed.clone()));
+        claimed
+    }
+
+    fn add_rewards(e: Env, from: Address, asset: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, StabilityPoolError::InvalidAmount);
+        }
+
+        token::Client::new(&e, &asset).transfer(&from, &e.current_contract_address(), &amount);
+        helper::distribute(&e, &asset, amount, true);
+
+        e.events().publish(("StabilityPool", Symbol::new(&e, "add_rewards")), (from, asset, amount));
+    }
+
+    fn liquidate(e: Env, user: Address, debt_amount: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if debt_amount <= 0 {
+            panic_with_error!(&e, StabilityPoolError::InvalidAmount);
+        }
+
+        // the pool is never fully depleted so outstanding shares always keep a value
+        let total_deposits = storage::get_total_deposits(&e);
+        if debt_amount >= total_deposits {
+            panic_with_error!(&e, StabilityPoolError::InsufficientBalanceError);
+        }
+
+        let (spent, seized) = helper::fill_liquidation(&e, &user, debt_amount);
+        storage::set_total_deposits(&e, total_deposits - spent);
+        for (asset, amount) in seized.iter() {
+            helper::distribute(&e, &asset, amount, false);
+        }
+
+        e.events().publish(("StabilityPool", Symbol::new(&e, "liquidate")), (user, spent, seized));
+    }
+
+    fn get_total_deposits(e: Env) -> i128 {
+        storage::get_total_deposits(&e)
+    }
+
+    fn get_balance(e: Env, user: Address) -> i128 {
+        let total_shares = storage::get_total_shares(&e);
+        if total_shares == 0 {
+            return 0;
+        }
+        let deposit = storage::get_deposit(&e, &user);
+        deposit.shares.fixed_mul_floor(storage::get_total_deposits(&e), total_shares).unwrap_optimized()
+    }
+
+    fn get_pending(e: Env, user: Address) -> Vec<(Address, i128)> {
+        let mut deposit = storage::get_deposit(&e, &user);
+        helper::settle(&e, &mut deposit);
+        let mut pending: Vec<(Address, i128)> = vec![&e];
+        for (asset, amount) in deposit.pending.iter() {
+            pending.push_back((asset, amount));
+        }
+        pending
+    }
+}
@@ -0,0 +1,21 @@
+use soroban_sdk::{self, contracterror};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StabilityPoolError {
+    /// not yet initialized
+    NotInitialized = 1101,
+
+    /// already initialized
+    AlreadyInitializedError = 1102,
+
+    /// amount is zero or negative
+    InvalidAmount = 1103,
+
+    /// not enough deposits or shares to execute the operation
+    InsufficientBalanceError = 1104,
+
+    /// no liquidation auction exists for the user
+    NoAuctionError = 1105,
+}
@@ -0,0 +1,156 @@
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{panic_with_error, token, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+use crate::dependencies::pool::{Client as PoolClient, Request};
+use crate::errors::StabilityPoolError;
+use crate::storage::{self, UserDeposit};
+
+/// Scalar of the accumulated amount per share of an asset
+pub(crate) const SCALAR_12: i128 = 1_000_000_000_000;
+
+/// Move the asset gains a user has earned since their last settlement into their pending balances
+///
+/// ### Arguments
+/// * `deposit` - The user's deposit
+pub fn settle(e: &Env, deposit: &mut UserDeposit) {
+    let mut assets = storage::get_collaterals(e);
+    assets.append(&storage::get_rewards(e));
+    for asset in assets.iter() {
+        let acc = storage::get_acc(e, &asset);
+        let snapshot = deposit.snapshots.get(asset.clone()).unwrap_or(0);
+        if deposit.shares > 0 && acc > snapshot {
+            let gain = deposit.shares.fixed_mul_floor(acc - snapshot, SCALAR_12).unwrap_optimized();
+            let pending = deposit.pending.get(asset.clone()).unwrap_or(0);
+            deposit.pending.set(asset.clone(), pending + gain);
+        }
+        deposit.snapshots.set(asset, acc);
+    }
+}
+
+/// Distribute an amount of an asset to all depositors pro-rata to their shares
+///
+/// ### Arguments
+/// * `asset` - The Address of the asset
+/// * `amount` - The amount distributed
+/// * `is_reward` - Whether the asset is a reward or collateral from a liquidation
+///
+/// ### Panics
+/// If the pool has no shares
+pub fn distribute(e: &Env, asset: &Address, amount: i128, is_reward: bool) {
+    let total_shares = storage::get_total_shares(e);
+    if total_shares <= 0 {
+        panic_with_error!(e, StabilityPoolError::InsufficientBalanceError);
+    }
+    let mut assets = if is_reward { storage::get_rewards(e) } else { storage::get_collaterals(e) };
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        if is_reward { storage::set_rewards(e, &assets) } else { storage::set_collaterals(e, &assets) }
+    }
+    let acc = storage::get_acc(e, asset);
+    let acc_delta = amount.fixed_mul_floor(SCALAR_12, total_shares).unwrap_optimized();
+    storage::set_acc(e, asset, acc + acc_delta);
+}
+
+/// Pay out a user's pending balance of each asset
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+/// * `deposit` - The user's deposit
+/// * `assets` - The assets to pay out
+///
+/// Returns the amount paid out of each asset
+pub fn claim(e: &Env, user: &Address, deposit: &mut UserDeposit, assets: &Vec<Address>) -> Vec<(Address, i128)> {
+    let mut claimed: Vec<(Address, i128)> = vec![e];
+    for asset in assets.iter() {
+        let pending = deposit.pending.get(asset.clone()).unwrap_or(0);
+        if pending > 0 {
+            token::Client::new(e, &asset).transfer(&e.current_contract_address(), user, &pending);
+            deposit.pending.remove(asset.clone());
+            claimed.push_back((asset, pending));
+        }
+    }
+    claimed
+}
+
+/// Fill a blend liquidation auction with the pool's stablecoin deposits
+///
+/// ### Arguments
+/// * `user` - The Address of the user being liquidated
+/// * `debt_amount` - The amount of stablecoin debt repaid
+///
+/// Returns the stablecoins spent and the collateral seized from the auction
+///
+/// ### Panics
+/// If no liquidation auction exists for the user
+pub fn fill_liquidation(e: &Env, user: &Address, debt_amount: i128) -> (i128, Vec<(Address, i128)>) {
+    let token = storage::get_token(e);
+    let blend_pool = storage::get_blend_pool(e);
+    let pool_client = PoolClient::new(e, &blend_pool);
+    let token_client = token::Client::new(e, &token);
+
+    let auction = pool_client.get_auction(&0, user);
+    let bid_amount = auction.bid.get(token.clone()).unwrap_or(0);
+    if bid_amount <= 0 {
+        panic_with_error!(e, StabilityPoolError::NoAuctionError);
+    }
+    // fill the percentage of the auction covered by the debt amount
+    let fill_pct = ((debt_amount * 100 + bid_amount - 1) / bid_amount).min(100);
+
+    let mut fill_requests = vec![
+        e,
+        Request {
+            request_type: 6 as u32, // liquidationAuction
+            address: user.clone(),
+            amount: fill_pct,
+        },
+        Request {
+            request_type: 5 as u32, // Repay
+            address: token.clone(),
+            amount: debt_amount,
+        },
+    ];
+    let mut lot_balances: Vec<(Address, i128)> = vec![e];
+    for (asset, lot_amount) in auction.lot.iter() {
+        fill_requests.push_back(Request {
+            request_type: 3 as u32, // Withdraw
+            address: asset.clone(),
+            amount: lot_amount * fill_pct / 100,
+        });
+        if asset != token {
+            let balance = token::Client::new(e, &asset).balance(&e.current_contract_address());
+            lot_balances.push_back((asset, balance));
+        }
+    }
+
+    let args: Vec<Val> = vec![
+        e,
+        e.current_contract_address().into_val(e),
+        blend_pool.into_val(e),
+        debt_amount.into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args: args.clone(),
+            },
+            sub_invocations: vec![e],
+        })
+    ]);
+    let token_before = token_client.balance(&e.current_contract_address());
+    pool_client.submit(&e.current_contract_address(), &e.current_contract_address(), &e.current_contract_address(), &fill_requests);
+    let spent = token_before - token_client.balance(&e.current_contract_address());
+
+    let mut seized: Vec<(Address, i128)> = vec![e];
+    for (asset, balance_before) in lot_balances.iter() {
+        let amount = token::Client::new(e, &asset).balance(&e.current_contract_address()) - balance_before;
+        if amount > 0 {
+            seized.push_back((asset, amount));
+        }
+    }
+    (spent, seized)
+}
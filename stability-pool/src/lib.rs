@@ -0,0 +1,10 @@
+#![no_std]
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod storage;
+mod contract;
+mod errors;
+mod helper;
+mod dependencies;
+pub use contract::*;
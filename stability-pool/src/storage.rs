@@ -0,0 +1,243 @@
+use soroban_sdk::{Address, contracttype, Env, Map, vec, Vec};
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
+pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
+
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    ADMIN,
+    TOKEN,
+    BLENDPOOL,
+    TOTALSHARES,
+    TOTALDEPOSITS,
+    COLLATERALS,
+    REWARDS,
+    ACC(Address), // mapping asset address to the accumulated amount per share
+    DEPOSIT(Address), // mapping user address to their deposit
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct UserDeposit {
+    pub shares: i128,                 // shares of the pool's stablecoin deposits
+    pub snapshots: Map<Address, i128>, // accumulated amount per share of each asset at the last settlement
+    pub pending: Map<Address, i128>,   // asset amounts owed to the user and not yet claimed
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if the contract has been initialized
+pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
+
+/// Fetch the current admin Address
+///
+/// ### Panics
+/// If the admin does not exist
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::ADMIN)
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ADMIN, new_admin);
+}
+
+/// Fetch the stablecoin deposited into the pool
+///
+/// ### Panics
+/// If the token does not exist
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKEN)
+        .unwrap_optimized()
+}
+
+/// Set the stablecoin deposited into the pool
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKEN, token);
+}
+
+/// Fetch the blend pool liquidations are filled on
+///
+/// ### Panics
+/// If the blend pool does not exist
+pub fn get_blend_pool(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::BLENDPOOL)
+        .unwrap_optimized()
+}
+
+/// Set the blend pool liquidations are filled on
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+pub fn set_blend_pool(e: &Env, blend_pool: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BLENDPOOL, blend_pool);
+}
+
+/// Fetch the total shares of the pool
+pub fn get_total_shares(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOTALSHARES)
+        .unwrap_or(0)
+}
+
+/// Set the total shares of the pool
+///
+/// ### Arguments
+/// * `total_shares` - The total shares
+pub fn set_total_shares(e: &Env, total_shares: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOTALSHARES, &total_shares);
+}
+
+/// Fetch the total stablecoin deposits of the pool
+pub fn get_total_deposits(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOTALDEPOSITS)
+        .unwrap_or(0)
+}
+
+/// Set the total stablecoin deposits of the pool
+///
+/// ### Arguments
+/// * `total_deposits` - The total deposits
+pub fn set_total_deposits(e: &Env, total_deposits: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOTALDEPOSITS, &total_deposits);
+}
+
+/// Fetch the collateral assets received from liquidations
+pub fn get_collaterals(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::COLLATERALS)
+        .unwrap_or(vec![e])
+}
+
+/// Set the collateral assets received from liquidations
+///
+/// ### Arguments
+/// * `collaterals` - The collateral assets
+pub fn set_collaterals(e: &Env, collaterals: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::COLLATERALS, collaterals);
+}
+
+/// Fetch the reward assets distributed to depositors
+pub fn get_rewards(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::REWARDS)
+        .unwrap_or(vec![e])
+}
+
+/// Set the reward assets distributed to depositors
+///
+/// ### Arguments
+/// * `rewards` - The reward assets
+pub fn set_rewards(e: &Env, rewards: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::REWARDS, rewards);
+}
+
+/// Fetch the accumulated amount per share of an asset
+///
+/// ### Arguments
+/// * `asset` - The Address of the asset
+pub fn get_acc(e: &Env, asset: &Address) -> i128 {
+    let key = DataKey::ACC(asset.clone());
+    match e.storage().persistent().get::<DataKey, i128>(&key) {
+        Some(acc) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            acc
+        }
+        None => 0,
+    }
+}
+
+/// Set the accumulated amount per share of an asset
+///
+/// ### Arguments
+/// * `asset` - The Address of the asset
+/// * `acc` - The accumulated amount per share
+pub fn set_acc(e: &Env, asset: &Address, acc: i128) {
+    let key = DataKey::ACC(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &acc);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch a user's deposit
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+pub fn get_deposit(e: &Env, user: &Address) -> UserDeposit {
+    let key = DataKey::DEPOSIT(user.clone());
+    match e.storage().persistent().get::<DataKey, UserDeposit>(&key) {
+        Some(deposit) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            deposit
+        }
+        None => UserDeposit {
+            shares: 0,
+            snapshots: Map::new(e),
+            pending: Map::new(e),
+        },
+    }
+}
+
+/// Set a user's deposit
+///
+/// ### Arguments
+/// * `user` - The Address of the user
+/// * `deposit` - The user's deposit
+pub fn set_deposit(e: &Env, user: &Address, deposit: &UserDeposit) {
+    let key = DataKey::DEPOSIT(user.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, UserDeposit>(&key, deposit);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use soroban_sdk::{
+    xdr::{LedgerEntry, LedgerKey},
+    Env,
+};
+
+/// The per transaction network limits
+pub const MAX_CPU_INSNS: u64 = 100_000_000;
+pub const MAX_MEM_BYTES: u64 = 41_943_040;
+pub const MAX_READ_ENTRIES: u64 = 40;
+pub const MAX_WRITE_ENTRIES: u64 = 25;
+
+/// The allowed growth over a recorded baseline, in basis points
+pub const TOLERANCE_BPS: u64 = 500;
+
+/// The resources consumed by a contract call
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceUsage {
+    pub cpu_insns: u64,
+    pub mem_bytes: u64,
+    /// Ledger entries in the footprint, including the entries written
+    pub read_entries: u64,
+    /// Ledger entries created, modified or deleted
+    pub write_entries: u64,
+}
+
+impl ResourceUsage {
+    fn fields(&self) -> [(&'static str, u64); 4] {
+        [
+            ("cpu_insns", self.cpu_insns),
+            ("mem_bytes", self.mem_bytes),
+            ("read_entries", self.read_entries),
+            ("write_entries", self.write_entries),
+        ]
+    }
+
+    fn parse(data: &str) -> ResourceUsage {
+        let mut usage = ResourceUsage::default();
+        for line in data.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, value) = line.split_once(' ').expect("malformed budget baseline");
+            let value: u64 = value.trim().parse().expect("malformed budget baseline");
+            match name {
+                "cpu_insns" => usage.cpu_insns = value,
+                "mem_bytes" => usage.mem_bytes = value,
+                "read_entries" => usage.read_entries = value,
+                "write_entries" => usage.write_entries = value,
+                _ => panic!("unknown budget field {}", name),
+            }
+        }
+        usage
+    }
+
+    fn serialize(&self) -> String {
+        self.fields()
+            .iter()
+            .map(|(name, value)| format!("{} {}\n", name, value))
+            .collect()
+    }
+}
+
+/// Measure the resources consumed by `f`
+///
+/// Resets the budget before running `f`, and leaves it unlimited afterwards.
+pub fn measure<T>(e: &Env, f: impl FnOnce() -> T) -> (T, ResourceUsage) {
+    let before = ledger_entries(e);
+    e.budget().reset_unlimited();
+    e.host()
+        .with_mut_storage(|storage| {
+            storage.footprint = Default::default();
+            Ok(())
+        })
+        .unwrap();
+
+    let result = f();
+
+    let cpu_insns = e.budget().cpu_instruction_cost();
+    let mem_bytes = e.budget().memory_bytes_cost();
+    let read_entries = e
+        .host()
+        .with_mut_storage(|storage| Ok(storage.footprint.0.len() as u64))
+        .unwrap();
+    let after = ledger_entries(e);
+    let mut write_entries = after.iter().filter(|(key, entry)| before.get(*key) != Some(*entry)).count() as u64;
+    write_entries += before.keys().filter(|key| !after.contains_key(*key)).count() as u64;
+    e.budget().reset_unlimited();
+
+    let usage = ResourceUsage {
+        cpu_insns,
+        mem_bytes,
+        read_entries,
+        write_entries,
+    };
+    (result, usage)
+}
+
+/// Assert `usage` is within the network limits and has not regressed past the baseline `name`
+///
+/// Baselines are stored in `test-suites/budgets`. A missing baseline is recorded from `usage`, and
+/// running with `UPDATE_BUDGETS=1` re-records every baseline.
+///
+/// ### Panics
+/// If any resource exceeds its network limit or its baseline by more than `TOLERANCE_BPS`
+pub fn assert_budget(name: &str, usage: &ResourceUsage) {
+    assert!(usage.cpu_insns <= MAX_CPU_INSNS, "{}: cpu_insns {} over network limit", name, usage.cpu_insns);
+    assert!(usage.mem_bytes <= MAX_MEM_BYTES, "{}: mem_bytes {} over network limit", name, usage.mem_bytes);
+    assert!(usage.read_entries <= MAX_READ_ENTRIES, "{}: read_entries {} over network limit", name, usage.read_entries);
+    assert!(usage.write_entries <= MAX_WRITE_ENTRIES, "{}: write_entries {} over network limit", name, usage.write_entries);
+
+    let path = baseline_path(name);
+    if std::env::var("UPDATE_BUDGETS").is_ok() || !path.exists() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, usage.serialize()).unwrap();
+        return;
+    }
+
+    let baseline = ResourceUsage::parse(&std::fs::read_to_string(&path).unwrap());
+    for ((field, value), (_, base)) in usage.fields().iter().zip(baseline.fields().iter()) {
+        let limit = base + base * TOLERANCE_BPS / 10_000;
+        assert!(
+            value <= &limit,
+            "{}: {} regressed from {} to {} (limit {}), rerun with UPDATE_BUDGETS=1 if expected",
+            name,
+            field,
+            base,
+            value,
+            limit
+        );
+    }
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("budgets").join(format!("{}.txt", name))
+}
+
+fn ledger_entries(e: &Env) -> BTreeMap<LedgerKey, LedgerEntry> {
+    e.to_ledger_snapshot()
+        .ledger_entries
+        .into_iter()
+        .map(|(key, (entry, _))| (*key, *entry))
+        .collect()
+}
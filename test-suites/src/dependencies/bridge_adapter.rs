@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod bridge_adapter_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/bridge_adapter.wasm");
+}
+pub use bridge_adapter_contract::{Client as BridgeAdapterClient, WASM as BRIDGE_ADAPTER_WASM};
+
+pub fn create_bridge_adapter<'a>(e: &Env, admin: &Address, treasury: &Address, token: &Address, relayer: &Address) -> (Address, BridgeAdapterClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, BRIDGE_ADAPTER_WASM);
+    let client = BridgeAdapterClient::new(e, &contract_id);
+    client.initialize(admin, treasury, token, relayer);
+    (contract_id, client)
+}
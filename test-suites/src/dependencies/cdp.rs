@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod cdp_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/cdp.wasm");
+}
+pub use cdp_contract::{CDPConfig, Client as CDPClient, OracleConfig, Position, WASM as CDP_WASM};
+
+pub fn create_cdp<'a>(e: &Env, admin: &Address, treasury: &Address, token: &Address, collateral: &Address, oracle_config: &OracleConfig, config: &CDPConfig) -> (Address, CDPClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, CDP_WASM);
+    let client = CDPClient::new(e, &contract_id);
+    client.initialize(admin, treasury, token, collateral, oracle_config, config);
+    (contract_id, client)
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod collateral_auction_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/collateral_auction.wasm");
+}
+pub use collateral_auction_contract::{Client as CollateralAuctionClient, Lot, WASM as COLLATERAL_AUCTION_WASM};
+
+pub fn create_collateral_auction<'a>(e: &Env, admin: &Address, treasury: &Address, token: &Address, duration: u32) -> (Address, CollateralAuctionClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, COLLATERAL_AUCTION_WASM);
+    let client = CollateralAuctionClient::new(e, &contract_id);
+    client.initialize(admin, treasury, token, &duration);
+    (contract_id, client)
+}
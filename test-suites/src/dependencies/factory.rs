@@ -0,0 +1,22 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use super::{bridge_oracle::BRIDGE_ORACLE_WASM, pegkeeper::PEGKEEPER_WASM, treasury::TREASURY_WASM};
+
+mod factory_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/factory.wasm");
+}
+pub use factory_contract::{Asset, Client as FactoryClient, OrbitConfig, OrbitDeployment, WasmHashes, WASM as FACTORY_WASM};
+
+/// Deploy the factory, uploading the treasury, pegkeeper and bridge oracle wasms it deploys
+pub fn create_factory<'a>(e: &Env, admin: &Address) -> (Address, FactoryClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, FACTORY_WASM);
+    let client = FactoryClient::new(e, &contract_id);
+    let wasm_hashes = WasmHashes {
+        treasury: e.deployer().upload_contract_wasm(TREASURY_WASM),
+        pegkeeper: e.deployer().upload_contract_wasm(PEGKEEPER_WASM),
+        bridge_oracle: e.deployer().upload_contract_wasm(BRIDGE_ORACLE_WASM),
+    };
+    client.initialize(admin, &wasm_hashes);
+    (contract_id, client)
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod governance_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/governance.wasm");
+}
+pub use governance_contract::{Call, Client as GovernanceClient, GovConfig, ProposalStatus, WASM as GOVERNANCE_WASM};
+
+pub fn create_governance<'a>(e: &Env, config: &GovConfig) -> (Address, GovernanceClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, GOVERNANCE_WASM);
+    let client = GovernanceClient::new(e, &contract_id);
+    client.initialize(config);
+    (contract_id, client)
+}
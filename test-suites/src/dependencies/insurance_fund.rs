@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod insurance_fund_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/insurance_fund.wasm");
+}
+pub use insurance_fund_contract::{Client as InsuranceFundClient, WASM as INSURANCE_FUND_WASM};
+
+pub fn create_insurance_fund<'a>(e: &Env, admin: &Address, treasury: &Address, token: &Address) -> (Address, InsuranceFundClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, INSURANCE_FUND_WASM);
+    let client = InsuranceFundClient::new(e, &contract_id);
+    client.initialize(admin, treasury, token);
+    (contract_id, client)
+}
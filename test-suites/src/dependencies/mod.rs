@@ -2,6 +2,17 @@ pub mod backstop;
 pub mod bridge_oracle;
 pub mod emitter;
 pub mod token;
+pub mod ousd_token;
+pub mod insurance_fund;
+pub mod bridge_adapter;
+pub mod collateral_auction;
+pub mod sentinel;
+pub mod stability_pool;
+pub mod psm;
+pub mod cdp;
+pub mod ousd_savings;
+pub mod governance;
+pub mod factory;
 pub mod pool;
 pub mod pool_factory;
 pub mod liquidity_pool;
@@ -0,0 +1,19 @@
+use sep_41_token::testutils::MockTokenClient;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use super::token::create_stellar_token;
+
+mod ousd_savings_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/ousd_savings.wasm");
+}
+pub use ousd_savings_contract::{Client as SavingsClient, WASM as SAVINGS_WASM};
+
+/// Deploy a savings vault for a stablecoin, with a share token administered by the vault
+pub fn create_savings<'a>(e: &Env, admin: &Address, token: &Address, rate_bps: u32) -> (Address, SavingsClient<'a>, MockTokenClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, SAVINGS_WASM);
+    let (_, share_token) = create_stellar_token(e, &contract_id);
+    let client = SavingsClient::new(e, &contract_id);
+    client.initialize(admin, token, &share_token.address, &rate_bps);
+    (contract_id, client, share_token)
+}
@@ -0,0 +1,26 @@
+use sep_41_token::testutils::MockTokenClient;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+mod ousd_token_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/ousd_token.wasm");
+}
+pub use ousd_token_contract::{Client as OusdTokenClient, WASM as OUSD_TOKEN_WASM};
+
+/// Deploy the OUSD token with 7 decimals. The returned client covers the SEP-41 and mint functions
+/// shared with the Stellar asset contract, and `OusdTokenClient` the freeze functions.
+pub fn create_ousd_token<'a>(e: &Env, admin: &Address) -> (Address, MockTokenClient<'a>) {
+    create_orbit_token(e, admin, "Orbit USD", "OUSD")
+}
+
+/// Deploy an Orbit stablecoin token, such as oEUR, with 7 decimals
+pub fn create_orbit_token<'a>(e: &Env, admin: &Address, name: &str, symbol: &str) -> (Address, MockTokenClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, OUSD_TOKEN_WASM);
+    OusdTokenClient::new(e, &contract_id).initialize(
+        admin,
+        &7,
+        &String::from_str(e, name),
+        &String::from_str(e, symbol),
+    );
+    (contract_id.clone(), MockTokenClient::new(e, &contract_id))
+}
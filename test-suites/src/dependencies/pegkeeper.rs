@@ -3,7 +3,7 @@ use soroban_sdk::{testutils::Address as _, Address, Env};
 mod pegkeeper_contract {
     soroban_sdk::contractimport!(file = "../wasm/orbit/pegkeeper.wasm");
 }
-pub use pegkeeper_contract::{Client as PegkeeperClient, WASM as PEGKEEPER_WASM};
+pub use pegkeeper_contract::{AuctionOp, BackstopAuctionConfig, Client as PegkeeperClient, FillAuctionOp, FlashLoanOp, FxConfig, PegConfig, Strategy, StrategyConfig, StrategyLimits, StrategyOp, TwapConfig, WASM as PEGKEEPER_WASM};
 
 pub fn create_pegkeeper<'a>(e: &Env) -> (Address, PegkeeperClient<'a>) {
     let contract_id = Address::generate(e);
@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod psm_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/psm.wasm");
+}
+pub use psm_contract::{Client as PSMClient, PSMConfig, WASM as PSM_WASM};
+
+pub fn create_psm<'a>(e: &Env, admin: &Address, treasury: &Address, token: &Address, reserve_asset: &Address, config: &PSMConfig) -> (Address, PSMClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, PSM_WASM);
+    let client = PSMClient::new(e, &contract_id);
+    client.initialize(admin, treasury, token, reserve_asset, config);
+    (contract_id, client)
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod sentinel_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/sentinel.wasm");
+}
+pub use sentinel_contract::{Client as SentinelClient, WASM as SENTINEL_WASM};
+
+pub fn create_sentinel<'a>(e: &Env, admin: &Address) -> (Address, SentinelClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, SENTINEL_WASM);
+    let client = SentinelClient::new(e, &contract_id);
+    client.initialize(admin);
+    (contract_id, client)
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+mod stability_pool_contract {
+    soroban_sdk::contractimport!(file = "../wasm/orbit/stability_pool.wasm");
+}
+pub use stability_pool_contract::{Client as StabilityPoolClient, WASM as STABILITY_POOL_WASM};
+
+pub fn create_stability_pool<'a>(e: &Env, admin: &Address, token: &Address, blend_pool: &Address) -> (Address, StabilityPoolClient<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_contract_wasm(&contract_id, STABILITY_POOL_WASM);
+    let client = StabilityPoolClient::new(e, &contract_id);
+    client.initialize(admin, token, blend_pool);
+    (contract_id, client)
+}
@@ -6,7 +6,7 @@ mod treasury_contract {
     );
 }
 
-pub use treasury_contract::{Client as TreasuryClient, WASM as TREASURY_WASM, Asset};
+pub use treasury_contract::{Client as TreasuryClient, WASM as TREASURY_WASM, AdminOp, Asset, AutoSupplyConfig, BackstopConfig, BreakerConfig, CollateralConfig, FeeTier, FeeTierConfig, FlashCollateralConfig, FlashLoanConfig, FlashMintConfig, InsuranceConfig, LeverageConfig, MultisigConfig, OracleConfig, Role, SupplyLimit, TreasuryInfo};
 
 pub fn create_treasury<'a>(e: &Env) -> (Address, TreasuryClient<'a>) {
     let contract_id = Address::generate(e);
@@ -0,0 +1,300 @@
+use sep_40_oracle::Asset as OracleAsset;
+use soroban_sdk::{testutils::Address as _, vec as svec, Address, String, Symbol, Vec as SVec};
+
+use crate::{
+    dependencies::mock_treasury::MockAsset,
+    dependencies::pool::{ReserveConfig, ReserveEmissionMetadata},
+    dependencies::treasury::{Asset, FxFeed},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+struct PoolSpec {
+    name: std::string::String,
+    backstop_take_rate: u32,
+    max_positions: u32,
+    reserves: std::vec::Vec<(TokenIndex, ReserveConfig)>,
+    emissions: std::vec::Vec<ReserveEmissionMetadata>,
+}
+
+/// Build a test fixture for a tailored scenario
+///
+/// Pools are created in the order they are added and are activated only if a whale backs them.
+/// The stablecoin is deployed on one pool, and the treasury supply is minted into it. oEUR can be
+/// deployed on the same pool, sharing the treasury and pegkeeper with OUSD.
+///
+/// ```ignore
+/// let fixture = FixtureBuilder::new()
+///     .with_whale()
+///     .with_pool("Teapot", 0_1000000, 4)
+///     .with_reserve(0, TokenIndex::OUSD, ousd_config())
+///     .with_treasury_supply(1_000_000 * SCALAR_7)
+///     .build();
+/// ```
+pub struct FixtureBuilder {
+    mock: bool,
+    whale: bool,
+    pools: std::vec::Vec<PoolSpec>,
+    pairs: std::vec::Vec<(TokenIndex, TokenIndex, i128, i128)>,
+    stablecoin_pool: Option<usize>,
+    treasury_supply: i128,
+    oeur_supply: i128,
+}
+
+impl FixtureBuilder {
+    pub fn new() -> Self {
+        FixtureBuilder {
+            mock: false,
+            whale: false,
+            pools: vec![],
+            pairs: vec![],
+            stablecoin_pool: None,
+            treasury_supply: 0,
+            oeur_supply: 0,
+        }
+    }
+
+    /// Use the mock treasury and pegkeeper instead of the real contracts
+    pub fn with_mock(mut self) -> Self {
+        self.mock = true;
+        self
+    }
+
+    /// Fund a whale that provides BLND-USDC liquidity and backs every pool in the backstop
+    pub fn with_whale(mut self) -> Self {
+        self.whale = true;
+        self
+    }
+
+    /// Add a blend pool
+    pub fn with_pool(mut self, name: &str, backstop_take_rate: u32, max_positions: u32) -> Self {
+        self.pools.push(PoolSpec {
+            name: name.into(),
+            backstop_take_rate,
+            max_positions,
+            reserves: vec![],
+            emissions: vec![],
+        });
+        self
+    }
+
+    /// Add a reserve to a pool. Reserve indexes follow the order reserves are added in.
+    pub fn with_reserve(mut self, pool_index: usize, asset: TokenIndex, config: ReserveConfig) -> Self {
+        self.pools[pool_index].reserves.push((asset, config));
+        self
+    }
+
+    /// Add an emission share to a reserve of a pool
+    pub fn with_emissions(mut self, pool_index: usize, res_index: u32, res_type: u32, share: u64) -> Self {
+        self.pools[pool_index].emissions.push(ReserveEmissionMetadata { res_index, res_type, share });
+        self
+    }
+
+    /// Add a soroswap pair. Unequal supplies of a stablecoin and its peg asset create a depegged pair.
+    pub fn with_pair(mut self, token_a: TokenIndex, token_b: TokenIndex, supply_a: i128, supply_b: i128) -> Self {
+        self.pairs.push((token_a, token_b, supply_a, supply_b));
+        self
+    }
+
+    /// Deploy OUSD, backed by USDC, on a pool
+    pub fn with_stablecoin(mut self, pool_index: usize) -> Self {
+        self.stablecoin_pool = Some(pool_index);
+        self
+    }
+
+    /// Mint OUSD into the stablecoin's pool with the treasury. Deploys OUSD on the first pool if no
+    /// stablecoin pool is set.
+    pub fn with_treasury_supply(mut self, amount: i128) -> Self {
+        if self.stablecoin_pool.is_none() {
+            self.stablecoin_pool = Some(0);
+        }
+        self.treasury_supply = amount;
+        self
+    }
+
+    /// Deploy oEUR, pegged to EUR at 1.08 USD, on the stablecoin's pool of the real treasury and mint into it.
+    /// Deploys OUSD on the first pool if no stablecoin pool is set. The pool needs an oEUR reserve.
+    pub fn with_oeur_supply(mut self, amount: i128) -> Self {
+        if self.stablecoin_pool.is_none() {
+            self.stablecoin_pool = Some(0);
+        }
+        self.oeur_supply = amount;
+        self
+    }
+
+    pub fn build<'a>(self) -> TestFixture<'a> {
+        let mut fixture = TestFixture::create(self.mock);
+        let frodo = fixture.users[0].clone();
+
+        if self.whale {
+            fixture.users.push(frodo.clone());
+            fixture.tokens[TokenIndex::XLM].mint(&frodo, &(10_000_000_000 * SCALAR_7)); // 10B XLM
+
+            // mint LP tokens with whale
+            fixture.tokens[TokenIndex::BLND].mint(&frodo, &(500_0010_000_0000_0000 * SCALAR_7));
+            fixture.tokens[TokenIndex::USDC].mint(&frodo, &(12_5010_000_0000_0000 * SCALAR_7));
+            fixture.lp.join_pool(
+                &(500_000_0000 * SCALAR_7),
+                &svec![
+                    &fixture.env,
+                    500_0010_000_0000_0000 * SCALAR_7,
+                    12_5010_000_0000_0000 * SCALAR_7,
+                ],
+                &frodo,
+            );
+        }
+
+        for (pool_index, spec) in self.pools.iter().enumerate() {
+            fixture.create_pool(String::from_str(&fixture.env, &spec.name), spec.backstop_take_rate, spec.max_positions);
+            for (asset, config) in spec.reserves.iter() {
+                fixture.create_pool_reserve(pool_index, *asset, config);
+            }
+            if !spec.emissions.is_empty() {
+                let mut emissions: SVec<ReserveEmissionMetadata> = SVec::new(&fixture.env);
+                for metadata in spec.emissions.iter() {
+                    emissions.push_back(metadata.clone());
+                }
+                fixture.pools[pool_index].pool.set_emissions_config(&emissions);
+            }
+        }
+
+        let token: Address = fixture.tokens[TokenIndex::OUSD].address.clone();
+        if let Some(pool_index) = self.stablecoin_pool {
+            let pool = fixture.pools[pool_index].pool.address.clone();
+            if self.mock {
+                let asset = MockAsset::Stellar(fixture.tokens[TokenIndex::USDC].address.clone());
+                fixture.mock_treasury.deploy_stablecoin(&token, &asset, &pool);
+                fixture.tokens[TokenIndex::OUSD].set_admin(&fixture.mock_treasury.address);
+            } else {
+                let asset = Asset::Stellar(fixture.tokens[TokenIndex::USDC].address.clone());
+                fixture.treasury.deploy_stablecoin(&token, &asset, &pool);
+                fixture.tokens[TokenIndex::OUSD].set_admin(&fixture.treasury.address);
+            }
+            if self.oeur_supply > 0 {
+                let oeur = fixture.tokens[TokenIndex::OEUR].address.clone();
+                let eur = Symbol::new(&fixture.env, "EUR");
+                fixture.treasury.deploy_stablecoin(&oeur, &Asset::Other(eur.clone()), &pool);
+                fixture.tokens[TokenIndex::OEUR].set_admin(&fixture.treasury.address);
+                // value oEUR at the EUR/USD rate of the fixture's oracle
+                fixture.treasury.set_currency(&oeur, &eur);
+                fixture.price_oracle.set_feed_price(&OracleAsset::Other(eur), &1_0800000);
+                let feed = FxFeed { oracle: fixture.price_oracle.address.clone(), max_age: 86400 };
+                fixture.treasury.set_fx_feed(&oeur, &Some(feed));
+            }
+        }
+
+        if self.whale && !fixture.pools.is_empty() {
+            // deposit into backstop, add to reward zone
+            for pool_fixture in fixture.pools.iter() {
+                fixture
+                    .backstop
+                    .deposit(&frodo, &pool_fixture.pool.address, &(50_000 * SCALAR_7));
+            }
+            fixture.backstop.update_tkn_val();
+            for pool_fixture in fixture.pools.iter() {
+                fixture
+                    .backstop
+                    .add_reward(&pool_fixture.pool.address, &Address::generate(&fixture.env));
+                pool_fixture.pool.set_status(&0);
+                pool_fixture.pool.update_status();
+            }
+
+            // enable emissions
+            fixture.emitter.distribute();
+            fixture.backstop.gulp_emissions();
+            for pool_fixture in fixture.pools.iter() {
+                pool_fixture.pool.gulp_emissions();
+            }
+        }
+
+        fixture.jump(60);
+
+        if self.treasury_supply > 0 {
+            if self.mock {
+                fixture.mock_treasury.increase_supply(&token, &self.treasury_supply);
+            } else {
+                fixture.treasury.increase_supply(&fixture.admin, &token, &self.treasury_supply);
+            }
+        }
+        if self.oeur_supply > 0 {
+            let oeur = fixture.tokens[TokenIndex::OEUR].address.clone();
+            fixture.treasury.increase_supply(&fixture.admin, &oeur, &self.oeur_supply);
+        }
+
+        for (token_a, token_b, supply_a, supply_b) in self.pairs.iter() {
+            fixture.create_pair(*token_a, *token_b, *supply_a, *supply_b);
+        }
+
+        fixture.jump(60 * 60); // 1 hr
+
+        fixture.env.budget().reset_unlimited();
+        fixture
+    }
+}
+
+/// The OUSD reserve config of the default fixture
+pub fn ousd_config() -> ReserveConfig {
+    ReserveConfig {
+        decimals: 7,
+        c_factor: 0,
+        l_factor: 1_000_0000,
+        util: 0_800_0000,
+        max_util: 1_000_0000,
+        r_base: 0_040_0000,
+        r_one: 0,
+        r_two: 0,
+        r_three: 0,
+        reactivity: 0,
+        index: 0,
+    }
+}
+
+/// The oEUR reserve config of the two stablecoin fixture
+pub fn oeur_config() -> ReserveConfig {
+    ReserveConfig {
+        index: 2,
+        ..ousd_config()
+    }
+}
+
+/// The XLM reserve config of the default fixture
+pub fn xlm_config() -> ReserveConfig {
+    ReserveConfig {
+        decimals: 7,
+        c_factor: 0_890_0000,
+        l_factor: 0,
+        util: 0,
+        max_util: 1_000_0000,
+        r_base: 0_040_0000,
+        r_one: 0,
+        r_two: 0,
+        r_three: 0,
+        reactivity: 0,
+        index: 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_multiple_pools_without_whale() {
+        let fixture = FixtureBuilder::new()
+            .with_pool("Teapot", 0_1000000, 4)
+            .with_reserve(0, TokenIndex::OUSD, ousd_config())
+            .with_pool("Kettle", 0_1000000, 4)
+            .with_reserve(1, TokenIndex::OUSD, ousd_config())
+            .with_stablecoin(1)
+            .with_pair(TokenIndex::OUSD, TokenIndex::USDC, 1_030_000 * SCALAR_7, 1_000_000 * SCALAR_7)
+            .build();
+
+        assert_eq!(fixture.pools.len(), 2);
+        assert_eq!(fixture.pairs.len(), 1);
+        assert_eq!(fixture.users.len(), 1);
+        assert_eq!(fixture.lp.balance(&fixture.backstop.address), 0);
+        assert_eq!(
+            fixture.treasury.get_pools(&fixture.tokens[TokenIndex::OUSD].address),
+            svec![&fixture.env, fixture.pools[1].pool.address.clone()]
+        );
+    }
+}
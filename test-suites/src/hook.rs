@@ -0,0 +1,40 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Val};
+
+#[derive(Clone)]
+#[contracttype]
+enum HookKey {
+    Calls,
+    Fail,
+}
+
+/// A treasury hook that counts its invocations, or panics on every invocation once set to fail
+#[contract]
+pub struct MockHook;
+
+#[contractimpl]
+impl MockHook {
+    /// Set whether the hook panics when invoked
+    pub fn set_fail(e: Env, fail: bool) {
+        e.storage().instance().set(&HookKey::Fail, &fail);
+    }
+
+    /// Fetch the number of successful invocations
+    pub fn calls(e: Env) -> u32 {
+        e.storage().instance().get(&HookKey::Calls).unwrap_or(0)
+    }
+
+    pub fn on_treasury_op(e: Env, _op: Val, _stage: Val, _token: Address, _amount: i128) {
+        if e.storage().instance().get(&HookKey::Fail).unwrap_or(false) {
+            panic!("hook failed");
+        }
+        let calls: u32 = e.storage().instance().get(&HookKey::Calls).unwrap_or(0);
+        e.storage().instance().set(&HookKey::Calls, &(calls + 1));
+    }
+}
+
+/// Register a mock hook
+pub fn create_mock_hook<'a>(e: &Env) -> (Address, MockHookClient<'a>) {
+    let contract_id = e.register_contract(None, MockHook);
+    let client = MockHookClient::new(e, &contract_id);
+    (contract_id, client)
+}
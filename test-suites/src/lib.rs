@@ -1,6 +1,12 @@
 #![allow(clippy::all)]
 mod setup;
-pub use setup::create_fixture_with_data;
+pub use setup::{create_fixture_with_data, create_fixture_with_stablecoins};
 pub mod assertions;
+pub mod budget;
+pub mod fixture_builder;
+pub mod hook;
+pub mod oracle;
+pub mod receiver;
+pub mod strategy;
 pub mod test_fixture;
 pub mod dependencies;
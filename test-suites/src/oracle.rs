@@ -0,0 +1,84 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+enum OracleKey {
+    Assets,
+    Decimals,
+    Price(Asset),
+    Stale(Asset),
+}
+
+/// A SEP-40 oracle whose prices are set directly by tests
+///
+/// Prices are reported at the current ledger timestamp, so they never go stale on their own.
+/// Setting a feed stale reports its price at timestamp zero instead, older than any max age.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    /// Set the price of a stellar asset, in the oracle's decimals
+    pub fn set_price(e: Env, asset: Address, price: i128) {
+        Self::set_feed_price(e, Asset::Stellar(asset), price);
+    }
+
+    /// Set the price of any oracle feed, in the oracle's decimals
+    pub fn set_feed_price(e: Env, feed: Asset, price: i128) {
+        let mut assets = Self::assets(e.clone());
+        if !assets.contains(&feed) {
+            assets.push_back(feed.clone());
+            e.storage().instance().set(&OracleKey::Assets, &assets);
+        }
+        e.storage().instance().set(&OracleKey::Price(feed), &price);
+    }
+
+    /// Set whether the price of a stellar asset is reported as stale
+    pub fn set_stale(e: Env, asset: Address, stale: bool) {
+        e.storage().instance().set(&OracleKey::Stale(Asset::Stellar(asset)), &stale);
+    }
+
+    /// Set the decimals prices are reported in. Prices already set are not rescaled.
+    pub fn set_decimals(e: Env, decimals: u32) {
+        e.storage().instance().set(&OracleKey::Decimals, &decimals);
+    }
+
+    pub fn base(e: Env) -> Asset {
+        Asset::Other(Symbol::new(&e, "USD"))
+    }
+
+    pub fn assets(e: Env) -> Vec<Asset> {
+        e.storage().instance().get(&OracleKey::Assets).unwrap_or(Vec::new(&e))
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        e.storage().instance().get(&OracleKey::Decimals).unwrap_or(7)
+    }
+
+    pub fn resolution(_e: Env) -> u32 {
+        300
+    }
+
+    pub fn price(e: Env, asset: Asset, _timestamp: u64) -> Option<PriceData> {
+        Self::lastprice(e, asset)
+    }
+
+    pub fn prices(e: Env, asset: Asset, _records: u32) -> Option<Vec<PriceData>> {
+        Self::lastprice(e.clone(), asset).map(|price_data| Vec::from_array(&e, [price_data]))
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        let price: i128 = e.storage().instance().get(&OracleKey::Price(asset.clone()))?;
+        let stale = e.storage().instance().get(&OracleKey::Stale(asset)).unwrap_or(false);
+        let timestamp = if stale { 0 } else { e.ledger().timestamp() };
+        Some(PriceData { price, timestamp })
+    }
+}
+
+/// Register a mock oracle
+pub fn create_test_oracle<'a>(e: &Env) -> (Address, MockOracleClient<'a>) {
+    let contract_id = e.register_contract(None, MockOracle);
+    let client = MockOracleClient::new(e, &contract_id);
+    (contract_id, client)
+}
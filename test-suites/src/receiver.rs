@@ -0,0 +1,98 @@
+use soroban_sdk::{contract, contractimpl, contracttype, token::Client as TokenClient, Address, Bytes, Env};
+
+use crate::dependencies::treasury::TreasuryClient;
+
+#[derive(Clone)]
+#[contracttype]
+enum ReceiverKey {
+    Treasury,
+    Shortfall,
+    Attack,
+}
+
+/// A flash loan receiver that repays the loan plus the fee, minus a configurable shortfall,
+/// out of its own balance
+#[contract]
+pub struct MockReceiver;
+
+#[contractimpl]
+impl MockReceiver {
+    pub fn initialize(e: Env, treasury: Address) {
+        e.storage().instance().set(&ReceiverKey::Treasury, &treasury);
+    }
+
+    /// Set the amount the receiver repays less than it owes
+    pub fn set_shortfall(e: Env, shortfall: i128) {
+        e.storage().instance().set(&ReceiverKey::Shortfall, &shortfall);
+    }
+
+    pub fn fl_receive(e: Env, token: Address, amount: i128, _data: Bytes, fee: i128) {
+        let treasury: Address = e.storage().instance().get(&ReceiverKey::Treasury).unwrap();
+        let shortfall: i128 = e.storage().instance().get(&ReceiverKey::Shortfall).unwrap_or(0);
+        TokenClient::new(&e, &token).approve(
+            &e.current_contract_address(),
+            &treasury,
+            &(amount + fee - shortfall),
+            &(e.ledger().sequence() + 1),
+        );
+    }
+}
+
+/// Register a mock receiver that repays the treasury
+pub fn create_mock_receiver<'a>(e: &Env, treasury: &Address) -> (Address, MockReceiverClient<'a>) {
+    let contract_id = e.register_contract(None, MockReceiver);
+    let client = MockReceiverClient::new(e, &contract_id);
+    client.initialize(treasury);
+    (contract_id, client)
+}
+
+/// The way a malicious receiver misbehaves when it receives a flash loan
+#[derive(Clone)]
+#[contracttype]
+pub enum Attack {
+    Hold,           // keep the loan and return without approving a repayment
+    Reenter,        // flash mint again from within the callback
+    DecreaseSupply, // decrease the treasury supply from within the callback
+    Transfer,       // repay with a transfer instead of approving the repayment
+}
+
+/// A flash loan receiver that misbehaves in the configured way
+#[contract]
+pub struct MaliciousReceiver;
+
+#[contractimpl]
+impl MaliciousReceiver {
+    pub fn initialize(e: Env, treasury: Address, attack: Attack) {
+        e.storage().instance().set(&ReceiverKey::Treasury, &treasury);
+        e.storage().instance().set(&ReceiverKey::Attack, &attack);
+    }
+
+    pub fn fl_receive(e: Env, token: Address, amount: i128, data: Bytes, fee: i128) {
+        let treasury: Address = e.storage().instance().get(&ReceiverKey::Treasury).unwrap();
+        let attack: Attack = e.storage().instance().get(&ReceiverKey::Attack).unwrap();
+        let treasury_client = TreasuryClient::new(&e, &treasury);
+        match attack {
+            Attack::Hold => return,
+            Attack::Reenter => treasury_client.flash_mint(&e.current_contract_address(), &token, &amount, &data, &None),
+            Attack::DecreaseSupply => treasury_client.decrease_supply(&e.current_contract_address(), &token, &amount),
+            Attack::Transfer => {
+                TokenClient::new(&e, &token).transfer(&e.current_contract_address(), &treasury, &(amount + fee));
+                return;
+            }
+        }
+        TokenClient::new(&e, &token).approve(
+            &e.current_contract_address(),
+            &treasury,
+            &(amount + fee),
+            &(e.ledger().sequence() + 1),
+        );
+    }
+}
+
+/// Register a malicious receiver for the treasury
+pub fn create_malicious_receiver<'a>(e: &Env, treasury: &Address, attack: Attack) -> (Address, MaliciousReceiverClient<'a>) {
+    let contract_id = e.register_contract(None, MaliciousReceiver);
+    let client = MaliciousReceiverClient::new(e, &contract_id);
+    client.initialize(treasury, &attack);
+    (contract_id, client)
+}
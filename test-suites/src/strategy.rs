@@ -0,0 +1,43 @@
+use soroban_sdk::{contract, contractimpl, contracttype, token::Client as TokenClient, Address, Bytes, Env};
+
+#[derive(Clone)]
+#[contracttype]
+enum StrategyKey {
+    Pegkeeper,
+    Profit,
+}
+
+/// A pegkeeper strategy that returns the tokens it receives plus a configurable profit
+/// out of its own balance
+#[contract]
+pub struct MockStrategy;
+
+#[contractimpl]
+impl MockStrategy {
+    pub fn initialize(e: Env, pegkeeper: Address) {
+        e.storage().instance().set(&StrategyKey::Pegkeeper, &pegkeeper);
+    }
+
+    /// Set the amount the strategy returns on top of the amount it receives. A negative profit
+    /// makes the strategy return less than it receives.
+    pub fn set_profit(e: Env, profit: i128) {
+        e.storage().instance().set(&StrategyKey::Profit, &profit);
+    }
+
+    pub fn execute(e: Env, token: Address, amount: i128, _params: Bytes) {
+        let pegkeeper: Address = e.storage().instance().get(&StrategyKey::Pegkeeper).unwrap();
+        let profit: i128 = e.storage().instance().get(&StrategyKey::Profit).unwrap_or(0);
+        let returned = amount + profit;
+        if returned > 0 {
+            TokenClient::new(&e, &token).transfer(&e.current_contract_address(), &pegkeeper, &returned);
+        }
+    }
+}
+
+/// Register a mock strategy that returns its proceeds to the pegkeeper
+pub fn create_mock_strategy<'a>(e: &Env, pegkeeper: &Address) -> (Address, MockStrategyClient<'a>) {
+    let contract_id = e.register_contract(None, MockStrategy);
+    let client = MockStrategyClient::new(e, &contract_id);
+    client.initialize(pegkeeper);
+    (contract_id, client)
+}
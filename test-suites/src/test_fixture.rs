@@ -8,6 +8,7 @@ use crate::dependencies::oracle::create_mock_oracle;
 use crate::dependencies::pool::POOL_WASM;
 use crate::dependencies::pool_factory::create_pool_factory;
 use crate::dependencies::token::{create_stellar_token, create_token};
+use crate::dependencies::ousd_token::{create_orbit_token, create_ousd_token};
 use crate::dependencies::backstop::BackstopClient;
 use crate::dependencies::emitter::EmitterClient;
 use crate::dependencies::pool::{
@@ -21,13 +22,14 @@ use soroban_sdk::testutils::{Address as _, BytesN as _, Ledger, LedgerInfo};
 use soroban_sdk::{vec as svec, Address, BytesN, Env, String, Map, Symbol};
 
 use crate::dependencies::pair::{PAIR_WASM, PairClient};
-use crate::dependencies::treasury::{TreasuryClient, TREASURY_WASM, create_treasury};
+use crate::dependencies::treasury::{OracleConfig, TreasuryClient, TREASURY_WASM, create_treasury};
 use crate::dependencies::bridge_oracle::{BridgeOracleClient, create_bridge_oracle};
 use crate::dependencies::pair_factory::{create_pair_factory, PairFactoryClient};
 use crate::dependencies::router::{create_router, RouterClient};
 use crate::dependencies::mock_treasury::{create_mock_treasury, MockTreasuryClient};
 use crate::dependencies::mock_pegkeeper::{create_mock_pegkeeper, MockPegkeeperClient};
 use crate::dependencies::pegkeeper::{create_pegkeeper, PegkeeperClient};
+use crate::oracle::{create_test_oracle, MockOracleClient};
 
 pub const SCALAR_7: i128 = 1_000_0000;
 pub const SCALAR_9: i128 = 1_000_000_000;
@@ -38,6 +40,7 @@ pub enum TokenIndex {
     USDC = 1,
     XLM = 2,
     OUSD = 3,
+    OEUR = 4,
 }
 
 pub struct PoolFixture<'a> {
@@ -61,6 +64,7 @@ pub struct TestFixture<'a> {
     pub backstop: BackstopClient<'a>,
     pub pool_factory: PoolFactoryClient<'a>,
     pub oracle: MockPriceOracleClient<'a>,
+    pub price_oracle: MockOracleClient<'a>,
     pub lp: LPClient<'a>,
     pub pools: Vec<PoolFixture<'a>>,
     pub tokens: Vec<MockTokenClient<'a>>,
@@ -77,7 +81,7 @@ pub struct TestFixture<'a> {
 impl TestFixture<'_> {
     /// Create a new TestFixture for the Orbit Protocol
     ///
-    /// Deploys BLND (0), USDC (1), wETH (2), XLM (3), and STABLE (4) test tokens, alongside all required
+    /// Deploys BLND (0), USDC (1), XLM (2), OUSD (3), and oEUR (4) test tokens, alongside all required
     /// Blend Protocol dependencies, including a BLND-USDC LP.
     
     pub fn create<'a>(mock: bool) -> TestFixture<'a> {
@@ -102,7 +106,8 @@ impl TestFixture<'_> {
         let (blnd_id, blnd_client) = create_stellar_token(&e, &admin);
         let (usdc_id, usdc_client) = create_stellar_token(&e, &admin);
         let (xlm_id, xlm_client) = create_stellar_token(&e, &admin);
-        let (ousd_id, ousd_client) = create_stellar_token(&e, &admin);
+        let (_, ousd_client) = create_ousd_token(&e, &admin);
+        let (_, oeur_client) = create_orbit_token(&e, &admin, "Orbit EUR", "oEUR");
 
         // deploy Blend Protocol dependencies
         let (backstop_id, backstop_client) = create_backstop(&e);
@@ -152,6 +157,7 @@ impl TestFixture<'_> {
                 &e,
                 Asset::Stellar(usdc_id),
                 Asset::Stellar(xlm_id.clone()),
+                Asset::Other(Symbol::new(&e, "EUR")),
             ],
             &7,
             &300,
@@ -160,6 +166,7 @@ impl TestFixture<'_> {
             &e,
             1_0000000,    // usdc
             0_1000000,    // xlm
+            1_0800000,    // eur
         ]);
 
         // Initialize soroswap
@@ -191,6 +198,13 @@ impl TestFixture<'_> {
         treasury_client.initialize(&admin, &bridge_oracle_id, &pegkeeper_id);
         mock_treasury_client.initialize(&admin, &bridge_oracle_id, &mock_pegkeeper_id);
 
+        // price the treasury's assets with an oracle tests can manipulate
+        let (price_oracle_id, price_oracle_client) = create_test_oracle(&e);
+        price_oracle_client.set_price(&usdc_client.address, &1_0000000);
+        price_oracle_client.set_price(&xlm_client.address, &0_1000000);
+        price_oracle_client.set_price(&ousd_client.address, &1_0000000);
+        price_oracle_client.set_price(&oeur_client.address, &1_0800000);
+        treasury_client.set_oracle(&OracleConfig { oracle: price_oracle_id, max_age: 86400 });
 
         let fixture = TestFixture {
             env: e,
@@ -202,6 +216,7 @@ impl TestFixture<'_> {
             pair_factory: pair_factory_client,
             router: router_client,
             oracle: mock_oracle_client,
+            price_oracle: price_oracle_client,
             bridge_oracle: bridge_oracle_client,
             lp: lp_client,
             pools: vec![],
@@ -210,7 +225,8 @@ impl TestFixture<'_> {
                 blnd_client,
                 usdc_client,
                 xlm_client,
-                ousd_client
+                ousd_client,
+                oeur_client
             ],
             treasury: treasury_client,
             mock_treasury: mock_treasury_client,
@@ -276,6 +292,16 @@ impl TestFixture<'_> {
         })
     }
 
+    /// Overwrite the storage layout version of the treasury, as a migration does
+    pub fn write_treasury_version(&self, version: u32) {
+        self.env.as_contract(&self.treasury.address, || {
+            self.env
+                .storage()
+                .instance()
+                .set(&svec![&self.env, Symbol::new(&self.env, "VERSION")], &version)
+        });
+    }
+
     pub fn read_pool_emissions(&self, pool_index: usize) -> Map<u32, u64> {
         let pool_fixture = &self.pools[pool_index];
         self.env.as_contract(&pool_fixture.pool.address, || {
@@ -339,6 +365,90 @@ impl TestFixture<'_> {
         })
     }
 
+    /********** Soroswap Helpers ***********/
+
+    /// Fetch the soroswap pair of a token and USDC
+    ///
+    /// ### Panics
+    /// If the pair was not created
+    pub fn peg_pair(&self, token: TokenIndex) -> &PairClient<'_> {
+        let token_id = &self.tokens[token].address;
+        let peg_id = &self.tokens[TokenIndex::USDC].address;
+        self.pairs
+            .iter()
+            .find(|pair| {
+                let (token_0, token_1) = (pair.token_0(), pair.token_1());
+                (token_0 == *token_id && token_1 == *peg_id) || (token_0 == *peg_id && token_1 == *token_id)
+            })
+            .expect("no USDC pair for token")
+    }
+
+    /// Fetch the price of a token in USDC from their soroswap pair, with 7 decimals
+    pub fn peg_price(&self, token: TokenIndex) -> i128 {
+        let (token_reserve, peg_reserve) = self.peg_reserves(token);
+        peg_reserve * SCALAR_7 / token_reserve
+    }
+
+    /// Push the price of a token in its USDC pair off peg by `bps` basis points
+    ///
+    /// A negative `bps` moves the token below its current price and a positive one above it. The
+    /// reserves are moved by minting into the pair and syncing it, so no user balances change.
+    ///
+    /// ### Panics
+    /// If the pair was not created or `bps` would make the price non-positive
+    pub fn depeg(&self, token: TokenIndex, bps: i128) {
+        assert!(bps > -10_000, "depeg below zero");
+        let pair = self.peg_pair(token);
+        let (token_reserve, peg_reserve) = self.peg_reserves(token);
+        if bps < 0 {
+            let target = token_reserve * 10_000 / (10_000 + bps);
+            self.tokens[token].mint(&pair.address, &(target - token_reserve));
+        } else if bps > 0 {
+            let target = peg_reserve * (10_000 + bps) / 10_000;
+            self.tokens[TokenIndex::USDC].mint(&pair.address, &(target - peg_reserve));
+        }
+        pair.sync();
+    }
+
+    /// Fetch the reserves of a token and USDC in their soroswap pair
+    fn peg_reserves(&self, token: TokenIndex) -> (i128, i128) {
+        let pair = self.peg_pair(token);
+        let (reserve_0, reserve_1) = pair.get_reserves();
+        if pair.token_0() == self.tokens[token].address {
+            (reserve_0, reserve_1)
+        } else {
+            (reserve_1, reserve_0)
+        }
+    }
+
+    /********** Oracle Helpers ***********/
+
+    /// Set the price the treasury's oracle reports for a token, in 7 decimals
+    pub fn set_price(&self, token: TokenIndex, price: i128) {
+        self.price_oracle.set_price(&self.tokens[token].address, &price);
+    }
+
+    /// Set whether the treasury's oracle reports the price of a token as stale
+    pub fn set_stale(&self, token: TokenIndex, stale: bool) {
+        self.price_oracle.set_stale(&self.tokens[token].address, &stale);
+    }
+
+    /********** Invariant Helpers ***********/
+
+    /// Check the treasury's accounting invariants for OUSD and oEUR, for those deployed on the treasury.
+    /// Called after the fixture is built and after every ledger jump.
+    ///
+    /// ### Panics
+    /// If an invariant does not hold
+    pub fn assert_invariants(&self) {
+        for index in [TokenIndex::OUSD, TokenIndex::OEUR] {
+            let token = &self.tokens[index].address;
+            if self.treasury.try_get_pools(token).is_ok() {
+                self.treasury.assert_invariants(token);
+            }
+        }
+    }
+
     /********** Chain Helpers ***********/
 
     pub fn jump(&self, time: u64) {
@@ -352,6 +462,7 @@ impl TestFixture<'_> {
             min_persistent_entry_ttl: 999999,
             max_entry_ttl: 9999999,
         });
+        self.assert_invariants();
     }
 
     pub fn jump_with_sequence(&self, time: u64) {
@@ -366,5 +477,6 @@ impl TestFixture<'_> {
             min_persistent_entry_ttl: 999999,
             max_entry_ttl: 9999999,
         });
+        self.assert_invariants();
     }
 }
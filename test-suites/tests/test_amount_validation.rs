@@ -0,0 +1,105 @@
+#![cfg(test)]
+use rand::Rng;
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+const MAX_FEE_BPS: u32 = 1_000;
+
+/// Boundary amounts that must be rejected, plus a few random negative amounts
+fn invalid_amounts() -> Vec<i128> {
+    let mut rng = rand::thread_rng();
+    let mut amounts = vec![i128::MIN, i128::MIN + 1, -SCALAR_7, -1, 0];
+    for _ in 0..10 {
+        amounts.push(rng.gen_range(i128::MIN, 0));
+    }
+    amounts
+}
+
+#[test]
+fn test_supply_changes_reject_non_positive_amounts() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    for amount in invalid_amounts() {
+        assert_eq!(
+            treasury.try_increase_supply(&fixture.admin, &token, &amount),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+        assert_eq!(
+            treasury.try_decrease_supply(&fixture.admin, &token, &amount),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+    }
+
+    // the smallest positive amount is accepted
+    treasury.increase_supply(&fixture.admin, &token, &1);
+    treasury.decrease_supply(&fixture.admin, &token, &1);
+}
+
+#[test]
+fn test_mint_rejects_non_positive_amounts() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let minter = Address::generate(&fixture.env);
+    let user = Address::generate(&fixture.env);
+    treasury.set_minter(&minter, &token, &(1_000 * SCALAR_7), &(1_000 * SCALAR_7));
+
+    for amount in invalid_amounts() {
+        assert_eq!(
+            treasury.try_mint(&minter, &user, &amount),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+    }
+
+    treasury.mint(&minter, &user, &1);
+    assert_eq!(fixture.tokens[TokenIndex::OUSD].balance(&user), 1);
+}
+
+#[test]
+fn test_deposit_surplus_rejects_non_positive_amounts() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let frodo = &fixture.users[0];
+    ousd.mint(frodo, &SCALAR_7);
+
+    for amount in invalid_amounts() {
+        assert_eq!(
+            treasury.try_deposit_surplus(frodo, &ousd.address, &amount),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+    }
+
+    let surplus = treasury.get_surplus(&ousd.address);
+    treasury.deposit_surplus(frodo, &ousd.address, &1);
+    assert_eq!(treasury.get_surplus(&ousd.address), surplus + 1);
+}
+
+#[test]
+fn test_fee_rates_reject_absurd_values() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+
+    for fee_bps in [MAX_FEE_BPS + 1, 10_000, u32::MAX] {
+        assert_eq!(
+            treasury.try_set_fee_bps(&fixture.admin, &fee_bps),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+        assert_eq!(
+            treasury.try_set_redemption_fee(&fixture.admin, &fee_bps),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+    }
+
+    // the boundaries are accepted
+    treasury.set_fee_bps(&fixture.admin, &0);
+    treasury.set_fee_bps(&fixture.admin, &MAX_FEE_BPS);
+    treasury.set_redemption_fee(&fixture.admin, &0);
+    treasury.set_redemption_fee(&fixture.admin, &MAX_FEE_BPS);
+}
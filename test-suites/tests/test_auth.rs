@@ -0,0 +1,153 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction},
+    Address, Bytes, Error, Symbol,
+};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{FlashLoanConfig, FlashMintConfig, Role},
+    receiver::create_mock_receiver,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const UNAUTHORIZED_ERROR: u32 = 503;
+const INVALID_RECEIVER: u32 = 547;
+
+/// Assert the last treasury invocation required the authorization of `signer`
+fn assert_authorized_by(fixture: &TestFixture, signer: &Address, fn_name: &str) {
+    let auths = fixture.env.auths();
+    let (address, invocation) = auths.first().expect("no authorization required");
+    assert_eq!(address, signer);
+    match &invocation.function {
+        AuthorizedFunction::Contract((contract, function, _)) => {
+            assert_eq!(contract, &fixture.treasury.address);
+            assert_eq!(function, &Symbol::new(&fixture.env, fn_name));
+        }
+        _ => panic!("unexpected authorized function"),
+    }
+}
+
+#[test]
+fn test_admin_entrypoints_require_admin() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: SCALAR_7, fee_bps: 0 });
+    assert_authorized_by(&fixture, &fixture.admin, "set_flash_mint");
+
+    treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: SCALAR_7, fee_bps: 0, enabled: true });
+    assert_authorized_by(&fixture, &fixture.admin, "add_flash_loaner");
+
+    treasury.update_flash_loaner(&receiver, &FlashLoanConfig { max_amount: SCALAR_7, fee_bps: 0, enabled: false });
+    assert_authorized_by(&fixture, &fixture.admin, "update_flash_loaner");
+
+    treasury.remove_flash_loaner(&receiver);
+    assert_authorized_by(&fixture, &fixture.admin, "remove_flash_loaner");
+
+    treasury.set_stability_fee(&ousd.address, &0);
+    assert_authorized_by(&fixture, &fixture.admin, "set_stability_fee");
+
+    let minter = Address::generate(&fixture.env);
+    treasury.set_minter(&minter, &ousd.address, &SCALAR_7, &SCALAR_7);
+    assert_authorized_by(&fixture, &fixture.admin, "set_minter");
+
+    treasury.remove_minter(&minter);
+    assert_authorized_by(&fixture, &fixture.admin, "remove_minter");
+}
+
+#[test]
+fn test_role_entrypoints_require_caller() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+
+    treasury.increase_supply(&fixture.admin, &ousd.address, &SCALAR_7);
+    assert_authorized_by(&fixture, &fixture.admin, "increase_supply");
+
+    treasury.decrease_supply(&fixture.admin, &ousd.address, &SCALAR_7);
+    assert_authorized_by(&fixture, &fixture.admin, "decrease_supply");
+
+    treasury.set_fee_bps(&fixture.admin, &10);
+    assert_authorized_by(&fixture, &fixture.admin, "set_fee_bps");
+
+    treasury.pause(&fixture.admin);
+    assert_authorized_by(&fixture, &fixture.admin, "pause");
+
+    treasury.unpause(&fixture.admin);
+    assert_authorized_by(&fixture, &fixture.admin, "unpause");
+}
+
+#[test]
+fn test_keep_peg_requires_keeper() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: 1_000 * SCALAR_7, fee_bps: 0, enabled: true });
+    ousd.mint(&receiver, &SCALAR_7); // cover the minimum fee
+
+    let stranger = Address::generate(&fixture.env);
+    let result = treasury.try_keep_peg(&stranger, &receiver, &ousd.address, &(1_000 * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR))));
+
+    treasury.grant_role(&Role::Keeper, &receiver);
+    treasury.keep_peg(&receiver, &receiver, &ousd.address, &(1_000 * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    assert_authorized_by(&fixture, &receiver, "keep_peg");
+}
+
+#[test]
+fn test_keep_peg_receiver_must_be_pegkeeper_or_keeper() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: 1_000 * SCALAR_7, fee_bps: 0, enabled: true });
+    ousd.mint(&receiver, &SCALAR_7); // cover the minimum fee
+
+    // a keeper can not direct a loan and its calldata into another whitelisted receiver
+    let result = treasury.try_keep_peg(&fixture.admin, &receiver, &ousd.address, &(1_000 * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR))));
+}
+
+#[test]
+fn test_liquidations_require_keeper_and_pegkeeper() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let user = Address::generate(&fixture.env);
+    let stranger = Address::generate(&fixture.env);
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: 1_000 * SCALAR_7, fee_bps: 0, enabled: true });
+
+    assert_eq!(
+        treasury.try_liquidate(&stranger, &fixture.pegkeeper.address, &ousd.address, &user, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR)))
+    );
+    assert_eq!(
+        treasury.try_fill_auction(&stranger, &ousd.address, &0, &user),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR)))
+    );
+    // a whitelisted receiver other than the pegkeeper can not take the liquidation path
+    assert_eq!(
+        treasury.try_liquidate(&fixture.admin, &receiver, &ousd.address, &user, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(INVALID_RECEIVER)))
+    );
+}
+
+#[test]
+fn test_role_entrypoints_reject_strangers() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let stranger = Address::generate(&fixture.env);
+    let unauthorized = Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR)));
+
+    assert_eq!(treasury.try_increase_supply(&stranger, &ousd.address, &SCALAR_7).map(|_| ()), unauthorized);
+    assert_eq!(treasury.try_decrease_supply(&stranger, &ousd.address, &SCALAR_7).map(|_| ()), unauthorized);
+    assert_eq!(treasury.try_set_fee_bps(&stranger, &10).map(|_| ()), unauthorized);
+    assert_eq!(treasury.try_pause(&stranger).map(|_| ()), unauthorized);
+    assert_eq!(treasury.try_recycle_fees(&stranger, &ousd.address, &0).map(|_| ()), unauthorized);
+    assert_eq!(treasury.try_deposit_backstop(&stranger, &ousd.address, &SCALAR_7).map(|_| ()), unauthorized);
+}
@@ -0,0 +1,87 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, vec, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{
+        pool::{Request, RequestType},
+        treasury::AutoSupplyConfig,
+    },
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+const AUTO_SUPPLY_DISABLED: u32 = 548;
+const ADJUSTMENT_TOO_SOON: u32 = 549;
+const STEP: i128 = 1_000 * SCALAR_7;
+
+fn band() -> AutoSupplyConfig {
+    AutoSupplyConfig {
+        min_util: 0_600_0000,
+        max_util: 0_850_0000,
+        target_util: 0_700_0000,
+        max_step: STEP,
+        interval: 100,
+    }
+}
+
+#[test]
+fn test_auto_adjust_supply_tracks_the_utilization_band() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pool = &fixture.pools[0].pool;
+
+    let result = treasury.try_auto_adjust_supply(&fixture.admin, &ousd.address);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(AUTO_SUPPLY_DISABLED))));
+    treasury.set_auto_supply(&ousd.address, &Some(band()));
+    assert_eq!(treasury.get_auto_supply(&ousd.address).unwrap().max_step, STEP);
+
+    // nothing is borrowed, so the supply is decreased by a step
+    let summary = treasury.get_position_summary(&ousd.address);
+    assert_eq!(treasury.auto_adjust_supply(&fixture.admin, &ousd.address), -STEP);
+    assert!(treasury.get_position_summary(&ousd.address).supplied <= summary.supplied - STEP + 1);
+    let result = treasury.try_auto_adjust_supply(&fixture.admin, &ousd.address);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(ADJUSTMENT_TOO_SOON))));
+
+    // borrowing pushes the utilization above the band, so the supply is increased by a step
+    let borrower = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&borrower, &(20_000_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 20_000_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: ousd.address.clone(),
+            amount: ousd.balance(&pool.address) - 50_000 * SCALAR_7,
+        },
+    ];
+    pool.submit(&borrower, &borrower, &borrower, &requests);
+    fixture.jump_with_sequence(100 * 5);
+    assert_eq!(treasury.auto_adjust_supply(&fixture.admin, &ousd.address), STEP);
+
+    // disabling the band stops the adjustments
+    treasury.set_auto_supply(&ousd.address, &None);
+    fixture.jump_with_sequence(100 * 5);
+    let result = treasury.try_auto_adjust_supply(&fixture.admin, &ousd.address);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(AUTO_SUPPLY_DISABLED))));
+}
+
+#[test]
+fn test_invalid_auto_supply_band_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD].address;
+
+    let mut config = band();
+    config.target_util = 0_900_0000;
+    let result = treasury.try_set_auto_supply(ousd, &Some(config));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+    let mut config = band();
+    config.max_step = 0;
+    let result = treasury.try_set_auto_supply(ousd, &Some(config));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+}
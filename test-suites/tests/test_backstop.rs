@@ -0,0 +1,86 @@
+#![cfg(test)]
+use soroban_sdk::Error;
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::BackstopConfig,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const BALANCE_ERROR: u32 = 505;
+const NOT_INITIALIZED_ERROR: u32 = 522;
+const ALLOCATION_EXCEEDED: u32 = 524;
+const SURPLUS: i128 = 10_000 * SCALAR_7;
+
+/// A fixture with backstop tokens in the treasury's surplus buffer and half of them allocated to the backstop
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = create_fixture_with_data(false);
+    let frodo = &fixture.users[0];
+    fixture.treasury.deposit_surplus(frodo, &fixture.lp.address, &SURPLUS);
+    fixture.treasury.set_backstop_config(&BackstopConfig {
+        backstop: fixture.backstop.address.clone(),
+        allocation_bps: 5_000,
+    });
+    fixture
+}
+
+#[test]
+fn test_deposit_and_withdraw_backstop() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let backstop_before = fixture.lp.balance(&fixture.backstop.address);
+
+    let shares = treasury.deposit_backstop(&fixture.admin, &ousd.address, &(4_000 * SCALAR_7));
+    assert!(shares > 0);
+    assert_eq!(fixture.lp.balance(&fixture.backstop.address), backstop_before + 4_000 * SCALAR_7);
+    assert_eq!(treasury.get_surplus(&fixture.lp.address), SURPLUS - 4_000 * SCALAR_7);
+    let position = treasury.get_backstop_position(&ousd.address);
+    assert_eq!(position.deposited, 4_000 * SCALAR_7);
+    assert_eq!(position.shares, shares);
+    assert_eq!(position.queued_shares, 0);
+
+    treasury.queue_backstop_withdrawal(&fixture.admin, &ousd.address, &shares);
+    let position = treasury.get_backstop_position(&ousd.address);
+    assert_eq!(position.shares, 0);
+    assert_eq!(position.queued_shares, shares);
+
+    fixture.jump(60 * 60 * 24 * 31);
+    let amount = treasury.withdraw_backstop(&fixture.admin, &ousd.address, &shares);
+    assert!(amount >= 4_000 * SCALAR_7 - 1);
+    assert_eq!(treasury.get_surplus(&fixture.lp.address), SURPLUS - 4_000 * SCALAR_7 + amount);
+    assert_eq!(treasury.get_backstop_position(&ousd.address).deposited, 0);
+}
+
+#[test]
+fn test_deposit_backstop_limits() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+
+    // above the allocation of half the treasury's backstop tokens
+    let result = treasury.try_deposit_backstop(&fixture.admin, &ousd.address, &(5_001 * SCALAR_7));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(ALLOCATION_EXCEEDED))));
+
+    // the allocation counts previous deposits
+    treasury.deposit_backstop(&fixture.admin, &ousd.address, &(3_000 * SCALAR_7));
+    let result = treasury.try_deposit_backstop(&fixture.admin, &ousd.address, &(2_001 * SCALAR_7));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(ALLOCATION_EXCEEDED))));
+    treasury.deposit_backstop(&fixture.admin, &ousd.address, &(2_000 * SCALAR_7));
+
+    // above the surplus
+    treasury.set_backstop_config(&BackstopConfig {
+        backstop: fixture.backstop.address.clone(),
+        allocation_bps: 10_000,
+    });
+    let result = treasury.try_deposit_backstop(&fixture.admin, &ousd.address, &(5_001 * SCALAR_7));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(BALANCE_ERROR))));
+}
+
+#[test]
+fn test_deposit_backstop_without_config() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let result = fixture.treasury.try_deposit_backstop(&fixture.admin, &ousd.address, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(NOT_INITIALIZED_ERROR))));
+}
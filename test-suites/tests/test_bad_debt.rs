@@ -0,0 +1,87 @@
+#![cfg(test)]
+use soroban_sdk::Error;
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{insurance_fund::create_insurance_fund, treasury::InsuranceConfig},
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const BALANCE_ERROR: u32 = 505;
+
+#[test]
+fn test_bad_debt_is_covered_by_the_surplus() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let frodo = &fixture.users[0];
+    ousd.mint(frodo, &(100 * SCALAR_7));
+    treasury.deposit_surplus(frodo, &ousd.address, &(100 * SCALAR_7));
+    let surplus = treasury.get_surplus(&ousd.address);
+    let balance = ousd.balance(&treasury.address);
+
+    let bad_debt = treasury.record_bad_debt(&ousd.address, &(40 * SCALAR_7));
+    assert_eq!(bad_debt.outstanding, 0);
+    assert_eq!(bad_debt.covered, 40 * SCALAR_7);
+    assert_eq!(treasury.get_surplus(&ousd.address), surplus - 40 * SCALAR_7);
+    // the surplus covering the bad debt is burnt
+    assert_eq!(ousd.balance(&treasury.address), balance - 40 * SCALAR_7);
+}
+
+#[test]
+fn test_uncovered_bad_debt_is_written_off() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let surplus = treasury.get_surplus(&ousd.address);
+
+    let bad_debt = treasury.record_bad_debt(&ousd.address, &(surplus + 50 * SCALAR_7));
+    assert_eq!(bad_debt.outstanding, 50 * SCALAR_7);
+
+    assert_eq!(
+        treasury.try_write_off(&ousd.address, &(50 * SCALAR_7 + 1)),
+        Err(Ok(Error::from_contract_error(BALANCE_ERROR)))
+    );
+    treasury.write_off(&ousd.address, &(50 * SCALAR_7));
+    let bad_debt = treasury.get_bad_debt(&ousd.address);
+    assert_eq!(bad_debt.outstanding, 0);
+    assert_eq!(bad_debt.covered, surplus);
+    assert_eq!(bad_debt.written_off, 50 * SCALAR_7);
+}
+
+#[test]
+fn test_outstanding_bad_debt_is_covered_after_a_refill() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (fund, fund_client) = create_insurance_fund(&fixture.env, &fixture.admin, &treasury.address, &ousd.address);
+    treasury.set_insurance_fund(&InsuranceConfig { fund, token: ousd.address.clone(), split_bps: 0 });
+    let surplus = treasury.get_surplus(&ousd.address);
+    let bad_debt = treasury.record_bad_debt(&ousd.address, &(surplus + 100 * SCALAR_7));
+    assert_eq!(bad_debt.outstanding, 100 * SCALAR_7);
+
+    // nothing to cover the bad debt with
+    assert_eq!(treasury.cover_bad_debt(&ousd.address), bad_debt);
+
+    // refill the surplus buffer and the insurance fund, the surplus is burnt first
+    let frodo = &fixture.users[0];
+    ousd.mint(frodo, &(80 * SCALAR_7));
+    treasury.deposit_surplus(frodo, &ousd.address, &(30 * SCALAR_7));
+    fund_client.deposit(frodo, &(50 * SCALAR_7));
+    let balance = ousd.balance(&treasury.address);
+
+    let bad_debt = treasury.cover_bad_debt(&ousd.address);
+    assert_eq!(bad_debt.outstanding, 20 * SCALAR_7);
+    assert_eq!(bad_debt.covered, surplus + 80 * SCALAR_7);
+    assert_eq!(treasury.get_surplus(&ousd.address), 0);
+    assert_eq!(fund_client.get_balance(), 0);
+    assert_eq!(fund_client.get_covered(), 50 * SCALAR_7);
+    // the surplus and the insurance payout are burnt
+    assert_eq!(ousd.balance(&treasury.address), balance - 30 * SCALAR_7);
+
+    // the remainder is socialized by writing it off
+    treasury.write_off(&ousd.address, &(20 * SCALAR_7));
+    let bad_debt = treasury.cover_bad_debt(&ousd.address);
+    assert_eq!(bad_debt.outstanding, 0);
+    assert_eq!(bad_debt.covered, surplus + 80 * SCALAR_7);
+    assert_eq!(bad_debt.written_off, 20 * SCALAR_7);
+}
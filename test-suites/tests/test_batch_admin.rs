@@ -0,0 +1,63 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, vec, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{AdminOp, FlashLoanConfig, FlashMintConfig},
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+
+#[test]
+fn test_batch_admin_applies_every_op() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let receiver = Address::generate(&fixture.env);
+    let minter = Address::generate(&fixture.env);
+    let flash_mint = FlashMintConfig { cap: 1_000 * SCALAR_7, fee_bps: 5 };
+
+    treasury.batch_admin(&vec![
+        &fixture.env,
+        AdminOp::SetFlashMint(token.clone(), flash_mint.clone()),
+        AdminOp::AddFlashLoaner(receiver.clone(), FlashLoanConfig { max_amount: 100 * SCALAR_7, fee_bps: 0, enabled: true }),
+        AdminOp::SetMinter(minter.clone(), token.clone(), 50 * SCALAR_7, 50 * SCALAR_7),
+        AdminOp::SetFeeBps(30),
+    ]);
+
+    assert_eq!(treasury.get_flash_mint(&token), Some(flash_mint));
+    assert!(treasury.get_flash_loaner(&receiver).is_some());
+    assert_eq!(treasury.get_minter(&minter).unwrap().total_cap, 50 * SCALAR_7);
+}
+
+#[test]
+fn test_batch_admin_applies_ops_in_order() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let minter = Address::generate(&fixture.env);
+
+    treasury.batch_admin(&vec![
+        &fixture.env,
+        AdminOp::SetMinter(minter.clone(), token.clone(), 50 * SCALAR_7, 50 * SCALAR_7),
+        AdminOp::RemoveMinter(minter.clone()),
+    ]);
+
+    assert!(treasury.get_minter(&minter).is_none());
+}
+
+#[test]
+fn test_batch_admin_is_atomic() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    let result = treasury.try_batch_admin(&vec![
+        &fixture.env,
+        AdminOp::SetFlashMint(token.clone(), FlashMintConfig { cap: 1_000 * SCALAR_7, fee_bps: 5 }),
+        AdminOp::SetFeeBps(10_001),
+    ]);
+
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+    assert_eq!(treasury.get_flash_mint(&token), None);
+}
@@ -0,0 +1,91 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Bytes, Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::bridge_adapter::create_bridge_adapter,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const UNAUTHORIZED: u32 = 503;
+const NONCE_USED: u32 = 2104;
+
+#[test]
+fn test_bridged_out_supply_is_reported_separately() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let relayer = Address::generate(&fixture.env);
+    let (adapter, adapter_client) = create_bridge_adapter(&fixture.env, &fixture.admin, &treasury.address, &ousd.address, &relayer);
+    treasury.set_bridge_adapter(&adapter);
+    treasury.set_minter(&adapter, &ousd.address, &(100 * SCALAR_7), &(100 * SCALAR_7));
+    let frodo = &fixture.users[0];
+    treasury.mint(&adapter, frodo, &(100 * SCALAR_7));
+    let report_before = treasury.get_backing_report(&ousd.address);
+    assert_eq!(report_before.bridged_out, 0);
+    assert_eq!(report_before.circulating, report_before.total_supply);
+
+    adapter_client.bridge_out(frodo, &(100 * SCALAR_7), &Symbol::new(&fixture.env, "ethereum"), &Bytes::from_array(&fixture.env, &[1; 20]));
+    assert_eq!(adapter_client.get_locked(), 100 * SCALAR_7);
+    assert_eq!(ousd.balance(&adapter), 100 * SCALAR_7);
+    let report = treasury.get_backing_report(&ousd.address);
+    assert_eq!(report.total_supply, report_before.total_supply);
+    assert_eq!(report.bridged_out, 100 * SCALAR_7);
+    assert_eq!(report.circulating, report_before.total_supply - 100 * SCALAR_7);
+    assert_eq!(report.collateral_ratio, report_before.collateral_ratio);
+
+    let sam = &fixture.users[1];
+    let sam_balance = ousd.balance(sam);
+    assert_eq!(adapter_client.bridge_in(&0, sam, &(60 * SCALAR_7)), 0);
+    assert_eq!(ousd.balance(sam) - sam_balance, 60 * SCALAR_7);
+    assert_eq!(treasury.get_backing_report(&ousd.address).bridged_out, 40 * SCALAR_7);
+    assert!(adapter_client.is_processed(&0));
+}
+
+#[test]
+fn test_returns_beyond_the_locked_supply_are_minted() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let relayer = Address::generate(&fixture.env);
+    let (adapter, adapter_client) = create_bridge_adapter(&fixture.env, &fixture.admin, &treasury.address, &ousd.address, &relayer);
+    treasury.set_bridge_adapter(&adapter);
+    treasury.set_minter(&adapter, &ousd.address, &(100 * SCALAR_7), &(1_000 * SCALAR_7));
+    let frodo = &fixture.users[0];
+    ousd.mint(frodo, &(50 * SCALAR_7));
+    adapter_client.bridge_out(frodo, &(50 * SCALAR_7), &Symbol::new(&fixture.env, "ethereum"), &Bytes::from_array(&fixture.env, &[1; 20]));
+    let supply_before = treasury.get_backing_report(&ousd.address).total_supply;
+
+    let sam = &fixture.users[1];
+    let sam_balance = ousd.balance(sam);
+    assert_eq!(adapter_client.bridge_in(&7, sam, &(80 * SCALAR_7)), 30 * SCALAR_7);
+    assert_eq!(ousd.balance(sam) - sam_balance, 80 * SCALAR_7);
+    assert_eq!(adapter_client.get_locked(), 0);
+    let report = treasury.get_backing_report(&ousd.address);
+    assert_eq!(report.bridged_out, 0);
+    assert_eq!(report.total_supply, supply_before + 30 * SCALAR_7);
+    assert_eq!(treasury.get_mint_allowance(&adapter), 70 * SCALAR_7);
+
+    // a relayed message can only be processed once
+    assert_eq!(
+        adapter_client.try_bridge_in(&7, sam, &(10 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(NONCE_USED)))
+    );
+}
+
+#[test]
+fn test_only_the_bridge_adapter_records_bridged_supply() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    assert_eq!(
+        treasury.try_record_bridge_out(&ousd.address, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+
+    let adapter = Address::generate(&fixture.env);
+    treasury.set_bridge_adapter(&adapter);
+    assert_eq!(treasury.get_bridge_adapter(), Some(adapter));
+    treasury.record_bridge_out(&ousd.address, &SCALAR_7);
+    assert_eq!(treasury.get_backing_report(&ousd.address).bridged_out, SCALAR_7);
+    assert!(treasury.try_record_bridge_in(&ousd.address, &(2 * SCALAR_7)).is_err());
+}
@@ -0,0 +1,46 @@
+#![cfg(test)]
+use soroban_sdk::Bytes;
+use test_suites::{
+    budget::{assert_budget, measure},
+    create_fixture_with_data,
+    dependencies::treasury::{FlashLoanConfig, Role},
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+#[test]
+fn test_budget_flash_loan() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: 100_000 * SCALAR_7, fee_bps: 0, enabled: true });
+    treasury.grant_role(&Role::Keeper, &receiver);
+
+    let (_, usage) = measure(&fixture.env, || {
+        treasury.keep_peg(&receiver, &receiver, &ousd.address, &(10_000 * SCALAR_7), &Bytes::new(&fixture.env), &None)
+    });
+    assert_budget("flash_loan", &usage);
+}
+
+#[test]
+fn test_budget_increase_supply() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let (_, usage) = measure(&fixture.env, || {
+        fixture.treasury.increase_supply(&fixture.admin, &ousd.address, &(10_000 * SCALAR_7))
+    });
+    assert_budget("increase_supply", &usage);
+}
+
+#[test]
+fn test_budget_decrease_supply() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let (_, usage) = measure(&fixture.env, || {
+        fixture.treasury.decrease_supply(&fixture.admin, &ousd.address, &(10_000 * SCALAR_7))
+    });
+    assert_budget("decrease_supply", &usage);
+}
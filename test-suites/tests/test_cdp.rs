@@ -0,0 +1,173 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    create_fixture_with_data,
+    dependencies::cdp::{create_cdp, CDPClient, CDPConfig, OracleConfig},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const POSITION_EXISTS: u32 = 1404;
+const POSITION_NOT_FOUND: u32 = 1405;
+const UNDERCOLLATERALIZED: u32 = 1406;
+const HEALTHY_POSITION: u32 = 1407;
+
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// Deploy an OUSD CDP against XLM, priced by the fixture's oracle at 0.1 USD, with a 150% minimum
+/// collateral ratio, a 120% liquidation threshold, a 5% liquidation bonus and a 10% stability fee
+fn setup_cdp<'a>(fixture: &TestFixture<'a>) -> CDPClient<'a> {
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let xlm = fixture.tokens[TokenIndex::XLM].address.clone();
+    let oracle_config = OracleConfig { oracle: fixture.price_oracle.address.clone(), max_age: 86400 };
+    let config = CDPConfig {
+        min_collateral_ratio_bps: 15_000,
+        liquidation_threshold_bps: 12_000,
+        liquidation_bonus_bps: 500,
+        stability_fee_bps: 1_000,
+    };
+    let (cdp_id, cdp) = create_cdp(&fixture.env, &fixture.admin, &fixture.treasury.address, &ousd, &xlm, &oracle_config, &config);
+    fixture.treasury.set_minter(&cdp_id, &ousd, &(1_000_000 * SCALAR_7), &(1_000_000 * SCALAR_7));
+    cdp
+}
+
+/// Mint XLM to a new account, returning it
+fn create_user(fixture: &TestFixture, xlm_amount: i128) -> Address {
+    let user = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&user, &xlm_amount);
+    user
+}
+
+#[test]
+fn test_open_adjust_and_close_position() {
+    let fixture = create_fixture_with_data(false);
+    let cdp = setup_cdp(&fixture);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let samwise = create_user(&fixture, 30_000 * SCALAR_7);
+
+    // 3,000 USD of XLM backs 1,000 OUSD at a 300% collateral ratio
+    cdp.open_position(&samwise, &(30_000 * SCALAR_7), &(1_000 * SCALAR_7));
+    assert_eq!(ousd.balance(&samwise), 1_000 * SCALAR_7);
+    assert_eq!(xlm.balance(&cdp.address), 30_000 * SCALAR_7);
+    assert_eq!(cdp.get_debt(&samwise), 1_000 * SCALAR_7);
+    assert_eq!(cdp.get_collateral_ratio(&samwise), 30_000);
+    assert_eq!(fixture.treasury.get_minter(&cdp.address).unwrap().minted, 1_000 * SCALAR_7);
+    assert_eq!(
+        cdp.try_open_position(&samwise, &SCALAR_7, &0),
+        Err(Ok(Error::from_contract_error(POSITION_EXISTS)))
+    );
+
+    // withdrawing collateral or minting is checked against the minimum collateral ratio
+    assert_eq!(
+        cdp.try_adjust_position(&samwise, &(-15_001 * SCALAR_7), &0),
+        Err(Ok(Error::from_contract_error(UNDERCOLLATERALIZED)))
+    );
+    assert_eq!(
+        cdp.try_adjust_position(&samwise, &0, &(1_001 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(UNDERCOLLATERALIZED)))
+    );
+    cdp.adjust_position(&samwise, &(-15_000 * SCALAR_7), &0);
+    assert_eq!(cdp.get_collateral_ratio(&samwise), 15_000);
+
+    // repaying burns the principal
+    cdp.adjust_position(&samwise, &0, &(-400 * SCALAR_7));
+    let position = cdp.get_position(&samwise).unwrap();
+    assert_eq!(position.debt, 600 * SCALAR_7);
+    assert_eq!(position.principal, 600 * SCALAR_7);
+    assert_eq!(position.collateral, 15_000 * SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&cdp.address).unwrap().minted, 600 * SCALAR_7);
+
+    assert_eq!(cdp.close_position(&samwise), 15_000 * SCALAR_7);
+    assert!(cdp.get_position(&samwise).is_none());
+    assert_eq!(ousd.balance(&samwise), 0);
+    assert_eq!(xlm.balance(&samwise), 30_000 * SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&cdp.address).unwrap().minted, 0);
+    assert_eq!(
+        cdp.try_close_position(&samwise),
+        Err(Ok(Error::from_contract_error(POSITION_NOT_FOUND)))
+    );
+
+    // a new position can not start below the minimum collateral ratio
+    let frodo = create_user(&fixture, 15_000 * SCALAR_7);
+    assert_eq!(
+        cdp.try_open_position(&frodo, &(15_000 * SCALAR_7), &(1_001 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(UNDERCOLLATERALIZED)))
+    );
+}
+
+#[test]
+fn test_stability_fee_accrues_and_is_paid_to_surplus() {
+    let fixture = create_fixture_with_data(false);
+    let cdp = setup_cdp(&fixture);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let samwise = create_user(&fixture, 30_000 * SCALAR_7);
+    cdp.open_position(&samwise, &(30_000 * SCALAR_7), &(1_000 * SCALAR_7));
+
+    // a year at a 10% stability fee adds 100 OUSD of debt, but not of principal
+    fixture.jump(SECONDS_PER_YEAR);
+    assert_eq!(cdp.get_debt(&samwise), 1_100 * SCALAR_7);
+    assert_eq!(cdp.get_position(&samwise).unwrap().principal, 1_000 * SCALAR_7);
+    assert_eq!(cdp.get_collateral_ratio(&samwise), 27_272);
+
+    // a repayment pays the stability fees first, into the treasury's surplus buffer, and burns the rest
+    let surplus = fixture.treasury.get_surplus(&ousd.address);
+    cdp.adjust_position(&samwise, &0, &(-150 * SCALAR_7));
+    assert_eq!(fixture.treasury.get_surplus(&ousd.address), surplus + 100 * SCALAR_7);
+    let position = cdp.get_position(&samwise).unwrap();
+    assert_eq!(position.debt, 950 * SCALAR_7);
+    assert_eq!(position.principal, 950 * SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&cdp.address).unwrap().minted, 950 * SCALAR_7);
+    assert_eq!(ousd.balance(&cdp.address), 0);
+
+    // fees keep accruing on the remaining debt
+    fixture.jump(SECONDS_PER_YEAR / 2);
+    assert_approx_eq_abs(cdp.get_debt(&samwise), 997_5000000, 2);
+
+    // closing pays the new fees to surplus and burns the rest of the principal
+    ousd.mint(&samwise, &(200 * SCALAR_7));
+    let debt = cdp.get_debt(&samwise);
+    let balance = ousd.balance(&samwise);
+    cdp.close_position(&samwise);
+    assert_eq!(ousd.balance(&samwise), balance - debt);
+    assert_eq!(fixture.treasury.get_surplus(&ousd.address), surplus + 100 * SCALAR_7 + debt - 950 * SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&cdp.address).unwrap().minted, 0);
+}
+
+#[test]
+fn test_liquidation_seizes_collateral_with_a_bonus() {
+    let fixture = create_fixture_with_data(false);
+    let cdp = setup_cdp(&fixture);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let samwise = create_user(&fixture, 15_000 * SCALAR_7);
+    let liquidator = Address::generate(&fixture.env);
+    ousd.mint(&liquidator, &(500 * SCALAR_7));
+    cdp.open_position(&samwise, &(15_000 * SCALAR_7), &(1_000 * SCALAR_7));
+
+    assert_eq!(
+        cdp.try_liquidate(&liquidator, &samwise, &(500 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(HEALTHY_POSITION)))
+    );
+
+    // at 0.075 USD per XLM the position is at a 112.5% collateral ratio
+    fixture.set_price(TokenIndex::XLM, 0_0750000);
+    assert_eq!(cdp.get_collateral_ratio(&samwise), 11_250);
+
+    // repaying 500 OUSD seizes 525 USD of XLM
+    let seized = cdp.liquidate(&liquidator, &samwise, &(500 * SCALAR_7));
+    assert_eq!(seized, 7_000 * SCALAR_7);
+    assert_eq!(xlm.balance(&liquidator), 7_000 * SCALAR_7);
+    assert_eq!(ousd.balance(&liquidator), 0);
+    let position = cdp.get_position(&samwise).unwrap();
+    assert_eq!(position.collateral, 8_000 * SCALAR_7);
+    assert_eq!(position.debt, 500 * SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&cdp.address).unwrap().minted, 500 * SCALAR_7);
+
+    // the remaining position is at 120%, no longer below the liquidation threshold
+    assert_eq!(cdp.get_collateral_ratio(&samwise), 12_000);
+    assert_eq!(
+        cdp.try_liquidate(&liquidator, &samwise, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(HEALTHY_POSITION)))
+    );
+}
@@ -0,0 +1,150 @@
+#![cfg(test)]
+use sep_40_oracle::testutils::{Asset, MockPriceOracleClient};
+use sep_40_oracle::Asset as FxAsset;
+use soroban_sdk::{vec, Bytes, Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{
+        oracle::create_mock_oracle,
+        treasury::{BreakerConfig, FlashMintConfig, FxFeed, OracleConfig},
+    },
+    receiver::create_mock_receiver,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const CIRCUIT_BREAKER_TRIPPED: u32 = 525;
+const DELAY: u32 = 100;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+/// A fixture with an oracle pricing OUSD and a 2% circuit breaker on OUSD
+fn setup<'a>() -> (TestFixture<'a>, MockPriceOracleClient<'a>) {
+    let fixture = create_fixture_with_data(false);
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let (oracle_id, oracle) = create_mock_oracle(&fixture.env);
+    oracle.set_data(
+        &fixture.admin,
+        &Asset::Other(Symbol::new(&fixture.env, "USD")),
+        &vec![&fixture.env, Asset::Stellar(ousd.clone())],
+        &7,
+        &300,
+    );
+    oracle.set_price_stable(&vec![&fixture.env, 1_0000000]);
+    fixture.treasury.set_oracle(&OracleConfig { oracle: oracle_id, max_age: 86400 });
+    fixture.treasury.set_breaker(&ousd, &Some(BreakerConfig { threshold_bps: 200, delay: DELAY }));
+    fixture.treasury.set_flash_mint(&ousd, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 0 });
+    (fixture, oracle)
+}
+
+fn jump_ledgers(fixture: &TestFixture, ledgers: u32) {
+    fixture.jump_with_sequence(u64::from(ledgers) * 5);
+}
+
+#[test]
+fn test_short_depeg_does_not_trip() {
+    let (fixture, oracle) = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    oracle.set_price_stable(&vec![&fixture.env, 0_9500000]);
+    let state = fixture.treasury.poke_breaker(&ousd);
+    assert_eq!(state.depeg_ledger, Some(fixture.env.ledger().sequence()));
+    assert!(!state.tripped);
+
+    jump_ledgers(&fixture, DELAY);
+    fixture.treasury.increase_supply(&fixture.admin, &ousd, &AMOUNT);
+}
+
+#[test]
+fn test_deviation_within_threshold_is_not_a_depeg() {
+    let (fixture, oracle) = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    oracle.set_price_stable(&vec![&fixture.env, 0_9900000]);
+    assert_eq!(fixture.treasury.poke_breaker(&ousd).depeg_ledger, None);
+
+    jump_ledgers(&fixture, DELAY + 1);
+    fixture.treasury.increase_supply(&fixture.admin, &ousd, &AMOUNT);
+}
+
+#[test]
+fn test_sustained_depeg_trips_breaker() {
+    let (fixture, oracle) = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let (receiver, _) = create_mock_receiver(&fixture.env, &fixture.treasury.address);
+
+    oracle.set_price_stable(&vec![&fixture.env, 1_0500000]);
+    fixture.treasury.poke_breaker(&ousd);
+    jump_ledgers(&fixture, DELAY + 1);
+
+    assert!(fixture.treasury.get_breaker(&ousd).unwrap().tripped);
+    assert_eq!(
+        fixture.treasury.try_increase_supply(&fixture.admin, &ousd, &AMOUNT),
+        Err(Ok(Error::from_contract_error(CIRCUIT_BREAKER_TRIPPED)))
+    );
+    assert_eq!(
+        fixture.treasury.try_flash_mint(&receiver, &ousd, &AMOUNT, &Bytes::new(&fixture.env), &None),
+        Err(Ok(Error::from_contract_error(CIRCUIT_BREAKER_TRIPPED)))
+    );
+}
+
+#[test]
+fn test_breaker_resets_after_recovery() {
+    let (fixture, oracle) = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    oracle.set_price_stable(&vec![&fixture.env, 0_9000000]);
+    fixture.treasury.poke_breaker(&ousd);
+    jump_ledgers(&fixture, DELAY + 1);
+    assert!(fixture.treasury.get_breaker(&ousd).unwrap().tripped);
+
+    oracle.set_price_stable(&vec![&fixture.env, 1_0000000]);
+    let state = fixture.treasury.get_breaker(&ousd).unwrap();
+    assert_eq!(state.depeg_ledger, None);
+    assert!(!state.tripped);
+    fixture.treasury.increase_supply(&fixture.admin, &ousd, &AMOUNT);
+
+    // a new depeg is timed from scratch
+    oracle.set_price_stable(&vec![&fixture.env, 0_9000000]);
+    fixture.treasury.poke_breaker(&ousd);
+    jump_ledgers(&fixture, DELAY);
+    fixture.treasury.increase_supply(&fixture.admin, &ousd, &AMOUNT);
+}
+
+#[test]
+fn test_removed_breaker_is_not_checked() {
+    let (fixture, oracle) = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    oracle.set_price_stable(&vec![&fixture.env, 0_9000000]);
+    fixture.treasury.poke_breaker(&ousd);
+    jump_ledgers(&fixture, DELAY + 1);
+    fixture.treasury.set_breaker(&ousd, &None);
+
+    assert!(fixture.treasury.get_breaker(&ousd).is_none());
+    fixture.treasury.increase_supply(&fixture.admin, &ousd, &AMOUNT);
+}
+
+#[test]
+fn test_breaker_targets_the_fx_price_of_a_non_usd_stablecoin() {
+    let (fixture, oracle) = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let eur = Symbol::new(&fixture.env, "EUR");
+    fixture.price_oracle.set_feed_price(&FxAsset::Other(eur.clone()), &1_0800000);
+    fixture.treasury.set_currency(&ousd, &eur);
+    let feed = FxFeed { oracle: fixture.price_oracle.address.clone(), max_age: 86400 };
+    fixture.treasury.set_fx_feed(&ousd, &Some(feed));
+
+    // on peg at the EUR rate, though 8% away from $1
+    oracle.set_price_stable(&vec![&fixture.env, 1_0800000]);
+    assert_eq!(fixture.treasury.poke_breaker(&ousd).depeg_ledger, None);
+    jump_ledgers(&fixture, DELAY + 1);
+    fixture.treasury.increase_supply(&fixture.admin, &ousd, &AMOUNT);
+
+    // $1 is more than 7% below the EUR peg
+    oracle.set_price_stable(&vec![&fixture.env, 1_0000000]);
+    assert_eq!(fixture.treasury.poke_breaker(&ousd).depeg_ledger, Some(fixture.env.ledger().sequence()));
+    jump_ledgers(&fixture, DELAY + 1);
+    assert_eq!(
+        fixture.treasury.try_increase_supply(&fixture.admin, &ousd, &AMOUNT),
+        Err(Ok(Error::from_contract_error(CIRCUIT_BREAKER_TRIPPED)))
+    );
+}
@@ -0,0 +1,63 @@
+#![cfg(test)]
+use soroban_sdk::{vec, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{pegkeeper::Strategy, treasury::Asset},
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+const COLLATERAL_NOT_ALLOWED: u32 = 527;
+
+#[test]
+fn test_collateral_registry() {
+    let fixture = create_fixture_with_data(false);
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let treasury = &fixture.treasury;
+
+    treasury.add_collateral(&xlm.address, &Asset::Stellar(xlm.address.clone()), &7_500, &500, &(100_000 * SCALAR_7));
+    treasury.add_collateral(&usdc.address, &Asset::Stellar(usdc.address.clone()), &8_000, &300, &(100 * SCALAR_7));
+
+    let config = treasury.get_collateral(&xlm.address).unwrap();
+    assert_eq!(config.oracle_feed, Asset::Stellar(xlm.address.clone()));
+    assert_eq!(config.ltv, 7_500);
+    assert_eq!(config.liquidation_bonus, 500);
+    assert_eq!(config.cap, 100_000 * SCALAR_7);
+    assert_eq!(treasury.get_collaterals(), vec![&fixture.env, xlm.address.clone(), usdc.address.clone()]);
+    // the registry is whitelisted on the pegkeeper for liquidations
+    assert_eq!(fixture.pegkeeper.get_collaterals(&Strategy::Liquidate), treasury.get_collaterals());
+    assert_eq!(fixture.pegkeeper.get_collaterals(&Strategy::Auction), treasury.get_collaterals());
+
+    treasury.remove_collateral(&xlm.address);
+    assert!(treasury.get_collateral(&xlm.address).is_none());
+    assert_eq!(treasury.get_collaterals(), vec![&fixture.env, usdc.address.clone()]);
+    assert_eq!(fixture.pegkeeper.get_collaterals(&Strategy::Liquidate), vec![&fixture.env, usdc.address.clone()]);
+}
+
+#[test]
+fn test_invalid_collateral_params_are_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let treasury = &fixture.treasury;
+    let feed = Asset::Stellar(xlm.address.clone());
+
+    let result = treasury.try_add_collateral(&xlm.address, &feed, &10_001, &500, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+    let result = treasury.try_add_collateral(&xlm.address, &feed, &7_500, &10_001, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+    let result = treasury.try_add_collateral(&xlm.address, &feed, &7_500, &500, &-1);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+    let result = treasury.try_remove_collateral(&xlm.address);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(COLLATERAL_NOT_ALLOWED))));
+}
+
+#[test]
+fn test_redeem_unregistered_collateral_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let result = fixture.treasury.try_redeem(&fixture.admin, &ousd.address, &xlm.address, &SCALAR_7, &0, &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(COLLATERAL_NOT_ALLOWED))));
+}
@@ -0,0 +1,87 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::collateral_auction::{create_collateral_auction, Lot},
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const DURATION: u32 = 100;
+const AUCTION_NOT_FOUND: u32 = 1904;
+const PRICE_EXCEEDED: u32 = 1905;
+const UNAUTHORIZED: u32 = 1906;
+
+#[test]
+fn test_take_at_declining_price_pays_treasury() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let treasury = &fixture.treasury;
+    let (auction_house, auction_client) = create_collateral_auction(&fixture.env, &fixture.admin, &treasury.address, &ousd.address, DURATION);
+    let seller = Address::generate(&fixture.env);
+    let frodo = &fixture.users[0];
+    xlm.mint(&seller, &(100 * SCALAR_7));
+    ousd.mint(frodo, &(100 * SCALAR_7));
+
+    let lot = Lot { asset: xlm.address.clone(), amount: 100 * SCALAR_7, start_price: 2_000_000, end_price: 1_000_000 };
+    assert_eq!(
+        auction_client.try_start_auction(&seller, &lot),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+    auction_client.set_seller(&seller, &true);
+    let id = auction_client.start_auction(&seller, &lot);
+    assert_eq!(xlm.balance(&auction_house), 100 * SCALAR_7);
+    assert_eq!(auction_client.get_price(&id), 2_000_000);
+
+    // the price declines linearly over the duration
+    fixture.jump_with_sequence(u64::from(DURATION / 2) * 5);
+    assert_eq!(auction_client.get_price(&id), 1_500_000);
+    assert_eq!(
+        auction_client.try_take(frodo, &id, &(40 * SCALAR_7), &1_400_000),
+        Err(Ok(Error::from_contract_error(PRICE_EXCEEDED)))
+    );
+
+    let surplus_before = treasury.get_surplus(&ousd.address);
+    let cost = auction_client.take(frodo, &id, &(40 * SCALAR_7), &1_500_000);
+    assert_eq!(cost, 6 * SCALAR_7);
+    assert_eq!(xlm.balance(frodo), 40 * SCALAR_7);
+    assert_eq!(treasury.get_surplus(&ousd.address) - surplus_before, cost);
+    assert_eq!(auction_client.get_auction(&id).lot.amount, 60 * SCALAR_7);
+
+    // the price stays at the end price once the duration has passed
+    fixture.jump_with_sequence(u64::from(DURATION) * 5);
+    assert_eq!(auction_client.get_price(&id), 1_000_000);
+    auction_client.take(frodo, &id, &(60 * SCALAR_7), &1_000_000);
+    assert_eq!(xlm.balance(frodo), 100 * SCALAR_7);
+    assert_eq!(
+        auction_client.try_get_auction(&id),
+        Err(Ok(Error::from_contract_error(AUCTION_NOT_FOUND)))
+    );
+}
+
+#[test]
+fn test_cancel_returns_lot_to_seller() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let (_, auction_client) = create_collateral_auction(&fixture.env, &fixture.admin, &fixture.treasury.address, &ousd.address, DURATION);
+    let seller = Address::generate(&fixture.env);
+    let frodo = &fixture.users[0];
+    xlm.mint(&seller, &(100 * SCALAR_7));
+    auction_client.set_seller(&seller, &true);
+    let id = auction_client.start_auction(
+        &seller,
+        &Lot { asset: xlm.address.clone(), amount: 100 * SCALAR_7, start_price: 2_000_000, end_price: 1_000_000 },
+    );
+
+    assert_eq!(
+        auction_client.try_cancel_auction(frodo, &id),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+    auction_client.cancel_auction(&fixture.admin, &id);
+    assert_eq!(xlm.balance(&seller), 100 * SCALAR_7);
+    assert_eq!(
+        auction_client.try_get_auction(&id),
+        Err(Ok(Error::from_contract_error(AUCTION_NOT_FOUND)))
+    );
+}
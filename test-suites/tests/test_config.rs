@@ -0,0 +1,29 @@
+#![cfg(test)]
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{OracleConfig, TreasuryInfo},
+};
+
+#[test]
+fn test_get_config() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+
+    let config: TreasuryInfo = treasury.get_config();
+    assert_eq!(config.admin, fixture.admin);
+    assert_eq!(config.pegkeeper, fixture.pegkeeper.address);
+    assert_eq!(config.bridge_oracle, fixture.bridge_oracle.address);
+    assert_eq!(config.oracle, Some(OracleConfig { oracle: fixture.price_oracle.address.clone(), max_age: 86400 }));
+    assert_eq!(config.max_fee_bps, 1_000);
+    assert_eq!(config.max_batch_loans, 10);
+    assert_eq!(config.max_ltv_bps, 5_000);
+    assert!(!config.paused);
+    assert_eq!(config.version, treasury.get_version());
+    assert_eq!(config.storage_version, config.version);
+
+    treasury.set_fee_bps(&fixture.admin, &25);
+    treasury.pause(&fixture.admin);
+    let config = treasury.get_config();
+    assert_eq!(config.fee_model.fee_bps, 25);
+    assert!(config.paused);
+}
@@ -0,0 +1,110 @@
+#![cfg(test)]
+use sep_40_oracle::Asset as OracleAsset;
+use soroban_sdk::{testutils::Address as _, Address, Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{Asset, FxFeed},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const STALE_ORACLE_ERROR: u32 = 515;
+const SLIPPAGE_EXCEEDED: u32 = 518;
+const COLLATERAL_CAP_EXCEEDED: u32 = 528;
+
+/// Price EUR on the fixture's oracle and use it as the FX feed of a stablecoin
+fn set_eur_feed(fixture: &TestFixture, token: &Address, price: i128) {
+    fixture.price_oracle.set_feed_price(&OracleAsset::Other(Symbol::new(&fixture.env, "EUR")), &price);
+    let feed = FxFeed { oracle: fixture.price_oracle.address.clone(), max_age: 86400 };
+    fixture.treasury.set_fx_feed(token, &Some(feed));
+}
+
+#[test]
+fn test_stablecoins_default_to_usd() {
+    let fixture = create_fixture_with_data(false);
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    assert_eq!(fixture.treasury.get_currency(&token), Symbol::new(&fixture.env, "USD"));
+}
+
+#[test]
+fn test_supply_is_tracked_per_currency() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let usd = Symbol::new(&fixture.env, "USD");
+    let eur = Symbol::new(&fixture.env, "EUR");
+
+    treasury.increase_supply(&fixture.admin, &token, &(1_000 * SCALAR_7));
+    assert_eq!(treasury.get_currency_supply(&usd), 1_000 * SCALAR_7);
+    assert_eq!(treasury.get_currency_supply(&eur), 0);
+
+    treasury.set_currency(&token, &eur);
+    assert_eq!(treasury.get_currency(&token), eur);
+    assert_eq!(treasury.get_currency_supply(&usd), 0);
+    assert_eq!(treasury.get_currency_supply(&eur), 1_000 * SCALAR_7);
+
+    treasury.decrease_supply(&fixture.admin, &token, &(400 * SCALAR_7));
+    assert_eq!(treasury.get_currency_supply(&eur), 600 * SCALAR_7);
+}
+
+#[test]
+fn test_peg_price_of_a_non_usd_stablecoin_requires_an_fx_feed() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let eur = Symbol::new(&fixture.env, "EUR");
+
+    assert_eq!(treasury.get_peg_price(&token), SCALAR_7);
+
+    treasury.set_currency(&token, &eur);
+    assert_eq!(treasury.try_get_peg_price(&token), Err(Ok(Error::from_contract_error(STALE_ORACLE_ERROR))));
+
+    set_eur_feed(&fixture, &token, 1_0800000);
+    assert_eq!(treasury.get_fx_feed(&token).unwrap().oracle, fixture.price_oracle.address);
+    assert_eq!(treasury.get_peg_price(&token), 1_0800000);
+
+    treasury.set_fx_feed(&token, &None);
+    assert_eq!(treasury.get_fx_feed(&token), None);
+    assert_eq!(treasury.try_get_peg_price(&token), Err(Ok(Error::from_contract_error(STALE_ORACLE_ERROR))));
+}
+
+#[test]
+fn test_redeem_pays_out_the_fx_value_of_the_stablecoins() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let xlm = fixture.tokens[TokenIndex::XLM].address.clone();
+    let samwise = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::OUSD].mint(&samwise, &(100 * SCALAR_7));
+
+    treasury.set_currency(&token, &Symbol::new(&fixture.env, "EUR"));
+    set_eur_feed(&fixture, &token, 1_0800000);
+
+    // 100 EUR stablecoins are worth 108 USD, or 1080 XLM at 0.1 USD
+    let out = 1_080 * SCALAR_7;
+    treasury.add_collateral(&xlm, &Asset::Stellar(xlm.clone()), &7_500, &500, &(100_000 * SCALAR_7));
+    let result = treasury.try_redeem(&samwise, &token, &xlm, &(100 * SCALAR_7), &(out + 1), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(SLIPPAGE_EXCEEDED))));
+
+    treasury.add_collateral(&xlm, &Asset::Stellar(xlm.clone()), &7_500, &500, &(out - 1));
+    let result = treasury.try_redeem(&samwise, &token, &xlm, &(100 * SCALAR_7), &0, &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(COLLATERAL_CAP_EXCEEDED))));
+}
+
+#[test]
+fn test_backing_report_values_the_supply_at_the_fx_price() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    let usd_report = treasury.get_backing_report(&token);
+
+    // the position is still worth 1 USD per stablecoin, but the supply is owed at 1.08 USD
+    treasury.set_currency(&token, &Symbol::new(&fixture.env, "EUR"));
+    set_eur_feed(&fixture, &token, 1_0800000);
+    let eur_report = treasury.get_backing_report(&token);
+
+    assert_eq!(eur_report.position_value, usd_report.position_value);
+    assert!(eur_report.collateral_ratio < usd_report.collateral_ratio);
+    assert!((eur_report.collateral_ratio * 108 / 100 - usd_report.collateral_ratio).abs() <= 2);
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Ledger, Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::FlashMintConfig,
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const DEADLINE_EXPIRED: u32 = 526;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_flash_mint_before_deadline() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fees
+    let sequence = u64::from(fixture.env.ledger().sequence());
+
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &Some(sequence));
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &Some(sequence + 1));
+}
+
+#[test]
+fn test_calls_past_deadline_are_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let treasury = &fixture.treasury;
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fees
+    let deadline = Some(u64::from(fixture.env.ledger().sequence()) - 1);
+    let data = Bytes::new(&fixture.env);
+
+    let result = treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &data, &deadline);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(DEADLINE_EXPIRED))));
+    let result = treasury.try_flash_collateral(&receiver, &xlm.address, &AMOUNT, &data, &deadline);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(DEADLINE_EXPIRED))));
+    let result = treasury.try_keep_peg(&fixture.admin, &receiver, &ousd.address, &AMOUNT, &data, &deadline);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(DEADLINE_EXPIRED))));
+    let result = treasury.try_redeem(&fixture.admin, &ousd.address, &xlm.address, &AMOUNT, &0, &deadline);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(DEADLINE_EXPIRED))));
+
+    // no deadline never expires
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &data, &None);
+}
@@ -0,0 +1,58 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, vec, Address, Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::FlashMintConfig,
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const ACCOUNT_DENIED: u32 = 530;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_denied_receiver_cannot_flash_mint() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fee
+
+    treasury.set_denied(&receiver, &true);
+    assert!(treasury.is_denied(&receiver));
+    assert_eq!(
+        treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None),
+        Err(Ok(Error::from_contract_error(ACCOUNT_DENIED)))
+    );
+
+    // lifting the denial restores access
+    treasury.set_denied(&receiver, &false);
+    assert!(!treasury.is_denied(&receiver));
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+}
+
+#[test]
+fn test_minter_cannot_mint_to_denied_accounts() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let minter = Address::generate(&fixture.env);
+    let samwise = Address::generate(&fixture.env);
+    let pippin = Address::generate(&fixture.env);
+    treasury.set_minter(&minter, &token, &(1_000 * SCALAR_7), &(1_000 * SCALAR_7));
+
+    treasury.set_denied_batch(&vec![&fixture.env, samwise.clone(), pippin.clone()], &true);
+    for account in [&samwise, &pippin] {
+        assert!(treasury.is_denied(account));
+        assert_eq!(
+            treasury.try_mint(&minter, account, &SCALAR_7),
+            Err(Ok(Error::from_contract_error(ACCOUNT_DENIED)))
+        );
+    }
+
+    treasury.set_denied_batch(&vec![&fixture.env, pippin.clone()], &false);
+    assert!(treasury.is_denied(&samwise));
+    treasury.mint(&minter, &pippin, &SCALAR_7);
+    assert_eq!(fixture.tokens[TokenIndex::OUSD].balance(&pippin), SCALAR_7);
+}
@@ -0,0 +1,54 @@
+#![cfg(test)]
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    fixture_builder::FixtureBuilder,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+/// A fixture with a pegged OUSD/USDC pair
+fn setup<'a>() -> TestFixture<'a> {
+    FixtureBuilder::new()
+        .with_pair(TokenIndex::OUSD, TokenIndex::USDC, 1_000_000 * SCALAR_7, 1_000_000 * SCALAR_7)
+        .build()
+}
+
+#[test]
+fn test_depeg_below_peg() {
+    let fixture = setup();
+    assert_eq!(fixture.peg_price(TokenIndex::OUSD), 1_0000000);
+
+    fixture.depeg(TokenIndex::OUSD, -300);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OUSD), 0_9700000, 10);
+
+    // depegs compound on the current price
+    fixture.depeg(TokenIndex::OUSD, -100);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OUSD), 0_9603000, 10);
+}
+
+#[test]
+fn test_depeg_above_peg() {
+    let fixture = setup();
+
+    fixture.depeg(TokenIndex::OUSD, 250);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OUSD), 1_0250000, 10);
+}
+
+#[test]
+fn test_depeg_keeps_user_balances() {
+    let fixture = setup();
+    let frodo = &fixture.users[0];
+    let ousd_before = fixture.tokens[TokenIndex::OUSD].balance(frodo);
+    let usdc_before = fixture.tokens[TokenIndex::USDC].balance(frodo);
+
+    fixture.depeg(TokenIndex::OUSD, -500);
+
+    assert_eq!(fixture.tokens[TokenIndex::OUSD].balance(frodo), ousd_before);
+    assert_eq!(fixture.tokens[TokenIndex::USDC].balance(frodo), usdc_before);
+}
+
+#[test]
+#[should_panic(expected = "no USDC pair for token")]
+fn test_depeg_without_pair() {
+    let fixture = FixtureBuilder::new().build();
+    fixture.depeg(TokenIndex::OUSD, -300);
+}
@@ -0,0 +1,201 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, vec, xdr::ToXdr, Address, Bytes, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    dependencies::{
+        pegkeeper::{FlashLoanOp, PegConfig, Strategy, StrategyOp},
+        pool::{Request, RequestType},
+        treasury::FlashLoanConfig,
+    },
+    fixture_builder::{ousd_config, xlm_config, FixtureBuilder},
+    strategy::create_mock_strategy,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const UNAUTHORIZED_ERROR: u32 = 115;
+
+const SUPPLY: i128 = 1_000_000 * SCALAR_7;
+const BORROWED: i128 = 250_000 * SCALAR_7;
+const PEG_CAPITAL: i128 = 50_000 * SCALAR_7;
+
+/// A fixture with OUSD supplied to a blend pool by the treasury, a pegged OUSD/USDC pair, and a
+/// pegkeeper defending the peg with 1% threshold
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = FixtureBuilder::new()
+        .with_whale()
+        .with_pool("Teapot", 0_9999999, 6)
+        .with_reserve(0, TokenIndex::XLM, xlm_config())
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_treasury_supply(SUPPLY)
+        .with_pair(TokenIndex::OUSD, TokenIndex::USDC, 1_000_000 * SCALAR_7, 1_000_000 * SCALAR_7)
+        .build();
+    let usdc = fixture.tokens[TokenIndex::USDC].address.clone();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    fixture.pegkeeper.set_peg_config(&PegConfig { stablecoin: ousd, threshold_bps: 100 });
+    fixture.pegkeeper.set_collaterals(&Strategy::Peg, &vec![&fixture.env, usdc]);
+    fixture
+}
+
+#[test]
+fn test_depeg_and_recover() {
+    let fixture = setup();
+    let treasury = &fixture.treasury;
+    let pegkeeper = &fixture.pegkeeper;
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    assert_eq!(fixture.peg_price(TokenIndex::OUSD), SCALAR_7);
+
+    // a borrower draws OUSD from the supply the treasury minted into the pool
+    let borrower = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&borrower, &(20_000_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 20_000_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: ousd.address.clone(),
+            amount: BORROWED,
+        },
+    ];
+    pool.submit(&borrower, &borrower, &borrower, &requests);
+    assert_eq!(ousd.balance(&borrower), BORROWED);
+    let summary = treasury.get_position_summary(&ousd.address);
+    assert!(summary.supplied >= SUPPLY);
+    assert_approx_eq_abs(summary.utilization, BORROWED * SCALAR_7 / summary.supplied, 1000);
+    fixture.jump_with_sequence(100 * 5);
+
+    // selling pressure drives OUSD 5% below the peg
+    fixture.depeg(TokenIndex::OUSD, -500);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OUSD), 0_9500000, 10);
+    assert_approx_eq_abs(pegkeeper.check_peg(&usdc.address), -500, 1);
+
+    // the pegkeeper buys OUSD back with its USDC until the pair is balanced
+    usdc.mint(&pegkeeper.address, &PEG_CAPITAL);
+    let quote = pegkeeper.simulate_rebalance(&usdc.address, &(10_000 * SCALAR_7));
+    assert!(quote.expected_profit > 0);
+    let deviation = pegkeeper.maybe_rebalance(&usdc.address);
+    assert_approx_eq_abs(deviation, -500, 1);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OUSD), SCALAR_7, 0_0010000);
+    assert!(pegkeeper.check_peg(&usdc.address).abs() <= 10);
+
+    // the OUSD bought back is worth more than the USDC spent, and the profit is recorded
+    let usdc_spent = PEG_CAPITAL - usdc.balance(&pegkeeper.address);
+    let ousd_bought = ousd.balance(&pegkeeper.address);
+    assert!(usdc_spent > 0);
+    let (expansion_profit, contraction_profit) = pegkeeper.get_peg_profits();
+    assert_eq!(expansion_profit, 0);
+    assert_eq!(contraction_profit, ousd_bought - usdc_spent);
+    assert!(contraction_profit > 0);
+
+    // a keeper flash loans OUSD into the pegkeeper to run a strategy, paying the treasury's fee
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    strategy_client.set_profit(&(100 * SCALAR_7));
+    ousd.mint(&strategy, &(100 * SCALAR_7));
+    pegkeeper.register_strategy(&1, &strategy);
+    // the pegkeeper is whitelisted on initialization, so only its config is updated
+    treasury.update_flash_loaner(&pegkeeper.address, &FlashLoanConfig { max_amount: SUPPLY, fee_bps: 0, enabled: true });
+    treasury.set_fee_bps(&fixture.admin, &10);
+    let fee = treasury.quote_repayment(&(10_000 * SCALAR_7)) - 10_000 * SCALAR_7;
+    let stats = treasury.get_stats();
+    let surplus = treasury.get_surplus(&ousd.address);
+
+    let data = FlashLoanOp::Strategy(StrategyOp { id: 1, params: Bytes::new(&fixture.env) }).to_xdr(&fixture.env);
+    treasury.keep_peg(&fixture.admin, &pegkeeper.address, &ousd.address, &(10_000 * SCALAR_7), &data, &None);
+
+    assert_eq!(ousd.balance(&pegkeeper.address), ousd_bought + 100 * SCALAR_7 - fee);
+    let after = treasury.get_stats();
+    assert_eq!(after.flash_loan_volume - stats.flash_loan_volume, 10_000 * SCALAR_7);
+    assert_eq!(after.fees_earned - stats.fees_earned, fee);
+    // a peg rebalance is not a liquidation
+    assert_eq!(after.liquidations, stats.liquidations);
+    assert_eq!(treasury.get_surplus(&ousd.address) - surplus, fee);
+
+    // the borrower repays, and the treasury's accounting still balances
+    fixture.jump_with_sequence(100 * 5);
+    ousd.mint(&borrower, &(1_000 * SCALAR_7)); // cover the interest
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Repay as u32,
+            address: ousd.address.clone(),
+            amount: BORROWED + 1_000 * SCALAR_7,
+        },
+    ];
+    pool.submit(&borrower, &borrower, &borrower, &requests);
+    assert_eq!(fixture.read_reserve_data(0, TokenIndex::OUSD).d_supply, 0);
+    fixture.assert_invariants();
+}
+
+#[test]
+fn test_expand_and_contract_through_the_treasury_minter() {
+    let fixture = setup();
+    let treasury = &fixture.treasury;
+    let pegkeeper = &fixture.pegkeeper;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    treasury.set_minter(&pegkeeper.address, &ousd.address, &PEG_CAPITAL, &PEG_CAPITAL);
+
+    // buying pressure drives OUSD 5% above the peg, and the pegkeeper mints OUSD to sell into the pair
+    fixture.depeg(TokenIndex::OUSD, 500);
+    let deviation = pegkeeper.maybe_rebalance(&usdc.address);
+    assert_approx_eq_abs(deviation, 500, 1);
+    assert!(pegkeeper.check_peg(&usdc.address).abs() <= 10);
+
+    let minted = pegkeeper.get_peg_debt();
+    assert!(minted > 0);
+    assert_eq!(treasury.get_minter(&pegkeeper.address).unwrap().minted, minted);
+    assert_eq!(ousd.balance(&pegkeeper.address), 0);
+    let (expansion_profit, contraction_profit) = pegkeeper.get_peg_profits();
+    assert_eq!(expansion_profit, usdc.balance(&pegkeeper.address) - minted);
+    assert!(expansion_profit > 0);
+    assert_eq!(contraction_profit, 0);
+
+    // selling pressure drives OUSD 5% below the peg, and the pegkeeper buys OUSD back with the USDC it
+    // earned and burns up to the amount it minted, keeping the profit
+    fixture.depeg(TokenIndex::OUSD, -500);
+    let usdc_balance = usdc.balance(&pegkeeper.address);
+    pegkeeper.maybe_rebalance(&usdc.address);
+    let usdc_spent = usdc_balance - usdc.balance(&pegkeeper.address);
+    assert!(usdc_spent > 0);
+
+    let debt = pegkeeper.get_peg_debt();
+    assert_eq!(debt, (minted - usdc_spent).max(0));
+    assert_eq!(treasury.get_minter(&pegkeeper.address).unwrap().minted, debt);
+    let (_, contraction_profit) = pegkeeper.get_peg_profits();
+    assert_eq!(ousd.balance(&pegkeeper.address), contraction_profit + usdc_spent - (minted - debt));
+    assert!(contraction_profit > 0);
+    fixture.assert_invariants();
+}
+
+#[test]
+fn test_peg_operations_require_admin_or_keeper() {
+    let fixture = setup();
+    let pegkeeper = &fixture.pegkeeper;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    fixture.treasury.set_minter(&pegkeeper.address, &ousd.address, &PEG_CAPITAL, &PEG_CAPITAL);
+    let keeper = Address::generate(&fixture.env);
+    let stranger = Address::generate(&fixture.env);
+    pegkeeper.set_keeper(&keeper);
+
+    fixture.depeg(TokenIndex::OUSD, 500);
+    assert_eq!(
+        pegkeeper.try_expand_peg(&stranger, &usdc.address, &(1_000 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR)))
+    );
+    assert!(pegkeeper.expand_peg(&keeper, &usdc.address, &(1_000 * SCALAR_7)) > 0);
+    assert_eq!(pegkeeper.get_peg_debt(), 1_000 * SCALAR_7);
+
+    fixture.depeg(TokenIndex::OUSD, -500);
+    assert_eq!(
+        pegkeeper.try_contract_peg(&stranger, &usdc.address, &(1_000 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR)))
+    );
+    assert!(pegkeeper.contract_peg(&keeper, &usdc.address, &(1_000 * SCALAR_7)) > 0);
+    assert!(pegkeeper.get_peg_debt() < 1_000 * SCALAR_7);
+}
@@ -0,0 +1,36 @@
+#![cfg(test)]
+use keeper::deployment::Deployments;
+
+const DEPLOYMENTS: &str = include_str!("../../deployments.toml");
+
+#[test]
+fn test_deployments_file_is_valid() {
+    let deployments = Deployments::parse(DEPLOYMENTS).unwrap();
+    deployments.validate().unwrap();
+
+    let testnet = deployments.network("testnet").unwrap();
+    assert!(testnet.rpc_url.is_some());
+    assert!(testnet.network_passphrase.is_some());
+    assert!(testnet.contracts.get("treasury").is_some());
+    assert!(testnet.tokens.contains_key("OUSD"));
+}
+
+#[test]
+fn test_deployments_record_contracts() {
+    let mut deployments = Deployments::default();
+    assert!(deployments.network("futurenet").is_err());
+
+    let treasury = "CCONXIBCWURVF6CP7ZN3O7Q33RIHZGAHHP66OJKHSAOUGF5QZ5WQR6CN".to_string();
+    let futurenet = deployments.network_mut("futurenet");
+    futurenet.contracts.set("treasury", treasury.clone()).unwrap();
+    assert!(futurenet.contracts.set("controller", treasury.clone()).is_err());
+    futurenet.tokens.insert("OUSD".to_string(), "not-an-address".to_string());
+
+    // invalid addresses are rejected
+    assert!(deployments.validate().is_err());
+    deployments.network_mut("futurenet").tokens.clear();
+    deployments.validate().unwrap();
+
+    let contracts: Vec<_> = deployments.network("futurenet").unwrap().contracts.iter().collect();
+    assert_eq!(contracts, vec![("treasury", &treasury)]);
+}
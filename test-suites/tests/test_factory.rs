@@ -0,0 +1,100 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::BytesN as _, token::StellarAssetClient, BytesN, Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{
+        bridge_oracle::{Asset as OracleAsset, BridgeOracleClient},
+        factory::{create_factory, Asset, OrbitConfig},
+        pegkeeper::PegkeeperClient,
+        token::create_stellar_token,
+        treasury::TreasuryClient,
+    },
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const PEGKEEPER_ALREADY_INITIALIZED: u32 = 102;
+const ALREADY_DEPLOYED: u32 = 1603;
+
+#[test]
+fn test_deploy_orbit_wires_a_new_stablecoin() {
+    let fixture = create_fixture_with_data(false);
+    let e = &fixture.env;
+    let (_, factory) = create_factory(e, &fixture.admin);
+    let (token, token_client) = create_stellar_token(e, &fixture.admin);
+    let eur = Symbol::new(e, "EUR");
+    let pool = fixture.pools[0].pool.address.clone();
+    let config = OrbitConfig {
+        admin: fixture.admin.clone(),
+        token: token.clone(),
+        asset: Asset::Other(eur.clone()),
+        currency: eur.clone(),
+        oracle: fixture.oracle.address.clone(),
+        router: fixture.router.address.clone(),
+        blend_pool: pool.clone(),
+    };
+
+    let deployment = factory.deploy_orbit(&BytesN::<32>::random(e), &config);
+    assert_eq!(deployment.token, token);
+    assert_eq!(factory.get_deployment(&token), Some(deployment.clone()));
+    assert_eq!(factory.get_deployments().len(), 1);
+
+    // the treasury is handed to the configured admin, wired to the new pegkeeper and bridge oracle
+    let treasury = TreasuryClient::new(e, &deployment.treasury);
+    let treasury_config = treasury.get_config();
+    assert_eq!(treasury_config.admin, fixture.admin);
+    assert_eq!(treasury_config.pegkeeper, deployment.pegkeeper);
+    assert_eq!(treasury_config.bridge_oracle, deployment.bridge_oracle);
+    assert_eq!(treasury.get_pools(&token).get_unchecked(0), pool);
+    assert_eq!(treasury.get_currency(&token), eur);
+    assert_eq!(
+        PegkeeperClient::new(e, &deployment.pegkeeper).try_initialize(&deployment.treasury, &fixture.router.address),
+        Err(Ok(Error::from_contract_error(PEGKEEPER_ALREADY_INITIALIZED)))
+    );
+
+    // the bridge oracle prices the stablecoin at the asset it is pegged to
+    let bridge_oracle = BridgeOracleClient::new(e, &deployment.bridge_oracle);
+    let price = bridge_oracle.lastprice(&OracleAsset::Stellar(token.clone())).unwrap();
+    assert_eq!(price.price, 1_0800000);
+
+    // the stablecoin's admin is handed to the treasury, which can then mint it
+    assert_eq!(StellarAssetClient::new(e, &token).admin(), deployment.treasury);
+    let minter = fixture.users[0].clone();
+    treasury.set_minter(&minter, &token, &(1_000 * SCALAR_7), &(1_000 * SCALAR_7));
+    treasury.mint(&minter, &minter, &(100 * SCALAR_7));
+    assert_eq!(token_client.balance(&minter), 100 * SCALAR_7);
+}
+
+#[test]
+fn test_deploy_orbit_rejects_a_deployed_stablecoin() {
+    let fixture = create_fixture_with_data(false);
+    let e = &fixture.env;
+    let (_, factory) = create_factory(e, &fixture.admin);
+    let (token, _) = create_stellar_token(e, &fixture.admin);
+    let mut config = OrbitConfig {
+        admin: fixture.admin.clone(),
+        token: token.clone(),
+        asset: Asset::Stellar(fixture.tokens[TokenIndex::USDC].address.clone()),
+        currency: Symbol::new(e, "USD"),
+        oracle: fixture.oracle.address.clone(),
+        router: fixture.router.address.clone(),
+        blend_pool: fixture.pools[0].pool.address.clone(),
+    };
+    let deployment = factory.deploy_orbit(&BytesN::<32>::random(e), &config);
+
+    assert_eq!(
+        factory.try_deploy_orbit(&BytesN::<32>::random(e), &config),
+        Err(Ok(Error::from_contract_error(ALREADY_DEPLOYED)))
+    );
+
+    // other stablecoins get their own contracts
+    let (other_token, _) = create_stellar_token(e, &fixture.admin);
+    config.token = other_token.clone();
+    let other = factory.deploy_orbit(&BytesN::<32>::random(e), &config);
+    assert_ne!(other.treasury, deployment.treasury);
+    assert_ne!(other.pegkeeper, deployment.pegkeeper);
+    assert_ne!(other.bridge_oracle, deployment.bridge_oracle);
+    let deployments = factory.get_deployments();
+    assert_eq!(deployments.len(), 2);
+    assert_eq!(deployments.get_unchecked(0).token, token);
+    assert_eq!(deployments.get_unchecked(1).token, other_token);
+}
@@ -0,0 +1,58 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::FlashMintConfig,
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_fees_are_split_with_the_recipient() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let recipient = Address::generate(&fixture.env);
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    treasury.set_fee_recipient(&recipient, &5_000);
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fee
+    let surplus_before = treasury.get_surplus(&ousd.address);
+
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+
+    let fee = AMOUNT * 10 / 10_000;
+    assert_eq!(ousd.balance(&recipient), fee / 2);
+    assert_eq!(treasury.get_surplus(&ousd.address) - surplus_before, fee - fee / 2);
+    let config = treasury.get_fee_recipient().unwrap();
+    assert_eq!(config.recipient, recipient);
+    assert_eq!(config.split_bps, 5_000);
+}
+
+#[test]
+fn test_fees_accrue_to_surplus_without_recipient() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fee
+    let surplus_before = treasury.get_surplus(&ousd.address);
+
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+
+    assert!(treasury.get_fee_recipient().is_none());
+    assert_eq!(treasury.get_surplus(&ousd.address) - surplus_before, AMOUNT * 10 / 10_000);
+}
+
+#[test]
+fn test_fee_split_above_100_percent_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let recipient = Address::generate(&fixture.env);
+
+    let result = fixture.treasury.try_set_fee_recipient(&recipient, &10_001);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+}
@@ -0,0 +1,76 @@
+#![cfg(test)]
+use soroban_sdk::{vec, Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{FeeTier, FeeTierConfig, FlashLoanConfig, Role},
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_keepers_get_fee_tier_discounts() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: AMOUNT, fee_bps: 0, enabled: true });
+    ousd.mint(&receiver, &SCALAR_7); // cover the fees
+    treasury.set_fee_bps(&fixture.admin, &10);
+    treasury.set_min_fee(&fixture.admin, &0);
+    let full_fee = treasury.quote_repayment(&AMOUNT) - AMOUNT;
+
+    // the receiver is its own keeper, so it can direct the loans into itself
+    let keeper = receiver.clone();
+    treasury.grant_role(&Role::Keeper, &keeper);
+    let config = FeeTierConfig {
+        stake_token: Some(blnd.address.clone()),
+        tiers: vec![
+            &fixture.env,
+            FeeTier { min_volume: AMOUNT, min_stake: 1_000_000 * SCALAR_7, discount_bps: 2_500 },
+            FeeTier { min_volume: 100 * AMOUNT, min_stake: 2_000_000 * SCALAR_7, discount_bps: 5_000 },
+        ],
+    };
+    treasury.set_fee_tiers(&fixture.admin, &config);
+    assert_eq!(treasury.get_fee_tiers().unwrap().tiers.len(), 2);
+    let stake = blnd.balance(&keeper);
+    assert!(stake < 1_000_000 * SCALAR_7);
+    assert_eq!(treasury.get_fee_for(&keeper, &AMOUNT), full_fee);
+
+    // the first loan pays the full fee and qualifies the keeper for the volume tier
+    let loans = vec![&fixture.env, (receiver.clone(), AMOUNT)];
+    treasury.flash_loan_batch(&keeper, &ousd.address, &loans, &Bytes::new(&fixture.env), &None);
+    assert_eq!(ousd.balance(&receiver), SCALAR_7 - full_fee);
+    assert_eq!(treasury.get_keeper_volume(&keeper), AMOUNT);
+    let discounted_fee = treasury.get_fee_for(&keeper, &AMOUNT);
+    assert_eq!(discounted_fee, full_fee * 3 / 4);
+
+    treasury.flash_loan_batch(&keeper, &ousd.address, &loans, &Bytes::new(&fixture.env), &None);
+    assert_eq!(ousd.balance(&receiver), SCALAR_7 - full_fee - discounted_fee);
+    assert_eq!(treasury.get_keeper_volume(&keeper), 2 * AMOUNT);
+
+    // staking enough governance tokens qualifies the keeper for the higher tier
+    blnd.mint(&keeper, &(2_000_000 * SCALAR_7 - stake));
+    assert_eq!(treasury.get_fee_for(&keeper, &AMOUNT), full_fee / 2);
+}
+
+#[test]
+fn test_invalid_fee_tiers_are_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    for tier in [
+        FeeTier { min_volume: 0, min_stake: SCALAR_7, discount_bps: 1_000 },
+        FeeTier { min_volume: SCALAR_7, min_stake: 0, discount_bps: 1_000 },
+        FeeTier { min_volume: SCALAR_7, min_stake: SCALAR_7, discount_bps: 10_001 },
+    ] {
+        let config = FeeTierConfig { stake_token: None, tiers: vec![&fixture.env, tier] };
+        assert_eq!(
+            treasury.try_set_fee_tiers(&fixture.admin, &config),
+            Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+        );
+    }
+    assert!(treasury.get_fee_tiers().is_none());
+}
@@ -0,0 +1,86 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, vec, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::pegkeeper::Strategy,
+    dependencies::pool::{Request, RequestType},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const AUCTION_NOT_FILLABLE: u32 = 117;
+
+/// Borrow OUSD against XLM and drop the XLM price so the position can be liquidated, returning the borrower
+fn create_liquidatable_user(fixture: &mut TestFixture) -> Address {
+    let xlm_amount = 10_000_000_000_00 * SCALAR_7;
+    fixture.create_pair(TokenIndex::OUSD, TokenIndex::XLM, (xlm_amount as f64 * 0.088) as i128, xlm_amount);
+
+    let henk = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&henk, &(100_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 100_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::OUSD].address.clone(),
+            amount: 8_800 * SCALAR_7,
+        },
+    ];
+    fixture.pools[0].pool.submit(&henk, &henk, &henk, &requests);
+
+    fixture.jump(60 * 60 * 24 * 7 * 4); // 4 weeks
+    fixture.oracle.set_price_stable(&vec![&fixture.env, 1_0000000, 0_0880000]);
+    henk
+}
+
+#[test]
+fn test_fill_user_liquidation_auction() {
+    let mut fixture = create_fixture_with_data(false);
+    let henk = create_liquidatable_user(&mut fixture);
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pegkeeper = &fixture.pegkeeper;
+    pegkeeper.set_collaterals(&Strategy::Liquidate, &vec![&fixture.env, fixture.tokens[TokenIndex::XLM].address.clone()]);
+
+    pool.new_liquidation_auction(&henk, &100);
+    // the lot is scaled up to its full size over the first 200 blocks, then the bid is scaled down
+    fixture.jump_with_sequence(100 * 5);
+    let early_quote = pegkeeper.quote_auction(&pool.address, &ousd.address, &0, &henk);
+    fixture.jump_with_sequence(150 * 5);
+    let quote = pegkeeper.quote_auction(&pool.address, &ousd.address, &0, &henk);
+    assert!(quote > 0);
+    assert!(quote < early_quote);
+
+    let balance_before = ousd.balance(&pegkeeper.address);
+    let liquidations = fixture.treasury.get_stats().liquidations;
+    fixture.treasury.fill_auction(&fixture.admin, &ousd.address, &0, &henk);
+    assert_eq!(fixture.treasury.get_stats().liquidations, liquidations + 1);
+
+    assert!(pool.try_get_auction(&0, &henk).is_err());
+    assert_eq!(pool.get_positions(&pegkeeper.address).liabilities.len(), 0);
+    assert!(ousd.balance(&pegkeeper.address) > balance_before);
+    fixture.assert_invariants();
+}
+
+#[test]
+fn test_fill_auction_lot_must_be_whitelisted() {
+    let mut fixture = create_fixture_with_data(false);
+    let henk = create_liquidatable_user(&mut fixture);
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    pool.new_liquidation_auction(&henk, &100);
+    fixture.jump_with_sequence(250 * 5);
+
+    // XLM is not whitelisted for the liquidate strategy
+    assert!(fixture.treasury.try_fill_auction(&fixture.admin, &ousd.address, &0, &henk).is_err());
+    // bad debt auctions need the backstop auction config to redeem their lot
+    assert!(fixture.treasury.try_fill_auction(&fixture.admin, &ousd.address, &1, &fixture.backstop.address).is_err());
+    assert_eq!(
+        fixture.pegkeeper.try_quote_auction(&pool.address, &ousd.address, &3, &henk),
+        Err(Ok(Error::from_contract_error(AUCTION_NOT_FILLABLE)))
+    );
+}
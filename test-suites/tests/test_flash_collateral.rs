@@ -0,0 +1,73 @@
+#![cfg(test)]
+use soroban_sdk::{Bytes, Error};
+use test_suites::{
+    dependencies::treasury::FlashCollateralConfig,
+    fixture_builder::{ousd_config, FixtureBuilder},
+    receiver::create_mock_receiver,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const BALANCE_ERROR: u32 = 505;
+const FLASHLOAN_CAP_EXCEEDED: u32 = 513;
+const FLASHLOAN_NOT_REPAID: u32 = 510;
+const IDLE: i128 = 50_000 * SCALAR_7;
+
+/// A fixture with idle XLM held by the treasury and XLM flash loans enabled with a 0.3% fee
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = FixtureBuilder::new()
+        .with_pool("Teapot", 0_1000000, 4)
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_stablecoin(0)
+        .build();
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    xlm.mint(&fixture.treasury.address, &IDLE);
+    fixture.treasury.set_flash_collateral(
+        &xlm.address,
+        &FlashCollateralConfig { cap: 100_000 * SCALAR_7, fee_bps: 30, blend_pool: None },
+    );
+    fixture
+}
+
+#[test]
+fn test_flash_collateral_from_idle_balance() {
+    let fixture = setup();
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let treasury = &fixture.treasury;
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    let amount = 10_000 * SCALAR_7;
+    let fee = 30 * SCALAR_7;
+    xlm.mint(&receiver, &fee);
+
+    treasury.flash_collateral(&receiver, &xlm.address, &amount, &Bytes::new(&fixture.env), &None);
+
+    assert_eq!(xlm.balance(&treasury.address), IDLE + fee);
+    assert_eq!(xlm.balance(&receiver), 0);
+    assert_eq!(treasury.get_surplus(&xlm.address), fee);
+}
+
+#[test]
+fn test_flash_collateral_not_repaid() {
+    let fixture = setup();
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let (receiver, receiver_client) = create_mock_receiver(&fixture.env, &fixture.treasury.address);
+    receiver_client.set_shortfall(&1);
+
+    let result = fixture.treasury.try_flash_collateral(&receiver, &xlm.address, &(10_000 * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID))));
+    assert_eq!(xlm.balance(&fixture.treasury.address), IDLE);
+}
+
+#[test]
+fn test_flash_collateral_limits() {
+    let fixture = setup();
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let (receiver, _) = create_mock_receiver(&fixture.env, &fixture.treasury.address);
+
+    // above the cap
+    let result = fixture.treasury.try_flash_collateral(&receiver, &xlm.address, &(100_001 * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_CAP_EXCEEDED))));
+
+    // above the idle balance without a blend pool to withdraw from
+    let result = fixture.treasury.try_flash_collateral(&receiver, &xlm.address, &(IDLE + 1), &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(BALANCE_ERROR))));
+}
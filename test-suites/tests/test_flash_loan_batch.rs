@@ -0,0 +1,107 @@
+#![cfg(test)]
+use soroban_sdk::{vec, xdr::ToXdr, Bytes, Error, Vec};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{
+        pegkeeper::{FlashLoanOp, StrategyOp},
+        treasury::{FlashLoanConfig, Role},
+    },
+    receiver::create_mock_receiver,
+    strategy::create_mock_strategy,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const UNAUTHORIZED_ERROR: u32 = 503;
+const FLASHLOAN_NOT_REPAID: u32 = 510;
+const INVALID_BATCH: u32 = 540;
+
+/// Register a mock strategy on the pegkeeper that returns a 100 OUSD profit, and build the calldata
+/// of a flash loan executing it
+fn setup_strategy(fixture: &TestFixture) -> Bytes {
+    let pegkeeper = &fixture.pegkeeper;
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    strategy_client.set_profit(&(100 * SCALAR_7));
+    fixture.tokens[TokenIndex::OUSD].mint(&strategy, &(100 * SCALAR_7));
+    pegkeeper.register_strategy(&1, &strategy);
+    FlashLoanOp::Strategy(StrategyOp { id: 1, params: Bytes::new(&fixture.env) }).to_xdr(&fixture.env)
+}
+
+#[test]
+fn test_batch_loans_to_the_pegkeeper_and_the_keeper() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let pegkeeper = &fixture.pegkeeper.address;
+    let (samwise, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&samwise, &FlashLoanConfig { max_amount: 1_000 * SCALAR_7, fee_bps: 10, enabled: true });
+    treasury.grant_role(&Role::Keeper, &samwise);
+    ousd.mint(&samwise, &SCALAR_7); // cover the fee
+    let data = setup_strategy(&fixture);
+    let samwise_fee = treasury.simulate_flash_loan(&samwise, &(1_000 * SCALAR_7)).fee;
+    let pegkeeper_fee = treasury.simulate_flash_loan(pegkeeper, &(500 * SCALAR_7)).fee;
+    let before = treasury.get_stats();
+
+    let loans = vec![&fixture.env, (samwise.clone(), 1_000 * SCALAR_7), (pegkeeper.clone(), 500 * SCALAR_7)];
+    treasury.flash_loan_batch(&samwise, &ousd.address, &loans, &data, &None);
+
+    assert_eq!(ousd.balance(&samwise), SCALAR_7 - samwise_fee);
+    assert_eq!(ousd.balance(pegkeeper), 100 * SCALAR_7 - pegkeeper_fee);
+    let stats = treasury.get_stats();
+    assert_eq!(stats.flash_loans - before.flash_loans, 2);
+    assert_eq!(stats.flash_loan_volume - before.flash_loan_volume, 1_500 * SCALAR_7);
+    assert_eq!(stats.fees_earned - before.fees_earned, samwise_fee + pegkeeper_fee);
+}
+
+#[test]
+fn test_batch_reverts_if_any_loan_is_not_repaid() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let pegkeeper = &fixture.pegkeeper.address;
+    let (pippin, pippin_client) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.add_flash_loaner(&pippin, &FlashLoanConfig { max_amount: 1_000 * SCALAR_7, fee_bps: 10, enabled: true });
+    treasury.grant_role(&Role::Keeper, &pippin);
+    ousd.mint(&pippin, &SCALAR_7); // cover the fee
+    pippin_client.set_shortfall(&1);
+    let data = setup_strategy(&fixture);
+
+    let loans = vec![&fixture.env, (pegkeeper.clone(), 1_000 * SCALAR_7), (pippin.clone(), 1_000 * SCALAR_7)];
+    assert_eq!(
+        treasury.try_flash_loan_batch(&pippin, &ousd.address, &loans, &data, &None),
+        Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID)))
+    );
+    assert_eq!(ousd.balance(pegkeeper), 0);
+    assert_eq!(ousd.balance(&pippin), SCALAR_7);
+}
+
+#[test]
+fn test_batch_rejects_a_third_party_receiver() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (samwise, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    let (pippin, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    for receiver in [&samwise, &pippin] {
+        treasury.add_flash_loaner(receiver, &FlashLoanConfig { max_amount: 1_000 * SCALAR_7, fee_bps: 10, enabled: true });
+        ousd.mint(receiver, &SCALAR_7); // cover the fees
+    }
+    treasury.grant_role(&Role::Keeper, &samwise);
+
+    // a keeper can not direct a loan and its calldata into another whitelisted receiver
+    let loans = vec![&fixture.env, (samwise.clone(), 1_000 * SCALAR_7), (pippin.clone(), 1_000 * SCALAR_7)];
+    assert_eq!(
+        treasury.try_flash_loan_batch(&samwise, &ousd.address, &loans, &Bytes::new(&fixture.env), &None),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED_ERROR)))
+    );
+    assert_eq!(ousd.balance(&pippin), SCALAR_7);
+}
+
+#[test]
+fn test_empty_batch_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    assert_eq!(
+        fixture.treasury.try_flash_loan_batch(&fixture.admin, &ousd.address, &Vec::new(&fixture.env), &Bytes::new(&fixture.env), &None),
+        Err(Ok(Error::from_contract_error(INVALID_BATCH)))
+    );
+}
@@ -0,0 +1,134 @@
+#![cfg(test)]
+use proptest::prelude::*;
+use soroban_sdk::{Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{FlashLoanConfig, FlashMintConfig, Role},
+    fixture_builder::{ousd_config, FixtureBuilder},
+    receiver::create_mock_receiver,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const FLASHLOAN_NOT_REPAID: u32 = 510;
+const MAX_FEE_BPS: u32 = 1_000;
+const CAP: i128 = 1_000_000 * SCALAR_7;
+
+/// A fixture with OUSD deployed and no treasury supply
+fn setup<'a>() -> TestFixture<'a> {
+    FixtureBuilder::new()
+        .with_pool("Teapot", 0_1000000, 4)
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_stablecoin(0)
+        .build()
+}
+
+fn calc_fee(amount: i128, fee_bps: u32) -> i128 {
+    (amount * i128::from(fee_bps) + 9_999) / 10_000
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn prop_flash_mint_repaid_increases_treasury_balance(amount in 1..CAP, fee_bps in 0..=MAX_FEE_BPS) {
+        let fixture = setup();
+        let ousd = &fixture.tokens[TokenIndex::OUSD];
+        let treasury = &fixture.treasury;
+        let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+        treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: CAP, fee_bps });
+        let fee = calc_fee(amount, fee_bps);
+        ousd.mint(&receiver, &fee);
+
+        let treasury_before = ousd.balance(&treasury.address);
+        treasury.flash_mint(&receiver, &ousd.address, &amount, &Bytes::new(&fixture.env), &None);
+
+        prop_assert!(ousd.balance(&treasury.address) >= treasury_before);
+        prop_assert_eq!(ousd.balance(&treasury.address), treasury_before + fee);
+        prop_assert_eq!(ousd.balance(&receiver), 0);
+    }
+
+    #[test]
+    fn prop_flash_mint_shortfall_reverts_mint(amount in 1..CAP, fee_bps in 0..=MAX_FEE_BPS, shortfall_bps in 1..=10_000i128) {
+        let fixture = setup();
+        let ousd = &fixture.tokens[TokenIndex::OUSD];
+        let treasury = &fixture.treasury;
+        let (receiver, receiver_client) = create_mock_receiver(&fixture.env, &treasury.address);
+        treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: CAP, fee_bps });
+        let fee = calc_fee(amount, fee_bps);
+        ousd.mint(&receiver, &fee);
+        receiver_client.set_shortfall(&(amount * shortfall_bps / 10_000).max(1));
+
+        let treasury_before = ousd.balance(&treasury.address);
+        let result = treasury.try_flash_mint(&receiver, &ousd.address, &amount, &Bytes::new(&fixture.env), &None);
+
+        prop_assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID))));
+        // the minted loan is reverted with the call
+        prop_assert_eq!(ousd.balance(&receiver), fee);
+        prop_assert_eq!(ousd.balance(&treasury.address), treasury_before);
+    }
+
+    #[test]
+    fn prop_flash_loan_repaid_increases_treasury_balance(amount in 1..CAP, fee_bps in 0..=MAX_FEE_BPS, min_fee in 0..SCALAR_7) {
+        let fixture = setup();
+        let ousd = &fixture.tokens[TokenIndex::OUSD];
+        let treasury = &fixture.treasury;
+        let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+        treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: CAP, fee_bps, enabled: true });
+        treasury.grant_role(&Role::Keeper, &receiver);
+        treasury.set_min_fee(&fixture.admin, &min_fee);
+        let fee = calc_fee(amount, fee_bps).max(min_fee);
+        ousd.mint(&receiver, &fee);
+
+        let treasury_before = ousd.balance(&treasury.address);
+        treasury.keep_peg(&receiver, &receiver, &ousd.address, &amount, &Bytes::new(&fixture.env), &None);
+
+        prop_assert!(ousd.balance(&treasury.address) >= treasury_before);
+        prop_assert_eq!(ousd.balance(&treasury.address), treasury_before + fee);
+        prop_assert_eq!(ousd.balance(&receiver), 0);
+    }
+
+    #[test]
+    fn prop_quote_repayment_is_exact(amount in 1..CAP, fee_bps in 0..=MAX_FEE_BPS, min_fee in 0..SCALAR_7) {
+        let fixture = setup();
+        let ousd = &fixture.tokens[TokenIndex::OUSD];
+        let treasury = &fixture.treasury;
+        let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+        // the receiver pays the default fee model
+        treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: CAP, fee_bps: 0, enabled: true });
+        treasury.grant_role(&Role::Keeper, &receiver);
+        treasury.set_fee_bps(&fixture.admin, &fee_bps);
+        treasury.set_min_fee(&fixture.admin, &min_fee);
+
+        let repayment = treasury.quote_repayment(&amount);
+        prop_assert_eq!(repayment, amount + calc_fee(amount, fee_bps).max(min_fee));
+        prop_assert_eq!(repayment, treasury.simulate_flash_loan(&receiver, &amount).required_repayment);
+
+        // funding the receiver with exactly the quoted fee repays the loan
+        ousd.mint(&receiver, &(repayment - amount));
+        treasury.keep_peg(&receiver, &receiver, &ousd.address, &amount, &Bytes::new(&fixture.env), &None);
+        prop_assert_eq!(ousd.balance(&receiver), 0);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn prop_decrease_supply_never_below_zero(amount in 1..(2_000_000 * SCALAR_7)) {
+        let fixture = create_fixture_with_data(false);
+        let ousd = &fixture.tokens[TokenIndex::OUSD];
+        let treasury = &fixture.treasury;
+
+        let supplied_before = treasury.get_position_summary(&ousd.address).supplied;
+        let result = treasury.try_decrease_supply(&fixture.admin, &ousd.address, &amount);
+        let supplied_after = treasury.get_position_summary(&ousd.address).supplied;
+
+        if amount <= supplied_before {
+            prop_assert!(result.is_ok());
+            prop_assert!(supplied_after >= 0);
+        } else {
+            prop_assert!(result.is_err());
+            prop_assert_eq!(supplied_after, supplied_before);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+#![cfg(test)]
+use sep_41_token::testutils::MockTokenClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Error, IntoVal, String, Symbol, Vec};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{
+        governance::{create_governance, Call, GovConfig, GovernanceClient, ProposalStatus},
+        token::create_stellar_token,
+    },
+    test_fixture::{TestFixture, SCALAR_7},
+};
+
+const INSUFFICIENT_BALANCE: u32 = 1504;
+const INVALID_PROPOSAL_STATUS: u32 = 1506;
+const ALREADY_VOTED: u32 = 1507;
+const PROPOSAL_DEFEATED: u32 = 1508;
+const TIMELOCK: u32 = 1509;
+const STAKE_LOCKED: u32 = 1510;
+
+const VOTING_PERIOD: u64 = 3 * 24 * 60 * 60;
+const TIMELOCK_PERIOD: u64 = 24 * 60 * 60;
+
+/// Deploy governance with a new vote token, a 1,000 token quorum and a 100 token proposal threshold,
+/// and make it the admin of the treasury
+fn setup_governance<'a>(fixture: &TestFixture<'a>) -> (GovernanceClient<'a>, MockTokenClient<'a>) {
+    let (vote_token_id, vote_token) = create_stellar_token(&fixture.env, &fixture.admin);
+    let config = GovConfig {
+        vote_token: vote_token_id,
+        voting_period: VOTING_PERIOD,
+        timelock: TIMELOCK_PERIOD,
+        quorum: 1_000 * SCALAR_7,
+        proposal_threshold: 100 * SCALAR_7,
+    };
+    let (governance_id, governance) = create_governance(&fixture.env, &config);
+    fixture.treasury.set_admin(&governance_id);
+    (governance, vote_token)
+}
+
+/// Mint vote tokens to a new account and stake them, returning the account
+fn create_staker(fixture: &TestFixture, governance: &GovernanceClient, vote_token: &MockTokenClient, amount: i128) -> Address {
+    let staker = Address::generate(&fixture.env);
+    vote_token.mint(&staker, &amount);
+    governance.stake(&staker, &amount);
+    staker
+}
+
+/// The calls of a proposal pausing the treasury, an admin-only change once governance is its admin
+fn pause_treasury_calls(fixture: &TestFixture, governance: &GovernanceClient) -> Vec<Call> {
+    vec![
+        &fixture.env,
+        Call {
+            contract: fixture.treasury.address.clone(),
+            fn_name: Symbol::new(&fixture.env, "pause"),
+            args: vec![&fixture.env, governance.address.into_val(&fixture.env)],
+        },
+    ]
+}
+
+#[test]
+fn test_proposal_lifecycle_executes_treasury_admin_calls() {
+    let fixture = create_fixture_with_data(false);
+    let (governance, vote_token) = setup_governance(&fixture);
+    assert_eq!(fixture.treasury.get_config().admin, governance.address);
+    let samwise = create_staker(&fixture, &governance, &vote_token, 600 * SCALAR_7);
+    let frodo = create_staker(&fixture, &governance, &vote_token, 500 * SCALAR_7);
+    let pippin = create_staker(&fixture, &governance, &vote_token, 50 * SCALAR_7);
+    let description = String::from_str(&fixture.env, "pause the treasury");
+    let calls = pause_treasury_calls(&fixture, &governance);
+
+    assert_eq!(
+        governance.try_propose(&pippin, &description, &calls),
+        Err(Ok(Error::from_contract_error(INSUFFICIENT_BALANCE)))
+    );
+    let proposal_id = governance.propose(&samwise, &description, &calls);
+    assert_eq!(proposal_id, 0);
+
+    governance.vote(&samwise, &proposal_id, &true);
+    governance.vote(&frodo, &proposal_id, &false);
+    assert_eq!(
+        governance.try_vote(&samwise, &proposal_id, &true),
+        Err(Ok(Error::from_contract_error(ALREADY_VOTED)))
+    );
+    let proposal = governance.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.for_votes, 600 * SCALAR_7);
+    assert_eq!(proposal.against_votes, 500 * SCALAR_7);
+    assert_eq!(
+        governance.try_queue(&proposal_id),
+        Err(Ok(Error::from_contract_error(INVALID_PROPOSAL_STATUS)))
+    );
+
+    // votes are closed at the end of the voting period
+    fixture.jump(VOTING_PERIOD);
+    assert_eq!(
+        governance.try_vote(&pippin, &proposal_id, &false),
+        Err(Ok(Error::from_contract_error(INVALID_PROPOSAL_STATUS)))
+    );
+
+    // a passed proposal is executed once the timelock has passed
+    governance.queue(&proposal_id);
+    let proposal = governance.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Queued);
+    assert_eq!(proposal.eta, fixture.env.ledger().timestamp() + TIMELOCK_PERIOD);
+    assert_eq!(
+        governance.try_execute(&proposal_id),
+        Err(Ok(Error::from_contract_error(TIMELOCK)))
+    );
+    fixture.jump(TIMELOCK_PERIOD);
+    assert!(!fixture.treasury.is_paused());
+    governance.execute(&proposal_id);
+    assert!(fixture.treasury.is_paused());
+    assert_eq!(governance.get_proposal(&proposal_id).unwrap().status, ProposalStatus::Executed);
+    assert_eq!(
+        governance.try_execute(&proposal_id),
+        Err(Ok(Error::from_contract_error(INVALID_PROPOSAL_STATUS)))
+    );
+}
+
+#[test]
+fn test_proposal_without_quorum_or_majority_is_defeated() {
+    let fixture = create_fixture_with_data(false);
+    let (governance, vote_token) = setup_governance(&fixture);
+    let samwise = create_staker(&fixture, &governance, &vote_token, 600 * SCALAR_7);
+    let frodo = create_staker(&fixture, &governance, &vote_token, 500 * SCALAR_7);
+    let description = String::from_str(&fixture.env, "pause the treasury");
+    let calls = pause_treasury_calls(&fixture, &governance);
+
+    // 600 votes do not reach the quorum
+    let no_quorum = governance.propose(&samwise, &description, &calls);
+    governance.vote(&samwise, &no_quorum, &true);
+
+    // 1,100 votes reach the quorum, but without a majority
+    let no_majority = governance.propose(&frodo, &description, &calls);
+    governance.vote(&samwise, &no_majority, &false);
+    governance.vote(&frodo, &no_majority, &true);
+
+    fixture.jump(VOTING_PERIOD);
+    assert_eq!(
+        governance.try_queue(&no_quorum),
+        Err(Ok(Error::from_contract_error(PROPOSAL_DEFEATED)))
+    );
+    assert_eq!(
+        governance.try_queue(&no_majority),
+        Err(Ok(Error::from_contract_error(PROPOSAL_DEFEATED)))
+    );
+    assert_eq!(
+        governance.try_execute(&no_majority),
+        Err(Ok(Error::from_contract_error(INVALID_PROPOSAL_STATUS)))
+    );
+    assert!(!fixture.treasury.is_paused());
+}
+
+#[test]
+fn test_stake_is_locked_until_the_vote_ends() {
+    let fixture = create_fixture_with_data(false);
+    let (governance, vote_token) = setup_governance(&fixture);
+    let samwise = create_staker(&fixture, &governance, &vote_token, 600 * SCALAR_7);
+    let description = String::from_str(&fixture.env, "pause the treasury");
+    let proposal_id = governance.propose(&samwise, &description, &pause_treasury_calls(&fixture, &governance));
+
+    // stake that has not voted can be moved
+    governance.unstake(&samwise, &(100 * SCALAR_7));
+    assert_eq!(vote_token.balance(&samwise), 100 * SCALAR_7);
+
+    governance.vote(&samwise, &proposal_id, &true);
+    let vote_end = governance.get_proposal(&proposal_id).unwrap().vote_end;
+    assert_eq!(governance.get_stake(&samwise).locked_until, vote_end);
+    assert_eq!(
+        governance.try_unstake(&samwise, &(100 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(STAKE_LOCKED)))
+    );
+
+    fixture.jump(VOTING_PERIOD);
+    governance.unstake(&samwise, &(500 * SCALAR_7));
+    assert_eq!(vote_token.balance(&samwise), 600 * SCALAR_7);
+    assert_eq!(governance.get_stake(&samwise).amount, 0);
+}
+
+#[test]
+fn test_proposal_updates_the_governance_config() {
+    let fixture = create_fixture_with_data(false);
+    let (governance, vote_token) = setup_governance(&fixture);
+    let samwise = create_staker(&fixture, &governance, &vote_token, 1_000 * SCALAR_7);
+    let mut config = governance.get_config();
+    config.quorum = 2_000 * SCALAR_7;
+    config.timelock = 2 * TIMELOCK_PERIOD;
+
+    // governance can not invoke itself, so a self-call to `set_config` is applied internally
+    let calls = vec![
+        &fixture.env,
+        Call {
+            contract: governance.address.clone(),
+            fn_name: Symbol::new(&fixture.env, "set_config"),
+            args: vec![&fixture.env, config.into_val(&fixture.env)],
+        },
+    ];
+    let proposal_id = governance.propose(&samwise, &String::from_str(&fixture.env, "raise the quorum"), &calls);
+    governance.vote(&samwise, &proposal_id, &true);
+    fixture.jump(VOTING_PERIOD);
+    governance.queue(&proposal_id);
+    fixture.jump(TIMELOCK_PERIOD);
+    governance.execute(&proposal_id);
+
+    let updated = governance.get_config();
+    assert_eq!(updated.quorum, 2_000 * SCALAR_7);
+    assert_eq!(updated.timelock, 2 * TIMELOCK_PERIOD);
+
+    // no other self-call is supported
+    let calls = vec![
+        &fixture.env,
+        Call {
+            contract: governance.address.clone(),
+            fn_name: Symbol::new(&fixture.env, "initialize"),
+            args: vec![&fixture.env, config.into_val(&fixture.env)],
+        },
+    ];
+    let frodo = create_staker(&fixture, &governance, &vote_token, 1_000 * SCALAR_7);
+    let proposal_id = governance.propose(&samwise, &String::from_str(&fixture.env, "initialize"), &calls);
+    governance.vote(&samwise, &proposal_id, &true);
+    governance.vote(&frodo, &proposal_id, &true);
+    fixture.jump(VOTING_PERIOD);
+    governance.queue(&proposal_id);
+    fixture.jump(2 * TIMELOCK_PERIOD);
+    assert_eq!(
+        governance.try_execute(&proposal_id),
+        Err(Ok(Error::from_contract_error(INVALID_PROPOSAL_STATUS)))
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(test)]
+use soroban_sdk::{vec, Bytes, Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::FlashMintConfig,
+    hook::create_mock_hook,
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const TOO_MANY_HOOKS: u32 = 531;
+const MAX_HOOKS: usize = 5;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_hooks_run_before_and_after_flash_mints() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fee
+    let (hook, hook_client) = create_mock_hook(&fixture.env);
+    treasury.set_hooks(&vec![&fixture.env, hook.clone()]);
+    assert_eq!(treasury.get_hooks(), vec![&fixture.env, hook]);
+
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(hook_client.calls(), 2);
+}
+
+#[test]
+fn test_failing_hook_does_not_block_operations() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (broken, broken_client) = create_mock_hook(&fixture.env);
+    let (healthy, healthy_client) = create_mock_hook(&fixture.env);
+    broken_client.set_fail(&true);
+    treasury.set_hooks(&vec![&fixture.env, broken, healthy]);
+    let usd = Symbol::new(&fixture.env, "USD");
+    let supply_before = treasury.get_currency_supply(&usd);
+
+    treasury.increase_supply(&fixture.admin, &ousd.address, &AMOUNT);
+    assert_eq!(treasury.get_currency_supply(&usd), supply_before + AMOUNT);
+    assert_eq!(broken_client.calls(), 0);
+    assert_eq!(healthy_client.calls(), 2);
+}
+
+#[test]
+fn test_hook_count_is_capped() {
+    let fixture = create_fixture_with_data(false);
+    let mut hooks = vec![&fixture.env];
+    for _ in 0..=MAX_HOOKS {
+        hooks.push_back(create_mock_hook(&fixture.env).0);
+    }
+
+    assert_eq!(
+        fixture.treasury.try_set_hooks(&hooks),
+        Err(Ok(Error::from_contract_error(TOO_MANY_HOOKS)))
+    );
+    hooks.pop_back();
+    fixture.treasury.set_hooks(&hooks);
+    assert_eq!(fixture.treasury.get_hooks().len() as usize, MAX_HOOKS);
+}
@@ -0,0 +1,71 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{insurance_fund::create_insurance_fund, treasury::{FlashMintConfig, InsuranceConfig}},
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INSUFFICIENT_BALANCE: u32 = 1804;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_fees_are_split_with_the_insurance_fund() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (fund, fund_client) = create_insurance_fund(&fixture.env, &fixture.admin, &treasury.address, &ousd.address);
+    treasury.set_insurance_fund(&InsuranceConfig { fund: fund.clone(), token: ousd.address.clone(), split_bps: 2_000 });
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fee
+    let surplus_before = treasury.get_surplus(&ousd.address);
+
+    treasury.flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+
+    let fee = AMOUNT * 10 / 10_000;
+    assert_eq!(fund_client.get_balance(), fee / 5);
+    assert_eq!(treasury.get_surplus(&ousd.address) - surplus_before, fee - fee / 5);
+}
+
+#[test]
+fn test_bad_debt_is_covered_up_to_the_fund_balance() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let (fund, fund_client) = create_insurance_fund(&fixture.env, &fixture.admin, &treasury.address, &ousd.address);
+    treasury.set_insurance_fund(&InsuranceConfig { fund, token: ousd.address.clone(), split_bps: 0 });
+    let frodo = &fixture.users[0];
+    ousd.mint(frodo, &(100 * SCALAR_7));
+    fund_client.deposit(frodo, &(100 * SCALAR_7));
+    let surplus = treasury.get_surplus(&ousd.address);
+
+    let bad_debt = treasury.record_bad_debt(&ousd.address, &(surplus + 60 * SCALAR_7));
+    assert_eq!(bad_debt.outstanding, 0);
+    assert_eq!(fund_client.get_balance(), 40 * SCALAR_7);
+    let bad_debt = treasury.record_bad_debt(&ousd.address, &(60 * SCALAR_7));
+    assert_eq!(bad_debt.outstanding, 20 * SCALAR_7);
+    assert_eq!(bad_debt.covered, surplus + 100 * SCALAR_7);
+    assert_eq!(fund_client.get_balance(), 0);
+    assert_eq!(fund_client.get_covered(), 100 * SCALAR_7);
+    assert_eq!(treasury.get_surplus(&ousd.address), 0);
+}
+
+#[test]
+fn test_withdrawals_are_limited_to_the_fund_balance() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (_, fund_client) = create_insurance_fund(&fixture.env, &fixture.admin, &fixture.treasury.address, &ousd.address);
+    let frodo = &fixture.users[0];
+    let governance = Address::generate(&fixture.env);
+    ousd.mint(frodo, &(10 * SCALAR_7));
+    fund_client.deposit(frodo, &(10 * SCALAR_7));
+
+    assert_eq!(
+        fund_client.try_withdraw(&governance, &(11 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(INSUFFICIENT_BALANCE)))
+    );
+    fund_client.withdraw(&governance, &(10 * SCALAR_7));
+    assert_eq!(ousd.balance(&governance), 10 * SCALAR_7);
+}
@@ -0,0 +1,85 @@
+#![cfg(test)]
+use sep_40_oracle::testutils::Asset;
+use soroban_sdk::{vec, Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{
+        oracle::create_mock_oracle,
+        treasury::{LeverageConfig, OracleConfig},
+    },
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const LEVERAGE_DISABLED: u32 = 541;
+const LTV_EXCEEDED: u32 = 542;
+
+/// A fixture with an oracle pricing XLM and OUSD and 10k XLM posted as treasury collateral
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = create_fixture_with_data(false);
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pool = fixture.pools[0].pool.address.clone();
+    let (oracle_id, oracle) = create_mock_oracle(&fixture.env);
+    oracle.set_data(
+        &fixture.admin,
+        &Asset::Other(Symbol::new(&fixture.env, "USD")),
+        &vec![&fixture.env, Asset::Stellar(xlm.address.clone()), Asset::Stellar(ousd.address.clone())],
+        &7,
+        &300,
+    );
+    oracle.set_price_stable(&vec![&fixture.env, 0_1000000, 1_0000000]);
+    fixture.treasury.set_oracle(&OracleConfig { oracle: oracle_id, max_age: 86400 });
+
+    xlm.mint(&fixture.treasury.address, &(10_000 * SCALAR_7));
+    fixture.treasury.add_pool(&xlm.address, &pool);
+    fixture.treasury.post_collateral(&pool, &xlm.address, &(10_000 * SCALAR_7));
+    fixture
+}
+
+#[test]
+fn test_borrow_within_max_ltv() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pool = fixture.pools[0].pool.address.clone();
+    let treasury = &fixture.treasury;
+    assert_eq!(
+        treasury.try_borrow(&pool, &ousd.address, &(100 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(LEVERAGE_DISABLED)))
+    );
+
+    treasury.set_leverage(&LeverageConfig { enabled: true, max_ltv_bps: 3_000 });
+    let balance = ousd.balance(&treasury.address);
+    treasury.borrow(&pool, &ousd.address, &(200 * SCALAR_7));
+    assert_eq!(ousd.balance(&treasury.address) - balance, 200 * SCALAR_7);
+    // 200 OUSD borrowed against 1k USD of XLM
+    let ltv = treasury.get_ltv(&pool);
+    assert!(ltv >= 2_000 && ltv <= 2_001);
+
+    assert_eq!(
+        treasury.try_borrow(&pool, &ousd.address, &(200 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(LTV_EXCEEDED)))
+    );
+}
+
+#[test]
+fn test_repay_clears_liabilities() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pool = fixture.pools[0].pool.address.clone();
+    let treasury = &fixture.treasury;
+    treasury.set_leverage(&LeverageConfig { enabled: true, max_ltv_bps: 3_000 });
+    treasury.borrow(&pool, &ousd.address, &(200 * SCALAR_7));
+    ousd.mint(&treasury.address, &SCALAR_7); // cover accrued interest
+    fixture.jump_with_sequence(60);
+
+    let repaid = treasury.repay(&fixture.admin, &pool, &ousd.address, &(1_000 * SCALAR_7));
+    assert!(repaid >= 200 * SCALAR_7 && repaid < 201 * SCALAR_7);
+    assert_eq!(treasury.get_ltv(&pool), 0);
+
+    // disabling leverage stops further borrowing
+    treasury.set_leverage(&LeverageConfig { enabled: false, max_ltv_bps: 3_000 });
+    assert_eq!(
+        treasury.try_borrow(&pool, &ousd.address, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(LEVERAGE_DISABLED)))
+    );
+}
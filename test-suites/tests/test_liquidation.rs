@@ -2,9 +2,10 @@
 use cast::i128;
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{
-    log, testutils::{Address as AddressTestTrait, Events, Logs}, vec, Address, Error, IntoVal, Symbol, Val, Vec
+    log, testutils::{Address as AddressTestTrait, Events, Logs}, vec, xdr::ToXdr, Address, Error, IntoVal, Symbol, Val, Vec
 };
 use test_suites::{
+    dependencies::pegkeeper::{AuctionOp, FlashLoanOp},
     dependencies::pool::{Request, RequestType, Positions, PoolDataKey, ReserveConfig, ReserveData},
     assertions::assert_approx_eq_abs,
     create_fixture_with_data,
@@ -143,9 +144,21 @@ fn test_liquidations_real() {
     //allow 250 blocks to pass
     fixture.jump_with_sequence(251 * 5);
 
-    let pair = &fixture.pairs[0];
-
-    treasury.keep_peg(&pair.address.clone(), &henk, &fixture.tokens[TokenIndex::OUSD].address.clone(), &ousd_bid_amount, &fixture.tokens[TokenIndex::XLM].address.clone(), &xlm_lot_amount,  &(100 as i128));
+    let data = FlashLoanOp::Auction(AuctionOp {
+        blend_pool: pool_fixture.pool.address.clone(),
+        auction_creator: henk.clone(),
+        liq_amount: 100,
+        lot_token: fixture.tokens[TokenIndex::XLM].address.clone(),
+        lot_amount: xlm_lot_amount,
+        path: vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::XLM].address.clone(),
+            fixture.tokens[TokenIndex::OUSD].address.clone(),
+        ],
+        min_out: 0,
+    })
+    .to_xdr(&fixture.env);
+    treasury.keep_peg(&fixture.admin, &pegkeeper.address.clone(), &fixture.tokens[TokenIndex::OUSD].address.clone(), &ousd_bid_amount, &data, &None);
 
     std::println!("OUSD Balance: {}", fixture.tokens[TokenIndex::OUSD].balance(&pegkeeper.address.clone()) / SCALAR_7);
     std::println!("XLM Balance: {}", fixture.tokens[TokenIndex::XLM].balance(&pegkeeper.address.clone()));
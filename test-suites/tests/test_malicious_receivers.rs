@@ -0,0 +1,106 @@
+#![cfg(test)]
+use soroban_sdk::{Address, Bytes, Error, String};
+use test_suites::{
+    dependencies::treasury::{FlashLoanConfig, FlashMintConfig, Role},
+    fixture_builder::{ousd_config, FixtureBuilder},
+    receiver::{create_malicious_receiver, create_mock_receiver, Attack},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const FLASHLOAN_NOT_REPAID: u32 = 510;
+const REENTRANCY_ERROR: u32 = 514;
+const INVALID_RECEIVER: u32 = 547;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+/// A fixture with OUSD deployed and flash minting enabled without a fee
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = FixtureBuilder::new()
+        .with_pool("Teapot", 0_1000000, 4)
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_stablecoin(0)
+        .build();
+    fixture.treasury.set_flash_mint(
+        &fixture.tokens[TokenIndex::OUSD].address,
+        &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 0 },
+    );
+    fixture
+}
+
+#[test]
+fn test_receiver_repays_too_little() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (receiver, receiver_client) = create_mock_receiver(&fixture.env, &fixture.treasury.address);
+    receiver_client.set_shortfall(&1);
+
+    let result = fixture.treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID))));
+    assert_eq!(ousd.balance(&receiver), 0);
+}
+
+#[test]
+fn test_receiver_holds_funds() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (receiver, _) = create_malicious_receiver(&fixture.env, &fixture.treasury.address, Attack::Hold);
+
+    let result = fixture.treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID))));
+    assert_eq!(ousd.balance(&receiver), 0);
+}
+
+#[test]
+fn test_receiver_holds_funds_of_whitelisted_flash_loan() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (receiver, _) = create_malicious_receiver(&fixture.env, &fixture.treasury.address, Attack::Hold);
+    fixture.treasury.add_flash_loaner(&receiver, &FlashLoanConfig { max_amount: AMOUNT, fee_bps: 0, enabled: true });
+    fixture.treasury.grant_role(&Role::Keeper, &receiver);
+
+    let result = fixture.treasury.try_keep_peg(&receiver, &receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID))));
+    assert_eq!(ousd.balance(&receiver), 0);
+}
+
+#[test]
+fn test_account_receiver_is_rejected() {
+    let fixture = setup();
+    let account = Address::from_string(&String::from_str(&fixture.env, "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"));
+
+    let result = fixture.treasury.try_add_flash_loaner(&account, &FlashLoanConfig { max_amount: AMOUNT, fee_bps: 0, enabled: true });
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_RECEIVER))));
+}
+
+#[test]
+fn test_receiver_repays_without_allowance() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (receiver, _) = create_malicious_receiver(&fixture.env, &fixture.treasury.address, Attack::Transfer);
+    fixture.tokens[TokenIndex::OUSD].mint(&receiver, &AMOUNT);
+
+    let result = fixture.treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FLASHLOAN_NOT_REPAID))));
+    assert_eq!(ousd.balance(&receiver), AMOUNT);
+}
+
+#[test]
+fn test_receiver_reenters_flash_mint() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (receiver, _) = create_malicious_receiver(&fixture.env, &fixture.treasury.address, Attack::Reenter);
+
+    let result = fixture.treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(REENTRANCY_ERROR))));
+    assert_eq!(ousd.balance(&receiver), 0);
+}
+
+#[test]
+fn test_receiver_decreases_supply() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (receiver, _) = create_malicious_receiver(&fixture.env, &fixture.treasury.address, Attack::DecreaseSupply);
+
+    let result = fixture.treasury.try_flash_mint(&receiver, &ousd.address, &AMOUNT, &Bytes::new(&fixture.env), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(REENTRANCY_ERROR))));
+    assert_eq!(ousd.balance(&receiver), 0);
+}
@@ -0,0 +1,66 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const MINT_CAP_EXCEEDED: u32 = 516;
+
+#[test]
+fn test_minter_daily_cap_resets_each_day() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let bridge = Address::generate(&fixture.env);
+    let user = Address::generate(&fixture.env);
+    treasury.set_minter(&bridge, &ousd.address, &(100 * SCALAR_7), &(1_000 * SCALAR_7));
+    assert_eq!(treasury.get_mint_allowance(&bridge), 100 * SCALAR_7);
+
+    treasury.mint(&bridge, &user, &(60 * SCALAR_7));
+    assert_eq!(treasury.get_mint_allowance(&bridge), 40 * SCALAR_7);
+    assert_eq!(
+        treasury.try_mint(&bridge, &user, &(41 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(MINT_CAP_EXCEEDED)))
+    );
+
+    // burning frees the total cap, but not the quota already minted today
+    ousd.transfer(&user, &bridge, &(60 * SCALAR_7));
+    treasury.burn(&bridge, &(60 * SCALAR_7));
+    assert_eq!(treasury.get_mint_allowance(&bridge), 40 * SCALAR_7);
+
+    fixture.jump(86400);
+    assert_eq!(treasury.get_mint_allowance(&bridge), 100 * SCALAR_7);
+    treasury.mint(&bridge, &user, &(100 * SCALAR_7));
+    let config = treasury.get_minter(&bridge).unwrap();
+    assert_eq!(config.minted, 100 * SCALAR_7);
+    assert_eq!(config.minted_today, 100 * SCALAR_7);
+}
+
+#[test]
+fn test_minter_total_cap_limits_outstanding() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let psm = Address::generate(&fixture.env);
+    let user = Address::generate(&fixture.env);
+    treasury.set_minter(&psm, &ousd.address, &(100 * SCALAR_7), &(150 * SCALAR_7));
+
+    treasury.mint(&psm, &user, &(100 * SCALAR_7));
+    fixture.jump(86400);
+    assert_eq!(treasury.get_mint_allowance(&psm), 50 * SCALAR_7);
+    assert_eq!(
+        treasury.try_mint(&psm, &user, &(51 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(MINT_CAP_EXCEEDED)))
+    );
+
+    // updating the caps keeps the outstanding amount
+    treasury.set_minter(&psm, &ousd.address, &(100 * SCALAR_7), &(200 * SCALAR_7));
+    assert_eq!(treasury.get_minter(&psm).unwrap().minted, 100 * SCALAR_7);
+    treasury.mint(&psm, &user, &(100 * SCALAR_7));
+    assert_eq!(treasury.get_mint_allowance(&psm), 0);
+
+    treasury.remove_minter(&psm);
+    assert_eq!(treasury.get_mint_allowance(&psm), 0);
+    assert!(treasury.try_mint(&psm, &user, &1).is_err());
+}
@@ -0,0 +1,101 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    fixture_builder::{ousd_config, xlm_config, FixtureBuilder},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const ALREADY_INITIALIZED: u32 = 502;
+const BALANCE_ERROR: u32 = 505;
+const POOL_NOT_FOUND: u32 = 519;
+
+/// A fixture with the treasury's OUSD supplied to a first pool and a second pool with an OUSD reserve
+fn setup<'a>() -> TestFixture<'a> {
+    FixtureBuilder::new()
+        .with_whale()
+        .with_pool("Teapot", 0_1000000, 6)
+        .with_reserve(0, TokenIndex::XLM, xlm_config())
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_pool("Kettle", 0_1000000, 6)
+        .with_reserve(1, TokenIndex::OUSD, ousd_config())
+        .with_treasury_supply(1_000_000 * SCALAR_7)
+        .build()
+}
+
+#[test]
+fn test_rebalance_between_pools() {
+    let fixture = setup();
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let teapot = fixture.pools[0].pool.address.clone();
+    let kettle = fixture.pools[1].pool.address.clone();
+
+    assert_eq!(
+        treasury.try_get_pool_position(&ousd.address, &kettle),
+        Err(Ok(Error::from_contract_error(POOL_NOT_FOUND)))
+    );
+    treasury.add_pool(&ousd.address, &kettle);
+    assert_eq!(
+        treasury.try_add_pool(&ousd.address, &kettle),
+        Err(Ok(Error::from_contract_error(ALREADY_INITIALIZED)))
+    );
+    assert_eq!(treasury.get_pools(&ousd.address).len(), 2);
+    assert_approx_eq_abs(treasury.get_pool_position(&ousd.address, &teapot).supplied, 1_000_000 * SCALAR_7, 2);
+    assert_eq!(treasury.get_pool_position(&ousd.address, &kettle).supplied, 0);
+
+    treasury.rebalance(&fixture.admin, &ousd.address, &teapot, &kettle, &(400_000 * SCALAR_7));
+    assert_eq!(ousd.balance(&teapot), 600_000 * SCALAR_7);
+    assert_eq!(ousd.balance(&kettle), 400_000 * SCALAR_7);
+    let teapot_position = treasury.get_pool_position(&ousd.address, &teapot);
+    let kettle_position = treasury.get_pool_position(&ousd.address, &kettle);
+    assert_approx_eq_abs(teapot_position.supplied, 600_000 * SCALAR_7, 2);
+    assert_approx_eq_abs(kettle_position.supplied, 400_000 * SCALAR_7, 2);
+    assert!(kettle_position.b_tokens > 0);
+    assert_eq!(kettle_position.d_tokens, 0);
+    assert_approx_eq_abs(treasury.get_total_position(&ousd.address).supplied, 1_000_000 * SCALAR_7, 4);
+
+    // the supply moved does not change the outstanding supply
+    assert_eq!(treasury.get_backing_report(&ousd.address).total_supply, 1_000_000 * SCALAR_7);
+    fixture.assert_invariants();
+
+    let unregistered = Address::generate(&fixture.env);
+    assert_eq!(
+        treasury.try_rebalance(&fixture.admin, &ousd.address, &teapot, &unregistered, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(POOL_NOT_FOUND)))
+    );
+    assert_eq!(
+        treasury.try_rebalance(&fixture.admin, &ousd.address, &kettle, &teapot, &(400_001 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(BALANCE_ERROR)))
+    );
+}
+
+#[test]
+fn test_new_supply_is_issued_to_the_primary_pool() {
+    let fixture = setup();
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let teapot = fixture.pools[0].pool.address.clone();
+    let kettle = fixture.pools[1].pool.address.clone();
+
+    assert_eq!(
+        treasury.try_set_primary_pool(&ousd.address, &kettle),
+        Err(Ok(Error::from_contract_error(POOL_NOT_FOUND)))
+    );
+    treasury.add_pool(&ousd.address, &kettle);
+    treasury.set_primary_pool(&ousd.address, &kettle);
+    assert_eq!(treasury.get_pool_reserve_data(&ousd.address).blend_pool, kettle);
+
+    treasury.increase_supply(&fixture.admin, &ousd.address, &(100_000 * SCALAR_7));
+    assert_eq!(ousd.balance(&teapot), 1_000_000 * SCALAR_7);
+    assert_eq!(ousd.balance(&kettle), 100_000 * SCALAR_7);
+    assert_approx_eq_abs(treasury.get_pool_position(&ousd.address, &kettle).supplied, 100_000 * SCALAR_7, 2);
+    assert_approx_eq_abs(treasury.get_total_position(&ousd.address).supplied, 1_100_000 * SCALAR_7, 4);
+    assert_eq!(treasury.get_backing_report(&ousd.address).total_supply, 1_100_000 * SCALAR_7);
+
+    // the supply is decreased from the primary pool too
+    treasury.decrease_supply(&fixture.admin, &ousd.address, &(50_000 * SCALAR_7));
+    assert_eq!(ousd.balance(&teapot), 1_000_000 * SCALAR_7);
+    assert_eq!(ousd.balance(&kettle), 50_000 * SCALAR_7);
+    fixture.assert_invariants();
+}
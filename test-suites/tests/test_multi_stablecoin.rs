@@ -0,0 +1,209 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, xdr::ToXdr, Address, Bytes, Error, Symbol};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    create_fixture_with_stablecoins,
+    dependencies::{
+        pegkeeper::{FlashLoanOp, FxConfig, PegConfig, Strategy, StrategyOp},
+        pool::{Request, RequestType},
+        treasury::Asset,
+    },
+    fixture_builder::{oeur_config, ousd_config, xlm_config, FixtureBuilder},
+    strategy::create_mock_strategy,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const OUSD_SUPPLY: i128 = 1_000_000 * SCALAR_7;
+const OEUR_SUPPLY: i128 = 500_000 * SCALAR_7;
+
+const SLIPPAGE_EXCEEDED: u32 = 518;
+
+/// Borrow all the oEUR in the pool but `left`, and some OUSD, returning the borrower
+fn borrow_both(fixture: &TestFixture, left: i128) -> Address {
+    let pool = &fixture.pools[0].pool;
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+    let borrower = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&borrower, &(20_000_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 20_000_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: oeur.address.clone(),
+            amount: oeur.balance(&pool.address) - left,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::OUSD].address.clone(),
+            amount: 100_000 * SCALAR_7,
+        },
+    ];
+    pool.submit(&borrower, &borrower, &borrower, &requests);
+    borrower
+}
+
+#[test]
+fn test_stablecoins_share_the_pool_with_separate_supplies() {
+    let fixture = create_fixture_with_stablecoins();
+    let treasury = &fixture.treasury;
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+
+    assert_eq!(treasury.get_pools(&ousd.address), vec![&fixture.env, pool.address.clone()]);
+    assert_eq!(treasury.get_pools(&oeur.address), vec![&fixture.env, pool.address.clone()]);
+    assert_eq!(ousd.balance(&pool.address), OUSD_SUPPLY);
+    assert_eq!(oeur.balance(&pool.address), OEUR_SUPPLY);
+    assert_eq!(treasury.get_price(&ousd.address), SCALAR_7);
+    assert_eq!(treasury.get_price(&oeur.address), 1_0800000);
+
+    // supplying one stablecoin leaves the other untouched
+    let ousd_summary = treasury.get_position_summary(&ousd.address);
+    treasury.increase_supply(&fixture.admin, &oeur.address, &(100_000 * SCALAR_7));
+    assert_eq!(oeur.balance(&pool.address), OEUR_SUPPLY + 100_000 * SCALAR_7);
+    assert_eq!(treasury.get_position_summary(&ousd.address).supplied, ousd_summary.supplied);
+    assert_eq!(ousd.balance(&pool.address), OUSD_SUPPLY);
+    fixture.assert_invariants();
+}
+
+#[test]
+fn test_withdrawal_queues_are_kept_per_stablecoin() {
+    let fixture = create_fixture_with_stablecoins();
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+    borrow_both(&fixture, 1_000 * SCALAR_7);
+    let supply_of = |token: &Address| treasury.get_supply_at(token, &fixture.env.ledger().sequence());
+    let ousd_supply = supply_of(&ousd.address);
+    let oeur_supply = supply_of(&oeur.address);
+
+    // oEUR is drained, so most of its decrease is queued
+    treasury.decrease_supply(&fixture.admin, &oeur.address, &(10_000 * SCALAR_7));
+    let oeur_burned = oeur_supply - supply_of(&oeur.address);
+    assert!(oeur_burned <= 1_000 * SCALAR_7);
+    assert_eq!(treasury.get_withdrawal_queue(&oeur.address).queued, 10_000 * SCALAR_7 - oeur_burned);
+
+    // OUSD still has liquidity, so its decrease is not queued behind oEUR's
+    treasury.decrease_supply(&fixture.admin, &ousd.address, &(10_000 * SCALAR_7));
+    assert_eq!(supply_of(&ousd.address), ousd_supply - 10_000 * SCALAR_7);
+    assert_eq!(treasury.get_withdrawal_queue(&ousd.address).queued, 0);
+    fixture.assert_invariants();
+}
+
+/// The pegkeeper defends the peg of a single stablecoin, but its strategies can flash loan either
+#[test]
+fn test_pegkeeper_strategies_flash_loan_either_stablecoin() {
+    let fixture = create_fixture_with_stablecoins();
+    let treasury = &fixture.treasury;
+    let pegkeeper = &fixture.pegkeeper;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    strategy_client.set_profit(&(10 * SCALAR_7));
+    ousd.mint(&strategy, &(10 * SCALAR_7));
+    oeur.mint(&strategy, &(10 * SCALAR_7));
+    pegkeeper.register_strategy(&1, &strategy);
+    treasury.set_fee_bps(&fixture.admin, &10);
+    let amount = 10_000 * SCALAR_7;
+    let fee = treasury.quote_repayment(&amount) - amount;
+
+    let data = FlashLoanOp::Strategy(StrategyOp { id: 1, params: Bytes::new(&fixture.env) }).to_xdr(&fixture.env);
+    treasury.keep_peg(&fixture.admin, &pegkeeper.address, &ousd.address, &amount, &data, &None);
+    assert_eq!(treasury.get_surplus(&ousd.address), fee);
+    assert_eq!(treasury.get_surplus(&oeur.address), 0);
+
+    treasury.keep_peg(&fixture.admin, &pegkeeper.address, &oeur.address, &amount, &data, &None);
+    assert_eq!(treasury.get_surplus(&ousd.address), fee);
+    assert_eq!(treasury.get_surplus(&oeur.address), fee);
+    assert_eq!(ousd.balance(&pegkeeper.address), 10 * SCALAR_7 - fee);
+    assert_eq!(oeur.balance(&pegkeeper.address), 10 * SCALAR_7 - fee);
+    fixture.assert_invariants();
+}
+
+#[test]
+fn test_currency_supplies_follow_each_stablecoin() {
+    let fixture = create_fixture_with_stablecoins();
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+    let usd = Symbol::new(&fixture.env, "USD");
+    let eur = Symbol::new(&fixture.env, "EUR");
+
+    assert_eq!(treasury.get_currency(&ousd.address), usd);
+    assert_eq!(treasury.get_currency(&oeur.address), eur);
+    assert_eq!(treasury.get_currency_supply(&usd), OUSD_SUPPLY);
+    assert_eq!(treasury.get_currency_supply(&eur), OEUR_SUPPLY);
+
+    treasury.increase_supply(&fixture.admin, &oeur.address, &(100_000 * SCALAR_7));
+    assert_eq!(treasury.get_currency_supply(&usd), OUSD_SUPPLY);
+    assert_eq!(treasury.get_currency_supply(&eur), OEUR_SUPPLY + 100_000 * SCALAR_7);
+    treasury.decrease_supply(&fixture.admin, &ousd.address, &(10_000 * SCALAR_7));
+    assert_eq!(treasury.get_currency_supply(&usd), OUSD_SUPPLY - 10_000 * SCALAR_7);
+    assert_eq!(treasury.get_currency_supply(&eur), OEUR_SUPPLY + 100_000 * SCALAR_7);
+}
+
+#[test]
+fn test_oeur_is_valued_at_the_eur_rate() {
+    let fixture = create_fixture_with_stablecoins();
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+
+    assert_eq!(treasury.get_peg_price(&ousd.address), SCALAR_7);
+    assert_eq!(treasury.get_peg_price(&oeur.address), 1_0800000);
+
+    // redeeming oEUR pays out 1.08 USD of collateral per oEUR
+    let xlm = fixture.tokens[TokenIndex::XLM].address.clone();
+    let samwise = Address::generate(&fixture.env);
+    oeur.mint(&samwise, &(100 * SCALAR_7));
+    treasury.add_collateral(&xlm, &Asset::Stellar(xlm.clone()), &7_500, &500, &(100_000 * SCALAR_7));
+    let result = treasury.try_redeem(&samwise, &oeur.address, &xlm, &(100 * SCALAR_7), &(1_080 * SCALAR_7 + 1), &None);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(SLIPPAGE_EXCEEDED))));
+}
+
+#[test]
+fn test_pegkeeper_defends_the_eur_peg_of_oeur() {
+    let fixture = FixtureBuilder::new()
+        .with_whale()
+        .with_pool("Teapot", 0_9999999, 6)
+        .with_reserve(0, TokenIndex::XLM, xlm_config())
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_reserve(0, TokenIndex::OEUR, oeur_config())
+        .with_treasury_supply(OUSD_SUPPLY)
+        .with_oeur_supply(OEUR_SUPPLY)
+        .with_pair(TokenIndex::OEUR, TokenIndex::USDC, 1_000_000 * SCALAR_7, 1_080_000 * SCALAR_7)
+        .build();
+    let pegkeeper = &fixture.pegkeeper;
+    let oeur = &fixture.tokens[TokenIndex::OEUR];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    pegkeeper.set_peg_config(&PegConfig { stablecoin: oeur.address.clone(), threshold_bps: 100 });
+    pegkeeper.set_collaterals(&Strategy::Peg, &vec![&fixture.env, usdc.address.clone()]);
+    let eur = Symbol::new(&fixture.env, "EUR");
+    let fx = FxConfig { oracle: fixture.price_oracle.address.clone(), currency: eur, max_age: 86400 };
+    pegkeeper.set_fx_config(&oeur.address, &Some(fx));
+
+    // oEUR trades at 1.08 USDC, on its EUR peg
+    assert_eq!(pegkeeper.get_peg_target(&oeur.address), 1_0800000);
+    assert_eq!(fixture.peg_price(TokenIndex::OEUR), 1_0800000);
+    assert!(pegkeeper.check_peg(&usdc.address).abs() <= 1);
+
+    // selling pressure drives oEUR 5% below its EUR peg, though still above $1
+    fixture.depeg(TokenIndex::OEUR, -500);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OEUR), 1_0260000, 10);
+    assert_approx_eq_abs(pegkeeper.check_peg(&usdc.address), -500, 1);
+
+    // the pegkeeper buys oEUR back with its USDC until the pair is at the EUR rate again
+    usdc.mint(&pegkeeper.address, &(100_000 * SCALAR_7));
+    let deviation = pegkeeper.maybe_rebalance(&usdc.address);
+    assert_approx_eq_abs(deviation, -500, 1);
+    assert_approx_eq_abs(fixture.peg_price(TokenIndex::OEUR), 1_0800000, 0_0010000);
+    assert!(pegkeeper.check_peg(&usdc.address).abs() <= 10);
+    assert!(oeur.balance(&pegkeeper.address) > 0);
+    let (_, contraction_profit) = pegkeeper.get_peg_profits();
+    assert!(contraction_profit > 0);
+    fixture.assert_invariants();
+}
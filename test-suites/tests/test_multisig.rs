@@ -0,0 +1,113 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as AddressTestTrait, AuthorizedFunction},
+    vec, Address, Error, Symbol,
+};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{AdminOp, MultisigConfig},
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const MULTISIG_REQUIRED: u32 = 533;
+const NOT_SIGNER: u32 = 534;
+const PROPOSAL_NOT_FOUND: u32 = 535;
+const THRESHOLD_NOT_MET: u32 = 536;
+const INVALID_MULTISIG: u32 = 537;
+
+#[test]
+fn test_proposal_executes_once_the_threshold_approves() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let signers = [Address::generate(&fixture.env), Address::generate(&fixture.env), Address::generate(&fixture.env)];
+    let minter = Address::generate(&fixture.env);
+    treasury.set_multisig(&MultisigConfig {
+        signers: vec![&fixture.env, signers[0].clone(), signers[1].clone(), signers[2].clone()],
+        threshold: 2,
+    });
+
+    // the admin can no longer change parameters directly
+    assert_eq!(
+        treasury.try_set_minter(&minter, &token, &(50 * SCALAR_7), &(50 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(MULTISIG_REQUIRED)))
+    );
+
+    let id = treasury.propose(&signers[0], &vec![&fixture.env, AdminOp::SetMinter(minter.clone(), token.clone(), 50 * SCALAR_7, 50 * SCALAR_7)]);
+    assert_eq!(treasury.try_execute(&id), Err(Ok(Error::from_contract_error(THRESHOLD_NOT_MET))));
+
+    treasury.approve(&signers[2], &id);
+    treasury.execute(&id);
+    assert_eq!(treasury.get_minter(&minter).unwrap().total_cap, 50 * SCALAR_7);
+    assert!(treasury.get_proposal(&id).unwrap().executed);
+    assert_eq!(treasury.try_execute(&id), Err(Ok(Error::from_contract_error(PROPOSAL_NOT_FOUND))));
+}
+
+#[test]
+fn test_only_signers_propose_and_approve() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let signer = Address::generate(&fixture.env);
+    let frodo = &fixture.users[0];
+    treasury.set_multisig(&MultisigConfig { signers: vec![&fixture.env, signer.clone()], threshold: 1 });
+
+    assert_eq!(
+        treasury.try_propose(frodo, &vec![&fixture.env, AdminOp::SetFeeBps(30)]),
+        Err(Ok(Error::from_contract_error(NOT_SIGNER)))
+    );
+    let id = treasury.propose(&signer, &vec![&fixture.env, AdminOp::SetFeeBps(30)]);
+    assert_eq!(treasury.try_approve(frodo, &id), Err(Ok(Error::from_contract_error(NOT_SIGNER))));
+}
+
+#[test]
+fn test_multisig_can_be_disabled_by_proposal() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let signer = Address::generate(&fixture.env);
+    let minter = Address::generate(&fixture.env);
+    treasury.set_multisig(&MultisigConfig { signers: vec![&fixture.env, signer.clone()], threshold: 1 });
+
+    let id = treasury.propose(&signer, &vec![&fixture.env, AdminOp::DisableMultisig]);
+    treasury.execute(&id);
+    assert!(treasury.get_multisig().is_none());
+    // back in single admin mode
+    treasury.set_minter(&minter, &token, &(50 * SCALAR_7), &(50 * SCALAR_7));
+}
+
+#[test]
+fn test_set_admin_proposal_requires_the_new_admin() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let signer = Address::generate(&fixture.env);
+    let new_admin = Address::generate(&fixture.env);
+    treasury.set_multisig(&MultisigConfig { signers: vec![&fixture.env, signer.clone()], threshold: 1 });
+
+    let id = treasury.propose(&signer, &vec![&fixture.env, AdminOp::SetAdmin(new_admin.clone())]);
+    treasury.execute(&id);
+    let auths = fixture.env.auths();
+    let (address, invocation) = auths.first().expect("no authorization required");
+    assert_eq!(address, &new_admin);
+    match &invocation.function {
+        AuthorizedFunction::Contract((contract, function, _)) => {
+            assert_eq!(contract, &treasury.address);
+            assert_eq!(function, &Symbol::new(&fixture.env, "execute"));
+        }
+        _ => panic!("unexpected authorized function"),
+    }
+}
+
+#[test]
+fn test_invalid_multisig_configs_are_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let signer = Address::generate(&fixture.env);
+
+    for config in [
+        MultisigConfig { signers: vec![&fixture.env, signer.clone()], threshold: 0 },
+        MultisigConfig { signers: vec![&fixture.env, signer.clone()], threshold: 2 },
+        MultisigConfig { signers: vec![&fixture.env, signer.clone(), signer.clone()], threshold: 1 },
+    ] {
+        assert_eq!(treasury.try_set_multisig(&config), Err(Ok(Error::from_contract_error(INVALID_MULTISIG))));
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(test)]
+use soroban_sdk::Error;
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::BreakerConfig,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const STALE_ORACLE_ERROR: u32 = 515;
+const CIRCUIT_BREAKER_TRIPPED: u32 = 525;
+const DELAY: u32 = 100;
+
+#[test]
+fn test_fixture_oracle_prices_are_manipulable() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let xlm = fixture.tokens[TokenIndex::XLM].address.clone();
+    assert_eq!(treasury.get_price(&ousd), SCALAR_7);
+    assert_eq!(treasury.get_price(&xlm), 0_1000000);
+
+    fixture.set_price(TokenIndex::OUSD, 0_9500000);
+    assert_eq!(treasury.get_price(&ousd), 0_9500000);
+
+    // prices are scaled from the oracle's decimals
+    fixture.price_oracle.set_decimals(&9);
+    fixture.set_price(TokenIndex::XLM, 0_120000000);
+    assert_eq!(treasury.get_price(&xlm), 0_1200000);
+}
+
+#[test]
+fn test_stale_prices_are_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    treasury.set_breaker(&ousd, &Some(BreakerConfig { threshold_bps: 200, delay: DELAY }));
+
+    fixture.set_stale(TokenIndex::OUSD, true);
+    assert_eq!(treasury.try_get_price(&ousd), Err(Ok(Error::from_contract_error(STALE_ORACLE_ERROR))));
+    assert_eq!(treasury.try_poke_breaker(&ousd).map(|_| ()), Err(Ok(Error::from_contract_error(STALE_ORACLE_ERROR))));
+
+    fixture.set_stale(TokenIndex::OUSD, false);
+    assert!(!treasury.poke_breaker(&ousd).tripped);
+}
+
+#[test]
+fn test_manipulated_price_trips_breaker() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    treasury.set_breaker(&ousd, &Some(BreakerConfig { threshold_bps: 200, delay: DELAY }));
+
+    fixture.set_price(TokenIndex::OUSD, 0_9000000);
+    treasury.poke_breaker(&ousd);
+    fixture.jump_with_sequence(u64::from(DELAY + 1) * 5);
+
+    assert_eq!(
+        treasury.try_increase_supply(&fixture.admin, &ousd, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(CIRCUIT_BREAKER_TRIPPED)))
+    );
+}
@@ -0,0 +1,131 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::ousd_savings::create_savings,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INTERNAL_ERROR: u32 = 501;
+const UNAUTHORIZED: u32 = 503;
+const BALANCE_ERROR: u32 = 505;
+const INVALID_AMOUNT: u32 = 1203;
+const INSUFFICIENT_BALANCE: u32 = 1204;
+
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+#[test]
+fn test_buffered_yield_is_released_at_the_savings_rate() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (_, savings, shares) = create_savings(&fixture.env, &fixture.admin, &ousd.address, 1_000);
+
+    let samwise = Address::generate(&fixture.env);
+    ousd.mint(&samwise, &(1_000 * SCALAR_7));
+    assert_eq!(savings.deposit(&samwise, &(1_000 * SCALAR_7)), 1_000 * SCALAR_7);
+    assert_eq!(shares.balance(&samwise), 1_000 * SCALAR_7);
+    assert_eq!(savings.get_exchange_rate(), SCALAR_7);
+
+    // yield is buffered, not added to the shares at once
+    ousd.mint(&fixture.admin, &(30 * SCALAR_7));
+    savings.distribute_yield(&(30 * SCALAR_7));
+    assert_eq!(savings.get_buffer(), 30 * SCALAR_7);
+    assert_eq!(savings.get_exchange_rate(), SCALAR_7);
+
+    // a quarter year at a 10% savings rate releases 2.5% of the assets
+    fixture.jump(SECONDS_PER_YEAR / 4);
+    assert_eq!(savings.get_total_assets(), 1_025 * SCALAR_7);
+    assert_eq!(savings.get_buffer(), 5 * SCALAR_7);
+    assert_eq!(savings.get_exchange_rate(), 1_0250000);
+
+    // the release is capped at the buffer
+    fixture.jump(SECONDS_PER_YEAR / 4);
+    assert_eq!(savings.get_total_assets(), 1_030 * SCALAR_7);
+    assert_eq!(savings.get_buffer(), 0);
+    assert_eq!(savings.get_exchange_rate(), 1_0300000);
+    fixture.jump(SECONDS_PER_YEAR / 4);
+    assert_eq!(savings.get_exchange_rate(), 1_0300000);
+
+    // shares are minted and burnt at the exchange rate
+    let frodo = Address::generate(&fixture.env);
+    ousd.mint(&frodo, &(1_030 * SCALAR_7));
+    assert_eq!(savings.deposit(&frodo, &(1_030 * SCALAR_7)), 1_000 * SCALAR_7);
+    assert_eq!(savings.withdraw(&samwise, &(1_000 * SCALAR_7)), 1_030 * SCALAR_7);
+    assert_eq!(ousd.balance(&samwise), 1_030 * SCALAR_7);
+    assert_eq!(shares.balance(&samwise), 0);
+    assert_eq!(savings.get_total_assets(), 1_030 * SCALAR_7);
+
+    assert_eq!(
+        savings.try_deposit(&frodo, &0),
+        Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+    );
+    assert_eq!(
+        savings.try_withdraw(&frodo, &(1_000 * SCALAR_7 + 1)),
+        Err(Ok(Error::from_contract_error(INSUFFICIENT_BALANCE)))
+    );
+}
+
+#[test]
+fn test_savings_rate_change_accrues_at_the_previous_rate() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (_, savings, _) = create_savings(&fixture.env, &fixture.admin, &ousd.address, 1_000);
+
+    let samwise = Address::generate(&fixture.env);
+    ousd.mint(&samwise, &(1_000 * SCALAR_7));
+    savings.deposit(&samwise, &(1_000 * SCALAR_7));
+    ousd.mint(&fixture.admin, &(100 * SCALAR_7));
+    savings.distribute_yield(&(100 * SCALAR_7));
+
+    fixture.jump(SECONDS_PER_YEAR / 4);
+    savings.set_savings_rate(&2_000);
+    assert_eq!(savings.get_savings_rate(), 2_000);
+    assert_eq!(savings.get_total_assets(), 1_025 * SCALAR_7);
+
+    fixture.jump(SECONDS_PER_YEAR / 4);
+    assert_eq!(savings.get_total_assets(), 1_076_2500000);
+    assert_eq!(savings.get_buffer(), 23_7500000);
+}
+
+#[test]
+fn test_treasury_distributes_surplus_to_the_savings_vault() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let (vault_id, savings, _) = create_savings(&fixture.env, &treasury.address, &ousd.address, 1_000);
+
+    let samwise = Address::generate(&fixture.env);
+    ousd.mint(&samwise, &(1_000 * SCALAR_7));
+    savings.deposit(&samwise, &(1_000 * SCALAR_7));
+    ousd.mint(&fixture.admin, &(100 * SCALAR_7));
+    treasury.deposit_surplus(&fixture.admin, &ousd.address, &(100 * SCALAR_7));
+    let surplus = treasury.get_surplus(&ousd.address);
+
+    assert_eq!(
+        treasury.try_distribute_yield(&fixture.admin, &ousd.address, &(60 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(INTERNAL_ERROR)))
+    );
+    treasury.set_savings_vault(&ousd.address, &vault_id);
+
+    let frodo = Address::generate(&fixture.env);
+    assert_eq!(
+        treasury.try_distribute_yield(&frodo, &ousd.address, &(60 * SCALAR_7)),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+    assert_eq!(
+        treasury.try_distribute_yield(&fixture.admin, &ousd.address, &(surplus + 1)),
+        Err(Ok(Error::from_contract_error(BALANCE_ERROR)))
+    );
+
+    let treasury_balance = ousd.balance(&treasury.address);
+    treasury.distribute_yield(&fixture.admin, &ousd.address, &(60 * SCALAR_7));
+    assert_eq!(treasury.get_surplus(&ousd.address), surplus - 60 * SCALAR_7);
+    assert_eq!(ousd.balance(&treasury.address), treasury_balance - 60 * SCALAR_7);
+    assert_eq!(ousd.balance(&vault_id), 1_060 * SCALAR_7);
+    assert_eq!(savings.get_buffer(), 60 * SCALAR_7);
+
+    // the distributed yield reaches the depositors over time
+    fixture.jump(SECONDS_PER_YEAR);
+    assert_eq!(savings.get_exchange_rate(), 1_0600000);
+    assert_eq!(savings.withdraw(&samwise, &(1_000 * SCALAR_7)), 1_060 * SCALAR_7);
+}
@@ -0,0 +1,77 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Error, String};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::ousd_token::OusdTokenClient,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INSUFFICIENT_ALLOWANCE: u32 = 1705;
+const FROZEN: u32 = 1707;
+const FREEZE_DISABLED: u32 = 1708;
+
+#[test]
+fn test_metadata_and_mint_authority() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let ousd_token = OusdTokenClient::new(&fixture.env, &ousd.address);
+
+    assert_eq!(ousd.decimals(), 7);
+    assert_eq!(ousd.name(), String::from_str(&fixture.env, "Orbit USD"));
+    assert_eq!(ousd.symbol(), String::from_str(&fixture.env, "OUSD"));
+    // the treasury holds the mint authority
+    assert_eq!(ousd_token.admin(), fixture.treasury.address);
+}
+
+#[test]
+fn test_burn_from() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let owner = Address::generate(&fixture.env);
+    let spender = Address::generate(&fixture.env);
+    let ousd_token = OusdTokenClient::new(&fixture.env, &ousd.address);
+    let supply = ousd_token.total_supply();
+    ousd.mint(&owner, &(10 * SCALAR_7));
+    ousd.approve(&owner, &spender, &(4 * SCALAR_7), &(fixture.env.ledger().sequence() + 100));
+
+    ousd.burn_from(&spender, &owner, &(3 * SCALAR_7));
+    assert_eq!(ousd.balance(&owner), 7 * SCALAR_7);
+    assert_eq!(ousd.allowance(&owner, &spender), SCALAR_7);
+    assert_eq!(ousd_token.total_supply(), supply + 7 * SCALAR_7);
+
+    let result = ousd.try_burn_from(&spender, &owner, &(2 * SCALAR_7));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INSUFFICIENT_ALLOWANCE))));
+}
+
+#[test]
+fn test_frozen_account_can_not_move_tokens() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let ousd_token = OusdTokenClient::new(&fixture.env, &ousd.address);
+    let compliance = Address::generate(&fixture.env);
+    let frodo = &fixture.users[0];
+    let samwise = Address::generate(&fixture.env);
+    ousd.mint(frodo, &(10 * SCALAR_7));
+
+    // freezing is disabled until a compliance authority is set
+    let result = ousd_token.try_set_frozen(frodo, &true);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FREEZE_DISABLED))));
+
+    ousd_token.set_compliance(&Some(compliance.clone()));
+    assert_eq!(ousd_token.compliance(), Some(compliance));
+    ousd_token.set_frozen(frodo, &true);
+    assert!(ousd_token.is_frozen(frodo));
+
+    let result = ousd.try_transfer(frodo, &samwise, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FROZEN))));
+    let result = ousd.try_transfer(&samwise, frodo, &0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FROZEN))));
+    let result = ousd.try_burn(frodo, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FROZEN))));
+    let result = ousd.try_mint(frodo, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(FROZEN))));
+
+    ousd_token.set_frozen(frodo, &false);
+    ousd.transfer(frodo, &samwise, &SCALAR_7);
+    assert_eq!(ousd.balance(&samwise), SCALAR_7);
+}
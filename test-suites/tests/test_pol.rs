@@ -0,0 +1,165 @@
+#![cfg(test)]
+use soroban_sdk::{Bytes, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    dependencies::treasury::FlashMintConfig,
+    fixture_builder::{ousd_config, FixtureBuilder},
+    receiver::create_mock_receiver,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const BALANCE_ERROR: u32 = 505;
+const INVALID_AMOUNT: u32 = 506;
+const SLIPPAGE_EXCEEDED: u32 = 518;
+const NOT_INITIALIZED_ERROR: u32 = 522;
+const INVALID_PAIR_ERROR: u32 = 523;
+
+/// A fixture with OUSD deployed and a pegged OUSD/USDC pair
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = FixtureBuilder::new()
+        .with_pool("Teapot", 0_1000000, 4)
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_stablecoin(0)
+        .with_pair(TokenIndex::OUSD, TokenIndex::USDC, 100_000 * SCALAR_7, 100_000 * SCALAR_7)
+        .build();
+    fixture.tokens[TokenIndex::USDC].mint(&fixture.admin, &(1_000_000 * SCALAR_7));
+    fixture
+}
+
+#[test]
+fn test_seed_liquidity() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pair = fixture.peg_pair(TokenIndex::OUSD);
+    let admin_usdc = usdc.balance(&fixture.admin);
+    let supply_before = fixture.treasury.get_backing_report(&ousd.address).total_supply;
+
+    let lp_tokens = fixture
+        .treasury
+        .seed_liquidity(&ousd.address, &pair.address, &(50_000 * SCALAR_7), &(50_000 * SCALAR_7));
+
+    assert!(lp_tokens > 0);
+    assert_eq!(pair.balance(&fixture.treasury.address), lp_tokens);
+    assert_eq!(usdc.balance(&fixture.admin), admin_usdc - 50_000 * SCALAR_7);
+    assert_eq!(
+        fixture.treasury.get_backing_report(&ousd.address).total_supply,
+        supply_before + 50_000 * SCALAR_7
+    );
+    assert_eq!(fixture.peg_price(TokenIndex::OUSD), 1_0000000);
+}
+
+#[test]
+fn test_seed_liquidity_validation() {
+    let mut fixture = setup();
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let pair = fixture.peg_pair(TokenIndex::OUSD).address.clone();
+
+    let result = fixture.treasury.try_seed_liquidity(&ousd, &pair, &0, &(1_000 * SCALAR_7));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+
+    // a pair without the stablecoin
+    fixture.create_pair(TokenIndex::XLM, TokenIndex::USDC, 1_000 * SCALAR_7, 1_000 * SCALAR_7);
+    let other = fixture.pairs[1].address.clone();
+    let result = fixture.treasury.try_seed_liquidity(&ousd, &other, &(1_000 * SCALAR_7), &(1_000 * SCALAR_7));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_PAIR_ERROR))));
+}
+
+#[test]
+fn test_pol_position_and_withdraw() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pair = fixture.peg_pair(TokenIndex::OUSD);
+    assert!(fixture.treasury.get_pol_position(&ousd.address).is_none());
+
+    let lp_tokens = fixture
+        .treasury
+        .seed_liquidity(&ousd.address, &pair.address, &(50_000 * SCALAR_7), &(50_000 * SCALAR_7));
+
+    let report = fixture.treasury.get_pol_position(&ousd.address).unwrap();
+    assert_eq!(report.pair, pair.address);
+    assert_eq!(report.counter, usdc.address);
+    assert_eq!(report.lp_tokens, lp_tokens);
+    assert_approx_eq_abs(report.token_amount, 50_000 * SCALAR_7, 10);
+    assert_approx_eq_abs(report.counter_amount, 50_000 * SCALAR_7, 10);
+    assert_approx_eq_abs(report.impermanent_value, 0, 20);
+
+    // withdraw half of the liquidity
+    let supply_before = fixture.treasury.get_backing_report(&ousd.address).total_supply;
+    let (token_out, counter_out) = fixture.treasury.withdraw_pol(
+        &ousd.address,
+        &(lp_tokens / 2),
+        &(24_000 * SCALAR_7),
+        &(24_000 * SCALAR_7),
+    );
+    assert_approx_eq_abs(token_out, 25_000 * SCALAR_7, 10);
+    assert_approx_eq_abs(counter_out, 25_000 * SCALAR_7, 10);
+    assert_eq!(pair.balance(&fixture.treasury.address), lp_tokens - lp_tokens / 2);
+    assert_eq!(ousd.balance(&fixture.treasury.address), 0);
+    assert_eq!(fixture.treasury.get_surplus(&usdc.address), counter_out);
+    assert_eq!(
+        fixture.treasury.get_backing_report(&ousd.address).total_supply,
+        supply_before - token_out
+    );
+
+    let report = fixture.treasury.get_pol_position(&ousd.address).unwrap();
+    assert_eq!(report.lp_tokens, lp_tokens - lp_tokens / 2);
+    assert_approx_eq_abs(report.token_seeded, 25_000 * SCALAR_7, 10);
+}
+
+#[test]
+fn test_withdraw_pol_validation() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pair = fixture.peg_pair(TokenIndex::OUSD);
+
+    // nothing seeded
+    let result = fixture.treasury.try_withdraw_pol(&ousd.address, &1, &0, &0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(BALANCE_ERROR))));
+
+    let lp_tokens = fixture
+        .treasury
+        .seed_liquidity(&ousd.address, &pair.address, &(1_000 * SCALAR_7), &(1_000 * SCALAR_7));
+    let result = fixture.treasury.try_withdraw_pol(&ousd.address, &(lp_tokens + 1), &0, &0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(BALANCE_ERROR))));
+
+    let result = fixture
+        .treasury
+        .try_withdraw_pol(&ousd.address, &lp_tokens, &(2_000 * SCALAR_7), &0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(SLIPPAGE_EXCEEDED))));
+}
+
+#[test]
+fn test_recycle_fees() {
+    let fixture = setup();
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let pair = fixture.peg_pair(TokenIndex::OUSD);
+
+    // nothing to recycle into
+    let result = treasury.try_recycle_fees(&fixture.admin, &ousd.address, &0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(NOT_INITIALIZED_ERROR))));
+
+    let lp_seeded = treasury.seed_liquidity(&ousd.address, &pair.address, &(50_000 * SCALAR_7), &(50_000 * SCALAR_7));
+
+    // no fees yet
+    let result = treasury.try_recycle_fees(&fixture.admin, &ousd.address, &0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_AMOUNT))));
+
+    // collect flash mint fees into the surplus buffer
+    let (receiver, _) = create_mock_receiver(&fixture.env, &treasury.address);
+    treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 100 });
+    ousd.mint(&receiver, &(1_000 * SCALAR_7));
+    treasury.flash_mint(&receiver, &ousd.address, &(100_000 * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    assert_eq!(treasury.get_surplus(&ousd.address), 1_000 * SCALAR_7);
+
+    let lp_tokens = treasury.recycle_fees(&fixture.admin, &ousd.address, &(490 * SCALAR_7));
+
+    assert!(lp_tokens > 0);
+    assert_eq!(pair.balance(&treasury.address), lp_seeded + lp_tokens);
+    assert_eq!(treasury.get_pol_position(&ousd.address).unwrap().lp_tokens, lp_seeded + lp_tokens);
+    // the unpaired remainder stays in the surplus buffer
+    assert!(treasury.get_surplus(&ousd.address) < 10 * SCALAR_7);
+    assert_eq!(ousd.balance(&treasury.address), treasury.get_surplus(&ousd.address));
+}
@@ -0,0 +1,22 @@
+#![cfg(test)]
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+#[test]
+fn test_pool_reserve_data_matches_the_blend_reserve() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    treasury.increase_supply(&fixture.admin, &token, &(1_000 * SCALAR_7));
+
+    let data = treasury.get_pool_reserve_data(&token);
+    let summary = treasury.get_position_summary(&token);
+    assert_eq!(data.blend_pool, fixture.pools[0].pool.address);
+    assert_eq!(data.utilization, summary.utilization);
+    assert!(data.utilization >= 0 && data.utilization <= SCALAR_7);
+    assert!(data.total_supplied >= summary.supplied);
+    assert!(data.total_borrowed <= data.total_supplied);
+    assert!(data.c_factor > 0 && data.max_util > 0);
+}
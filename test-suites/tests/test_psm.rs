@@ -0,0 +1,149 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::psm::{create_psm, PSMClient, PSMConfig},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const RESERVE_CAP_EXCEEDED: u32 = 1304;
+const INSUFFICIENT_BALANCE: u32 = 1305;
+const PAUSED: u32 = 1308;
+const UNAUTHORIZED: u32 = 1309;
+const INVALID_REFERRER: u32 = 1310;
+
+const RESERVE_CAP: i128 = 10_000 * SCALAR_7;
+
+/// Deploy an OUSD/USDC PSM charging 0.1% in, 0.2% out and sharing half the fee with referrers,
+/// registered as an OUSD minter on the treasury
+fn setup_psm<'a>(fixture: &TestFixture<'a>) -> PSMClient<'a> {
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let usdc = fixture.tokens[TokenIndex::USDC].address.clone();
+    let config = PSMConfig { fee_in_bps: 10, fee_out_bps: 20, referral_bps: 5_000, reserve_cap: RESERVE_CAP };
+    let (psm_id, psm) = create_psm(&fixture.env, &fixture.admin, &fixture.treasury.address, &ousd, &usdc, &config);
+    fixture.treasury.set_minter(&psm_id, &ousd, &(1_000_000 * SCALAR_7), &(1_000_000 * SCALAR_7));
+    psm
+}
+
+/// Mint USDC to a new account, returning it
+fn create_user(fixture: &TestFixture, usdc_amount: i128) -> Address {
+    let user = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::USDC].mint(&user, &usdc_amount);
+    user
+}
+
+#[test]
+fn test_swap_in_and_out_charges_fees() {
+    let fixture = create_fixture_with_data(false);
+    let psm = setup_psm(&fixture);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let samwise = create_user(&fixture, 1_000 * SCALAR_7);
+
+    // 0.1% of the USDC swapped in is kept as a fee, the rest backs the minted OUSD
+    assert_eq!(psm.swap_in(&samwise, &(1_000 * SCALAR_7), &None, &None), 999 * SCALAR_7);
+    assert_eq!(ousd.balance(&samwise), 999 * SCALAR_7);
+    assert_eq!(psm.get_reserve(), 999 * SCALAR_7);
+    assert_eq!(psm.get_fees(), SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&psm.address).unwrap().minted, 999 * SCALAR_7);
+
+    // 0.2% of the OUSD swapped out is kept as a fee, and all of it is burnt
+    assert_eq!(psm.swap_out(&samwise, &(500 * SCALAR_7), &None, &None), 499 * SCALAR_7);
+    assert_eq!(ousd.balance(&samwise), 499 * SCALAR_7);
+    assert_eq!(usdc.balance(&samwise), 499 * SCALAR_7);
+    assert_eq!(psm.get_reserve(), 499 * SCALAR_7);
+    assert_eq!(psm.get_fees(), 2 * SCALAR_7);
+    assert_eq!(fixture.treasury.get_minter(&psm.address).unwrap().minted, 499 * SCALAR_7);
+    assert_eq!(usdc.balance(&psm.address), psm.get_reserve() + psm.get_fees());
+
+    let treasurer = Address::generate(&fixture.env);
+    assert_eq!(psm.withdraw_fees(&treasurer), 2 * SCALAR_7);
+    assert_eq!(usdc.balance(&treasurer), 2 * SCALAR_7);
+    assert_eq!(psm.get_fees(), 0);
+    assert_eq!(usdc.balance(&psm.address), psm.get_reserve());
+}
+
+#[test]
+fn test_reserve_is_capped() {
+    let fixture = create_fixture_with_data(false);
+    let psm = setup_psm(&fixture);
+    let samwise = create_user(&fixture, 20_000 * SCALAR_7);
+
+    // the cap applies to the reserve, net of the fee
+    psm.swap_in(&samwise, &(10_010 * SCALAR_7), &None, &None);
+    assert!(psm.get_reserve() <= RESERVE_CAP);
+    assert_eq!(
+        psm.try_swap_in(&samwise, &(20 * SCALAR_7), &None, &None),
+        Err(Ok(Error::from_contract_error(RESERVE_CAP_EXCEEDED)))
+    );
+
+    // stablecoins can only be swapped out against the reserve
+    let reserve = psm.get_reserve();
+    fixture.tokens[TokenIndex::OUSD].mint(&samwise, &SCALAR_7);
+    assert_eq!(
+        psm.try_swap_out(&samwise, &(reserve + 1), &None, &None),
+        Err(Ok(Error::from_contract_error(INSUFFICIENT_BALANCE)))
+    );
+}
+
+#[test]
+fn test_pause_blocks_swaps() {
+    let fixture = create_fixture_with_data(false);
+    let psm = setup_psm(&fixture);
+    let samwise = create_user(&fixture, 1_000 * SCALAR_7);
+    let sentinel = Address::generate(&fixture.env);
+    psm.swap_in(&samwise, &(500 * SCALAR_7), &None, &None);
+
+    assert_eq!(
+        psm.try_pause(&samwise),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+    psm.set_pauser(&sentinel);
+    psm.pause(&sentinel);
+    assert!(psm.is_paused());
+    assert_eq!(
+        psm.try_swap_in(&samwise, &(500 * SCALAR_7), &None, &None),
+        Err(Ok(Error::from_contract_error(PAUSED)))
+    );
+    assert_eq!(
+        psm.try_swap_out(&samwise, &(100 * SCALAR_7), &None, &None),
+        Err(Ok(Error::from_contract_error(PAUSED)))
+    );
+
+    psm.unpause(&fixture.admin);
+    assert!(!psm.is_paused());
+    psm.swap_out(&samwise, &(100 * SCALAR_7), &None, &None);
+}
+
+#[test]
+fn test_referrer_shares_the_fee() {
+    let fixture = create_fixture_with_data(false);
+    let psm = setup_psm(&fixture);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let samwise = create_user(&fixture, 2_000 * SCALAR_7);
+    let frontend = Address::generate(&fixture.env);
+
+    // half of each fee is accrued to the referrer
+    psm.swap_in(&samwise, &(1_000 * SCALAR_7), &None, &Some(frontend.clone()));
+    assert_eq!(psm.get_referral_fees(&frontend), 0_5000000);
+    assert_eq!(psm.get_fees(), 0_5000000);
+    psm.swap_out(&samwise, &(500 * SCALAR_7), &None, &Some(frontend.clone()));
+    assert_eq!(psm.get_referral_fees(&frontend), 1_0000000);
+    assert_eq!(psm.get_fees(), 1_0000000);
+
+    assert_eq!(psm.claim_referral_fees(&frontend), 1_0000000);
+    assert_eq!(usdc.balance(&frontend), 1_0000000);
+    assert_eq!(psm.get_referral_fees(&frontend), 0);
+    assert_eq!(psm.claim_referral_fees(&frontend), 0);
+    assert_eq!(usdc.balance(&psm.address), psm.get_reserve() + psm.get_fees());
+
+    // a sender can not refer their own swaps to rebate their fee
+    assert_eq!(
+        psm.try_swap_in(&samwise, &(1_000 * SCALAR_7), &None, &Some(samwise.clone())),
+        Err(Ok(Error::from_contract_error(INVALID_REFERRER)))
+    );
+    assert_eq!(
+        psm.try_swap_out(&samwise, &(100 * SCALAR_7), &None, &Some(samwise.clone())),
+        Err(Ok(Error::from_contract_error(INVALID_REFERRER)))
+    );
+}
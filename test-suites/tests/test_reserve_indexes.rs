@@ -0,0 +1,25 @@
+#![cfg(test)]
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+#[test]
+fn test_refreshed_reserve_indexes_resolve_the_stablecoin_reserve() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    treasury.increase_supply(&fixture.admin, &ousd, &(1_000 * SCALAR_7));
+    let summary = treasury.get_position_summary(&ousd);
+
+    treasury.refresh_reserve_indexes();
+    // refreshing twice is idempotent
+    treasury.refresh_reserve_indexes();
+
+    let refreshed = treasury.get_position_summary(&ousd);
+    assert_eq!(refreshed.supplied, summary.supplied);
+    assert_eq!(refreshed.b_tokens, summary.b_tokens);
+    let data = treasury.get_pool_reserve_data(&ousd);
+    assert_eq!(data.c_factor, fixture.read_reserve_config(0, TokenIndex::OUSD).c_factor);
+    assert_eq!(data.l_factor, fixture.read_reserve_config(0, TokenIndex::OUSD).l_factor);
+}
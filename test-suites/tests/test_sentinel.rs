@@ -0,0 +1,61 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::{sentinel::{create_sentinel, SentinelClient}, treasury::Role},
+    test_fixture::TestFixture,
+};
+
+const UNAUTHORIZED: u32 = 2003;
+const TARGET_NOT_FOUND: u32 = 2004;
+
+/// A sentinel allowed to pause the treasury and the pegkeeper, with a guardian
+fn setup<'a>(fixture: &TestFixture<'a>) -> (SentinelClient<'a>, Address) {
+    let (sentinel, sentinel_client) = create_sentinel(&fixture.env, &fixture.admin);
+    fixture.treasury.grant_role(&Role::Pauser, &sentinel);
+    fixture.pegkeeper.set_pauser(&sentinel);
+    sentinel_client.add_target(&fixture.treasury.address);
+    sentinel_client.add_target(&fixture.pegkeeper.address);
+    let guardian = Address::generate(&fixture.env);
+    sentinel_client.set_guardian(&guardian, &true);
+    (sentinel_client, guardian)
+}
+
+#[test]
+fn test_guardian_pauses_the_protocol() {
+    let fixture = create_fixture_with_data(false);
+    let (sentinel, guardian) = setup(&fixture);
+    let stranger = Address::generate(&fixture.env);
+
+    assert_eq!(
+        sentinel.try_pause_all(&stranger),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+
+    assert_eq!(sentinel.pause_all(&guardian), 2);
+    assert!(fixture.treasury.is_paused());
+    assert!(fixture.pegkeeper.is_paused());
+
+    assert_eq!(sentinel.unpause_all(), 2);
+    assert!(!fixture.treasury.is_paused());
+    assert!(!fixture.pegkeeper.is_paused());
+}
+
+#[test]
+fn test_guardian_pauses_a_single_target() {
+    let fixture = create_fixture_with_data(false);
+    let (sentinel, guardian) = setup(&fixture);
+
+    sentinel.pause(&guardian, &fixture.pegkeeper.address);
+    assert!(fixture.pegkeeper.is_paused());
+    assert!(!fixture.treasury.is_paused());
+
+    sentinel.remove_target(&fixture.treasury.address);
+    assert_eq!(
+        sentinel.try_pause(&guardian, &fixture.treasury.address),
+        Err(Ok(Error::from_contract_error(TARGET_NOT_FOUND)))
+    );
+
+    sentinel.unpause(&fixture.pegkeeper.address);
+    assert!(!fixture.pegkeeper.is_paused());
+}
@@ -0,0 +1,131 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, vec, Address, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    create_fixture_with_data,
+    dependencies::pool::{Request, RequestType},
+    fixture_builder::{ousd_config, xlm_config, FixtureBuilder},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const UNAUTHORIZED: u32 = 503;
+const OVERFLOW_ERROR: u32 = 507;
+
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+/// A stability fee of about 10% a year, per second in 18 decimals
+const FEE_RATE: i128 = 3_170_979_198;
+
+/// A fixture whose pool passes most of the interest on borrowed OUSD to its suppliers, with half of
+/// the treasury's supply borrowed
+fn setup_borrowed_supply<'a>() -> TestFixture<'a> {
+    let fixture = FixtureBuilder::new()
+        .with_whale()
+        .with_pool("Teapot", 0_1000000, 6)
+        .with_reserve(0, TokenIndex::XLM, xlm_config())
+        .with_reserve(0, TokenIndex::OUSD, ousd_config())
+        .with_treasury_supply(1_000_000 * SCALAR_7)
+        .build();
+
+    let henk = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&henk, &(20_000_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 20_000_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::OUSD].address.clone(),
+            amount: 500_000 * SCALAR_7,
+        },
+    ];
+    fixture.pools[0].pool.submit(&henk, &henk, &henk, &requests);
+    fixture
+}
+
+#[test]
+fn test_stability_fee_accrues_on_the_outstanding_supply() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    assert_eq!(treasury.get_accrued_fees(&ousd), 0);
+    assert_eq!(treasury.accrue(&ousd), 0);
+
+    treasury.set_stability_fee(&ousd, &FEE_RATE);
+    fixture.jump(SECONDS_PER_YEAR);
+    let fees = treasury.get_accrued_fees(&ousd);
+    assert_approx_eq_abs(fees, 100_000 * SCALAR_7, SCALAR_7);
+    assert_eq!(treasury.accrue(&ousd), fees);
+    assert_eq!(treasury.accrue(&ousd), fees);
+
+    // supply changes accrue at the previous supply first
+    let minter = Address::generate(&fixture.env);
+    treasury.set_minter(&minter, &ousd, &(1_000_000 * SCALAR_7), &(1_000_000 * SCALAR_7));
+    fixture.jump(SECONDS_PER_YEAR / 2);
+    treasury.mint(&minter, &minter, &(1_000_000 * SCALAR_7));
+    let fees_before_mint = treasury.get_accrued_fees(&ousd);
+    assert_approx_eq_abs(fees_before_mint, fees + 50_000 * SCALAR_7, SCALAR_7);
+    fixture.jump(SECONDS_PER_YEAR / 2);
+    assert_approx_eq_abs(treasury.get_accrued_fees(&ousd), fees_before_mint + 100_000 * SCALAR_7, SCALAR_7);
+
+    // a rate change accrues at the previous rate first
+    treasury.set_stability_fee(&ousd, &0);
+    let total = treasury.get_accrued_fees(&ousd);
+    fixture.jump(SECONDS_PER_YEAR);
+    assert_eq!(treasury.accrue(&ousd), total);
+}
+
+#[test]
+fn test_stability_fee_overflow_is_an_error() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    treasury.set_stability_fee(&ousd, &(i128::MAX / 2));
+    fixture.jump(5);
+    assert_eq!(
+        treasury.try_get_accrued_fees(&ousd),
+        Err(Ok(Error::from_contract_error(OVERFLOW_ERROR)))
+    );
+    assert_eq!(
+        treasury.try_accrue(&ousd),
+        Err(Ok(Error::from_contract_error(OVERFLOW_ERROR)))
+    );
+}
+
+#[test]
+fn test_collect_stability_fees_from_the_interest_earned() {
+    let fixture = setup_borrowed_supply();
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    assert_eq!(treasury.collect_stability_fees(&fixture.admin, &ousd.address), 0);
+
+    treasury.set_stability_fee(&ousd.address, &FEE_RATE);
+    fixture.jump(SECONDS_PER_YEAR);
+    let accrued = treasury.get_accrued_fees(&ousd.address);
+    assert_approx_eq_abs(accrued, 100_000 * SCALAR_7, SCALAR_7);
+
+    let frodo = Address::generate(&fixture.env);
+    assert_eq!(
+        treasury.try_collect_stability_fees(&frodo, &ousd.address),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+
+    // the interest earned on the borrowed half of the supply does not cover the fees, so only the
+    // backing above the supply is moved into the surplus buffer
+    let surplus = treasury.get_surplus(&ousd.address);
+    let balance = ousd.balance(&treasury.address);
+    let collected = treasury.collect_stability_fees(&fixture.admin, &ousd.address);
+    assert!(collected > 0 && collected < accrued);
+    assert_eq!(treasury.get_surplus(&ousd.address), surplus + collected);
+    assert_eq!(ousd.balance(&treasury.address), balance + collected);
+    fixture.assert_invariants();
+
+    // the rest stays owed until more interest is earned
+    assert_eq!(treasury.collect_stability_fees(&fixture.admin, &ousd.address), 0);
+    assert_eq!(treasury.get_accrued_fees(&ousd.address), accrued);
+    fixture.jump(SECONDS_PER_YEAR / 12);
+    assert!(treasury.collect_stability_fees(&fixture.admin, &ousd.address) > 0);
+}
@@ -0,0 +1,183 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, vec, Address, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    create_fixture_with_data,
+    dependencies::{
+        pegkeeper::Strategy,
+        pool::{Request, RequestType},
+        stability_pool::{create_stability_pool, StabilityPoolClient},
+    },
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 1103;
+const INSUFFICIENT_BALANCE: u32 = 1104;
+
+/// Deploy an OUSD stability pool on the fixture's blend pool, administered by the treasury
+fn setup_stability_pool<'a>(fixture: &TestFixture<'a>) -> StabilityPoolClient<'a> {
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let (_, stability_pool) = create_stability_pool(&fixture.env, &fixture.treasury.address, &ousd, &fixture.pools[0].pool.address);
+    stability_pool
+}
+
+/// Mint OUSD to a new depositor and deposit it into the stability pool, returning the depositor
+fn deposit(fixture: &TestFixture, stability_pool: &StabilityPoolClient, amount: i128) -> Address {
+    let depositor = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::OUSD].mint(&depositor, &amount);
+    stability_pool.deposit(&depositor, &amount);
+    depositor
+}
+
+/// Borrow OUSD against XLM and drop the XLM price so the position can be liquidated, returning the borrower
+fn create_liquidatable_user(fixture: &mut TestFixture) -> Address {
+    let xlm_amount = 10_000_000_000_00 * SCALAR_7;
+    fixture.create_pair(TokenIndex::OUSD, TokenIndex::XLM, (xlm_amount as f64 * 0.088) as i128, xlm_amount);
+
+    let henk = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&henk, &(100_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 100_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::OUSD].address.clone(),
+            amount: 8_800 * SCALAR_7,
+        },
+    ];
+    fixture.pools[0].pool.submit(&henk, &henk, &henk, &requests);
+
+    fixture.jump(60 * 60 * 24 * 7 * 4); // 4 weeks
+    fixture.oracle.set_price_stable(&vec![&fixture.env, 1_0000000, 0_0880000]);
+    henk
+}
+
+#[test]
+fn test_deposit_and_withdraw_share_accounting() {
+    let fixture = create_fixture_with_data(false);
+    let stability_pool = setup_stability_pool(&fixture);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let samwise = deposit(&fixture, &stability_pool, 1_000 * SCALAR_7);
+    let frodo = deposit(&fixture, &stability_pool, 500 * SCALAR_7);
+    assert_eq!(stability_pool.get_total_deposits(), 1_500 * SCALAR_7);
+    assert_eq!(stability_pool.get_balance(&samwise), 1_000 * SCALAR_7);
+    assert_eq!(stability_pool.get_balance(&frodo), 500 * SCALAR_7);
+    assert_eq!(ousd.balance(&stability_pool.address), 1_500 * SCALAR_7);
+
+    // shares are burnt at the current deposits per share
+    assert_eq!(stability_pool.withdraw(&samwise, &(400 * SCALAR_7)), 400 * SCALAR_7);
+    assert_eq!(stability_pool.get_balance(&samwise), 600 * SCALAR_7);
+    assert_eq!(stability_pool.get_total_deposits(), 1_100 * SCALAR_7);
+    assert_eq!(ousd.balance(&samwise), 400 * SCALAR_7);
+
+    assert_eq!(
+        stability_pool.try_withdraw(&frodo, &(500 * SCALAR_7 + 1)),
+        Err(Ok(Error::from_contract_error(INSUFFICIENT_BALANCE)))
+    );
+    assert_eq!(
+        stability_pool.try_deposit(&frodo, &0),
+        Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+    );
+}
+
+#[test]
+fn test_rewards_are_split_by_shares() {
+    let fixture = create_fixture_with_data(false);
+    let stability_pool = setup_stability_pool(&fixture);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+
+    let samwise = deposit(&fixture, &stability_pool, 1_000 * SCALAR_7);
+    let frodo = deposit(&fixture, &stability_pool, 500 * SCALAR_7);
+    usdc.mint(&fixture.admin, &(300 * SCALAR_7));
+    stability_pool.add_rewards(&fixture.admin, &usdc.address, &(300 * SCALAR_7));
+
+    // a later depositor does not earn rewards distributed before their deposit
+    let pippin = deposit(&fixture, &stability_pool, 1_000 * SCALAR_7);
+    assert_eq!(stability_pool.get_pending(&pippin), vec![&fixture.env]);
+
+    let claimed = stability_pool.claim_rewards(&samwise);
+    assert_eq!(claimed, vec![&fixture.env, (usdc.address.clone(), 200 * SCALAR_7)]);
+    assert_eq!(usdc.balance(&samwise), 200 * SCALAR_7);
+    assert_eq!(stability_pool.get_pending(&frodo), vec![&fixture.env, (usdc.address.clone(), 100 * SCALAR_7)]);
+    assert_eq!(stability_pool.claim_rewards(&samwise), vec![&fixture.env]);
+}
+
+#[test]
+fn test_treasury_liquidates_through_the_stability_pool() {
+    let mut fixture = create_fixture_with_data(false);
+    let henk = create_liquidatable_user(&mut fixture);
+    let stability_pool = setup_stability_pool(&fixture);
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    fixture.treasury.set_stability_pool(&ousd.address, &stability_pool.address);
+
+    let samwise = deposit(&fixture, &stability_pool, 20_000 * SCALAR_7);
+    let frodo = deposit(&fixture, &stability_pool, 10_000 * SCALAR_7);
+    let auction = pool.new_liquidation_auction(&henk, &100);
+    // cover the interest accrued on the bid until the fill, the excess repayment is refunded
+    let debt_amount = auction.bid.get_unchecked(ousd.address.clone()) * 101 / 100;
+    fixture.jump_with_sequence(200 * 5);
+
+    let liquidations = fixture.treasury.get_stats().liquidations;
+    fixture.treasury.liquidate(&fixture.admin, &fixture.pegkeeper.address, &ousd.address, &henk, &debt_amount);
+    assert_eq!(fixture.treasury.get_stats().liquidations, liquidations + 1);
+    assert!(pool.try_get_auction(&0, &henk).is_err());
+
+    // the deposits spent on the debt are lost pro-rata, and the seized XLM is owed pro-rata
+    let spent = 30_000 * SCALAR_7 - stability_pool.get_total_deposits();
+    assert!(spent > 0 && spent <= debt_amount);
+    assert_eq!(ousd.balance(&stability_pool.address), 30_000 * SCALAR_7 - spent);
+    assert_approx_eq_abs(stability_pool.get_balance(&samwise), 2 * stability_pool.get_balance(&frodo), 2);
+    let seized = xlm.balance(&stability_pool.address);
+    assert!(seized > 0);
+    let samwise_xlm = stability_pool.get_pending(&samwise).get_unchecked(0).1;
+    let frodo_xlm = stability_pool.get_pending(&frodo).get_unchecked(0).1;
+    assert_approx_eq_abs(samwise_xlm, 2 * frodo_xlm, 2);
+    assert!(samwise_xlm + frodo_xlm <= seized);
+
+    let claimed = stability_pool.claim_collateral(&samwise);
+    assert_eq!(claimed, vec![&fixture.env, (xlm.address.clone(), samwise_xlm)]);
+    assert_eq!(xlm.balance(&samwise), samwise_xlm);
+    assert_eq!(stability_pool.get_pending(&samwise), vec![&fixture.env]);
+
+    // the remaining deposits can still be withdrawn in full
+    let balance = stability_pool.get_balance(&frodo);
+    stability_pool.withdraw(&frodo, &balance);
+    assert_eq!(ousd.balance(&frodo), balance);
+    fixture.assert_invariants();
+}
+
+#[test]
+fn test_small_stability_pool_falls_back_to_a_flash_loan() {
+    let mut fixture = create_fixture_with_data(false);
+    let henk = create_liquidatable_user(&mut fixture);
+    let stability_pool = setup_stability_pool(&fixture);
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pegkeeper = &fixture.pegkeeper;
+    fixture.treasury.set_stability_pool(&ousd.address, &stability_pool.address);
+    pegkeeper.set_collaterals(&Strategy::Liquidate, &vec![&fixture.env, fixture.tokens[TokenIndex::XLM].address.clone()]);
+
+    // the deposits can not cover the debt, so the pegkeeper fills the auction with a flash loan
+    deposit(&fixture, &stability_pool, 100 * SCALAR_7);
+    let auction = pool.new_liquidation_auction(&henk, &100);
+    // cover the interest accrued on the bid until the fill, the excess repayment is refunded
+    let debt_amount = auction.bid.get_unchecked(ousd.address.clone()) * 101 / 100;
+    fixture.jump_with_sequence(250 * 5);
+
+    let volume = fixture.treasury.get_stats().flash_loan_volume;
+    let balance_before = ousd.balance(&pegkeeper.address);
+    fixture.treasury.liquidate(&fixture.admin, &pegkeeper.address, &ousd.address, &henk, &debt_amount);
+    assert!(pool.try_get_auction(&0, &henk).is_err());
+    assert_eq!(fixture.treasury.get_stats().flash_loan_volume, volume + debt_amount);
+    assert!(ousd.balance(&pegkeeper.address) > balance_before);
+    assert_eq!(stability_pool.get_total_deposits(), 100 * SCALAR_7);
+    assert_eq!(fixture.tokens[TokenIndex::XLM].balance(&stability_pool.address), 0);
+    fixture.assert_invariants();
+}
@@ -0,0 +1,68 @@
+#![cfg(test)]
+use soroban_sdk::Bytes;
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::FlashMintConfig,
+    receiver::create_mock_receiver,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+#[test]
+fn test_stats_count_supply_changes() {
+    let fixture = create_fixture_with_data(false);
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let before = fixture.treasury.get_stats();
+
+    fixture.treasury.increase_supply(&fixture.admin, &token, &(1_000 * SCALAR_7));
+    fixture.treasury.decrease_supply(&fixture.admin, &token, &(300 * SCALAR_7));
+    fixture.treasury.increase_supply(&fixture.admin, &token, &(100 * SCALAR_7));
+
+    let stats = fixture.treasury.get_stats();
+    assert_eq!(stats.minted - before.minted, 1_100 * SCALAR_7);
+    assert_eq!(stats.burned - before.burned, 300 * SCALAR_7);
+}
+
+#[test]
+fn test_stats_count_flash_mints() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let amount = 1_000 * SCALAR_7;
+    fixture.treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &fixture.treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fees
+    let before = fixture.treasury.get_stats();
+
+    fixture.treasury.flash_mint(&receiver, &ousd.address, &amount, &Bytes::new(&fixture.env), &None);
+    fixture.treasury.flash_mint(&receiver, &ousd.address, &amount, &Bytes::new(&fixture.env), &None);
+
+    let stats = fixture.treasury.get_stats();
+    assert_eq!(stats.flash_loans - before.flash_loans, 2);
+    assert_eq!(stats.flash_loan_volume - before.flash_loan_volume, 2 * amount);
+    assert_eq!(stats.fees_earned - before.fees_earned, 2 * amount * 10 / 10_000);
+    assert_eq!(stats.minted, before.minted);
+}
+
+#[test]
+fn test_recent_flash_loans_drop_the_oldest() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    fixture.treasury.set_flash_mint(&ousd.address, &FlashMintConfig { cap: 1_000_000 * SCALAR_7, fee_bps: 10 });
+    let (receiver, _) = create_mock_receiver(&fixture.env, &fixture.treasury.address);
+    ousd.mint(&receiver, &SCALAR_7); // cover the fees
+
+    // the history keeps the 20 most recent flash loans, oldest first
+    for i in 1..=25 {
+        fixture.treasury.flash_mint(&receiver, &ousd.address, &(i * SCALAR_7), &Bytes::new(&fixture.env), &None);
+    }
+
+    let history = fixture.treasury.get_recent_flash_loans();
+    assert_eq!(history.len(), 20);
+    for (i, record) in history.iter().enumerate() {
+        let amount = (i as i128 + 6) * SCALAR_7;
+        assert_eq!(record.receiver, receiver);
+        assert_eq!(record.token, ousd.address);
+        assert_eq!(record.amount, amount);
+        assert_eq!(record.fee, amount * 10 / 10_000);
+        assert!(record.success);
+    }
+}
@@ -0,0 +1,146 @@
+#![cfg(test)]
+use soroban_sdk::{Bytes, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::pegkeeper::StrategyLimits,
+    strategy::create_mock_strategy,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INSUFFICIENT_BALANCE: u32 = 104;
+const INVALID_VALUE: u32 = 107;
+const STRATEGY_NOT_ALLOWED: u32 = 110;
+const STRATEGY_LIMIT_EXCEEDED: u32 = 111;
+const PROFIT_TOO_LOW: u32 = 116;
+const AMOUNT: i128 = 1_000 * SCALAR_7;
+
+#[test]
+fn test_execute_registered_strategy() {
+    let fixture = create_fixture_with_data(false);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pegkeeper = &fixture.pegkeeper;
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    strategy_client.set_profit(&(5 * SCALAR_7));
+    usdc.mint(&strategy, &(5 * SCALAR_7));
+    usdc.mint(&pegkeeper.address, &AMOUNT);
+
+    pegkeeper.register_strategy(&1, &strategy);
+    let config = pegkeeper.get_strategy(&1).unwrap();
+    assert_eq!(config.contract, strategy);
+    assert!(config.enabled);
+
+    let profit = pegkeeper.execute_strategy(&1, &usdc.address, &AMOUNT, &Bytes::new(&fixture.env));
+    assert_eq!(profit, 5 * SCALAR_7);
+    assert_eq!(usdc.balance(&pegkeeper.address), AMOUNT + 5 * SCALAR_7);
+    assert_eq!(usdc.balance(&strategy), 0);
+}
+
+#[test]
+fn test_unregistered_or_disabled_strategy_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pegkeeper = &fixture.pegkeeper;
+    let (strategy, _) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    usdc.mint(&pegkeeper.address, &AMOUNT);
+
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &Bytes::new(&fixture.env));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(STRATEGY_NOT_ALLOWED))));
+    let result = pegkeeper.try_set_strategy_enabled(&1, &false);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(STRATEGY_NOT_ALLOWED))));
+
+    pegkeeper.register_strategy(&1, &strategy);
+    pegkeeper.set_strategy_enabled(&1, &false);
+    assert!(!pegkeeper.get_strategy(&1).unwrap().enabled);
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &Bytes::new(&fixture.env));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(STRATEGY_NOT_ALLOWED))));
+
+    pegkeeper.set_strategy_enabled(&1, &true);
+    pegkeeper.execute_strategy(&1, &usdc.address, &AMOUNT, &Bytes::new(&fixture.env));
+    assert_eq!(usdc.balance(&pegkeeper.address), AMOUNT);
+}
+
+#[test]
+fn test_losing_strategy_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pegkeeper = &fixture.pegkeeper;
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    strategy_client.set_profit(&(-SCALAR_7));
+    usdc.mint(&pegkeeper.address, &AMOUNT);
+    pegkeeper.register_strategy(&1, &strategy);
+
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &Bytes::new(&fixture.env));
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INSUFFICIENT_BALANCE))));
+    assert_eq!(usdc.balance(&pegkeeper.address), AMOUNT);
+}
+
+#[test]
+fn test_strategy_limits_are_enforced() {
+    let fixture = create_fixture_with_data(false);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pegkeeper = &fixture.pegkeeper;
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    usdc.mint(&pegkeeper.address, &(2 * AMOUNT));
+    usdc.mint(&strategy, &(10 * SCALAR_7));
+    strategy_client.set_profit(&SCALAR_7);
+    pegkeeper.register_strategy(&1, &strategy);
+    pegkeeper.set_strategy_limits(
+        &1,
+        &Some(StrategyLimits { max_notional: AMOUNT, max_daily_volume: 3 * AMOUNT / 2, min_profit: SCALAR_7 }),
+    );
+    let params = Bytes::new(&fixture.env);
+
+    // above the max notional
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &(AMOUNT + 1), &params);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(STRATEGY_LIMIT_EXCEEDED))));
+
+    pegkeeper.execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+
+    // above the max daily volume
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(STRATEGY_LIMIT_EXCEEDED))));
+
+    // the daily volume resets the next day
+    fixture.jump(60 * 60 * 24);
+    pegkeeper.execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+
+    // below the min profit
+    fixture.jump(60 * 60 * 24);
+    strategy_client.set_profit(&(SCALAR_7 - 1));
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(STRATEGY_LIMIT_EXCEEDED))));
+
+    // removing the limits lifts them
+    pegkeeper.set_strategy_limits(&1, &None);
+    assert!(pegkeeper.get_strategy_limits(&1).is_none());
+    pegkeeper.execute_strategy(&1, &usdc.address, &(2 * AMOUNT), &params);
+}
+
+#[test]
+fn test_min_profit_is_enforced() {
+    let fixture = create_fixture_with_data(false);
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let pegkeeper = &fixture.pegkeeper;
+    let (strategy, strategy_client) = create_mock_strategy(&fixture.env, &pegkeeper.address);
+    usdc.mint(&pegkeeper.address, &AMOUNT);
+    usdc.mint(&strategy, &(10 * SCALAR_7));
+    pegkeeper.register_strategy(&1, &strategy);
+    let params = Bytes::new(&fixture.env);
+    assert_eq!(pegkeeper.get_min_profit(), 0);
+
+    let result = pegkeeper.try_set_min_profit(&-1);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INVALID_VALUE))));
+    pegkeeper.set_min_profit(&SCALAR_7);
+    assert_eq!(pegkeeper.get_min_profit(), SCALAR_7);
+
+    // break-even churn is rejected
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(PROFIT_TOO_LOW))));
+    strategy_client.set_profit(&(SCALAR_7 - 1));
+    let result = pegkeeper.try_execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(PROFIT_TOO_LOW))));
+
+    strategy_client.set_profit(&SCALAR_7);
+    let profit = pegkeeper.execute_strategy(&1, &usdc.address, &AMOUNT, &params);
+    assert_eq!(profit, SCALAR_7);
+}
@@ -0,0 +1,77 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const BALANCE_ERROR: u32 = 505;
+const STREAM_NOT_FOUND: u32 = 538;
+const INVALID_STREAM: u32 = 539;
+const TOTAL: i128 = 100 * SCALAR_7;
+const DURATION: u64 = 100 * 24 * 60 * 60;
+
+#[test]
+fn test_stream_vests_linearly_from_the_surplus() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let frodo = &fixture.users[0];
+    let contributor = Address::generate(&fixture.env);
+    ousd.mint(frodo, &TOTAL);
+    treasury.deposit_surplus(frodo, &ousd.address, &TOTAL);
+    let surplus = treasury.get_surplus(&ousd.address);
+    let start = fixture.env.ledger().timestamp();
+
+    let id = treasury.create_stream(&ousd.address, &contributor, &TOTAL, &start, &(start + DURATION));
+    assert_eq!(treasury.get_surplus(&ousd.address), surplus - TOTAL);
+
+    fixture.jump(DURATION / 4);
+    assert_eq!(treasury.withdraw_stream(&id), TOTAL / 4);
+    assert_eq!(treasury.withdraw_stream(&id), 0);
+
+    fixture.jump(DURATION);
+    assert_eq!(treasury.withdraw_stream(&id), TOTAL - TOTAL / 4);
+    assert_eq!(ousd.balance(&contributor), TOTAL);
+    // a fully withdrawn stream is removed
+    assert!(treasury.get_stream(&id).is_none());
+}
+
+#[test]
+fn test_cancel_stream_returns_the_unvested_amount() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let frodo = &fixture.users[0];
+    let contributor = Address::generate(&fixture.env);
+    ousd.mint(frodo, &TOTAL);
+    treasury.deposit_surplus(frodo, &ousd.address, &TOTAL);
+    let surplus = treasury.get_surplus(&ousd.address);
+    let start = fixture.env.ledger().timestamp();
+    let id = treasury.create_stream(&ousd.address, &contributor, &TOTAL, &start, &(start + DURATION));
+
+    fixture.jump(DURATION / 2);
+    treasury.cancel_stream(&id);
+    assert_eq!(ousd.balance(&contributor), TOTAL / 2);
+    assert_eq!(treasury.get_surplus(&ousd.address), surplus - TOTAL / 2);
+    assert_eq!(treasury.try_withdraw_stream(&id), Err(Ok(Error::from_contract_error(STREAM_NOT_FOUND))));
+}
+
+#[test]
+fn test_invalid_streams_are_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let contributor = Address::generate(&fixture.env);
+    let surplus = treasury.get_surplus(&ousd.address);
+    let start = fixture.env.ledger().timestamp();
+
+    assert_eq!(
+        treasury.try_create_stream(&ousd.address, &contributor, &SCALAR_7, &start, &start),
+        Err(Ok(Error::from_contract_error(INVALID_STREAM)))
+    );
+    assert_eq!(
+        treasury.try_create_stream(&ousd.address, &contributor, &(surplus + 1), &start, &(start + DURATION)),
+        Err(Ok(Error::from_contract_error(BALANCE_ERROR)))
+    );
+}
@@ -0,0 +1,71 @@
+#![cfg(test)]
+use soroban_sdk::Error;
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    create_fixture_with_data,
+    dependencies::treasury::SupplyLimit,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const INVALID_AMOUNT: u32 = 506;
+const SUPPLY_LIMIT_EXCEEDED: u32 = 520;
+
+#[test]
+fn test_supply_changes_are_limited_within_a_window() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+
+    assert_eq!(
+        treasury.try_set_supply_limit(&SupplyLimit { max_change_bps: 1_000, window: 0 }),
+        Err(Ok(Error::from_contract_error(INVALID_AMOUNT)))
+    );
+    // 10% of the 1,000,000 OUSD supplied can be issued or withdrawn every 100 ledgers
+    treasury.set_supply_limit(&SupplyLimit { max_change_bps: 1_000, window: 100 });
+
+    treasury.increase_supply(&fixture.admin, &ousd, &(60_000 * SCALAR_7));
+    treasury.decrease_supply(&fixture.admin, &ousd, &(20_000 * SCALAR_7));
+    treasury.increase_supply(&fixture.admin, &ousd, &(20_000 * SCALAR_7));
+    assert_eq!(
+        treasury.try_increase_supply(&fixture.admin, &ousd, &1),
+        Err(Ok(Error::from_contract_error(SUPPLY_LIMIT_EXCEEDED)))
+    );
+    // increases and decreases both count towards the limit
+    assert_eq!(
+        treasury.try_decrease_supply(&fixture.admin, &ousd, &1),
+        Err(Ok(Error::from_contract_error(SUPPLY_LIMIT_EXCEEDED)))
+    );
+    assert_eq!(treasury.get_backing_report(&ousd).total_supply, 1_060_000 * SCALAR_7);
+}
+
+#[test]
+fn test_supply_limit_resets_with_each_window() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    treasury.set_supply_limit(&SupplyLimit { max_change_bps: 1_000, window: 100 });
+
+    treasury.increase_supply(&fixture.admin, &ousd, &(100_000 * SCALAR_7));
+    assert_eq!(
+        treasury.try_increase_supply(&fixture.admin, &ousd, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(SUPPLY_LIMIT_EXCEEDED)))
+    );
+
+    // the window is still open one ledger before it ends
+    fixture.jump_with_sequence(99 * 5);
+    assert_eq!(
+        treasury.try_increase_supply(&fixture.admin, &ousd, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(SUPPLY_LIMIT_EXCEEDED)))
+    );
+
+    // the next window is relative to the supply at its start
+    fixture.jump_with_sequence(5);
+    let base_supply = treasury.get_total_position(&ousd).supplied;
+    let max_change = base_supply / 10;
+    treasury.increase_supply(&fixture.admin, &ousd, &max_change);
+    assert_eq!(
+        treasury.try_increase_supply(&fixture.admin, &ousd, &1),
+        Err(Ok(Error::from_contract_error(SUPPLY_LIMIT_EXCEEDED)))
+    );
+    assert_approx_eq_abs(max_change, 110_000 * SCALAR_7, 1);
+}
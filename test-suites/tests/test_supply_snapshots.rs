@@ -0,0 +1,34 @@
+#![cfg(test)]
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+#[test]
+fn test_supply_at_past_ledgers() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let start = fixture.env.ledger().sequence();
+    let initial = treasury.get_supply_at(&token, &start);
+    assert!(initial > 0);
+
+    fixture.jump_with_sequence(50);
+    let minted_at = fixture.env.ledger().sequence();
+    treasury.increase_supply(&fixture.admin, &token, &(1_000 * SCALAR_7));
+    // a second change in the same ledger replaces its snapshot
+    treasury.increase_supply(&fixture.admin, &token, &(500 * SCALAR_7));
+
+    fixture.jump_with_sequence(50);
+    let burned_at = fixture.env.ledger().sequence();
+    treasury.decrease_supply(&fixture.admin, &token, &(300 * SCALAR_7));
+
+    fixture.jump_with_sequence(50);
+    assert_eq!(treasury.get_supply_at(&token, &start), initial);
+    assert_eq!(treasury.get_supply_at(&token, &(minted_at - 1)), initial);
+    assert_eq!(treasury.get_supply_at(&token, &minted_at), initial + 1_500 * SCALAR_7);
+    assert_eq!(treasury.get_supply_at(&token, &(burned_at - 1)), initial + 1_500 * SCALAR_7);
+    assert_eq!(treasury.get_supply_at(&token, &burned_at), initial + 1_200 * SCALAR_7);
+    assert_eq!(treasury.get_supply_at(&token, &fixture.env.ledger().sequence()), initial + 1_200 * SCALAR_7);
+    assert_eq!(treasury.get_supply_at(&token, &0), 0);
+}
@@ -0,0 +1,65 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::token::create_token,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const BALANCE_ERROR: u32 = 505;
+const TOKEN_NOT_SWEEPABLE: u32 = 550;
+const PEGKEEPER_INSUFFICIENT_BALANCE: u32 = 104;
+
+#[test]
+fn test_stray_tokens_are_swept_from_the_treasury_and_pegkeeper() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let (stray, stray_client) = create_token(&fixture.env, &fixture.admin, 7, "STRAY");
+    stray_client.mint(&treasury.address, &(100 * SCALAR_7));
+    stray_client.mint(&fixture.pegkeeper.address, &(50 * SCALAR_7));
+    let recipient = Address::generate(&fixture.env);
+
+    treasury.sweep(&stray, &recipient, &(100 * SCALAR_7));
+    assert_eq!(stray_client.balance(&treasury.address), 0);
+    assert_eq!(stray_client.balance(&recipient), 100 * SCALAR_7);
+    assert_eq!(
+        treasury.try_sweep(&stray, &recipient, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(BALANCE_ERROR)))
+    );
+
+    treasury.sweep_pegkeeper(&stray, &recipient, &(50 * SCALAR_7));
+    assert_eq!(stray_client.balance(&fixture.pegkeeper.address), 0);
+    assert_eq!(stray_client.balance(&recipient), 150 * SCALAR_7);
+    assert_eq!(
+        treasury.try_sweep_pegkeeper(&stray, &recipient, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(PEGKEEPER_INSUFFICIENT_BALANCE)))
+    );
+}
+
+#[test]
+fn test_tracked_tokens_cannot_be_swept() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    ousd.mint(&treasury.address, &SCALAR_7);
+    let recipient = Address::generate(&fixture.env);
+
+    assert_eq!(
+        treasury.try_sweep(&ousd.address, &recipient, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(TOKEN_NOT_SWEEPABLE)))
+    );
+    assert_eq!(
+        treasury.try_sweep_pegkeeper(&ousd.address, &recipient, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(TOKEN_NOT_SWEEPABLE)))
+    );
+
+    // stray tokens become tracked once they are deposited into the surplus buffer
+    let (stray, stray_client) = create_token(&fixture.env, &fixture.admin, 7, "STRAY");
+    let frodo = &fixture.users[0];
+    stray_client.mint(frodo, &SCALAR_7);
+    treasury.deposit_surplus(frodo, &stray, &SCALAR_7);
+    assert_eq!(
+        treasury.try_sweep(&stray, &recipient, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(TOKEN_NOT_SWEEPABLE)))
+    );
+}
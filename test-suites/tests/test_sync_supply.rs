@@ -0,0 +1,44 @@
+#![cfg(test)]
+use soroban_sdk::{Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::ousd_token::OusdTokenClient,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const SUPPLY_UNAVAILABLE: u32 = 532;
+
+#[test]
+fn test_sync_supply_reconciles_mints_outside_the_treasury() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let treasury = &fixture.treasury;
+    let ousd_token = OusdTokenClient::new(&fixture.env, &ousd.address);
+    let usd = Symbol::new(&fixture.env, "USD");
+
+    // every OUSD was minted by the treasury, so the recorded supply has not drifted
+    assert_eq!(treasury.sync_supply(&ousd.address), 0);
+    let (signer, _) = fixture.env.auths().first().cloned().expect("no authorization required");
+    assert_eq!(signer, fixture.admin);
+    assert_eq!(treasury.get_currency_supply(&usd), ousd_token.total_supply());
+
+    // minted directly by the token admin, bypassing the treasury's accounting
+    let frodo = &fixture.users[0];
+    ousd.mint(frodo, &(10 * SCALAR_7));
+    assert_eq!(treasury.sync_supply(&ousd.address), 10 * SCALAR_7);
+    assert_eq!(treasury.get_currency_supply(&usd), ousd_token.total_supply());
+
+    ousd.burn(frodo, &(4 * SCALAR_7));
+    assert_eq!(treasury.sync_supply(&ousd.address), -4 * SCALAR_7);
+}
+
+#[test]
+fn test_sync_supply_requires_a_reported_total_supply() {
+    let fixture = create_fixture_with_data(false);
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    assert_eq!(
+        fixture.treasury.try_sync_supply(&xlm.address),
+        Err(Ok(Error::from_contract_error(SUPPLY_UNAVAILABLE)))
+    );
+}
@@ -0,0 +1,43 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as AddressTestTrait, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::ousd_token::OusdTokenClient,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const TOKEN_ADMIN_NOT_PENDING: u32 = 529;
+
+#[test]
+fn test_token_admin_rotation() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let ousd_token = OusdTokenClient::new(&fixture.env, &ousd.address);
+    let treasury = &fixture.treasury;
+    let governance = Address::generate(&fixture.env);
+    assert_eq!(ousd_token.admin(), treasury.address);
+
+    treasury.transfer_token_admin(&ousd.address, &governance);
+    assert_eq!(treasury.get_pending_token_admin(&ousd.address), Some(governance.clone()));
+    // the treasury keeps the admin until the transfer is accepted
+    assert_eq!(ousd_token.admin(), treasury.address);
+
+    treasury.accept_token_admin(&ousd.address);
+    assert_eq!(ousd_token.admin(), governance);
+    assert!(treasury.get_pending_token_admin(&ousd.address).is_none());
+
+    // the new admin mints
+    let frodo = &fixture.users[0];
+    let balance = ousd.balance(frodo);
+    ousd.mint(frodo, &SCALAR_7);
+    assert_eq!(ousd.balance(frodo), balance + SCALAR_7);
+}
+
+#[test]
+fn test_accept_without_pending_transfer_is_rejected() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let result = fixture.treasury.try_accept_token_admin(&ousd.address);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(TOKEN_ADMIN_NOT_PENDING))));
+}
@@ -0,0 +1,83 @@
+#![cfg(test)]
+use soroban_sdk::{vec, Error};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    dependencies::pegkeeper::{PegConfig, Strategy, TwapConfig},
+    fixture_builder::FixtureBuilder,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const TWAP_UNAVAILABLE: u32 = 112;
+const TWAP_DEVIATION_EXCEEDED: u32 = 113;
+const WINDOW: u32 = 10;
+
+/// A fixture with a pegged OUSD/USDC pair defended by the pegkeeper with a 10 ledger, 2% TWAP guard
+fn setup<'a>() -> TestFixture<'a> {
+    let fixture = FixtureBuilder::new()
+        .with_pair(TokenIndex::OUSD, TokenIndex::USDC, 1_000_000 * SCALAR_7, 1_000_000 * SCALAR_7)
+        .build();
+    let pegkeeper = &fixture.pegkeeper;
+    let ousd = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let usdc = fixture.tokens[TokenIndex::USDC].address.clone();
+    pegkeeper.set_peg_config(&PegConfig { stablecoin: ousd, threshold_bps: 100 });
+    pegkeeper.set_collaterals(&Strategy::Peg, &vec![&fixture.env, usdc]);
+    pegkeeper.set_twap_config(&Some(TwapConfig { window: WINDOW, max_deviation_bps: 200 }));
+    fixture
+}
+
+fn jump_ledgers(fixture: &TestFixture, ledgers: u32) {
+    fixture.jump_with_sequence(u64::from(ledgers) * 5);
+}
+
+#[test]
+fn test_twap_is_recorded_over_the_window() {
+    let fixture = setup();
+    let usdc = &fixture.tokens[TokenIndex::USDC].address;
+
+    assert_eq!(fixture.pegkeeper.record_price(usdc), 0);
+    jump_ledgers(&fixture, WINDOW / 2);
+    assert_eq!(fixture.pegkeeper.record_price(usdc), 0);
+    jump_ledgers(&fixture, WINDOW / 2);
+    assert_approx_eq_abs(fixture.pegkeeper.record_price(usdc), SCALAR_7, 10);
+}
+
+#[test]
+fn test_rebalance_without_price_history_is_rejected() {
+    let fixture = setup();
+    let usdc = &fixture.tokens[TokenIndex::USDC].address;
+    fixture.depeg(TokenIndex::OUSD, -500);
+
+    let result = fixture.pegkeeper.try_maybe_rebalance(usdc);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(TWAP_UNAVAILABLE))));
+}
+
+#[test]
+fn test_rebalance_on_manipulated_price_is_rejected() {
+    let fixture = setup();
+    let usdc = &fixture.tokens[TokenIndex::USDC].address;
+    fixture.pegkeeper.record_price(usdc);
+    jump_ledgers(&fixture, WINDOW);
+    fixture.pegkeeper.record_price(usdc);
+
+    // a sudden 5% move away from the TWAP
+    fixture.depeg(TokenIndex::OUSD, -500);
+    let result = fixture.pegkeeper.try_maybe_rebalance(usdc);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(TWAP_DEVIATION_EXCEEDED))));
+    let result = fixture.pegkeeper.try_contract_peg(&fixture.treasury.address, usdc, &SCALAR_7);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(TWAP_DEVIATION_EXCEEDED))));
+}
+
+#[test]
+fn test_rebalance_on_sustained_depeg_passes_the_guard() {
+    let fixture = setup();
+    let usdc = &fixture.tokens[TokenIndex::USDC].address;
+
+    // a 1.5% depeg held over the window moves the TWAP with it
+    fixture.depeg(TokenIndex::OUSD, -150);
+    fixture.pegkeeper.record_price(usdc);
+    jump_ledgers(&fixture, WINDOW);
+    fixture.pegkeeper.record_price(usdc);
+
+    let deviation = fixture.pegkeeper.maybe_rebalance(usdc);
+    assert!(deviation < -100);
+}
@@ -0,0 +1,39 @@
+#![cfg(test)]
+use soroban_sdk::{Error, Symbol};
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TokenIndex, SCALAR_7},
+};
+
+const UNAUTHORIZED: u32 = 503;
+
+#[test]
+fn test_unwind_position_burns_without_liabilities() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let usd = Symbol::new(&fixture.env, "USD");
+    treasury.increase_supply(&fixture.admin, &token, &(1_000 * SCALAR_7));
+    let supply = treasury.get_currency_supply(&usd);
+    let supplied = treasury.get_position_summary(&token).supplied;
+    let balance = fixture.tokens[TokenIndex::OUSD].balance(&treasury.address);
+
+    let burned = treasury.unwind_position(&fixture.admin, &token, &(400 * SCALAR_7));
+    assert_eq!(burned, 400 * SCALAR_7);
+    assert_eq!(treasury.get_currency_supply(&usd), supply - 400 * SCALAR_7);
+    assert!(treasury.get_position_summary(&token).supplied <= supplied - 400 * SCALAR_7 + 1);
+    // nothing withdrawn is left in the treasury
+    assert_eq!(fixture.tokens[TokenIndex::OUSD].balance(&treasury.address), balance);
+}
+
+#[test]
+fn test_unwind_position_requires_the_keeper_role() {
+    let fixture = create_fixture_with_data(false);
+    let token = fixture.tokens[TokenIndex::OUSD].address.clone();
+    let frodo = &fixture.users[0];
+
+    assert_eq!(
+        fixture.treasury.try_unwind_position(frodo, &token, &SCALAR_7),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+}
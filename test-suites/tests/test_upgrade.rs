@@ -0,0 +1,105 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::treasury::{Role, TREASURY_WASM},
+};
+
+const UNAUTHORIZED: u32 = 503;
+const UPGRADE_UNLOCKED: u32 = 551;
+const ROLLBACK_UNAVAILABLE: u32 = 552;
+const WASM_HASH_MISMATCH: u32 = 553;
+const STORAGE_MIGRATED: u32 = 554;
+const TIMELOCK_SECONDS: u64 = 120960 * 5;
+const ROLLBACK_WINDOW_SECONDS: u64 = 17280 * 5;
+
+#[test]
+fn test_guardian_vetoes_queued_upgrade_during_timelock() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let guardian = Address::generate(&fixture.env);
+    treasury.grant_role(&Role::Guardian, &guardian);
+    let wasm_hash = fixture.env.deployer().upload_contract_wasm(TREASURY_WASM);
+
+    treasury.queue_upgrade(&fixture.admin, &wasm_hash, &wasm_hash);
+    assert_eq!(
+        treasury.try_veto_upgrade(&fixture.users[0]),
+        Err(Ok(Error::from_contract_error(UNAUTHORIZED)))
+    );
+    treasury.veto_upgrade(&guardian);
+    assert!(treasury.get_queued_upgrade().is_none());
+
+    // once the timelock has passed, the upgrade can no longer be vetoed
+    treasury.queue_upgrade(&fixture.admin, &wasm_hash, &wasm_hash);
+    fixture.jump_with_sequence(TIMELOCK_SECONDS);
+    assert_eq!(
+        treasury.try_veto_upgrade(&guardian),
+        Err(Ok(Error::from_contract_error(UPGRADE_UNLOCKED)))
+    );
+    treasury.upgrade(&fixture.admin, &wasm_hash);
+}
+
+#[test]
+fn test_upgrade_rolls_back_within_window() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let wasm_hash = fixture.env.deployer().upload_contract_wasm(TREASURY_WASM);
+
+    // the running wasm is recorded when the first upgrade is queued, so it can be rolled back
+    treasury.queue_upgrade(&fixture.admin, &wasm_hash, &wasm_hash);
+    fixture.jump_with_sequence(TIMELOCK_SECONDS);
+    treasury.upgrade(&fixture.admin, &wasm_hash);
+    let rollback = treasury.get_rollback().unwrap();
+    assert_eq!(rollback.wasm_hash, wasm_hash);
+    treasury.rollback(&fixture.admin);
+    assert!(treasury.get_rollback().is_none());
+    assert_eq!(
+        treasury.try_rollback(&fixture.admin),
+        Err(Ok(Error::from_contract_error(ROLLBACK_UNAVAILABLE)))
+    );
+
+    // the rollback window closes after the configured number of ledgers
+    treasury.queue_upgrade(&fixture.admin, &wasm_hash, &wasm_hash);
+    fixture.jump_with_sequence(TIMELOCK_SECONDS);
+    treasury.upgrade(&fixture.admin, &wasm_hash);
+    fixture.jump_with_sequence(ROLLBACK_WINDOW_SECONDS + 5);
+    assert_eq!(
+        treasury.try_rollback(&fixture.admin),
+        Err(Ok(Error::from_contract_error(ROLLBACK_UNAVAILABLE)))
+    );
+}
+
+#[test]
+fn test_rollback_is_refused_after_a_migration() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let wasm_hash = fixture.env.deployer().upload_contract_wasm(TREASURY_WASM);
+
+    treasury.queue_upgrade(&fixture.admin, &wasm_hash, &wasm_hash);
+    fixture.jump_with_sequence(TIMELOCK_SECONDS);
+    treasury.upgrade(&fixture.admin, &wasm_hash);
+    let version = treasury.get_rollback().unwrap().version;
+    assert_eq!(version, treasury.get_version());
+
+    // the new wasm migrates the storage to a layout the previous wasm cannot read
+    fixture.write_treasury_version(version + 1);
+    assert_eq!(
+        treasury.try_rollback(&fixture.admin),
+        Err(Ok(Error::from_contract_error(STORAGE_MIGRATED)))
+    );
+    assert!(treasury.get_rollback().is_some());
+}
+
+#[test]
+fn test_queue_upgrade_requires_the_recorded_wasm_hash() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let wasm_hash = fixture.env.deployer().upload_contract_wasm(TREASURY_WASM);
+    let other_hash = BytesN::from_array(&fixture.env, &[1; 32]);
+
+    treasury.queue_upgrade(&fixture.admin, &wasm_hash, &wasm_hash);
+    assert_eq!(
+        treasury.try_queue_upgrade(&fixture.admin, &wasm_hash, &other_hash),
+        Err(Ok(Error::from_contract_error(WASM_HASH_MISMATCH)))
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(test)]
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Address, Error};
+use test_suites::{
+    create_fixture_with_data,
+    dependencies::pool::{Request, RequestType},
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+const WITHDRAWAL_NOT_FOUND: u32 = 543;
+const WITHDRAWAL_NOT_NEXT: u32 = 544;
+const INSUFFICIENT_LIQUIDITY: u32 = 545;
+
+/// Borrow all the OUSD in the pool but `left`, returning the borrower
+fn drain_ousd(fixture: &TestFixture, left: i128) -> Address {
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let borrower = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&borrower, &(20_000_000 * SCALAR_7));
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 20_000_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: ousd.address.clone(),
+            amount: ousd.balance(&pool.address) - left,
+        },
+    ];
+    pool.submit(&borrower, &borrower, &borrower, &requests);
+    borrower
+}
+
+#[test]
+fn test_decrease_supply_queues_what_the_pool_can_not_serve() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = &fixture.treasury;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let pool = &fixture.pools[0].pool;
+    let borrower = drain_ousd(&fixture, 500 * SCALAR_7);
+    let current_supply = || treasury.get_supply_at(&ousd.address, &fixture.env.ledger().sequence());
+    let supply = current_supply();
+
+    // the available liquidity is burnt and the rest is queued
+    treasury.decrease_supply(&fixture.admin, &ousd.address, &(1_000 * SCALAR_7));
+    let burned = supply - current_supply();
+    assert!(burned > 0 && burned <= 500 * SCALAR_7);
+    let withdrawal = treasury.get_withdrawal(&0).unwrap();
+    assert_eq!(withdrawal.amount, 1_000 * SCALAR_7 - burned);
+    assert_eq!(withdrawal.position, 0);
+    assert!(withdrawal.burn);
+
+    // later withdrawals queue behind it, even if the pool could serve them
+    treasury.decrease_supply(&fixture.admin, &ousd.address, &SCALAR_7);
+    assert_eq!(current_supply(), supply - burned);
+    assert_eq!(treasury.get_withdrawal(&1).unwrap().position, 1);
+    let queue = treasury.get_withdrawal_queue(&ousd.address);
+    assert_eq!((queue.head, queue.tail, queue.queued), (0, 2, 1_001 * SCALAR_7 - burned));
+
+    let result = treasury.try_claim(&1);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(WITHDRAWAL_NOT_NEXT))));
+    let result = treasury.try_claim(&0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(INSUFFICIENT_LIQUIDITY))));
+
+    // repayments restore the liquidity to claim the withdrawals in order
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Repay as u32,
+            address: ousd.address.clone(),
+            amount: 2_000 * SCALAR_7,
+        },
+    ];
+    pool.submit(&borrower, &borrower, &borrower, &requests);
+    assert_eq!(treasury.claim(&0), 1_000 * SCALAR_7 - burned);
+    assert_eq!(treasury.claim(&1), SCALAR_7);
+    assert_eq!(current_supply(), supply - 1_001 * SCALAR_7);
+    let queue = treasury.get_withdrawal_queue(&ousd.address);
+    assert_eq!((queue.head, queue.tail, queue.queued), (2, 2, 0));
+
+    assert!(treasury.get_withdrawal(&0).is_none());
+    let result = treasury.try_claim(&0);
+    assert_eq!(result, Err(Ok(Error::from_contract_error(WITHDRAWAL_NOT_FOUND))));
+}
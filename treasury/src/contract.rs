@@ -1,6 +1,13 @@
-use crate::storage;
-use crate::dependencies::pool::{Client as PoolClient, Request};
-use soroban_sdk::{contract, contractclient, contractimpl, log, panic_with_error, symbol_short, token, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+use crate::helper::{self, SCALAR_7, SCALAR_9, SCALAR_BPS};
+use crate::storage::{self, AutoSupplyConfig, BackstopConfig, BadDebt, BreakerConfig, BreakerState, CollateralConfig, DataKey, FeeModel, FeeRecipientConfig, FeeTierConfig, FlashCollateralConfig, FlashLoanConfig, FlashLoanRecord, FlashMintConfig, FxFeed, InsuranceConfig, LeverageConfig, MinterConfig, MultisigConfig, PolPosition, OracleConfig, QueuedUpgrade, Role, Rollback, Stream, SupplyLimit, SurplusConfig, TreasuryConfig, TreasuryStats, Withdrawal, WithdrawalQueue};
+use crate::dependencies::pool::{self, Client as PoolClient};
+use crate::dependencies::hook::{self, HookOp, HookStage};
+use crate::dependencies::oracle;
+use crate::dependencies::pegkeeper;
+use crate::dependencies::pair::Client as PairClient;
+use crate::dependencies::backstop::Client as BackstopClient;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, log, panic_with_error, symbol_short, token, vec, Address, Bytes, BytesN, Env, IntoVal, InvokeError, Symbol, Val, Vec};
 use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
 use crate::errors::TreasuryError;
 use sep_40_oracle::Asset;
@@ -8,6 +15,255 @@ use token::Client as TokenClient;
 use sep_41_token::StellarAssetClient;
 use token::StellarAssetClient as TokenAdminClient;
 
+/// The storage layout version of this contract code
+const CONTRACT_VERSION: u32 = 5;
+
+/// The treasury's position in the blend pool for a stablecoin
+#[derive(Clone)]
+#[contracttype]
+pub struct PositionSummary {
+    pub supplied: i128,    // underlying amount supplied by the treasury
+    pub b_tokens: i128,    // bTokens held by the treasury
+    pub d_tokens: i128,    // dTokens owed by the treasury
+    pub utilization: i128, // utilization of the reserve, in 7 decimals
+}
+
+/// The configuration of a treasury deployment, fetched in a single call
+#[derive(Clone)]
+#[contracttype]
+pub struct TreasuryInfo {
+    pub admin: Address,                            // admin of the treasury
+    pub pegkeeper: Address,                        // pegkeeper contract
+    pub bridge_oracle: Address,                    // oracle pricing stablecoins for blend pools
+    pub oracle: Option<OracleConfig>,              // oracle pricing stablecoins and collateral, if set
+    pub fee_model: FeeModel,                       // default flash loan fee
+    pub fee_recipient: Option<FeeRecipientConfig>, // recipient of a share of each fee
+    pub surplus_config: Option<SurplusConfig>,     // recipient of the surplus above a cap
+    pub max_fee_bps: u32,                          // maximum flash loan fee rate, in basis points
+    pub max_batch_loans: u32,                      // maximum number of loans in a flash loan batch
+    pub max_ltv_bps: u32,                          // maximum loan to value of treasury borrowing, in basis points
+    pub paused: bool,                              // whether the treasury is paused
+    pub version: u32,                              // version of the contract code
+    pub storage_version: u32,                      // storage layout version, behind the code until migrated
+}
+
+/// The blend reserve of a stablecoin in the treasury's primary pool, as reported by the pool
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolReserveData {
+    pub blend_pool: Address,   // blend pool of the reserve
+    pub c_factor: u32,         // collateral factor, in 7 decimals
+    pub l_factor: u32,         // liability factor, in 7 decimals
+    pub max_util: u32,         // maximum utilization, in 7 decimals
+    pub ir_mod: i128,          // interest rate modifier of the rate curve, in 9 decimals
+    pub b_rate: i128,          // conversion rate from bTokens to the underlying, in 9 decimals
+    pub d_rate: i128,          // conversion rate from dTokens to the underlying, in 9 decimals
+    pub total_supplied: i128,  // underlying amount supplied to the reserve
+    pub total_borrowed: i128,  // underlying amount borrowed from the reserve
+    pub utilization: i128,     // utilization of the reserve, in 7 decimals
+    pub backstop_credit: i128, // interest owed to the backstop, in the underlying
+    pub last_time: u64,        // timestamp the rates were last updated at
+}
+
+/// The outcome of a flash loan, as simulated before executing it
+#[derive(Clone)]
+#[contracttype]
+pub struct FlashLoanQuote {
+    pub fee: i128,                // fee charged on the loan
+    pub required_repayment: i128, // loan plus fee the receiver must repay
+    pub cap_remaining: i128,      // receiver's maximum amount minus the loan, negative if the loan is not allowed
+}
+
+/// The outcome of a supply decrease, as simulated before executing it
+#[derive(Clone)]
+#[contracttype]
+pub struct SupplyQuote {
+    pub b_tokens: i128,            // bTokens burnt from the treasury's position
+    pub remaining_supplied: i128,  // underlying amount supplied by the treasury after the decrease, negative if it is not covered
+    pub available_liquidity: i128, // underlying amount the reserve can currently pay out
+}
+
+/// The treasury's backstop position for a stablecoin's primary blend pool
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopPosition {
+    pub deposited: i128,     // backstop tokens deposited and not yet withdrawn
+    pub shares: i128,        // backstop shares held, excluding queued shares
+    pub queued_shares: i128, // backstop shares queued for withdrawal
+}
+
+/// The backing of a stablecoin's outstanding supply, valued with the oracle
+#[derive(Clone)]
+#[contracttype]
+pub struct BackingReport {
+    pub total_supply: i128,     // stablecoins minted by the treasury and not yet burnt
+    pub bridged_out: i128,      // stablecoins of the supply locked in the bridge adapter for other chains
+    pub circulating: i128,      // stablecoins of the supply circulating natively, excluding those bridged out
+    pub position_value: i128,   // USD value of the treasury's blend positions net of liabilities, in 7 decimals
+    pub surplus: i128,          // surplus buffer balance, in the stablecoin
+    pub collateral_ratio: i128, // value of the positions and surplus over the value of the supply, in 7 decimals
+}
+
+/// The treasury's protocol-owned liquidity in a stablecoin's soroswap pair, valued at the pair price
+#[derive(Clone)]
+#[contracttype]
+pub struct PolReport {
+    pub pair: Address,           // soroswap pair of the stablecoin and the counter asset
+    pub counter: Address,        // counter asset of the pair
+    pub lp_tokens: i128,         // pair LP tokens held by the treasury
+    pub token_amount: i128,      // stablecoins the LP tokens can be withdrawn for
+    pub counter_amount: i128,    // counter assets the LP tokens can be withdrawn for
+    pub token_seeded: i128,      // stablecoins deposited for the LP tokens still held
+    pub counter_seeded: i128,    // counter assets deposited for the LP tokens still held
+    pub impermanent_value: i128, // value of the liquidity over the value of holding the seeded assets, in the stablecoin
+}
+
+/// A parameter change executed by `batch_admin` or a multisig proposal
+#[derive(Clone)]
+#[contracttype]
+pub enum AdminOp {
+    SetFlashMint(Address, FlashMintConfig),               // token, config
+    SetFlashCollateral(Address, FlashCollateralConfig),   // collateral, config
+    AddFlashLoaner(Address, FlashLoanConfig),             // receiver, config
+    RemoveFlashLoaner(Address),                           // receiver
+    SetFeeBps(u32),                                       // default flash loan fee, in basis points
+    SetMinFee(i128),                                      // minimum flash loan fee
+    SetMinter(Address, Address, i128, i128),              // minter, token, daily cap, total cap
+    RemoveMinter(Address),                                // minter
+    SetSupplyLimit(SupplyLimit),                          // supply limit
+    SetStabilityFee(Address, i128),                       // token, rate
+    GrantRole(Role, Address),                             // role, account
+    RevokeRole(Role, Address),                            // role, account
+    SetAdmin(Address),                                    // admin, who must authorize the execution
+    SetMultisig(MultisigConfig),                          // signer set and threshold
+    DisableMultisig,                                      // return to single admin mode
+    CreateStream(Address, Address, i128, u64, u64),       // token, recipient, total, start, end
+    CancelStream(u32),                                    // stream id
+    SetLeverage(LeverageConfig),                          // leveraged backstop mode
+    PostCollateral(Address, Address, i128),               // blend pool, token, amount
+    Borrow(Address, Address, i128),                       // blend pool, token, amount
+}
+
+/// Parameter changes proposed by a multisig signer, executed once enough signers approve them
+#[derive(Clone)]
+#[contracttype]
+pub struct Proposal {
+    pub ops: Vec<AdminOp>,       // operations executed in order
+    pub approvals: Vec<Address>, // signers that approved the proposal, including the proposer
+    pub expiration: u32,         // last ledger sequence the proposal can be executed in
+    pub executed: bool,          // whether the proposal was executed
+}
+
+/// Execute a parameter change of `batch_admin` or a multisig proposal
+fn execute_admin_op(e: &Env, op: AdminOp) {
+    match op {
+        AdminOp::SetFlashMint(token, config) => helper::set_flash_mint(e, &token, &config),
+        AdminOp::SetFlashCollateral(collateral, config) => helper::set_flash_collateral(e, &collateral, &config),
+        AdminOp::AddFlashLoaner(receiver, config) => helper::add_flash_loaner(e, &receiver, &config),
+        AdminOp::RemoveFlashLoaner(receiver) => helper::remove_flash_loaner(e, &receiver),
+        AdminOp::SetFeeBps(fee_bps) => helper::set_fee_bps(e, fee_bps),
+        AdminOp::SetMinFee(min_fee) => helper::set_min_fee(e, min_fee),
+        AdminOp::SetMinter(minter, token, daily_cap, total_cap) => helper::set_minter(e, &minter, &token, daily_cap, total_cap),
+        AdminOp::RemoveMinter(minter) => helper::remove_minter(e, &minter),
+        AdminOp::SetSupplyLimit(limit) => helper::set_supply_limit(e, &limit),
+        AdminOp::SetStabilityFee(token, rate) => helper::set_stability_fee(e, &token, rate),
+        AdminOp::GrantRole(role, account) => {
+            storage::set_role(e, role, &account);
+            e.events().publish(("Treasury", Symbol::new(e, "grant_role")), (role, account));
+        }
+        AdminOp::RevokeRole(role, account) => {
+            storage::del_role(e, role, &account);
+            e.events().publish(("Treasury", Symbol::new(e, "revoke_role")), (role, account));
+        }
+        AdminOp::SetAdmin(admin) => {
+            admin.require_auth();
+            storage::set_admin(e, &admin);
+            e.events().publish(("Treasury", Symbol::new(e, "set_admin")), admin);
+        }
+        AdminOp::SetMultisig(config) => {
+            helper::require_valid_multisig(e, &config);
+            storage::set_multisig(e, &config);
+            e.events().publish(("Treasury", Symbol::new(e, "set_multisig")), (config.signers, config.threshold));
+        }
+        AdminOp::DisableMultisig => {
+            storage::del_multisig(e);
+            e.events().publish(("Treasury", Symbol::new(e, "disable_multisig")), ());
+        }
+        AdminOp::CreateStream(token, recipient, total, start, end) => {
+            helper::create_stream(e, &token, &recipient, total, start, end);
+        }
+        AdminOp::CancelStream(id) => helper::cancel_stream(e, id),
+        AdminOp::SetLeverage(config) => helper::set_leverage(e, &config),
+        AdminOp::PostCollateral(blend_pool, token, amount) => helper::post_collateral(e, &blend_pool, &token, amount),
+        AdminOp::Borrow(blend_pool, token, amount) => helper::borrow(e, &blend_pool, &token, amount),
+    }
+}
+
+/// Value a protocol-owned liquidity position at the current reserves of its pair
+fn pol_report(e: &Env, token: &Address, position: PolPosition) -> PolReport {
+    let pair_client = PairClient::new(e, &position.pair);
+    let (reserve_0, reserve_1) = pair_client.get_reserves();
+    let (token_reserve, counter_reserve) = if pair_client.token_0() == *token {
+        (reserve_0, reserve_1)
+    } else {
+        (reserve_1, reserve_0)
+    };
+    let total_lp = pair_client.total_supply();
+    let (token_amount, counter_amount) = if total_lp > 0 {
+        (
+            helper::require_no_overflow(e, position.lp_tokens.fixed_mul_floor(token_reserve, total_lp)),
+            helper::require_no_overflow(e, position.lp_tokens.fixed_mul_floor(counter_reserve, total_lp)),
+        )
+    } else {
+        (0, 0)
+    };
+
+    // value the counter asset in stablecoins at the pair price
+    let impermanent_value = if counter_reserve > 0 {
+        let lp_value = token_amount + helper::require_no_overflow(e, counter_amount.fixed_mul_floor(token_reserve, counter_reserve));
+        let held_value = position.token_seeded + helper::require_no_overflow(e, position.counter_seeded.fixed_mul_floor(token_reserve, counter_reserve));
+        lp_value - held_value
+    } else {
+        0
+    };
+
+    PolReport {
+        pair: position.pair,
+        counter: position.counter,
+        lp_tokens: position.lp_tokens,
+        token_amount,
+        counter_amount,
+        token_seeded: position.token_seeded,
+        counter_seeded: position.counter_seeded,
+        impermanent_value,
+    }
+}
+
+/// Summarize the treasury's position in a blend pool for a stablecoin
+fn position_summary(e: &Env, blend_pool: &Address, token: &Address) -> PositionSummary {
+    let reserve = pool::get_reserve(e, blend_pool, token);
+    let positions = PoolClient::new(e, blend_pool).get_positions(&e.current_contract_address());
+
+    let b_tokens = positions.supply.get(reserve.index).unwrap_or(0);
+    let d_tokens = positions.liabilities.get(reserve.index).unwrap_or(0);
+    let supplied = helper::require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+
+    let total_supply = helper::require_no_overflow(e, reserve.b_supply.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+    let total_liabilities = helper::require_no_overflow(e, reserve.d_supply.fixed_mul_ceil(reserve.d_rate, SCALAR_9));
+    let utilization = if total_supply == 0 {
+        0
+    } else {
+        helper::require_no_overflow(e, total_liabilities.fixed_div_ceil(total_supply, SCALAR_7))
+    };
+
+    PositionSummary {
+        supplied,
+        b_tokens,
+        d_tokens,
+        utilization,
+    }
+}
+
 #[contract]
 pub struct TreasuryContract;
 
@@ -35,139 +291,3277 @@ pub trait Treasury {
     /// If the caller is not the admin
     fn set_admin(e: Env, admin: Address);
 
-    /// Flashloan function for keeping the peg of stablecoins
+    /// (Admin only) Start handing the admin of a stablecoin's stellar asset contract, held by the
+    /// treasury to mint, to a new admin such as a new treasury or a governance contract. The new admin
+    /// takes over once it calls `accept_token_admin`.
     ///
     /// ### Arguments
-    /// * `caller` - The Address of the caller
-    /// * `token` - The Address of the token
-    /// * `liquidation` - The Address of the liquidation contract
-    /// * `amount` - The amount of the flashloan
-    fn keep_peg(e: Env, pair: Address, auction_creator: Address, token_a: Address, token_a_bid_amount: i128, token_b: Address, token_b_lot_amount: i128, liq_amount: i128);
+    /// * `token` - The Address of the stablecoin
+    /// * `new_admin` - The Address of the new admin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn transfer_token_admin(e: Env, token: Address, new_admin: Address);
 
-    /// (Admin only) Increase the supply of the pool
+    /// (Pending admin only) Accept the admin of a stablecoin's stellar asset contract
     ///
     /// ### Arguments
-    /// * `amount` - The amount to increase the supply by
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If no admin transfer of the stablecoin is pending or the caller is not the pending admin
+    fn accept_token_admin(e: Env, token: Address);
+
+    /// Fetch the pending admin of a stablecoin's stellar asset contract, if any
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_pending_token_admin(e: Env, token: Address) -> Option<Address>;
+
+    /// (Admin only) Add an account to or remove it from the deny-list. Denied accounts can not
+    /// receive flash loans, flash mints or minted stablecoins, or swap through the PSM.
+    ///
+    /// ### Arguments
+    /// * `account` - The Address of the account
+    /// * `denied` - Whether the account is denied
     ///
     /// ### Panics
     /// If the caller is not the admin
-    fn increase_supply(e: Env, token: Address, amount: i128);
-}
+    fn set_denied(e: Env, account: Address, denied: bool);
 
-#[contractimpl]
-impl Treasury for TreasuryContract {
+    /// (Admin only) Add accounts to or remove them from the deny-list in a single call
+    ///
+    /// ### Arguments
+    /// * `accounts` - The Addresses of the accounts
+    /// * `denied` - Whether the accounts are denied
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_denied_batch(e: Env, accounts: Vec<Address>, denied: bool);
 
-    fn initialize(e: Env, admin: Address, bridge_oracle: Address, pegkeeper: Address) {
-        storage::extend_instance(&e);
-        if storage::is_init(&e) {
-            panic_with_error!(&e, TreasuryError::AlreadyInitializedError);
-        }
+    /// Check if an account is on the deny-list
+    ///
+    /// ### Arguments
+    /// * `account` - The Address of the account
+    fn is_denied(e: Env, account: Address) -> bool;
 
-        storage::set_pegkeeper(&e, &pegkeeper);
-        storage::set_bridge_oracle(&e, &bridge_oracle);
-        storage::set_admin(&e, &admin);
-    }
+    /// (Admin only) Replace the hooks invoked before and after supply changes and flash loans.
+    /// Each hook's `on_treasury_op(op, stage, token, amount)` is invoked in a failure-isolated call,
+    /// so a broken hook can not block the operation.
+    ///
+    /// ### Arguments
+    /// * `hooks` - The Addresses of the hook contracts
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or there are more than `MAX_HOOKS` hooks
+    fn set_hooks(e: Env, hooks: Vec<Address>);
 
-    fn deploy_stablecoin(e: Env, token: Address, asset: Asset, blend_pool: Address) {
-        storage::extend_instance(&e);
+    /// Fetch the hooks invoked before and after supply changes and flash loans
+    fn get_hooks(e: Env) -> Vec<Address>;
 
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
+    /// (Admin only) Grant a role to an account
+    ///
+    /// ### Arguments
+    /// * `role` - The role
+    /// * `account` - The Address of the account
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn grant_role(e: Env, role: Role, account: Address);
 
-        let bridge_oracle = storage::get_bridge_oracle(&e);
-        let token_asset = Asset::Stellar(token.clone());
-        let add_asset_args = vec![
-            &e,
-            token_asset.into_val(&e),
-            asset.into_val(&e),
-        ];
+    /// (Admin only) Revoke a role from an account
+    ///
+    /// ### Arguments
+    /// * `role` - The role
+    /// * `account` - The Address of the account
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn revoke_role(e: Env, role: Role, account: Address);
 
-        e.invoke_contract::<Val>(&bridge_oracle, &Symbol::new(&e, "add_asset"), add_asset_args);
+    /// Check if an account holds a role. The admin and accounts granted the `Admin` role hold every role
+    ///
+    /// ### Arguments
+    /// * `role` - The role
+    /// * `account` - The Address of the account
+    fn has_role(e: Env, role: Role, account: Address) -> bool;
 
-        storage::set_blend_pool(&e, &token, &blend_pool);
-    }
+    /// (Admin only) Execute several parameter changes atomically, in order
+    ///
+    /// Each operation is validated and emits the same event as its standalone entrypoint.
+    ///
+    /// ### Arguments
+    /// * `ops` - The operations to execute
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or any operation is invalid
+    fn batch_admin(e: Env, ops: Vec<AdminOp>);
 
-    fn set_admin(e: Env, new_admin: Address) {
-        storage::extend_instance(&e);
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
-        new_admin.require_auth();
+    /// (Admin only) Put the treasury in multisig mode. Admin functions then reject the admin, and
+    /// parameter changes are executed through proposals approved by `threshold` of the signers.
+    /// Admin functions without an `AdminOp` are unavailable until the multisig is disabled by a proposal.
+    ///
+    /// ### Arguments
+    /// * `config` - The multisig config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the treasury is already in multisig mode, or the config is invalid
+    fn set_multisig(e: Env, config: MultisigConfig);
 
-        storage::set_admin(&e, &new_admin);
-    }
+    /// Fetch the multisig config, if the treasury is in multisig mode
+    fn get_multisig(e: Env) -> Option<MultisigConfig>;
 
-    fn increase_supply(e: Env, token: Address, amount: i128) {
-        storage::extend_instance(&e);
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
+    /// (Signer only) Propose parameter changes, approved by the proposer
+    ///
+    /// Returns the id of the proposal
+    ///
+    /// ### Arguments
+    /// * `signer` - The Address of the proposer
+    /// * `ops` - The operations to execute
+    ///
+    /// ### Panics
+    /// If the treasury is not in multisig mode or the caller is not a signer
+    fn propose(e: Env, signer: Address, ops: Vec<AdminOp>) -> u32;
 
-        let blend = storage::get_blend_pool(&e, &token);
-        StellarAssetClient::new(&e, &token).mint(&e.current_contract_address(), &amount);
-        let args: Vec<Val> = vec![
-            &e,
-            e.current_contract_address().into_val(&e),
-            blend.into_val(&e),
-            amount.into_val(&e),
-        ];
-        e.authorize_as_current_contract(vec![
-            &e,
-            InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: token.clone(),
-                    fn_name: Symbol::new(&e, "transfer"),
-                    args: args.clone(),
-                },
-                sub_invocations: vec![&e],
-            })
-        ]);
-        PoolClient::new(&e, &blend).submit(&e.current_contract_address(), &e.current_contract_address(), &e.current_contract_address(), &vec![
-            &e,
-            Request {
-                request_type: 0_u32, // SUPPLY RequestType
-                address: token.clone(),
-                amount,
-            },
-        ]);
-    }
+    /// (Signer only) Approve a proposal
+    ///
+    /// ### Arguments
+    /// * `signer` - The Address of the signer
+    /// * `id` - The id of the proposal
+    ///
+    /// ### Panics
+    /// If the caller is not a signer, or the proposal does not exist, has expired, or has been executed
+    fn approve(e: Env, signer: Address, id: u32);
 
-    fn keep_peg(e: Env, pair: Address, auction_creator: Address, token_a: Address, token_a_bid_amount: i128, token_b: Address, token_b_lot_amount: i128, liq_amount: i128) {
-        storage::extend_instance(&e);
-        
-        log!(&e, "================================= Real: Treasury FlashLoan Function Start ============================");
+    /// Execute a proposal approved by at least the threshold of the current signers
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the proposal
+    ///
+    /// ### Panics
+    /// If the proposal does not exist, has expired, or has been executed, does not have enough
+    /// approvals, or any operation is invalid
+    fn execute(e: Env, id: u32);
+
+    /// Fetch a multisig proposal, if any
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the proposal
+    fn get_proposal(e: Env, id: u32) -> Option<Proposal>;
+
+    /// (Pauser only) Pause flash loans, liquidations, minting and supply increases
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    ///
+    /// ### Panics
+    /// If the caller does not hold the pauser role
+    fn pause(e: Env, caller: Address);
+
+    /// (Pauser only) Unpause the treasury
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    ///
+    /// ### Panics
+    /// If the caller does not hold the pauser role
+    fn unpause(e: Env, caller: Address);
 
-        let pegkeeper: Address = storage::get_pegkeeper(&e);
-        let blend_pool: Address = storage::get_blend_pool(&e, &token_a);
+    /// Check if the treasury is paused
+    fn is_paused(e: Env) -> bool;
 
-        StellarAssetClient::new(&e, &token_a).mint(&pegkeeper, &token_a_bid_amount);
+    /// Fetch the configuration of the treasury: its admin, pegkeeper, oracles, fee model, caps,
+    /// pause state and version
+    ///
+    /// ### Panics
+    /// If the treasury is not initialized
+    fn get_config(e: Env) -> TreasuryInfo;
 
-        let token_client = TokenClient::new(&e, &token_a);
-        let token_balance_before = token_client.balance(&e.current_contract_address());
+    /// Fetch the version of the contract code
+    fn get_version(e: Env) -> u32;
 
-        // Execute operation
-        let fl_receive_args = vec![
-            &e,
-            pair.into_val(&e),
-            auction_creator.into_val(&e),
-            token_a.into_val(&e),
-            token_a_bid_amount.into_val(&e),
-            token_b.into_val(&e),
-            token_b_lot_amount.into_val(&e),
-            blend_pool.into_val(&e),
-            liq_amount.into_val(&e),
-        ];
-        e.invoke_contract::<Val>(&pegkeeper, &Symbol::new(&e, "fl_receive"), fl_receive_args);
+    /// (Keeper only) Flash loan stablecoins to a receiver to keep the peg of stablecoins
+    ///
+    /// The receiver's `fl_receive` function is invoked with the token, the loaned amount, the
+    /// calldata and the flash loan fee, and must approve the treasury to pull the amount plus the fee.
+    /// The calldata is executed by the receiver, so only keepers can choose it. The fee is discounted
+    /// by the keeper's fee tier, and the amount is added to the keeper's cumulative volume.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the keeper
+    /// * `receiver` - The Address of the whitelisted flash loan receiver, the registered pegkeeper or the keeper itself
+    /// * `token` - The Address of the stablecoin to loan
+    /// * `amount` - The amount of the flashloan
+    /// * `data` - The operation for the receiver to execute, opaque to the treasury
+    /// * `deadline` - The last ledger sequence the call can execute in, or None for no deadline
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role or the deadline has passed
+    /// If the receiver is neither the registered pegkeeper nor the caller
+    /// If the receiver is not a whitelisted contract, is disabled, or the amount exceeds its maximum
+    /// If called while another flash loan is in progress
+    fn keep_peg(e: Env, caller: Address, receiver: Address, token: Address, amount: i128, data: Bytes, deadline: Option<u64>);
 
-        let _ = token_client.try_transfer_from(&e.current_contract_address(), &pegkeeper, &e.current_contract_address(), &token_a_bid_amount);
-        // Check if the flashloan was fully repaid
+    /// (Keeper only) Flash loan stablecoins to the pegkeeper and the keeper itself atomically, e.g. for
+    /// arbitrage spanning both receiver contracts, paying the auth and storage overhead of a single flash loan
+    ///
+    /// Every loan is minted before any receiver is invoked, then each receiver's `fl_receive` function
+    /// is invoked in order with the token, its loaned amount, the calldata and its fee. Each receiver
+    /// must approve the treasury to pull its amount plus its fee, and all repayments are pulled once
+    /// every receiver has returned. The fees are discounted by the keeper's fee tier.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the keeper
+    /// * `token` - The Address of the stablecoin to loan
+    /// * `loans` - The whitelisted receivers and the amounts loaned to them
+    /// * `data` - The operation for the receivers to execute, opaque to the treasury
+    /// * `deadline` - The last ledger sequence the call can execute in, or None for no deadline
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role or the deadline has passed
+    /// If the batch is empty or has more than `MAX_BATCH_LOANS` loans
+    /// If a receiver is neither the registered pegkeeper nor the keeper
+    /// If a receiver is not a whitelisted contract, is disabled, or its amount exceeds its maximum
+    /// If called while another flash loan is in progress or a loan plus its fee is not repaid
+    fn flash_loan_batch(e: Env, caller: Address, token: Address, loans: Vec<(Address, i128)>, data: Bytes, deadline: Option<u64>);
 
-        let token_balance_after = token_client.balance(&e.current_contract_address());
-        log!(&e, "================================= Real: After FlashLoan Function {} {} ============================", token_balance_before, token_balance_after);
-        if token_balance_after.clone() < token_balance_before.clone() + token_a_bid_amount.clone() {
-            panic_with_error!(&e, TreasuryError::FlashloanNotRepaid);
-        }
+    /// Flash mint stablecoins to any receiver, without a blend pool round-trip
+    ///
+    /// The receiver's `fl_receive` function is invoked with the token, the minted amount, the
+    /// calldata and the flash mint fee, and must approve the treasury to pull the amount plus the fee.
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the receiver
+    /// * `token` - The Address of the stablecoin to mint
+    /// * `amount` - The amount to mint
+    /// * `data` - The calldata passed to the receiver
+    /// * `deadline` - The last ledger sequence the call can execute in, or None for no deadline
+    ///
+    /// ### Panics
+    /// If flash minting is not enabled for the token, the amount exceeds the flash mint cap, or the deadline has passed
+    /// If called while another flash loan is in progress or the loan plus the fee is not repaid
+    fn flash_mint(e: Env, receiver: Address, token: Address, amount: i128, data: Bytes, deadline: Option<u64>);
+
+    /// (Admin only) Set the flash mint cap and fee of a token, enabling flash minting
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `config` - The flash mint config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the cap is negative, or the fee is above 10%
+    fn set_flash_mint(e: Env, token: Address, config: FlashMintConfig);
 
-        // Burn the tokens
-        token_client.burn(&e.current_contract_address(), &(token_balance_after.clone() - token_balance_before.clone()));
-        log!(&e, "================================= Real: Treasury FlashLoan Function End ============================");
+    /// (Admin only) Set the circuit breaker of a token, which disables supply increases and flash
+    /// mints while the oracle price deviates from the USD price of the token's currency by more than the
+    /// threshold for longer than the delay
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `config` - The circuit breaker config, or None to remove the breaker
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the threshold is above 100%
+    fn set_breaker(e: Env, token: Address, config: Option<BreakerConfig>);
+
+    /// Record the circuit breaker state of a token at the current oracle price, so a depeg is timed
+    /// from the first ledger it is observed
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the token has no circuit breaker or the oracle price is missing or stale
+    fn poke_breaker(e: Env, token: Address) -> BreakerState;
+
+    /// Fetch the circuit breaker state of a token at the current oracle price, if it has a breaker
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the oracle price is missing or stale
+    fn get_breaker(e: Env, token: Address) -> Option<BreakerState>;
+
+    /// Fetch the flash mint config of a token, if flash minting is enabled
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_flash_mint(e: Env, token: Address) -> Option<FlashMintConfig>;
+
+    /// Flash loan a collateral held by the treasury to any receiver, from its idle balance and then
+    /// from its blend position
+    ///
+    /// The receiver's `fl_receive` function is invoked with the collateral, the loaned amount, the
+    /// calldata and the fee, and must approve the treasury to pull the amount plus the fee.
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the receiver
+    /// * `collateral` - The Address of the collateral to loan
+    /// * `amount` - The amount to loan
+    /// * `data` - The calldata passed to the receiver
+    /// * `deadline` - The last ledger sequence the call can execute in, or None for no deadline
+    ///
+    /// ### Panics
+    /// If flash loans of the collateral are not enabled, the amount exceeds the cap, or the treasury holds too little
+    /// If the deadline has passed
+    /// If called while another flash loan is in progress or the loan plus the fee is not repaid
+    fn flash_collateral(e: Env, receiver: Address, collateral: Address, amount: i128, data: Bytes, deadline: Option<u64>);
+
+    /// (Admin only) Set the flash loan cap, fee and source pool of a collateral, enabling flash loans of it
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the collateral
+    /// * `config` - The flash loan config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the cap is negative, the fee is above 10%, or the pool is not a blend pool of a stablecoin
+    fn set_flash_collateral(e: Env, collateral: Address, config: FlashCollateralConfig);
+
+    /// Fetch the flash loan config of a collateral, if flash loans of it are enabled
+    ///
+    /// ### Arguments
+    /// * `collateral` - The Address of the collateral
+    fn get_flash_collateral(e: Env, collateral: Address) -> Option<FlashCollateralConfig>;
+
+    /// (Admin only) Register a collateral with its risk parameters, or update them. The registry is
+    /// the set of collaterals accepted by redemptions and whitelisted on the pegkeeper for liquidations.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the collateral
+    /// * `oracle_feed` - The asset the collateral is priced as by the treasury's oracle
+    /// * `ltv` - The maximum debt against the collateral's value, in basis points
+    /// * `liquidation_bonus` - The discount on the collateral's value paid to liquidators, in basis points
+    /// * `cap` - The maximum amount of the collateral paid out by a single redemption
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the ltv or liquidation bonus is above 100%, or the cap is negative
+    fn add_collateral(e: Env, token: Address, oracle_feed: Asset, ltv: u32, liquidation_bonus: u32, cap: i128);
+
+    /// (Admin only) Remove a collateral from the registry
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the collateral
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the collateral is not registered
+    fn remove_collateral(e: Env, token: Address);
+
+    /// Fetch the risk parameters of a collateral, if it is registered
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the collateral
+    fn get_collateral(e: Env, token: Address) -> Option<CollateralConfig>;
+
+    /// Fetch the registered collaterals, in registration order
+    fn get_collaterals(e: Env) -> Vec<Address>;
+
+    /// (Keeper only) Fill a blend liquidation auction, through the token's stability pool if it can
+    /// cover the debt amount, otherwise by flash loaning stablecoins to the pegkeeper
+    ///
+    /// On the flash loan path, the pegkeeper's `fl_receive` function is invoked with a `Liquidate` operation
+    /// and must approve the treasury to pull the amount plus the flash loan fee.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `receiver` - The Address of the flash loan receiver, which must be the registered pegkeeper
+    /// * `token` - The Address of the stablecoin
+    /// * `user` - The Address of the user being liquidated
+    /// * `debt_amount` - The amount of stablecoin debt to repay
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, or the receiver is not the registered pegkeeper
+    /// If the receiver is disabled, or the amount exceeds its maximum
+    /// If the loan plus the fee is not repaid
+    fn liquidate(e: Env, caller: Address, receiver: Address, token: Address, user: Address, debt_amount: i128);
+
+    /// (Keeper only) Fill a blend auction of the token's primary pool in full by flash loaning
+    /// stablecoins to the pegkeeper, which pays the bid and sells the lot
+    ///
+    /// The loaned amount is quoted by the pegkeeper from the auction's bid, scaled by the blocks the
+    /// auction has run for, so keepers only pick the auction. The pegkeeper's `fl_receive` function
+    /// is invoked with a `FillAuction` operation and must repay the loan plus the flash loan fee.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `auction_type` - The blend auction type, 0 for user liquidations, 1 for bad debt, 2 for interest
+    /// * `user` - The Address the auction was created for, the backstop for bad debt and interest auctions
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role
+    /// If the auction does not exist or can not be filled by the pegkeeper
+    /// If the loan plus the fee is not repaid, or the fill realizes less than the pegkeeper's minimum profit
+    fn fill_auction(e: Env, caller: Address, token: Address, auction_type: u32, user: Address);
+
+    /// (Admin only) Invoke an admin-only function on the pegkeeper, which is administered by the treasury
+    ///
+    /// ### Arguments
+    /// * `fn_name` - The pegkeeper function to invoke
+    /// * `args` - The arguments of the function
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn call_pegkeeper(e: Env, fn_name: Symbol, args: Vec<Val>);
+
+    /// (Admin only) Set the stability pool liquidations of a token are routed through
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `stability_pool` - The Address of the stability pool
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_stability_pool(e: Env, token: Address, stability_pool: Address);
+
+    /// (Admin only) Whitelist a new flash loan receiver
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the flash loan receiver
+    /// * `config` - The flash loan config for the receiver
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the receiver is already whitelisted or is not a contract
+    /// If the fee is above 10% or the max amount is not positive
+    fn add_flash_loaner(e: Env, receiver: Address, config: FlashLoanConfig);
+
+    /// (Admin only) Update the config of a whitelisted flash loan receiver
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the flash loan receiver
+    /// * `config` - The new flash loan config for the receiver
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the receiver is not whitelisted
+    /// If the fee is above 10% or the max amount is not positive
+    fn update_flash_loaner(e: Env, receiver: Address, config: FlashLoanConfig);
+
+    /// (Admin only) Remove a flash loan receiver from the whitelist
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the flash loan receiver
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn remove_flash_loaner(e: Env, receiver: Address);
+
+    /// Fetch the flash loan config of a receiver, if it is whitelisted
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the flash loan receiver
+    fn get_flash_loaner(e: Env, receiver: Address) -> Option<FlashLoanConfig>;
+
+    /// Fetch the most recent flash loans, oldest first
+    fn get_recent_flash_loans(e: Env) -> Vec<FlashLoanRecord>;
+
+    /// Fetch the lifetime counters of the treasury: flash loan volume, fees earned, mints, burns
+    /// and liquidations filled
+    fn get_stats(e: Env) -> TreasuryStats;
+
+    /// (Fee manager only) Set the default flash loan fee rate
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `fee_bps` - The fee charged on the borrowed amount, in basis points
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or the fee is above 10%
+    fn set_fee_bps(e: Env, caller: Address, fee_bps: u32);
+
+    /// (Fee manager only) Set the minimum fee charged on any flash loan
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `min_fee` - The minimum fee
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or the fee is negative
+    fn set_min_fee(e: Env, caller: Address, min_fee: i128);
+
+    /// (Fee manager only) Set the flash loan fee tiers of keepers. Keepers with enough cumulative
+    /// flash loan volume or staked governance tokens get the largest discount of the tiers they qualify for.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `config` - The fee tier config
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role, there are more than `MAX_FEE_TIERS` tiers,
+    /// a threshold is not positive, or a discount is above 100%
+    fn set_fee_tiers(e: Env, caller: Address, config: FeeTierConfig);
+
+    /// Fetch the flash loan fee tiers of keepers, if any
+    fn get_fee_tiers(e: Env) -> Option<FeeTierConfig>;
+
+    /// Fetch the cumulative flash loan volume of a keeper
+    ///
+    /// ### Arguments
+    /// * `keeper` - The Address of the keeper
+    fn get_keeper_volume(e: Env, keeper: Address) -> i128;
+
+    /// Fetch the flash loan fee a keeper pays under the treasury's default fee model, after its fee tier discount
+    ///
+    /// Returns the discounted fee
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the keeper
+    /// * `amount` - The amount of the flash loan
+    ///
+    /// ### Panics
+    /// If the amount is zero or negative
+    fn get_fee_for(e: Env, caller: Address, amount: i128) -> i128;
+
+    /// (Admin only) Set the oracle used to price stablecoins and collateral
+    ///
+    /// ### Arguments
+    /// * `config` - The oracle config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_oracle(e: Env, config: OracleConfig);
+
+    /// Fetch the USD price of an asset from the oracle, scaled to 7 decimals
+    ///
+    /// ### Arguments
+    /// * `asset` - The Address of the asset
+    ///
+    /// ### Panics
+    /// If the oracle has no price for the asset or the price is stale
+    fn get_price(e: Env, asset: Address) -> i128;
+
+    /// (Admin only) Set the recipient of the surplus buffer and the cap above which surplus is forwarded to it
+    ///
+    /// ### Arguments
+    /// * `config` - The surplus config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the cap is negative
+    fn set_surplus_recipient(e: Env, config: SurplusConfig);
+
+    /// (Admin only) Route a share of every flash loan and redemption fee directly to an external recipient
+    /// instead of the surplus buffer
+    ///
+    /// ### Arguments
+    /// * `recipient` - The Address receiving its share of fees
+    /// * `split_bps` - The share of each fee sent to the recipient, in basis points
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the split is above 100%
+    fn set_fee_recipient(e: Env, recipient: Address, split_bps: u32);
+
+    /// Fetch the fee recipient config, if any
+    fn get_fee_recipient(e: Env) -> Option<FeeRecipientConfig>;
+
+    /// (Admin only) Route a share of the fees retained by the treasury in the fund's token to an
+    /// insurance fund, which covers bad debt left by liquidations
+    ///
+    /// ### Arguments
+    /// * `config` - The insurance fund config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the split is above 100%
+    fn set_insurance_fund(e: Env, config: InsuranceConfig);
+
+    /// Fetch the insurance fund config, if any
+    fn get_insurance_fund(e: Env) -> Option<InsuranceConfig>;
+
+    /// (Admin only) Transfer tokens accidentally sent to the treasury
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `to` - The Address receiving the tokens
+    /// * `amount` - The amount to transfer
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the token is tracked by the treasury's accounting, the amount is not
+    /// positive, or the amount exceeds the treasury's balance
+    fn sweep(e: Env, token: Address, to: Address, amount: i128);
+
+    /// (Admin only) Transfer tokens accidentally sent to the pegkeeper
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `to` - The Address receiving the tokens
+    /// * `amount` - The amount to transfer
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the token is tracked by the treasury's accounting, the amount is not
+    /// positive, or the amount exceeds the pegkeeper's balance
+    fn sweep_pegkeeper(e: Env, token: Address, to: Address, amount: i128);
+
+    /// (Admin only) Set the bridge adapter, which locks stablecoins issued on other chains
+    ///
+    /// ### Arguments
+    /// * `adapter` - The Address of the bridge adapter
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_bridge_adapter(e: Env, adapter: Address);
+
+    /// Fetch the bridge adapter, if any
+    fn get_bridge_adapter(e: Env) -> Option<Address>;
+
+    /// (Bridge adapter only) Record stablecoins locked in the bridge adapter for another chain
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount locked
+    ///
+    /// ### Panics
+    /// If the caller is not the bridge adapter, the token is not registered, or the amount is not positive
+    fn record_bridge_out(e: Env, token: Address, amount: i128);
+
+    /// (Bridge adapter only) Record stablecoins released by the bridge adapter on their return from another chain
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount released
+    ///
+    /// ### Panics
+    /// If the caller is not the bridge adapter, the amount is not positive, or the amount exceeds the supply bridged out
+    fn record_bridge_in(e: Env, token: Address, amount: i128);
+
+    /// (Admin only) Record stablecoins left unbacked by a liquidation or shutdown as bad debt, and
+    /// cover as much of the outstanding bad debt as possible by burning the surplus buffer, then
+    /// payouts of the insurance fund
+    ///
+    /// Returns the bad debt of the stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount of unbacked stablecoins
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the amount is not positive
+    fn record_bad_debt(e: Env, token: Address, amount: i128) -> BadDebt;
+
+    /// Cover as much of the outstanding bad debt of a stablecoin as possible by burning the surplus
+    /// buffer, then payouts of the insurance fund, e.g. after the insurance fund is refilled
+    ///
+    /// Returns the bad debt of the stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn cover_bad_debt(e: Env, token: Address) -> BadDebt;
+
+    /// (Admin only) Write off outstanding bad debt that can not be covered, socializing the loss
+    /// across the holders of the stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount of bad debt to write off
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the amount is not positive, or the amount exceeds the outstanding bad debt
+    fn write_off(e: Env, token: Address, amount: i128);
+
+    /// Fetch the bad debt of a stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_bad_debt(e: Env, token: Address) -> BadDebt;
+
+    /// (Admin only) Reconcile the recorded outstanding supply of a stablecoin with the total supply
+    /// reported by the token, e.g. after a partially failed operation or mints by another token admin.
+    /// The whole total supply is attributed to the treasury, so the admin must confirm no other issuer
+    /// holds the token's admin. The stability fee is accrued on the recorded supply first.
+    ///
+    /// Returns the drift, the total supply less the previously recorded supply
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    /// If the token does not report its total supply, e.g. a Stellar asset contract
+    fn sync_supply(e: Env, token: Address) -> i128;
+
+    /// Check the core accounting invariants of a stablecoin, for tests and on-chain monitoring: the
+    /// recorded supply does not exceed the total supply reported by the token, the recorded supply is
+    /// backed by the treasury's pool positions, minters, protocol-owned liquidity and outstanding bad
+    /// debt, and the surplus buffer is not negative
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If an invariant does not hold
+    fn assert_invariants(e: Env, token: Address);
+
+    /// (Fee manager only) Transfer tokens from the surplus buffer to the surplus recipient
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to transfer
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role, no recipient is set, or the amount exceeds the surplus
+    fn transfer_surplus(e: Env, caller: Address, token: Address, amount: i128);
+
+    /// Deposit tokens into the surplus buffer, e.g. pegkeeper profits
+    ///
+    /// ### Arguments
+    /// * `from` - The Address depositing the tokens
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to deposit
+    ///
+    /// ### Panics
+    /// If the amount is zero or negative
+    fn deposit_surplus(e: Env, from: Address, token: Address, amount: i128);
+
+    /// Fetch the surplus buffer balance for a token
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    fn get_surplus(e: Env, token: Address) -> i128;
+
+    /// (Admin only) Stream a payment out of the surplus buffer, vesting linearly between two
+    /// timestamps, e.g. for contributor or grant payments. The total is reserved out of the surplus
+    /// buffer when the stream is created.
+    ///
+    /// Returns the id of the stream
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `recipient` - The Address receiving the payments
+    /// * `total` - The amount paid out over the whole stream
+    /// * `start` - The timestamp the payments start vesting at
+    /// * `end` - The timestamp the whole amount is vested at
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the total is not positive or exceeds the surplus, or the
+    /// stream ends before it starts
+    fn create_stream(e: Env, token: Address, recipient: Address, total: i128, start: u64, end: u64) -> u32;
+
+    /// (Recipient only) Withdraw the vested amount of a payment stream not yet withdrawn
+    ///
+    /// Returns the amount withdrawn
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the stream
+    ///
+    /// ### Panics
+    /// If the stream does not exist or the caller is not its recipient
+    fn withdraw_stream(e: Env, id: u32) -> i128;
+
+    /// (Admin only) Cancel a payment stream, paying the vested amount not yet withdrawn to the
+    /// recipient and returning the unvested amount to the surplus buffer
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the stream
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the stream does not exist
+    fn cancel_stream(e: Env, id: u32);
+
+    /// Fetch a payment stream, if any
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the stream
+    fn get_stream(e: Env, id: u32) -> Option<Stream>;
+
+    /// (Admin only) Set the leveraged backstop mode, letting the treasury post collateral to and borrow
+    /// from blend pools up to a maximum loan-to-value ratio
+    ///
+    /// ### Arguments
+    /// * `config` - The leverage config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the maximum loan-to-value ratio is above `MAX_LTV_BPS`
+    fn set_leverage(e: Env, config: LeverageConfig);
+
+    /// Fetch the leverage config, if any
+    fn get_leverage(e: Env) -> Option<LeverageConfig>;
+
+    /// (Admin only) Post tokens held by the treasury as collateral in a blend pool
+    ///
+    /// ### Arguments
+    /// * `blend_pool` - The Address of the blend pool
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to post
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the amount is not positive, the pool is not registered for the
+    /// token, or the treasury's balance does not cover the amount
+    fn post_collateral(e: Env, blend_pool: Address, token: Address, amount: i128);
+
+    /// (Admin only) Borrow tokens from a blend pool against the treasury's collateral in it
+    ///
+    /// ### Arguments
+    /// * `blend_pool` - The Address of the blend pool
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to borrow
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, borrowing is not enabled, the amount is not positive, or the
+    /// loan-to-value ratio would exceed the maximum of the leverage config
+    fn borrow(e: Env, blend_pool: Address, token: Address, amount: i128);
+
+    /// (Keeper only) Repay the treasury's liabilities in a blend pool with tokens held by the treasury
+    ///
+    /// Returns the amount repaid, at most the treasury's liabilities of the token
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `blend_pool` - The Address of the blend pool
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to repay
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role or the amount is not positive
+    fn repay(e: Env, caller: Address, blend_pool: Address, token: Address, amount: i128) -> i128;
+
+    /// Fetch the loan-to-value ratio of the treasury's position in a blend pool, in basis points
+    ///
+    /// ### Arguments
+    /// * `blend_pool` - The Address of the blend pool
+    fn get_ltv(e: Env, blend_pool: Address) -> i128;
+
+    /// (Admin only) Set the savings vault treasury yield of a token is distributed to
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `vault` - The Address of the savings vault
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_savings_vault(e: Env, token: Address, vault: Address);
+
+    /// (Fee manager only) Distribute yield from the surplus buffer to the token's savings vault
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the token
+    /// * `amount` - The amount to distribute
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role, no savings vault is set, or the amount exceeds the surplus
+    fn distribute_yield(e: Env, caller: Address, token: Address, amount: i128);
+
+    /// (Admin only) Set the blend backstop and the share of the treasury's backstop tokens that can be deposited
+    ///
+    /// ### Arguments
+    /// * `config` - The backstop config
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the allocation is above 100%
+    fn set_backstop_config(e: Env, config: BackstopConfig);
+
+    /// (Fee manager only) Deposit backstop tokens from the surplus buffer into the backstop of a
+    /// stablecoin's primary blend pool. Returns the backstop shares minted.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount of backstop tokens to deposit
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or the backstop config is not set
+    /// If the amount exceeds the surplus of backstop tokens, or the deposits would exceed the allocation
+    fn deposit_backstop(e: Env, caller: Address, token: Address, amount: i128) -> i128;
+
+    /// (Fee manager only) Queue backstop shares of a stablecoin's primary blend pool for withdrawal
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `shares` - The amount of backstop shares to queue
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or the backstop config is not set
+    fn queue_backstop_withdrawal(e: Env, caller: Address, token: Address, shares: i128);
+
+    /// (Fee manager only) Withdraw queued backstop shares of a stablecoin's primary blend pool into
+    /// the surplus buffer. Returns the backstop tokens withdrawn.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `shares` - The amount of backstop shares to withdraw
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or the backstop config is not set
+    /// If the shares are not queued or still locked
+    fn withdraw_backstop(e: Env, caller: Address, token: Address, shares: i128) -> i128;
+
+    /// Fetch the treasury's backstop position for a stablecoin's primary blend pool
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the backstop config is not set
+    fn get_backstop_position(e: Env, token: Address) -> BackstopPosition;
+
+    /// (Admin only) Register a contract allowed to mint a stablecoin through the treasury within
+    /// quotas, e.g. the PSM or a bridge, or update the quotas of a registered minter
+    ///
+    /// ### Arguments
+    /// * `minter` - The Address of the minter
+    /// * `token` - The Address of the stablecoin it can mint
+    /// * `daily_cap` - The maximum amount the minter can mint in a day
+    /// * `total_cap` - The maximum amount the minter can have outstanding
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or either cap is negative
+    fn set_minter(e: Env, minter: Address, token: Address, daily_cap: i128, total_cap: i128);
+
+    /// (Admin only) Remove a minter
+    ///
+    /// ### Arguments
+    /// * `minter` - The Address of the minter
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn remove_minter(e: Env, minter: Address);
+
+    /// Fetch the minter config of a minter, if it is registered
+    ///
+    /// ### Arguments
+    /// * `minter` - The Address of the minter
+    fn get_minter(e: Env, minter: Address) -> Option<MinterConfig>;
+
+    /// Fetch the amount a minter can still mint today, within both its daily and total caps
+    ///
+    /// ### Arguments
+    /// * `minter` - The Address of the minter
+    ///
+    /// Returns 0 if the minter is not registered
+    fn get_mint_allowance(e: Env, minter: Address) -> i128;
+
+    /// (Minter only) Mint stablecoins
+    ///
+    /// ### Arguments
+    /// * `minter` - The Address of the minter
+    /// * `to` - The Address receiving the stablecoins
+    /// * `amount` - The amount to mint
+    ///
+    /// ### Panics
+    /// If the minter is not registered, the recipient is denied, or the amount would exceed its daily or total cap
+    fn mint(e: Env, minter: Address, to: Address, amount: i128);
+
+    /// (Minter only) Burn stablecoins held by the minter, reducing its outstanding amount
+    ///
+    /// ### Arguments
+    /// * `minter` - The Address of the minter
+    /// * `amount` - The amount to burn
+    ///
+    /// ### Panics
+    /// If the minter is not registered or the amount exceeds its outstanding amount
+    fn burn(e: Env, minter: Address, amount: i128);
+
+    /// (Keeper only) Increase the supply of the pool
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `amount` - The amount to increase the supply by
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, a flash loan is in progress, the amount is not
+    /// positive, or the supply limit is exceeded
+    fn increase_supply(e: Env, caller: Address, token: Address, amount: i128);
+
+    /// Redeem stablecoins for collateral from the treasury's blend position, valuing the stablecoins at the
+    /// USD price of their currency and the collateral at the oracle price, minus the redemption fee. The fee is split between the fee recipient and the surplus buffer, and the rest is burnt.
+    /// Collateral the pool does not have the liquidity to pay out is queued as a withdrawal claimable with `claim`.
+    ///
+    /// ### Arguments
+    /// * `from` - The Address redeeming
+    /// * `token` - The Address of the stablecoin
+    /// * `collateral` - The Address of the collateral to receive
+    /// * `amount` - The amount of stablecoins to redeem
+    /// * `min_collateral_out` - The minimum amount of collateral to receive
+    /// * `deadline` - The last ledger sequence the call can execute in, or None for no deadline
+    ///
+    /// Returns the amount of collateral received immediately
+    ///
+    /// ### Panics
+    /// If the amount is not positive, the collateral is not registered, the collateral redeemed is below
+    /// the minimum or above the collateral's cap, the treasury's position does not hold enough collateral,
+    /// or the deadline has passed
+    /// If the stablecoin is not pegged to USD and its FX price is missing or stale
+    fn redeem(e: Env, from: Address, token: Address, collateral: Address, amount: i128, min_collateral_out: i128, deadline: Option<u64>) -> i128;
+
+    /// (Fee manager only) Set the redemption fee
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `fee_bps` - The fee charged on redeemed stablecoins, in basis points
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or the fee is above 10%
+    fn set_redemption_fee(e: Env, caller: Address, fee_bps: u32);
+
+    /// (Admin only) Limit the supply increased and decreased within a window of ledgers, relative
+    /// to the supply at the start of the window
+    ///
+    /// ### Arguments
+    /// * `limit` - The supply limit
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the window is zero
+    fn set_supply_limit(e: Env, limit: SupplyLimit);
+
+    /// (Keeper only) Decrease the supply of the pool, withdrawing and burning stablecoins. Stablecoins the
+    /// pool does not have the liquidity to pay out are queued as a withdrawal burnt when it is claimed.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount to decrease the supply by
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, a flash loan is in progress, the supply limit is
+    /// exceeded, the stablecoin is not a reserve of the pool, or the treasury's supply does not cover the amount
+    fn decrease_supply(e: Env, caller: Address, token: Address, amount: i128);
+
+    /// (Keeper only) Unwind the treasury's position in the pool, withdrawing stablecoins, repaying the
+    /// treasury's liabilities of the stablecoin in the pool, and burning the rest in one call
+    ///
+    /// Returns the amount burnt
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount to withdraw
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, a flash loan is in progress, the supply limit is
+    /// exceeded, the stablecoin is not a reserve of the pool, or the treasury's supply does not cover the amount
+    fn unwind_position(e: Env, caller: Address, token: Address, amount: i128) -> i128;
+
+    /// (Admin only) Set the utilization band the supply of a stablecoin is automatically adjusted to
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `config` - The automatic supply adjustment config, or None to disable automatic adjustments
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the band is empty or above 100%, the target is outside the band,
+    /// the step is not positive, or the interval is zero
+    fn set_auto_supply(e: Env, token: Address, config: Option<AutoSupplyConfig>);
+
+    /// Fetch the automatic supply adjustment config of a stablecoin, if any
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_auto_supply(e: Env, token: Address) -> Option<AutoSupplyConfig>;
+
+    /// (Keeper only) Adjust the supply of a stablecoin to its utilization band in its primary blend pool,
+    /// so issuance tracks borrowing demand. The supply is increased once the utilization is above the band
+    /// and decreased once it is below, towards the target utilization and by at most the step limit.
+    ///
+    /// Returns the amount the supply was increased by, or decreased by if negative
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, a flash loan is in progress, automatic adjustments are
+    /// not configured, the last adjustment was less than the interval ago, or an increase exceeds the
+    /// supply limit or is blocked by the circuit breaker
+    fn auto_adjust_supply(e: Env, caller: Address, token: Address) -> i128;
+
+    /// Claim a withdrawal queued by a redemption or a supply decrease once the pool has the liquidity
+    /// to serve it, paying the asset to its recipient or burning it. Withdrawals of an asset are
+    /// claimed in the order they were queued in.
+    ///
+    /// Returns the amount withdrawn
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the withdrawal
+    ///
+    /// ### Panics
+    /// If the withdrawal does not exist, an earlier withdrawal of the asset is still queued, or the
+    /// pool does not have the liquidity to serve it
+    fn claim(e: Env, id: u32) -> i128;
+
+    /// Fetch a queued withdrawal, if any
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the withdrawal
+    fn get_withdrawal(e: Env, id: u32) -> Option<Withdrawal>;
+
+    /// Fetch the withdrawal queue of an asset
+    ///
+    /// ### Arguments
+    /// * `asset` - The Address of the asset
+    fn get_withdrawal_queue(e: Env, asset: Address) -> WithdrawalQueue;
+
+    /// Simulate a flash loan to a receiver without executing it
+    ///
+    /// ### Arguments
+    /// * `receiver` - The Address of the flash loan receiver
+    /// * `amount` - The amount of the flash loan
+    fn simulate_flash_loan(e: Env, receiver: Address, amount: i128) -> FlashLoanQuote;
+
+    /// Quote the repayment owed for a flash loan under the treasury's default fee model, so receivers
+    /// can compute the exact repayment on-chain. Receivers with their own fee rate should use
+    /// `simulate_flash_loan` instead.
+    ///
+    /// Returns the amount plus the fee
+    ///
+    /// ### Arguments
+    /// * `amount` - The amount of the flash loan
+    ///
+    /// ### Panics
+    /// If the amount is zero or negative
+    fn quote_repayment(e: Env, amount: i128) -> i128;
+
+    /// Simulate a decrease of the supply of the pool without executing it
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `amount` - The amount to decrease the supply by
+    fn simulate_decrease_supply(e: Env, token: Address, amount: i128) -> SupplyQuote;
+
+    /// Fetch a summary of the treasury's position in the blend pool for a stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_position_summary(e: Env, token: Address) -> PositionSummary;
+
+    /// Fetch the blend reserve of a stablecoin in the treasury's primary pool, so integrators can
+    /// display its utilization and rates without using the blend pool's interface
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the stablecoin is not a reserve of the pool
+    fn get_pool_reserve_data(e: Env, token: Address) -> PoolReserveData;
+
+    /// Fetch a summary of the treasury's position in a registered blend pool for a stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `blend_pool` - The Address of the blend pool
+    ///
+    /// ### Panics
+    /// If the blend pool is not registered for the stablecoin
+    fn get_pool_position(e: Env, token: Address, blend_pool: Address) -> PositionSummary;
+
+    /// Fetch a summary of the treasury's positions across all blend pools for a stablecoin.
+    /// The utilization is weighted by the amount supplied to each pool.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_total_position(e: Env, token: Address) -> PositionSummary;
+
+    /// (Admin only) Set the stability fee accrued per second on the outstanding supply of a stablecoin.
+    /// Fees owed under the previous rate are accrued first.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `rate` - The fee accrued per second, in 18 decimals
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the rate is negative
+    fn set_stability_fee(e: Env, token: Address, rate: i128);
+
+    /// Accrue the stability fee of a stablecoin on its outstanding supply
+    ///
+    /// Returns the total fees accrued
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn accrue(e: Env, token: Address) -> i128;
+
+    /// Fetch the total stability fees accrued on the outstanding supply of a stablecoin, including
+    /// fees not yet accrued and fees already collected
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_accrued_fees(e: Env, token: Address) -> i128;
+
+    /// (Fee manager only) Collect the stability fees accrued on a stablecoin into its surplus buffer,
+    /// withdrawing them from the interest earned on the treasury's position in its primary blend pool.
+    /// At most the backing above the outstanding supply is collected, the rest stays owed.
+    ///
+    /// Returns the amount collected
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the caller does not hold the fee manager role or a flash loan is in progress
+    fn collect_stability_fees(e: Env, caller: Address, token: Address) -> i128;
+
+    /// Fetch a report of the backing of a stablecoin's outstanding supply, valued with the oracle. The supply
+    /// is valued at the USD price of its currency. The collateral ratio is zero while nothing is outstanding.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the oracle has no price, or a stale price, for an asset of the treasury's positions
+    /// If the stablecoin is not pegged to USD and its FX price is missing or stale
+    fn get_backing_report(e: Env, token: Address) -> BackingReport;
+
+    /// (Admin only) Seed a soroswap pair of a stablecoin with protocol-owned liquidity
+    ///
+    /// Mints stablecoins and pulls the counter asset from the admin straight into the pair, and
+    /// deposits them for LP tokens held by the treasury. Returns the LP tokens minted.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `pair` - The Address of the soroswap pair of the stablecoin and the counter asset
+    /// * `token_amount` - The amount of stablecoins to mint into the pair
+    /// * `counter_amount` - The amount of the counter asset to transfer from the admin into the pair
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or an amount is zero or negative
+    /// If the pair does not hold the stablecoin, or the stablecoin was already seeded into another pair
+    fn seed_liquidity(e: Env, token: Address, pair: Address, token_amount: i128, counter_amount: i128) -> i128;
+
+    /// (Admin only) Withdraw protocol-owned liquidity from a stablecoin's soroswap pair
+    ///
+    /// The withdrawn stablecoins are burnt and the withdrawn counter assets are added to the surplus buffer.
+    /// Returns (stablecoins withdrawn, counter assets withdrawn).
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `lp_amount` - The amount of LP tokens to withdraw
+    /// * `min_token` - The minimum amount of stablecoins to withdraw
+    /// * `min_counter` - The minimum amount of counter assets to withdraw
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the amount is zero or negative, or the treasury holds too few LP tokens
+    /// If less than the minimum amounts are withdrawn
+    fn withdraw_pol(e: Env, token: Address, lp_amount: i128, min_token: i128, min_counter: i128) -> (i128, i128);
+
+    /// Fetch the protocol-owned liquidity of a stablecoin valued at the current pair reserves, if any
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_pol_position(e: Env, token: Address) -> Option<PolReport>;
+
+    /// (Keeper only) Recycle a stablecoin's surplus buffer, where flash loan and flash mint fees accrue,
+    /// into its protocol-owned liquidity
+    ///
+    /// Swaps half of the surplus to the counter asset through the pair and deposits both sides for
+    /// LP tokens. Any amount that does not fit the pair ratio stays in the surplus buffers.
+    /// Returns the LP tokens minted.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the keeper
+    /// * `token` - The Address of the stablecoin
+    /// * `min_counter` - The minimum amount of the counter asset to receive from the swap
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, or the treasury is paused
+    /// If the stablecoin has no protocol-owned liquidity or no surplus
+    /// If the swap returns less than the minimum amount
+    fn recycle_fees(e: Env, caller: Address, token: Address, min_counter: i128) -> i128;
+
+    /// Fetch the blend pools the treasury supplies a stablecoin to
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_pools(e: Env, token: Address) -> Vec<Address>;
+
+    /// (Admin only) Set the fiat currency a stablecoin is pegged to. Stablecoins default to USD.
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `currency` - The currency code, e.g. EUR
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_currency(e: Env, token: Address, currency: Symbol);
+
+    /// Fetch the fiat currency a stablecoin is pegged to
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_currency(e: Env, token: Address) -> Symbol;
+
+    /// (Admin only) Set the FX oracle pricing the currency of a non-USD stablecoin, used wherever the
+    /// stablecoin is valued at its peg, e.g. by redemptions, the backing report and the circuit breaker
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `feed` - The FX feed, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_fx_feed(e: Env, token: Address, feed: Option<FxFeed>);
+
+    /// Fetch the FX oracle pricing the currency of a stablecoin, if any
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    fn get_fx_feed(e: Env, token: Address) -> Option<FxFeed>;
+
+    /// Fetch the USD price of the currency a stablecoin is pegged to, scaled to 7 decimals
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    ///
+    /// ### Panics
+    /// If the stablecoin is not pegged to USD and has no FX feed, or its FX price is missing or stale
+    fn get_peg_price(e: Env, token: Address) -> i128;
+
+    /// Fetch the outstanding supply minted by the treasury across every stablecoin pegged to a currency
+    ///
+    /// ### Arguments
+    /// * `currency` - The currency code, e.g. EUR
+    fn get_currency_supply(e: Env, currency: Symbol) -> i128;
+
+    /// Fetch the outstanding supply of a token minted by the treasury at the end of a ledger, e.g.
+    /// for governance voting weight or analytics based on historical issuance
+    ///
+    /// Returns 0 for ledgers before the first supply change recorded
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the token
+    /// * `ledger` - The ledger sequence
+    fn get_supply_at(e: Env, token: Address, ledger: u32) -> i128;
+
+    /// (Admin only) Register a blend pool the treasury can supply a stablecoin to
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `blend_pool` - The Address of the blend pool
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the blend pool is already registered
+    fn add_pool(e: Env, token: Address, blend_pool: Address);
+
+    /// Rediscover the reserve indexes of every registered blend pool, e.g. after a pool adds reserves.
+    /// Indexes are discovered when a pool is registered and used instead of fetching the pool's reserve list.
+    fn refresh_reserve_indexes(e: Env);
+
+    /// (Admin only) Set the registered blend pool new supply of a stablecoin is issued to
+    ///
+    /// ### Arguments
+    /// * `token` - The Address of the stablecoin
+    /// * `blend_pool` - The Address of the blend pool
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the blend pool is not registered
+    fn set_primary_pool(e: Env, token: Address, blend_pool: Address);
+
+    /// (Keeper only) Move supply of a stablecoin from one registered blend pool to another
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `token` - The Address of the stablecoin
+    /// * `from_pool` - The Address of the blend pool to withdraw from
+    /// * `to_pool` - The Address of the blend pool to supply to
+    /// * `amount` - The amount to move
+    ///
+    /// ### Panics
+    /// If the caller does not hold the keeper role, a blend pool is not registered, or the
+    /// treasury's supply in the source pool does not cover the amount
+    fn rebalance(e: Env, caller: Address, token: Address, from_pool: Address, to_pool: Address, amount: i128);
+
+    /// (Upgrader only) Queue a new contract wasm. The upgrade can only be executed once the timelock has passed.
+    ///
+    /// The hash of the running wasm is recorded on the first upgrade queued, as the contract can not
+    /// read it, so every upgrade can be rolled back to the wasm it replaced.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `new_wasm_hash` - The hash of the uploaded wasm to upgrade to
+    /// * `current_wasm_hash` - The hash of the running wasm
+    ///
+    /// ### Panics
+    /// If the caller does not hold the upgrader role, or the running wasm hash does not match the recorded one
+    fn queue_upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>, current_wasm_hash: BytesN<32>);
+
+    /// (Upgrader only) Cancel the queued upgrade
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    ///
+    /// ### Panics
+    /// If the caller does not hold the upgrader role or no upgrade is queued
+    fn cancel_upgrade(e: Env, caller: Address);
+
+    /// (Guardian only) Veto the queued upgrade while its timelock has not passed
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    ///
+    /// ### Panics
+    /// If the caller does not hold the guardian role, no upgrade is queued, or the timelock has passed
+    fn veto_upgrade(e: Env, caller: Address);
+
+    /// Fetch the queued upgrade, if any
+    fn get_queued_upgrade(e: Env) -> Option<QueuedUpgrade>;
+
+    /// (Upgrader only) Upgrade the contract to the queued wasm. The upgrade can be rolled back to the
+    /// running wasm during the rollback window.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    /// * `new_wasm_hash` - The hash of the queued wasm
+    ///
+    /// ### Panics
+    /// If the caller does not hold the upgrader role, the hash was not queued, or the timelock has not passed
+    fn upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>);
+
+    /// (Upgrader only) Roll the last upgrade back to the wasm running before it. Storage migrations
+    /// are not reverted, so the rollback is refused once the storage has been migrated since the upgrade.
+    ///
+    /// ### Arguments
+    /// * `caller` - The Address of the caller
+    ///
+    /// ### Panics
+    /// If the caller does not hold the upgrader role, no upgrade can be rolled back, or the storage
+    /// version changed since the upgrade
+    fn rollback(e: Env, caller: Address);
+
+    /// Fetch the rollback of the last upgrade, if any
+    fn get_rollback(e: Env) -> Option<Rollback>;
+
+    /// Bump the rent of the contract instance and all of its enumerable persistent entries.
+    /// Callable by anyone so the treasury's state does not expire.
+    fn extend_all(e: Env);
+
+    /// (Admin only) Migrate the storage layout to the version of the current contract code.
+    /// Must be called after an upgrade before using the contract.
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn migrate(e: Env);
+}
+
+#[contractimpl]
+impl Treasury for TreasuryContract {
+
+    fn initialize(e: Env, admin: Address, bridge_oracle: Address, pegkeeper: Address) {
+        storage::extend_instance(&e);
+        if storage::is_init(&e) {
+            panic_with_error!(&e, TreasuryError::AlreadyInitializedError);
+        }
+
+        storage::set_config(&e, &TreasuryConfig {
+            pegkeeper: pegkeeper.clone(),
+            fee_model: FeeModel {
+                fee_bps: 0,
+                min_fee: 0,
+            },
+            fee_recipient: None,
+            surplus_config: None,
+        });
+        storage::set_flash_loaner(&e, &pegkeeper, &FlashLoanConfig {
+            max_amount: i128::MAX,
+            fee_bps: 0,
+            enabled: true,
+        });
+        storage::set_bridge_oracle(&e, &bridge_oracle);
+        storage::set_admin(&e, &admin);
+        storage::set_version(&e, CONTRACT_VERSION);
+    }
+
+    fn deploy_stablecoin(e: Env, token: Address, asset: Asset, blend_pool: Address) {
+        storage::extend_instance(&e);
+
+        helper::require_admin(&e);
+
+        let bridge_oracle = storage::get_bridge_oracle(&e);
+        let token_asset = Asset::Stellar(token.clone());
+        let add_asset_args = vec![
+            &e,
+            token_asset.into_val(&e),
+            asset.into_val(&e),
+        ];
+
+        e.invoke_contract::<Val>(&bridge_oracle, &Symbol::new(&e, "add_asset"), add_asset_args);
+
+        storage::set_blend_pool(&e, &token, &blend_pool);
+        storage::set_pools(&e, &token, &vec![&e, blend_pool.clone()]);
+        helper::refresh_reserve_indexes(&e, &blend_pool);
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        new_admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+    }
+
+    fn transfer_token_admin(e: Env, token: Address, new_admin: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_pending_token_admin(&e, &token, &new_admin);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "transfer_token_admin")), (token, new_admin));
+    }
+
+    fn accept_token_admin(e: Env, token: Address) {
+        storage::extend_instance(&e);
+        let new_admin = match storage::get_pending_token_admin(&e, &token) {
+            Some(new_admin) => new_admin,
+            None => panic_with_error!(&e, TreasuryError::TokenAdminNotPending),
+        };
+        new_admin.require_auth();
+
+        storage::del_pending_token_admin(&e, &token);
+        TokenAdminClient::new(&e, &token).set_admin(&new_admin);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "accept_token_admin")), (token, new_admin));
+    }
+
+    fn get_pending_token_admin(e: Env, token: Address) -> Option<Address> {
+        storage::extend_instance(&e);
+        storage::get_pending_token_admin(&e, &token)
+    }
+
+    fn set_denied(e: Env, account: Address, denied: bool) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_denied(&e, &vec![&e, account], denied);
+    }
+
+    fn set_denied_batch(e: Env, accounts: Vec<Address>, denied: bool) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_denied(&e, &accounts, denied);
+    }
+
+    fn is_denied(e: Env, account: Address) -> bool {
+        storage::is_denied(&e, &account)
+    }
+
+    fn set_hooks(e: Env, hooks: Vec<Address>) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_hooks(&e, &hooks);
+    }
+
+    fn get_hooks(e: Env) -> Vec<Address> {
+        storage::get_hooks(&e)
+    }
+
+    fn grant_role(e: Env, role: Role, account: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_role(&e, role, &account);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "grant_role")), (role, account));
+    }
+
+    fn revoke_role(e: Env, role: Role, account: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::del_role(&e, role, &account);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "revoke_role")), (role, account));
+    }
+
+    fn batch_admin(e: Env, ops: Vec<AdminOp>) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        for op in ops.iter() {
+            execute_admin_op(&e, op);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "batch_admin")), ops.len());
+    }
+
+    fn set_multisig(e: Env, config: MultisigConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        execute_admin_op(&e, AdminOp::SetMultisig(config));
+    }
+
+    fn get_multisig(e: Env) -> Option<MultisigConfig> {
+        storage::get_multisig(&e)
+    }
+
+    fn propose(e: Env, signer: Address, ops: Vec<AdminOp>) -> u32 {
+        storage::extend_instance(&e);
+        helper::require_signer(&e, &signer);
+
+        let id = storage::next_proposal_id(&e);
+        storage::set_proposal(&e, id, &Proposal {
+            ops,
+            approvals: vec![&e, signer.clone()],
+            expiration: e.ledger().sequence() + storage::PROPOSAL_DURATION,
+            executed: false,
+        });
+
+        e.events().publish(("Treasury", Symbol::new(&e, "propose")), (id, signer));
+        id
+    }
+
+    fn approve(e: Env, signer: Address, id: u32) {
+        storage::extend_instance(&e);
+        helper::require_signer(&e, &signer);
+
+        let mut proposal = match storage::get_proposal(&e, id) {
+            Some(proposal) if !proposal.executed && e.ledger().sequence() <= proposal.expiration => proposal,
+            _ => panic_with_error!(&e, TreasuryError::ProposalNotFound),
+        };
+        if !proposal.approvals.contains(&signer) {
+            proposal.approvals.push_back(signer.clone());
+            storage::set_proposal(&e, id, &proposal);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "approve")), (id, signer));
+    }
+
+    fn execute(e: Env, id: u32) {
+        storage::extend_instance(&e);
+
+        let mut proposal = match storage::get_proposal(&e, id) {
+            Some(proposal) if !proposal.executed && e.ledger().sequence() <= proposal.expiration => proposal,
+            _ => panic_with_error!(&e, TreasuryError::ProposalNotFound),
+        };
+        let config = match storage::get_multisig(&e) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::ProposalNotFound),
+        };
+        // only approvals of the current signers count, in case the signer set changed
+        let approvals = proposal.approvals.iter().filter(|signer| config.signers.contains(signer)).count() as u32;
+        if approvals < config.threshold {
+            panic_with_error!(&e, TreasuryError::ThresholdNotMet);
+        }
+        proposal.executed = true;
+        storage::set_proposal(&e, id, &proposal);
+
+        for op in proposal.ops.iter() {
+            execute_admin_op(&e, op);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "execute")), id);
+    }
+
+    fn get_proposal(e: Env, id: u32) -> Option<Proposal> {
+        storage::get_proposal(&e, id)
+    }
+
+    fn has_role(e: Env, role: Role, account: Address) -> bool {
+        helper::has_role(&e, role, &account)
+    }
+
+    fn pause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Pauser, &caller);
+
+        storage::set_paused(&e);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "pause")), caller);
+    }
+
+    fn unpause(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Pauser, &caller);
+
+        storage::del_paused(&e);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "unpause")), caller);
+    }
+
+    fn get_config(e: Env) -> TreasuryInfo {
+        storage::extend_instance(&e);
+        let config = storage::get_config(&e);
+        TreasuryInfo {
+            admin: storage::get_admin(&e),
+            pegkeeper: config.pegkeeper,
+            bridge_oracle: storage::get_bridge_oracle(&e),
+            oracle: storage::find_oracle_config(&e),
+            fee_model: config.fee_model,
+            fee_recipient: config.fee_recipient,
+            surplus_config: config.surplus_config,
+            max_fee_bps: helper::MAX_FEE_BPS,
+            max_batch_loans: helper::MAX_BATCH_LOANS,
+            max_ltv_bps: helper::MAX_LTV_BPS,
+            paused: storage::is_paused(&e),
+            version: CONTRACT_VERSION,
+            storage_version: storage::get_version(&e),
+        }
+    }
+
+    fn get_version(_e: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    fn is_paused(e: Env) -> bool {
+        storage::is_paused(&e)
+    }
+
+    fn increase_supply(e: Env, caller: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::Keeper, &caller);
+        helper::require_positive(&e, amount);
+
+        helper::issue_supply(&e, &token, amount);
+    }
+
+    fn redeem(e: Env, from: Address, token: Address, collateral: Address, amount: i128, min_collateral_out: i128, deadline: Option<u64>) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+        from.require_auth();
+        helper::require_positive(&e, amount);
+
+        let fee = helper::require_no_overflow(&e, amount.fixed_mul_ceil(i128::from(storage::get_redemption_fee(&e)), SCALAR_BPS));
+        let redeemed = amount - fee;
+        let collateral_config = match storage::get_collateral(&e, &collateral) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::CollateralNotAllowed),
+        };
+        // value the stablecoins at the USD price of their currency, then pay that value out in collateral
+        let redeemed_value = helper::require_no_overflow(&e, redeemed.fixed_mul_floor(helper::get_peg_price(&e, &token), SCALAR_7));
+        let price = oracle::get_feed_price(&e, &storage::get_oracle_config(&e), &collateral_config.oracle_feed);
+        let collateral_out = helper::require_no_overflow(&e, redeemed_value.fixed_div_floor(price, SCALAR_7));
+        if collateral_out < min_collateral_out || collateral_out <= 0 {
+            panic_with_error!(&e, TreasuryError::SlippageExceeded);
+        }
+        if collateral_out > collateral_config.cap {
+            panic_with_error!(&e, TreasuryError::CollateralCapExceeded);
+        }
+
+        TokenClient::new(&e, &token).transfer(&from, &e.current_contract_address(), &amount);
+        TokenClient::new(&e, &token).burn(&e.current_contract_address(), &redeemed);
+        helper::update_supply(&e, &token, -redeemed);
+        if fee > 0 {
+            helper::add_fee(&e, &storage::get_config(&e), &token, fee);
+            let mut stats = storage::get_stats(&e);
+            stats.fees_earned += fee;
+            storage::set_stats(&e, &stats);
+        }
+        let (withdrawn, queued) = helper::withdraw_or_queue(&e, &storage::get_blend_pool(&e, &token), &collateral, collateral_out, &from, false);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "redeem")), (from, token, collateral, amount, collateral_out, queued));
+        withdrawn
+    }
+
+    fn set_redemption_fee(e: Env, caller: Address, fee_bps: u32) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        helper::require_valid_fee_bps(&e, fee_bps);
+        storage::set_redemption_fee(&e, fee_bps);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_redemption_fee")), fee_bps);
+    }
+
+    fn set_supply_limit(e: Env, limit: SupplyLimit) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_supply_limit(&e, &limit);
+    }
+
+    fn decrease_supply(e: Env, caller: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::Keeper, &caller);
+        helper::require_positive(&e, amount);
+        helper::check_supply_limit(&e, &token, amount);
+
+        let blend = storage::get_blend_pool(&e, &token);
+        let (burned, queued) = helper::withdraw_or_queue(&e, &blend, &token, amount, &e.current_contract_address(), true);
+        if burned > 0 {
+            hook::call_hooks(&e, HookOp::DecreaseSupply, HookStage::Before, &token, burned);
+            TokenClient::new(&e, &token).burn(&e.current_contract_address(), &burned);
+            helper::update_supply(&e, &token, -burned);
+            hook::call_hooks(&e, HookOp::DecreaseSupply, HookStage::After, &token, burned);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "decrease_supply")), (token, amount, queued));
+    }
+
+    fn unwind_position(e: Env, caller: Address, token: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::Keeper, &caller);
+        helper::require_positive(&e, amount);
+        helper::check_supply_limit(&e, &token, amount);
+
+        let blend = storage::get_blend_pool(&e, &token);
+        helper::withdraw_from_pool(&e, &blend, &token, amount, &e.current_contract_address());
+        let repaid = helper::calc_liabilities(&e, &blend, &token).min(amount);
+        if repaid > 0 {
+            helper::repay_to_pool(&e, &blend, &token, repaid);
+        }
+        let burned = amount - repaid;
+        if burned > 0 {
+            hook::call_hooks(&e, HookOp::DecreaseSupply, HookStage::Before, &token, burned);
+            TokenClient::new(&e, &token).burn(&e.current_contract_address(), &burned);
+            helper::update_supply(&e, &token, -burned);
+            hook::call_hooks(&e, HookOp::DecreaseSupply, HookStage::After, &token, burned);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "unwind_position")), (token, amount, repaid, burned));
+        burned
+    }
+
+    fn set_auto_supply(e: Env, token: Address, config: Option<AutoSupplyConfig>) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_auto_supply(&e, &token, &config);
+    }
+
+    fn get_auto_supply(e: Env, token: Address) -> Option<AutoSupplyConfig> {
+        storage::get_auto_supply(&e, &token)
+    }
+
+    fn auto_adjust_supply(e: Env, caller: Address, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::Keeper, &caller);
+        let config = match storage::get_auto_supply(&e, &token) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::AutoSupplyDisabled),
+        };
+        let ledger = e.ledger().sequence();
+        if let Some(last) = storage::get_auto_supply_ledger(&e, &token) {
+            if ledger < last + config.interval {
+                panic_with_error!(&e, TreasuryError::AdjustmentTooSoon);
+            }
+        }
+
+        let adjustment = helper::calc_auto_adjustment(&e, &token, &config);
+        let action = if adjustment.amount > 0 {
+            helper::issue_supply(&e, &token, adjustment.amount);
+            Symbol::new(&e, "increase")
+        } else if adjustment.amount < 0 {
+            let amount = -adjustment.amount;
+            helper::check_supply_limit(&e, &token, amount);
+            hook::call_hooks(&e, HookOp::DecreaseSupply, HookStage::Before, &token, amount);
+            helper::withdraw_from_pool(&e, &adjustment.blend_pool, &token, amount, &e.current_contract_address());
+            TokenClient::new(&e, &token).burn(&e.current_contract_address(), &amount);
+            helper::update_supply(&e, &token, -amount);
+            hook::call_hooks(&e, HookOp::DecreaseSupply, HookStage::After, &token, amount);
+            Symbol::new(&e, "decrease")
+        } else {
+            Symbol::new(&e, "hold")
+        };
+
+        // sampled on every adjustment, so the controller's behavior can be audited from the event history
+        e.events().publish(
+            ("Treasury", Symbol::new(&e, "utilization_sampled")),
+            (token.clone(), adjustment.blend_pool.clone(), adjustment.utilization, config.min_util, config.max_util, action, adjustment.amount),
+        );
+        if adjustment.amount != 0 {
+            storage::set_auto_supply_ledger(&e, &token, ledger);
+            e.events().publish(
+                ("Treasury", Symbol::new(&e, "issuance_target_updated")),
+                (token, adjustment.blend_pool, config.target_util, adjustment.target_supplied, adjustment.amount),
+            );
+        }
+        adjustment.amount
+    }
+
+    fn claim(e: Env, id: u32) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+
+        let withdrawal = helper::claim_withdrawal(&e, id);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "claim")), (id, withdrawal.asset, withdrawal.recipient, withdrawal.amount));
+        withdrawal.amount
+    }
+
+    fn get_withdrawal(e: Env, id: u32) -> Option<Withdrawal> {
+        storage::get_withdrawal(&e, id)
+    }
+
+    fn get_withdrawal_queue(e: Env, asset: Address) -> WithdrawalQueue {
+        storage::get_withdrawal_queue(&e, &asset)
+    }
+
+    fn simulate_flash_loan(e: Env, receiver: Address, amount: i128) -> FlashLoanQuote {
+        storage::extend_instance(&e);
+        let config = storage::get_flash_loaner(&e, &receiver)
+            .filter(|config| config.enabled)
+            .unwrap_or(FlashLoanConfig { max_amount: 0, fee_bps: 0, enabled: false });
+        let fee = helper::calc_flash_loan_fee(&e, &storage::get_fee_model(&e), &config, amount);
+
+        FlashLoanQuote {
+            fee,
+            required_repayment: amount + fee,
+            cap_remaining: config.max_amount.saturating_sub(amount),
+        }
+    }
+
+    fn quote_repayment(e: Env, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_positive(&e, amount);
+        let config = FlashLoanConfig { max_amount: i128::MAX, fee_bps: 0, enabled: true };
+        amount + helper::calc_flash_loan_fee(&e, &storage::get_fee_model(&e), &config, amount)
+    }
+
+    fn simulate_decrease_supply(e: Env, token: Address, amount: i128) -> SupplyQuote {
+        storage::extend_instance(&e);
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        let reserve = pool::get_reserve(&e, &blend_pool, &token);
+        let summary = position_summary(&e, &blend_pool, &token);
+
+        let total_supply = helper::require_no_overflow(&e, reserve.b_supply.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+        let total_liabilities = helper::require_no_overflow(&e, reserve.d_supply.fixed_mul_ceil(reserve.d_rate, SCALAR_9));
+        SupplyQuote {
+            b_tokens: helper::require_no_overflow(&e, amount.fixed_div_ceil(reserve.b_rate, SCALAR_9)),
+            remaining_supplied: summary.supplied - amount,
+            available_liquidity: total_supply - total_liabilities,
+        }
+    }
+
+    fn get_position_summary(e: Env, token: Address) -> PositionSummary {
+        storage::extend_instance(&e);
+        position_summary(&e, &storage::get_blend_pool(&e, &token), &token)
+    }
+
+    fn get_pool_reserve_data(e: Env, token: Address) -> PoolReserveData {
+        storage::extend_instance(&e);
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        let reserve = pool::get_reserve(&e, &blend_pool, &token);
+
+        let total_supplied = helper::require_no_overflow(&e, reserve.b_supply.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+        let total_borrowed = helper::require_no_overflow(&e, reserve.d_supply.fixed_mul_ceil(reserve.d_rate, SCALAR_9));
+        let utilization = if total_supplied == 0 {
+            0
+        } else {
+            helper::require_no_overflow(&e, total_borrowed.fixed_div_ceil(total_supplied, SCALAR_7))
+        };
+        PoolReserveData {
+            blend_pool,
+            c_factor: reserve.c_factor,
+            l_factor: reserve.l_factor,
+            max_util: reserve.max_util,
+            ir_mod: reserve.ir_mod,
+            b_rate: reserve.b_rate,
+            d_rate: reserve.d_rate,
+            total_supplied,
+            total_borrowed,
+            utilization,
+            backstop_credit: reserve.backstop_credit,
+            last_time: reserve.last_time,
+        }
+    }
+
+    fn get_pool_position(e: Env, token: Address, blend_pool: Address) -> PositionSummary {
+        storage::extend_instance(&e);
+        helper::require_pool(&e, &token, &blend_pool);
+        position_summary(&e, &blend_pool, &token)
+    }
+
+    fn get_total_position(e: Env, token: Address) -> PositionSummary {
+        storage::extend_instance(&e);
+        let mut total = PositionSummary { supplied: 0, b_tokens: 0, d_tokens: 0, utilization: 0 };
+        // utilization across pools, weighted by each pool's supplied amount
+        let mut weighted_utilization: i128 = 0;
+        for blend_pool in storage::get_pools(&e, &token).iter() {
+            let summary = position_summary(&e, &blend_pool, &token);
+            total.supplied += summary.supplied;
+            total.b_tokens += summary.b_tokens;
+            total.d_tokens += summary.d_tokens;
+            weighted_utilization += summary.supplied * summary.utilization;
+        }
+        if total.supplied > 0 {
+            total.utilization = weighted_utilization / total.supplied;
+        }
+        total
+    }
+
+    fn set_stability_fee(e: Env, token: Address, rate: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_stability_fee(&e, &token, rate);
+    }
+
+    fn accrue(e: Env, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::accrue_stability_fee(&e, &token)
+    }
+
+    fn get_accrued_fees(e: Env, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        match storage::get_stability_fee(&e, &token) {
+            Some(fee) => helper::calc_stability_fee(&e, &fee, storage::get_supply(&e, &token)).accrued,
+            None => 0,
+        }
+    }
+
+    fn collect_stability_fees(e: Env, caller: Address, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        helper::collect_stability_fees(&e, &token)
+    }
+
+    fn get_backing_report(e: Env, token: Address) -> BackingReport {
+        storage::extend_instance(&e);
+        let oracle_config = storage::get_oracle_config(&e);
+        let total_supply = storage::get_supply(&e, &token);
+        let bridged_out = storage::get_bridged(&e, &token);
+        let surplus = storage::get_surplus(&e, &token);
+
+        let mut position_value = 0;
+        for blend_pool in storage::get_pools(&e, &token).iter() {
+            position_value += helper::calc_position_value(&e, &oracle_config, &blend_pool);
+        }
+
+        // the supply is a liability at the USD price of its currency
+        let price = helper::get_peg_price(&e, &token);
+        let supply_value = helper::require_no_overflow(&e, total_supply.fixed_mul_ceil(price, SCALAR_7));
+        let surplus_value = helper::require_no_overflow(&e, surplus.fixed_mul_floor(price, SCALAR_7));
+        let collateral_ratio = if supply_value > 0 {
+            helper::require_no_overflow(&e, (position_value + surplus_value).fixed_div_floor(supply_value, SCALAR_7))
+        } else {
+            0
+        };
+
+        BackingReport {
+            total_supply,
+            bridged_out,
+            circulating: total_supply - bridged_out,
+            position_value,
+            surplus,
+            collateral_ratio,
+        }
+    }
+
+    fn seed_liquidity(e: Env, token: Address, pair: Address, token_amount: i128, counter_amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        let admin = helper::require_admin(&e);
+        helper::require_positive(&e, token_amount);
+        helper::require_positive(&e, counter_amount);
+        storage::get_blend_pool(&e, &token);
+
+        let pair_client = PairClient::new(&e, &pair);
+        let (token_0, token_1) = (pair_client.token_0(), pair_client.token_1());
+        let counter = if token_0 == token {
+            token_1
+        } else if token_1 == token {
+            token_0
+        } else {
+            panic_with_error!(&e, TreasuryError::InvalidPairError)
+        };
+        let mut position = storage::get_pol_position(&e, &token).unwrap_or(PolPosition {
+            pair: pair.clone(),
+            counter: counter.clone(),
+            lp_tokens: 0,
+            token_seeded: 0,
+            counter_seeded: 0,
+        });
+        if position.pair != pair {
+            panic_with_error!(&e, TreasuryError::InvalidPairError);
+        }
+
+        StellarAssetClient::new(&e, &token).mint(&pair, &token_amount);
+        helper::update_supply(&e, &token, token_amount);
+        TokenClient::new(&e, &counter).transfer(&admin, &pair, &counter_amount);
+        let lp_before = pair_client.balance(&e.current_contract_address());
+        pair_client.deposit(&e.current_contract_address());
+        let lp_tokens = pair_client.balance(&e.current_contract_address()) - lp_before;
+
+        position.lp_tokens += lp_tokens;
+        position.token_seeded += token_amount;
+        position.counter_seeded += counter_amount;
+        storage::set_pol_position(&e, &token, &position);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "seed_liquidity")), (token, pair, token_amount, counter_amount, lp_tokens));
+        lp_tokens
+    }
+
+    fn withdraw_pol(e: Env, token: Address, lp_amount: i128, min_token: i128, min_counter: i128) -> (i128, i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        helper::require_positive(&e, lp_amount);
+
+        let mut position = match storage::get_pol_position(&e, &token) {
+            Some(position) if position.lp_tokens >= lp_amount => position,
+            _ => panic_with_error!(&e, TreasuryError::BalanceError),
+        };
+
+        let self_address = e.current_contract_address();
+        let token_client = TokenClient::new(&e, &token);
+        let counter_client = TokenClient::new(&e, &position.counter);
+        let token_before = token_client.balance(&self_address);
+        let counter_before = counter_client.balance(&self_address);
+        let pair_client = PairClient::new(&e, &position.pair);
+        pair_client.transfer(&self_address, &position.pair, &lp_amount);
+        pair_client.withdraw(&self_address);
+        let token_out = token_client.balance(&self_address) - token_before;
+        let counter_out = counter_client.balance(&self_address) - counter_before;
+        if token_out < min_token || counter_out < min_counter {
+            panic_with_error!(&e, TreasuryError::SlippageExceeded);
+        }
+
+        // release the cost basis of the withdrawn share
+        let token_basis = helper::require_no_overflow(&e, position.token_seeded.fixed_mul_floor(lp_amount, position.lp_tokens));
+        let counter_basis = helper::require_no_overflow(&e, position.counter_seeded.fixed_mul_floor(lp_amount, position.lp_tokens));
+        position.lp_tokens -= lp_amount;
+        position.token_seeded -= token_basis;
+        position.counter_seeded -= counter_basis;
+        storage::set_pol_position(&e, &token, &position);
+
+        if token_out > 0 {
+            token_client.burn(&self_address, &token_out);
+            helper::update_supply(&e, &token, -token_out);
+        }
+        if counter_out > 0 {
+            helper::add_surplus(&e, &storage::get_config(&e), &position.counter, counter_out);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "withdraw_pol")), (token, lp_amount, token_out, counter_out));
+        (token_out, counter_out)
+    }
+
+    fn get_pol_position(e: Env, token: Address) -> Option<PolReport> {
+        storage::extend_instance(&e);
+        storage::get_pol_position(&e, &token).map(|position| pol_report(&e, &token, position))
+    }
+
+    fn recycle_fees(e: Env, caller: Address, token: Address, min_counter: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::Keeper, &caller);
+
+        let mut position = match storage::get_pol_position(&e, &token) {
+            Some(position) => position,
+            None => panic_with_error!(&e, TreasuryError::NotInitializedError),
+        };
+        let surplus = storage::get_surplus(&e, &token);
+        helper::require_positive(&e, surplus / 2);
+
+        let swapped = surplus / 2;
+        let counter_out = helper::swap_in_pair(&e, &position.pair, &token, swapped);
+        if counter_out < min_counter {
+            panic_with_error!(&e, TreasuryError::SlippageExceeded);
+        }
+
+        // deposit both sides at the pair ratio after the swap
+        let pair_client = PairClient::new(&e, &position.pair);
+        let (reserve_0, reserve_1) = pair_client.get_reserves();
+        let (token_reserve, counter_reserve) = if pair_client.token_0() == token {
+            (reserve_0, reserve_1)
+        } else {
+            (reserve_1, reserve_0)
+        };
+        let remaining = surplus - swapped;
+        let counter_needed = helper::require_no_overflow(&e, remaining.fixed_mul_floor(counter_reserve, token_reserve));
+        let (token_in, counter_in) = if counter_needed <= counter_out {
+            (remaining, counter_needed)
+        } else {
+            (helper::require_no_overflow(&e, counter_out.fixed_mul_floor(token_reserve, counter_reserve)), counter_out)
+        };
+
+        let self_address = e.current_contract_address();
+        TokenClient::new(&e, &token).transfer(&self_address, &position.pair, &token_in);
+        TokenClient::new(&e, &position.counter).transfer(&self_address, &position.pair, &counter_in);
+        let lp_before = pair_client.balance(&self_address);
+        pair_client.deposit(&self_address);
+        let lp_tokens = pair_client.balance(&self_address) - lp_before;
+
+        storage::set_surplus(&e, &token, remaining - token_in);
+        if counter_out > counter_in {
+            helper::add_surplus(&e, &storage::get_config(&e), &position.counter, counter_out - counter_in);
+        }
+        position.lp_tokens += lp_tokens;
+        position.token_seeded += token_in;
+        position.counter_seeded += counter_in;
+        storage::set_pol_position(&e, &token, &position);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "recycle_fees")), (token, swapped, token_in, counter_in, lp_tokens));
+        lp_tokens
+    }
+
+    fn get_pools(e: Env, token: Address) -> Vec<Address> {
+        storage::get_pools(&e, &token)
+    }
+
+    fn set_currency(e: Env, token: Address, currency: Symbol) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_currency(&e, &token, &currency);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_currency")), (token, currency));
+    }
+
+    fn get_currency(e: Env, token: Address) -> Symbol {
+        storage::get_currency(&e, &token)
+    }
+
+    fn set_fx_feed(e: Env, token: Address, feed: Option<FxFeed>) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        match feed {
+            Some(feed) => storage::set_fx_feed(&e, &token, &feed),
+            None => storage::del_fx_feed(&e, &token),
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_fx_feed")), token);
+    }
+
+    fn get_fx_feed(e: Env, token: Address) -> Option<FxFeed> {
+        storage::get_fx_feed(&e, &token)
+    }
+
+    fn get_peg_price(e: Env, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::get_peg_price(&e, &token)
+    }
+
+    fn get_currency_supply(e: Env, currency: Symbol) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_index(&e, &DataKey::TOKENS)
+            .iter()
+            .filter(|token| storage::get_currency(&e, token) == currency)
+            .map(|token| storage::get_supply(&e, &token))
+            .sum()
+    }
+
+    fn get_supply_at(e: Env, token: Address, ledger: u32) -> i128 {
+        if ledger >= e.ledger().sequence() {
+            return storage::get_supply(&e, &token);
+        }
+        helper::calc_supply_at(&e, &token, ledger)
+    }
+
+    fn add_pool(e: Env, token: Address, blend_pool: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        let mut pools = storage::get_pools(&e, &token);
+        if pools.contains(&blend_pool) {
+            panic_with_error!(&e, TreasuryError::AlreadyInitializedError);
+        }
+        pools.push_back(blend_pool.clone());
+        storage::set_pools(&e, &token, &pools);
+        helper::refresh_reserve_indexes(&e, &blend_pool);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "add_pool")), (token, blend_pool));
+    }
+
+    fn refresh_reserve_indexes(e: Env) {
+        storage::extend_instance(&e);
+
+        let mut refreshed: Vec<Address> = vec![&e];
+        for token in storage::get_index(&e, &DataKey::TOKENS).iter() {
+            for blend_pool in storage::get_pools(&e, &token).iter() {
+                if !refreshed.contains(&blend_pool) {
+                    helper::refresh_reserve_indexes(&e, &blend_pool);
+                    refreshed.push_back(blend_pool);
+                }
+            }
+        }
+    }
+
+    fn set_primary_pool(e: Env, token: Address, blend_pool: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::require_pool(&e, &token, &blend_pool);
+        storage::set_blend_pool(&e, &token, &blend_pool);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_primary_pool")), (token, blend_pool));
+    }
+
+    fn rebalance(e: Env, caller: Address, token: Address, from_pool: Address, to_pool: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        if storage::is_locked(&e) {
+            panic_with_error!(&e, TreasuryError::ReentrancyError);
+        }
+        helper::require_role(&e, Role::Keeper, &caller);
+        helper::require_positive(&e, amount);
+        helper::require_pool(&e, &token, &from_pool);
+        helper::require_pool(&e, &token, &to_pool);
+
+        helper::withdraw_from_pool(&e, &from_pool, &token, amount, &e.current_contract_address());
+        helper::supply_to_pool(&e, &to_pool, &token, amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "rebalance")), (token, from_pool, to_pool, amount));
+    }
+
+    fn keep_peg(e: Env, caller: Address, receiver: Address, token: Address, amount: i128, data: Bytes, deadline: Option<u64>) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+        helper::require_role(&e, Role::Keeper, &caller);
+
+        let fl_receive_args = vec![
+            &e,
+            token.into_val(&e),
+            amount.into_val(&e),
+            data.into_val(&e),
+        ];
+        helper::flash_loan(&e, &receiver, &caller, &token, amount, Symbol::new(&e, "fl_receive"), fl_receive_args);
+    }
+
+    fn flash_loan_batch(e: Env, caller: Address, token: Address, loans: Vec<(Address, i128)>, data: Bytes, deadline: Option<u64>) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+        helper::require_role(&e, Role::Keeper, &caller);
+
+        helper::flash_loan_batch(&e, &caller, &token, &loans, &data);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "flash_loan_batch")), (token, loans.len()));
+    }
+
+    fn flash_mint(e: Env, receiver: Address, token: Address, amount: i128, data: Bytes, deadline: Option<u64>) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+        helper::require_breaker_closed(&e, &token);
+
+        helper::flash_mint(&e, &receiver, &token, amount, data);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "flash_mint")), (receiver, token, amount));
+    }
+
+    fn set_flash_mint(e: Env, token: Address, config: FlashMintConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_flash_mint(&e, &token, &config);
+    }
+
+    fn set_breaker(e: Env, token: Address, config: Option<BreakerConfig>) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        match &config {
+            Some(config) => {
+                if i128::from(config.threshold_bps) > SCALAR_BPS {
+                    panic_with_error!(&e, TreasuryError::InvalidAmount);
+                }
+                storage::set_breaker_config(&e, &token, config);
+            }
+            None => {
+                storage::del_breaker_config(&e, &token);
+                storage::del_depeg_ledger(&e, &token);
+            }
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_breaker")), (token, config));
+    }
+
+    fn poke_breaker(e: Env, token: Address) -> BreakerState {
+        storage::extend_instance(&e);
+        let config = match storage::get_breaker_config(&e, &token) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::NotInitializedError),
+        };
+        let state = helper::update_breaker(&e, &token, &config);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "poke_breaker")), (token, state.price, state.tripped));
+        state
+    }
+
+    fn get_breaker(e: Env, token: Address) -> Option<BreakerState> {
+        storage::get_breaker_config(&e, &token).map(|config| helper::calc_breaker_state(&e, &token, &config))
+    }
+
+    fn get_flash_mint(e: Env, token: Address) -> Option<FlashMintConfig> {
+        storage::extend_instance(&e);
+        storage::get_flash_mint_config(&e, &token)
+    }
+
+    fn flash_collateral(e: Env, receiver: Address, collateral: Address, amount: i128, data: Bytes, deadline: Option<u64>) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_before_deadline(&e, deadline);
+
+        helper::flash_collateral(&e, &receiver, &collateral, amount, data);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "flash_collateral")), (receiver, collateral, amount));
+    }
+
+    fn set_flash_collateral(e: Env, collateral: Address, config: FlashCollateralConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_flash_collateral(&e, &collateral, &config);
+    }
+
+    fn get_flash_collateral(e: Env, collateral: Address) -> Option<FlashCollateralConfig> {
+        storage::extend_instance(&e);
+        storage::get_flash_collateral_config(&e, &collateral)
+    }
+
+    fn add_collateral(e: Env, token: Address, oracle_feed: Asset, ltv: u32, liquidation_bonus: u32, cap: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        if i128::from(ltv) > SCALAR_BPS || i128::from(liquidation_bonus) > SCALAR_BPS || cap < 0 {
+            panic_with_error!(&e, TreasuryError::InvalidAmount);
+        }
+
+        let config = CollateralConfig { oracle_feed, ltv, liquidation_bonus, cap };
+        storage::set_collateral(&e, &token, &config);
+        pegkeeper::set_liquidation_collaterals(&e, &storage::get_pegkeeper(&e), &storage::get_index(&e, &DataKey::COLLATERALS));
+
+        e.events().publish(("Treasury", Symbol::new(&e, "add_collateral")), (token, ltv, liquidation_bonus, cap));
+    }
+
+    fn remove_collateral(e: Env, token: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        if storage::get_collateral(&e, &token).is_none() {
+            panic_with_error!(&e, TreasuryError::CollateralNotAllowed);
+        }
+
+        storage::del_collateral(&e, &token);
+        pegkeeper::set_liquidation_collaterals(&e, &storage::get_pegkeeper(&e), &storage::get_index(&e, &DataKey::COLLATERALS));
+
+        e.events().publish(("Treasury", Symbol::new(&e, "remove_collateral")), token);
+    }
+
+    fn get_collateral(e: Env, token: Address) -> Option<CollateralConfig> {
+        storage::extend_instance(&e);
+        storage::get_collateral(&e, &token)
+    }
+
+    fn get_collaterals(e: Env) -> Vec<Address> {
+        storage::extend_instance(&e);
+        storage::get_index(&e, &DataKey::COLLATERALS)
+    }
+
+    fn liquidate(e: Env, caller: Address, receiver: Address, token: Address, user: Address, debt_amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_role(&e, Role::Keeper, &caller);
+        // the liquidation calldata is only understood by the pegkeeper
+        helper::require_pegkeeper(&e, &receiver);
+
+        if let Some(stability_pool) = storage::get_stability_pool(&e, &token) {
+            let deposits: i128 = e.invoke_contract(&stability_pool, &Symbol::new(&e, "get_total_deposits"), vec![&e]);
+            // the stability pool can not be fully depleted
+            if deposits > debt_amount {
+                let args: Vec<Val> = vec![&e, user.into_val(&e), debt_amount.into_val(&e)];
+                e.invoke_contract::<()>(&stability_pool, &Symbol::new(&e, "liquidate"), args);
+                helper::record_liquidation(&e);
+                return;
+            }
+        }
+
+        let blend_pool: Address = storage::get_blend_pool(&e, &token);
+
+        let fl_receive_args = vec![
+            &e,
+            token.into_val(&e),
+            debt_amount.into_val(&e),
+            pegkeeper::liquidate_calldata(&e, &blend_pool, &user).into_val(&e),
+        ];
+        helper::flash_loan(&e, &receiver, &caller, &token, debt_amount, Symbol::new(&e, "fl_receive"), fl_receive_args);
+        helper::record_liquidation(&e);
+    }
+
+    fn fill_auction(e: Env, caller: Address, token: Address, auction_type: u32, user: Address) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_role(&e, Role::Keeper, &caller);
+
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        let pegkeeper = storage::get_pegkeeper(&e);
+        let amount = pegkeeper::quote_auction(&e, &pegkeeper, &blend_pool, &token, auction_type, &user);
+        helper::require_positive(&e, amount);
+
+        let fl_receive_args = vec![
+            &e,
+            token.into_val(&e),
+            amount.into_val(&e),
+            pegkeeper::fill_auction_calldata(&e, &blend_pool, auction_type, &user).into_val(&e),
+        ];
+        helper::flash_loan(&e, &pegkeeper, &caller, &token, amount, Symbol::new(&e, "fl_receive"), fl_receive_args);
+        helper::record_liquidation(&e);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "fill_auction")), (token, auction_type, user, amount));
+    }
+
+    fn call_pegkeeper(e: Env, fn_name: Symbol, args: Vec<Val>) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        let pegkeeper = storage::get_pegkeeper(&e);
+        e.invoke_contract::<Val>(&pegkeeper, &fn_name, args);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "call_pegkeeper")), fn_name);
+    }
+
+    fn set_stability_pool(e: Env, token: Address, stability_pool: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_stability_pool(&e, &token, &stability_pool);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_stability_pool")), (token, stability_pool));
+    }
+
+    fn add_flash_loaner(e: Env, receiver: Address, config: FlashLoanConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::add_flash_loaner(&e, &receiver, &config);
+    }
+
+    fn update_flash_loaner(e: Env, receiver: Address, config: FlashLoanConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        if storage::get_flash_loaner(&e, &receiver).is_none() {
+            panic_with_error!(&e, TreasuryError::UnauthorizedError);
+        }
+        helper::require_valid_fee_bps(&e, config.fee_bps);
+        helper::require_positive(&e, config.max_amount);
+        storage::set_flash_loaner(&e, &receiver, &config);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "update_flash_loaner")), (receiver, config));
+    }
+
+    fn remove_flash_loaner(e: Env, receiver: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::remove_flash_loaner(&e, &receiver);
+    }
+
+    fn get_flash_loaner(e: Env, receiver: Address) -> Option<FlashLoanConfig> {
+        storage::extend_instance(&e);
+        storage::get_flash_loaner(&e, &receiver)
+    }
+
+    fn get_recent_flash_loans(e: Env) -> Vec<FlashLoanRecord> {
+        storage::get_flash_loan_history(&e)
+    }
+
+    fn get_stats(e: Env) -> TreasuryStats {
+        storage::get_stats(&e)
+    }
+
+    fn set_fee_bps(e: Env, caller: Address, fee_bps: u32) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        helper::set_fee_bps(&e, fee_bps);
+    }
+
+    fn set_min_fee(e: Env, caller: Address, min_fee: i128) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        helper::set_min_fee(&e, min_fee);
+    }
+
+    fn set_fee_tiers(e: Env, caller: Address, config: FeeTierConfig) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        helper::set_fee_tiers(&e, &config);
+    }
+
+    fn get_fee_tiers(e: Env) -> Option<FeeTierConfig> {
+        storage::extend_instance(&e);
+        storage::get_fee_tiers(&e)
+    }
+
+    fn get_keeper_volume(e: Env, keeper: Address) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_keeper_volume(&e, &keeper)
+    }
+
+    fn get_fee_for(e: Env, caller: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_positive(&e, amount);
+        let config = FlashLoanConfig { max_amount: i128::MAX, fee_bps: 0, enabled: true };
+        helper::calc_keeper_fee(&e, &storage::get_fee_model(&e), &config, Some(&caller), amount)
+    }
+
+    fn set_oracle(e: Env, config: OracleConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_oracle_config(&e, &config);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_oracle")), (config.oracle, config.max_age));
+    }
+
+    fn get_price(e: Env, asset: Address) -> i128 {
+        storage::extend_instance(&e);
+        oracle::get_price(&e, &storage::get_oracle_config(&e), &asset)
+    }
+
+    fn set_surplus_recipient(e: Env, config: SurplusConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        if config.cap < 0 {
+            panic_with_error!(&e, TreasuryError::NegativeAmountError);
+        }
+        storage::set_surplus_config(&e, &config);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_surplus_recipient")), (config.recipient, config.cap));
+    }
+
+    fn set_fee_recipient(e: Env, recipient: Address, split_bps: u32) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        if i128::from(split_bps) > SCALAR_BPS {
+            panic_with_error!(&e, TreasuryError::InvalidAmount);
+        }
+        storage::set_fee_recipient(&e, &FeeRecipientConfig { recipient: recipient.clone(), split_bps });
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_fee_recipient")), (recipient, split_bps));
+    }
+
+    fn get_fee_recipient(e: Env) -> Option<FeeRecipientConfig> {
+        storage::get_fee_recipient(&e)
+    }
+
+    fn set_insurance_fund(e: Env, config: InsuranceConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        if i128::from(config.split_bps) > SCALAR_BPS {
+            panic_with_error!(&e, TreasuryError::InvalidAmount);
+        }
+        storage::set_insurance_config(&e, &config);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_insurance_fund")), (config.fund, config.token, config.split_bps));
+    }
+
+    fn get_insurance_fund(e: Env) -> Option<InsuranceConfig> {
+        storage::get_insurance_config(&e)
+    }
+
+    fn sweep(e: Env, token: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        helper::require_sweepable(&e, &token);
+        helper::require_positive(&e, amount);
+
+        let token_client = TokenClient::new(&e, &token);
+        if amount > token_client.balance(&e.current_contract_address()) {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        token_client.transfer(&e.current_contract_address(), &to, &amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "sweep")), (token, to, amount));
+    }
+
+    fn sweep_pegkeeper(e: Env, token: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        helper::require_sweepable(&e, &token);
+        helper::require_positive(&e, amount);
+
+        pegkeeper::sweep(&e, &storage::get_pegkeeper(&e), &token, &to, amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "sweep_pegkeeper")), (token, to, amount));
+    }
+
+    fn set_bridge_adapter(e: Env, adapter: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_bridge_adapter(&e, &adapter);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_bridge_adapter")), adapter);
+    }
+
+    fn get_bridge_adapter(e: Env) -> Option<Address> {
+        storage::get_bridge_adapter(&e)
+    }
+
+    fn record_bridge_out(e: Env, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_bridge_adapter(&e);
+        helper::require_positive(&e, amount);
+        storage::get_blend_pool(&e, &token);
+
+        storage::set_bridged(&e, &token, storage::get_bridged(&e, &token) + amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "record_bridge_out")), (token, amount));
+    }
+
+    fn record_bridge_in(e: Env, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_bridge_adapter(&e);
+        helper::require_positive(&e, amount);
+
+        let bridged = storage::get_bridged(&e, &token);
+        if amount > bridged {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        storage::set_bridged(&e, &token, bridged - amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "record_bridge_in")), (token, amount));
+    }
+
+    fn record_bad_debt(e: Env, token: Address, amount: i128) -> BadDebt {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        helper::require_positive(&e, amount);
+
+        let mut bad_debt = storage::get_bad_debt(&e, &token);
+        bad_debt.outstanding += amount;
+        storage::set_bad_debt(&e, &token, &bad_debt);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "record_bad_debt")), (token.clone(), amount));
+        helper::cover_bad_debt(&e, &token)
+    }
+
+    fn cover_bad_debt(e: Env, token: Address) -> BadDebt {
+        storage::extend_instance(&e);
+        helper::cover_bad_debt(&e, &token)
+    }
+
+    fn write_off(e: Env, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+        helper::require_positive(&e, amount);
+
+        let mut bad_debt = storage::get_bad_debt(&e, &token);
+        if amount > bad_debt.outstanding {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        bad_debt.outstanding -= amount;
+        bad_debt.written_off += amount;
+        storage::set_bad_debt(&e, &token, &bad_debt);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "write_off")), (token, amount));
+    }
+
+    fn get_bad_debt(e: Env, token: Address) -> BadDebt {
+        storage::get_bad_debt(&e, &token)
+    }
+
+    fn assert_invariants(e: Env, token: Address) {
+        helper::check_invariants(&e, &token);
+    }
+
+    fn sync_supply(e: Env, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        let actual = match e.try_invoke_contract::<i128, InvokeError>(&token, &Symbol::new(&e, "total_supply"), vec![&e]) {
+            Ok(Ok(supply)) => supply,
+            _ => panic_with_error!(&e, TreasuryError::SupplyUnavailable),
+        };
+
+        helper::accrue_stability_fee(&e, &token);
+        let recorded = storage::get_supply(&e, &token);
+        let drift = actual - recorded;
+        if drift != 0 {
+            helper::set_supply(&e, &token, actual);
+            e.events().publish(("Treasury", Symbol::new(&e, "supply_drift")), (token, recorded, actual));
+        }
+        drift
+    }
+
+    fn transfer_surplus(e: Env, caller: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        let config = match storage::get_surplus_config(&e) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::InternalError),
+        };
+        let surplus = storage::get_surplus(&e, &token);
+        helper::require_positive(&e, amount);
+        if amount > surplus {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        storage::set_surplus(&e, &token, surplus - amount);
+        TokenClient::new(&e, &token).transfer(&e.current_contract_address(), &config.recipient, &amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "transfer_surplus")), (token, config.recipient, amount));
+    }
+
+    fn set_savings_vault(e: Env, token: Address, vault: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        storage::set_savings_vault(&e, &token, &vault);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_savings_vault")), (token, vault));
+    }
+
+    fn distribute_yield(e: Env, caller: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+
+        let vault = match storage::get_savings_vault(&e, &token) {
+            Some(vault) => vault,
+            None => panic_with_error!(&e, TreasuryError::InternalError),
+        };
+        let surplus = storage::get_surplus(&e, &token);
+        helper::require_positive(&e, amount);
+        if amount > surplus {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        storage::set_surplus(&e, &token, surplus - amount);
+
+        let args: Vec<Val> = vec![
+            &e,
+            e.current_contract_address().into_val(&e),
+            vault.into_val(&e),
+            amount.into_val(&e),
+        ];
+        e.authorize_as_current_contract(vec![
+            &e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token.clone(),
+                    fn_name: Symbol::new(&e, "transfer"),
+                    args,
+                },
+                sub_invocations: vec![&e],
+            }),
+        ]);
+        e.invoke_contract::<()>(&vault, &Symbol::new(&e, "distribute_yield"), vec![&e, amount.into_val(&e)]);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "distribute_yield")), (token, vault, amount));
+    }
+
+    fn set_backstop_config(e: Env, config: BackstopConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        if i128::from(config.allocation_bps) > SCALAR_BPS {
+            panic_with_error!(&e, TreasuryError::InvalidAmount);
+        }
+        storage::set_backstop_config(&e, &config);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "set_backstop_config")), (config.backstop, config.allocation_bps));
+    }
+
+    fn deposit_backstop(e: Env, caller: Address, token: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+        helper::require_positive(&e, amount);
+
+        let config = storage::get_backstop_config(&e);
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        let backstop_client = BackstopClient::new(&e, &config.backstop);
+        let backstop_token = backstop_client.backstop_token();
+        let surplus = storage::get_surplus(&e, &backstop_token);
+        if amount > surplus {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        // the deposits of all pools share the allocation of the treasury's backstop tokens
+        let deposited = storage::get_index(&e, &DataKey::TOKENS)
+            .iter()
+            .map(|token| storage::get_backstop_deposit(&e, &token))
+            .sum::<i128>();
+        let allocation = helper::require_no_overflow(&e, (surplus + deposited).fixed_mul_floor(i128::from(config.allocation_bps), SCALAR_BPS));
+        if deposited + amount > allocation {
+            panic_with_error!(&e, TreasuryError::AllocationExceeded);
+        }
+        storage::set_surplus(&e, &backstop_token, surplus - amount);
+        storage::set_backstop_deposit(&e, &token, storage::get_backstop_deposit(&e, &token) + amount);
+
+        let args: Vec<Val> = vec![
+            &e,
+            e.current_contract_address().into_val(&e),
+            config.backstop.into_val(&e),
+            amount.into_val(&e),
+        ];
+        e.authorize_as_current_contract(vec![
+            &e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: backstop_token,
+                    fn_name: Symbol::new(&e, "transfer"),
+                    args,
+                },
+                sub_invocations: vec![&e],
+            }),
+        ]);
+        let shares = backstop_client.deposit(&e.current_contract_address(), &blend_pool, &amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "deposit_backstop")), (token, blend_pool, amount, shares));
+        shares
+    }
+
+    fn queue_backstop_withdrawal(e: Env, caller: Address, token: Address, shares: i128) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+        helper::require_positive(&e, shares);
+
+        let config = storage::get_backstop_config(&e);
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        BackstopClient::new(&e, &config.backstop).queue_withdrawal(&e.current_contract_address(), &blend_pool, &shares);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "queue_backstop_withdrawal")), (token, blend_pool, shares));
+    }
+
+    fn withdraw_backstop(e: Env, caller: Address, token: Address, shares: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::FeeManager, &caller);
+        helper::require_positive(&e, shares);
+
+        let config = storage::get_backstop_config(&e);
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        let backstop_client = BackstopClient::new(&e, &config.backstop);
+        let amount = backstop_client.withdraw(&e.current_contract_address(), &blend_pool, &shares);
+
+        let deposited = storage::get_backstop_deposit(&e, &token);
+        storage::set_backstop_deposit(&e, &token, (deposited - amount).max(0));
+        helper::add_surplus(&e, &storage::get_config(&e), &backstop_client.backstop_token(), amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "withdraw_backstop")), (token, blend_pool, shares, amount));
+        amount
+    }
+
+    fn get_backstop_position(e: Env, token: Address) -> BackstopPosition {
+        storage::extend_instance(&e);
+        let config = storage::get_backstop_config(&e);
+        let blend_pool = storage::get_blend_pool(&e, &token);
+        let balance = BackstopClient::new(&e, &config.backstop).user_balance(&blend_pool, &e.current_contract_address());
+        BackstopPosition {
+            deposited: storage::get_backstop_deposit(&e, &token),
+            shares: balance.shares,
+            queued_shares: balance.q4w.iter().map(|q4w| q4w.amount).sum(),
+        }
+    }
+
+    fn set_minter(e: Env, minter: Address, token: Address, daily_cap: i128, total_cap: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_minter(&e, &minter, &token, daily_cap, total_cap);
+    }
+
+    fn remove_minter(e: Env, minter: Address) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::remove_minter(&e, &minter);
+    }
+
+    fn get_minter(e: Env, minter: Address) -> Option<MinterConfig> {
+        storage::get_minter(&e, &minter)
+    }
+
+    fn get_mint_allowance(e: Env, minter: Address) -> i128 {
+        match storage::get_minter(&e, &minter) {
+            Some(config) => helper::calc_mint_allowance(&e, &config),
+            None => 0,
+        }
+    }
+
+    fn mint(e: Env, minter: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        minter.require_auth();
+
+        let mut config = match storage::get_minter(&e, &minter) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::UnauthorizedError),
+        };
+        helper::require_positive(&e, amount);
+        helper::require_not_denied(&e, &to);
+        helper::record_mint(&e, &mut config, amount);
+        storage::set_minter(&e, &minter, &config);
+        TokenAdminClient::new(&e, &config.token).mint(&to, &amount);
+        helper::update_supply(&e, &config.token, amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "mint")), (minter, to, amount));
+    }
+
+    fn burn(e: Env, minter: Address, amount: i128) {
+        storage::extend_instance(&e);
+        minter.require_auth();
+
+        let mut config = match storage::get_minter(&e, &minter) {
+            Some(config) => config,
+            None => panic_with_error!(&e, TreasuryError::UnauthorizedError),
+        };
+        helper::require_positive(&e, amount);
+        if amount > config.minted {
+            panic_with_error!(&e, TreasuryError::BalanceError);
+        }
+        config.minted -= amount;
+        storage::set_minter(&e, &minter, &config);
+        TokenClient::new(&e, &config.token).burn(&minter, &amount);
+        helper::update_supply(&e, &config.token, -amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "burn")), (minter, amount));
+    }
+
+    fn deposit_surplus(e: Env, from: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        helper::require_positive(&e, amount);
+
+        TokenClient::new(&e, &token).transfer(&from, &e.current_contract_address(), &amount);
+        helper::add_surplus(&e, &storage::get_config(&e), &token, amount);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "deposit_surplus")), (from, token, amount));
+    }
+
+    fn get_surplus(e: Env, token: Address) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_surplus(&e, &token)
+    }
+
+    fn create_stream(e: Env, token: Address, recipient: Address, total: i128, start: u64, end: u64) -> u32 {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::create_stream(&e, &token, &recipient, total, start, end)
+    }
+
+    fn withdraw_stream(e: Env, id: u32) -> i128 {
+        storage::extend_instance(&e);
+        let mut stream = helper::require_stream(&e, id);
+        stream.recipient.require_auth();
+
+        let amount = helper::calc_vested(&e, &stream) - stream.withdrawn;
+        if amount > 0 {
+            stream.withdrawn += amount;
+            if stream.withdrawn == stream.total {
+                storage::del_stream(&e, id);
+            } else {
+                storage::set_stream(&e, id, &stream);
+            }
+            TokenClient::new(&e, &stream.token).transfer(&e.current_contract_address(), &stream.recipient, &amount);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "withdraw_stream")), (id, amount));
+        amount
+    }
+
+    fn cancel_stream(e: Env, id: u32) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::cancel_stream(&e, id);
+    }
+
+    fn get_stream(e: Env, id: u32) -> Option<Stream> {
+        storage::get_stream(&e, id)
+    }
+
+    fn set_leverage(e: Env, config: LeverageConfig) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::set_leverage(&e, &config);
+    }
+
+    fn get_leverage(e: Env) -> Option<LeverageConfig> {
+        storage::get_leverage(&e)
+    }
+
+    fn post_collateral(e: Env, blend_pool: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        helper::post_collateral(&e, &blend_pool, &token, amount);
+    }
+
+    fn borrow(e: Env, blend_pool: Address, token: Address, amount: i128) {
+        storage::extend_instance(&e);
+        helper::require_not_paused(&e);
+        helper::require_admin(&e);
+
+        helper::borrow(&e, &blend_pool, &token, amount);
+    }
+
+    fn repay(e: Env, caller: Address, blend_pool: Address, token: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Keeper, &caller);
+        helper::require_positive(&e, amount);
+
+        let repaid = amount.min(helper::calc_liabilities(&e, &blend_pool, &token));
+        if repaid > 0 {
+            helper::repay_to_pool(&e, &blend_pool, &token, repaid);
+        }
+
+        e.events().publish(("Treasury", Symbol::new(&e, "repay")), (blend_pool, token, repaid));
+        repaid
+    }
+
+    fn get_ltv(e: Env, blend_pool: Address) -> i128 {
+        helper::calc_ltv(&e, &storage::get_oracle_config(&e), &blend_pool)
+    }
+
+    fn queue_upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>, current_wasm_hash: BytesN<32>) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Upgrader, &caller);
+
+        match storage::get_wasm_hash(&e) {
+            Some(wasm_hash) if wasm_hash != current_wasm_hash => panic_with_error!(&e, TreasuryError::WasmHashMismatch),
+            Some(_) => {}
+            None => storage::set_wasm_hash(&e, &current_wasm_hash),
+        }
+
+        let unlock_ledger = e.ledger().sequence() + storage::UPGRADE_TIMELOCK;
+        storage::set_queued_upgrade(&e, &QueuedUpgrade {
+            wasm_hash: new_wasm_hash.clone(),
+            unlock_ledger,
+        });
+
+        e.events().publish(("Treasury", Symbol::new(&e, "queue_upgrade")), (new_wasm_hash, unlock_ledger));
+    }
+
+    fn cancel_upgrade(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Upgrader, &caller);
+
+        let queued = match storage::get_queued_upgrade(&e) {
+            Some(queued) => queued,
+            None => panic_with_error!(&e, TreasuryError::UpgradeNotQueuedError),
+        };
+        storage::del_queued_upgrade(&e);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "cancel_upgrade")), queued.wasm_hash);
+    }
+
+    fn veto_upgrade(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Guardian, &caller);
+
+        let queued = match storage::get_queued_upgrade(&e) {
+            Some(queued) => queued,
+            None => panic_with_error!(&e, TreasuryError::UpgradeNotQueuedError),
+        };
+        if e.ledger().sequence() >= queued.unlock_ledger {
+            panic_with_error!(&e, TreasuryError::UpgradeUnlockedError);
+        }
+        storage::del_queued_upgrade(&e);
+
+        e.events().publish(("Treasury", Symbol::new(&e, "veto_upgrade")), (caller, queued.wasm_hash));
+    }
+
+    fn get_queued_upgrade(e: Env) -> Option<QueuedUpgrade> {
+        storage::get_queued_upgrade(&e)
+    }
+
+    fn upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Upgrader, &caller);
+
+        let queued = match storage::get_queued_upgrade(&e) {
+            Some(queued) => queued,
+            None => panic_with_error!(&e, TreasuryError::UpgradeNotQueuedError),
+        };
+        if queued.wasm_hash != new_wasm_hash {
+            panic_with_error!(&e, TreasuryError::UpgradeNotQueuedError);
+        }
+        if e.ledger().sequence() < queued.unlock_ledger {
+            panic_with_error!(&e, TreasuryError::UpgradeLockedError);
+        }
+        storage::del_queued_upgrade(&e);
+        // the running wasm is recorded when the upgrade is queued
+        match storage::get_wasm_hash(&e) {
+            Some(wasm_hash) => storage::set_rollback(&e, &Rollback {
+                wasm_hash,
+                expiration_ledger: e.ledger().sequence() + storage::ROLLBACK_WINDOW,
+                version: storage::get_version(&e),
+            }),
+            None => storage::del_rollback(&e),
+        }
+        storage::set_wasm_hash(&e, &new_wasm_hash);
+
+        e.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        e.events().publish(("Treasury", Symbol::new(&e, "upgrade")), new_wasm_hash);
+    }
+
+    fn rollback(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        helper::require_role(&e, Role::Upgrader, &caller);
+
+        let rollback = match storage::get_rollback(&e) {
+            Some(rollback) if e.ledger().sequence() <= rollback.expiration_ledger => rollback,
+            _ => panic_with_error!(&e, TreasuryError::RollbackUnavailable),
+        };
+        // the previous wasm cannot read a layout migrated after the upgrade
+        if storage::get_version(&e) != rollback.version {
+            panic_with_error!(&e, TreasuryError::StorageMigrated);
+        }
+        storage::del_rollback(&e);
+        storage::set_wasm_hash(&e, &rollback.wasm_hash);
+
+        e.deployer().update_current_contract_wasm(rollback.wasm_hash.clone());
+        e.events().publish(("Treasury", Symbol::new(&e, "rollback")), rollback.wasm_hash);
+    }
+
+    fn get_rollback(e: Env) -> Option<Rollback> {
+        storage::get_rollback(&e)
+    }
+
+    fn extend_all(e: Env) {
+        storage::extend_all(&e);
+    }
+
+    fn migrate(e: Env) {
+        storage::extend_instance(&e);
+        helper::require_admin(&e);
+
+        let version = storage::get_version(&e);
+        // storage layout changes for each version are applied here in order
+        if version < 5 {
+            // the configs read by flash loans are packed under a single key. Migrated first, as
+            // the other migrations read the packed config.
+            storage::migrate_config(&e);
+        }
+        if version < 2 {
+            // flash loan fees moved from a flat fee to a fee model
+            storage::set_fee_model(&e, &FeeModel {
+                fee_bps: 0,
+                min_fee: 0,
+            });
+        }
+        if version < 3 {
+            // flash loan receivers are indexed for `extend_all`, re-set the pegkeeper's config to index it
+            let pegkeeper = storage::get_pegkeeper(&e);
+            if let Some(config) = storage::get_flash_loaner(&e, &pegkeeper) {
+                storage::set_flash_loaner(&e, &pegkeeper, &config);
+            }
+        }
+        if version < 4 {
+            // the outstanding supply is tracked, seed it from the supplied positions and minters
+            for token in storage::get_index(&e, &DataKey::TOKENS).iter() {
+                helper::set_supply(&e, &token, helper::calc_total_supplied(&e, &token));
+            }
+            for minter in storage::get_index(&e, &DataKey::MINTERS).iter() {
+                if let Some(config) = storage::get_minter(&e, &minter) {
+                    helper::update_supply(&e, &config.token, config.minted);
+                }
+            }
+        }
+        if version < CONTRACT_VERSION {
+            storage::set_version(&e, CONTRACT_VERSION);
+            e.events().publish(("Treasury", Symbol::new(&e, "migrate")), (version, CONTRACT_VERSION));
+        }
     }
 }
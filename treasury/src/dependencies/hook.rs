@@ -0,0 +1,49 @@
+use soroban_sdk::{contracttype, vec, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec};
+
+use crate::storage;
+
+/// The treasury operations hooks are invoked around
+#[derive(Clone, Copy)]
+#[contracttype]
+pub enum HookOp {
+    IncreaseSupply,
+    DecreaseSupply,
+    FlashLoan,
+    FlashMint,
+    FlashCollateral,
+}
+
+/// Whether a hook is invoked before or after the operation
+#[derive(Clone, Copy)]
+#[contracttype]
+pub enum HookStage {
+    Before,
+    After,
+}
+
+/// Invoke the `on_treasury_op` function of every registered hook
+///
+/// Hooks are invoked with `try_invoke_contract`, so a hook that panics or returns an error is rolled
+/// back and reported with a `hook_failed` event instead of failing the operation. The number of hooks
+/// is capped by `MAX_HOOKS` to bound the resources spent on them, since a hook exceeding the
+/// transaction's budget can not be caught.
+///
+/// ### Arguments
+/// * `op` - The operation
+/// * `stage` - Whether the operation is about to execute or has executed
+/// * `token` - The Address of the token of the operation
+/// * `amount` - The amount of the operation
+pub fn call_hooks(e: &Env, op: HookOp, stage: HookStage, token: &Address, amount: i128) {
+    let hooks = storage::get_hooks(e);
+    if hooks.is_empty() {
+        return;
+    }
+    let fn_name = Symbol::new(e, "on_treasury_op");
+    for hook in hooks.iter() {
+        let args: Vec<Val> = vec![e, op.into_val(e), stage.into_val(e), token.into_val(e), amount.into_val(e)];
+        let result = e.try_invoke_contract::<Val, InvokeError>(&hook, &fn_name, args);
+        if result.is_err() {
+            e.events().publish(("Treasury", Symbol::new(e, "hook_failed")), (hook, op, stage));
+        }
+    }
+}
@@ -1 +1,6 @@
-pub mod pool;
\ No newline at end of file
+pub mod pool;
+pub mod oracle;
+pub mod pegkeeper;
+pub mod pair;
+pub mod backstop;
+pub mod hook;
@@ -0,0 +1,54 @@
+use sep_40_oracle::{Asset, PriceFeedClient};
+use soroban_sdk::{panic_with_error, Address, Env, Symbol};
+
+use crate::errors::TreasuryError;
+use crate::storage::{FxFeed, OracleConfig};
+
+const SCALAR_7: i128 = 1_0000000;
+
+/// Fetch the USD price of an asset from the oracle, scaled to 7 decimals
+///
+/// ### Arguments
+/// * `config` - The oracle config
+/// * `asset` - The Address of the asset
+///
+/// ### Panics
+/// If the oracle has no price for the asset or the price is older than the max age
+pub fn get_price(e: &Env, config: &OracleConfig, asset: &Address) -> i128 {
+    get_feed_price(e, config, &Asset::Stellar(asset.clone()))
+}
+
+/// Fetch the USD price of an oracle feed, scaled to 7 decimals
+///
+/// ### Arguments
+/// * `config` - The oracle config
+/// * `feed` - The asset of the oracle feed
+///
+/// ### Panics
+/// If the oracle has no price for the feed or the price is older than the max age
+pub fn get_feed_price(e: &Env, config: &OracleConfig, feed: &Asset) -> i128 {
+    let oracle_client = PriceFeedClient::new(e, &config.oracle);
+    let price_data = match oracle_client.lastprice(feed) {
+        Some(price_data) => price_data,
+        None => panic_with_error!(e, TreasuryError::StaleOracleError),
+    };
+    if price_data.timestamp + config.max_age < e.ledger().timestamp() {
+        panic_with_error!(e, TreasuryError::StaleOracleError);
+    }
+
+    let decimals = oracle_client.decimals();
+    price_data.price * SCALAR_7 / 10i128.pow(decimals)
+}
+
+/// Fetch the USD price of a fiat currency from an FX oracle, scaled to 7 decimals
+///
+/// ### Arguments
+/// * `feed` - The FX feed
+/// * `currency` - The currency code, e.g. EUR
+///
+/// ### Panics
+/// If the FX oracle has no price for the currency or the price is older than the max age
+pub fn get_fx_price(e: &Env, feed: &FxFeed, currency: &Symbol) -> i128 {
+    let config = OracleConfig { oracle: feed.oracle.clone(), max_age: feed.max_age };
+    get_feed_price(e, &config, &Asset::Other(currency.clone()))
+}
@@ -0,0 +1,100 @@
+use soroban_sdk::{contracttype, vec, xdr::ToXdr, Address, Bytes, Env, IntoVal, Symbol, Val, Vec};
+
+/// The flash loan operations of the pegkeeper's `fl_receive` calldata that the treasury builds.
+/// Must match the encoding of the pegkeeper's `FlashLoanOp`.
+#[derive(Clone)]
+#[contracttype]
+pub enum FlashLoanOp {
+    Liquidate(LiquidateOp),
+    FillAuction(FillAuctionOp),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct LiquidateOp {
+    pub blend_pool: Address, // blend pool of the user
+    pub user: Address,       // user being liquidated
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FillAuctionOp {
+    pub blend_pool: Address, // blend pool of the auction
+    pub auction_type: u32,   // blend auction type, 0 for user liquidations, 1 for bad debt, 2 for interest
+    pub user: Address,       // user the auction was created for, the backstop for bad debt and interest auctions
+}
+
+/// The pegkeeper strategies the treasury whitelists collaterals for.
+/// Must match the encoding of the pegkeeper's `Strategy`.
+#[derive(Clone, Copy)]
+#[contracttype]
+pub enum Strategy {
+    Peg,
+    Auction,
+    Liquidate,
+}
+
+/// Whitelist the collaterals the pegkeeper can receive from liquidations, replacing its current whitelists
+///
+/// ### Arguments
+/// * `pegkeeper` - The Address of the pegkeeper
+/// * `collaterals` - The Addresses of the collaterals
+pub fn set_liquidation_collaterals(e: &Env, pegkeeper: &Address, collaterals: &Vec<Address>) {
+    for strategy in [Strategy::Auction, Strategy::Liquidate] {
+        let args: Vec<Val> = vec![e, strategy.into_val(e), collaterals.into_val(e)];
+        e.invoke_contract::<()>(pegkeeper, &Symbol::new(e, "set_collaterals"), args);
+    }
+}
+
+/// Transfer tokens accidentally sent to the pegkeeper
+///
+/// ### Arguments
+/// * `pegkeeper` - The Address of the pegkeeper
+/// * `token` - The Address of the token
+/// * `to` - The Address receiving the tokens
+/// * `amount` - The amount to transfer
+pub fn sweep(e: &Env, pegkeeper: &Address, token: &Address, to: &Address, amount: i128) {
+    let args: Vec<Val> = vec![e, token.into_val(e), to.into_val(e), amount.into_val(e)];
+    e.invoke_contract::<()>(pegkeeper, &Symbol::new(e, "sweep"), args);
+}
+
+/// Encode the calldata to liquidate a blend user
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `user` - The Address of the user being liquidated
+pub fn liquidate_calldata(e: &Env, blend_pool: &Address, user: &Address) -> Bytes {
+    FlashLoanOp::Liquidate(LiquidateOp {
+        blend_pool: blend_pool.clone(),
+        user: user.clone(),
+    })
+    .to_xdr(e)
+}
+
+/// Encode the calldata to fill a blend auction in full
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `auction_type` - The blend auction type
+/// * `user` - The Address the auction was created for
+pub fn fill_auction_calldata(e: &Env, blend_pool: &Address, auction_type: u32, user: &Address) -> Bytes {
+    FlashLoanOp::FillAuction(FillAuctionOp {
+        blend_pool: blend_pool.clone(),
+        auction_type,
+        user: user.clone(),
+    })
+    .to_xdr(e)
+}
+
+/// Quote the stablecoins the pegkeeper needs to fill a blend auction in full at the current ledger
+///
+/// ### Arguments
+/// * `pegkeeper` - The Address of the pegkeeper
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the stablecoin
+/// * `auction_type` - The blend auction type
+/// * `user` - The Address the auction was created for
+pub fn quote_auction(e: &Env, pegkeeper: &Address, blend_pool: &Address, token: &Address, auction_type: u32, user: &Address) -> i128 {
+    let args: Vec<Val> = vec![e, blend_pool.into_val(e), token.into_val(e), auction_type.into_val(e), user.into_val(e)];
+    e.invoke_contract::<i128>(pegkeeper, &Symbol::new(e, "quote_auction"), args)
+}
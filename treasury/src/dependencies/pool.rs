@@ -1,3 +1,50 @@
-use soroban_sdk::contractimport;
+use soroban_sdk::{contractimport, panic_with_error, vec, Address, Env, IntoVal, Symbol, Vec};
 
-contractimport!(file = "../wasm/blend/pool.wasm");
\ No newline at end of file
+use crate::errors::TreasuryError;
+use crate::storage;
+
+contractimport!(file = "../wasm/blend/pool.wasm");
+
+/// Fetch the reserve list of the blend pool, ordered by reserve index
+///
+/// ### Arguments
+/// * `pool` - The Address of the blend pool
+pub fn get_reserve_list(e: &Env, pool: &Address) -> Vec<Address> {
+    e.invoke_contract::<Vec<Address>>(pool, &Symbol::new(e, "get_reserve_list"), vec![e])
+}
+
+/// Fetch the reserve index of an asset, as discovered when the pool was registered, or from the
+/// blend pool's reserve list if the asset was added to the pool since
+///
+/// ### Arguments
+/// * `pool` - The Address of the blend pool
+/// * `asset` - The Address of the reserve asset
+///
+/// ### Panics
+/// If the asset is not a reserve of the pool
+pub fn get_reserve_index(e: &Env, pool: &Address, asset: &Address) -> u32 {
+    if let Some(index) = storage::get_reserve_index(e, pool, asset) {
+        return index;
+    }
+    match get_reserve_list(e, pool).first_index_of(asset) {
+        Some(index) => index,
+        None => panic_with_error!(e, TreasuryError::ReserveNotFoundError),
+    }
+}
+
+/// Fetch the reserve for an asset from the blend pool
+///
+/// ### Arguments
+/// * `pool` - The Address of the blend pool
+/// * `asset` - The Address of the reserve asset
+///
+/// ### Panics
+/// If the asset is not a reserve of the pool
+pub fn get_reserve(e: &Env, pool: &Address, asset: &Address) -> Reserve {
+    let index = get_reserve_index(e, pool, asset);
+    let reserve = e.invoke_contract::<Reserve>(pool, &Symbol::new(e, "get_reserve"), vec![e, asset.into_val(e)]);
+    if reserve.index != index {
+        panic_with_error!(e, TreasuryError::ReserveNotFoundError);
+    }
+    reserve
+}
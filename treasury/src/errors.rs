@@ -3,18 +3,170 @@ use soroban_sdk::contracterror;
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
-/// Error codes for the pool factory contract. Common errors are codes that match up with the built-in
-/// dependencies error reporting. Treasury specific errors start at 2000.
+/// Error codes for the treasury contract. Common errors are codes that match up with the built-in
+/// dependencies error reporting.
 pub enum TreasuryError {
     // Common Errors
+    /// unexpected state, e.g. a required recipient is not set
     InternalError = 501,
+
+    /// already initialized or registered
     AlreadyInitializedError = 502,
+
+    /// the caller or receiver is not allowed to execute the operation
     UnauthorizedError = 503,
+
+    /// amount is negative
     NegativeAmountError = 504,
+
+    /// not enough balance to execute the operation
     BalanceError = 505,
+
+    /// amount is zero or negative, or a fee is out of range
     InvalidAmount = 506,
+
+    /// arithmetic overflow
     OverflowError = 507,
+
+    /// the flash loan receiver failed
     FlashloanFailedError = 508,
+
+    /// the supply change failed
     SupplyError = 509,
-    FlashloanNotRepaid = 510
-}
\ No newline at end of file
+
+    /// the flash loan plus fee was not repaid
+    FlashloanNotRepaid = 510,
+
+    // Treasury Errors
+    /// no upgrade is queued for the wasm hash
+    UpgradeNotQueuedError = 511,
+
+    /// the upgrade timelock has not passed
+    UpgradeLockedError = 512,
+
+    /// the flash loan exceeds the receiver's maximum amount
+    FlashloanCapExceeded = 513,
+
+    /// called while a flash loan is in progress
+    ReentrancyError = 514,
+
+    /// the oracle price is missing or older than the max age
+    StaleOracleError = 515,
+
+    /// the mint exceeds the minter's daily or total cap
+    MintCapExceeded = 516,
+
+    /// the treasury is paused
+    PausedError = 517,
+
+    /// the amount received is below the minimum
+    SlippageExceeded = 518,
+
+    /// the blend pool is not registered for the token
+    PoolNotFoundError = 519,
+
+    /// the supply change exceeds the supply limit
+    SupplyLimitExceeded = 520,
+
+    /// the token is not a reserve of the blend pool
+    ReserveNotFoundError = 521,
+
+    /// not yet initialized, or a required config is not set
+    NotInitializedError = 522,
+
+    /// the soroswap pair does not hold the stablecoin, or differs from the registered pair
+    InvalidPairError = 523,
+
+    /// the deposit exceeds the allocation set by the admin
+    AllocationExceeded = 524,
+
+    /// the circuit breaker is tripped by a sustained depeg
+    CircuitBreakerTripped = 525,
+
+    /// the current ledger sequence is past the deadline of the call
+    DeadlineExpired = 526,
+
+    /// the collateral is not registered with the treasury
+    CollateralNotAllowed = 527,
+
+    /// the amount exceeds the cap of the collateral
+    CollateralCapExceeded = 528,
+
+    /// no admin transfer of the token is pending for the caller
+    TokenAdminNotPending = 529,
+
+    /// the account is on the deny-list
+    AccountDenied = 530,
+
+    /// more hooks than `MAX_HOOKS` were registered
+    TooManyHooks = 531,
+
+    /// the token does not report its total supply
+    SupplyUnavailable = 532,
+
+    /// admin functions must be executed through a multisig proposal
+    MultisigRequired = 533,
+
+    /// the caller is not a multisig signer
+    NotSigner = 534,
+
+    /// the proposal does not exist, has expired, or has already been executed
+    ProposalNotFound = 535,
+
+    /// the proposal does not have enough approvals
+    ThresholdNotMet = 536,
+
+    /// the threshold is zero, above the number of signers, or the signers contain duplicates
+    InvalidMultisig = 537,
+
+    /// the payment stream does not exist
+    StreamNotFound = 538,
+
+    /// the payment stream ends before it starts
+    InvalidStream = 539,
+
+    /// the flash loan batch is empty or has more than the maximum number of loans
+    InvalidBatch = 540,
+
+    /// borrowing is not enabled
+    LeverageDisabled = 541,
+
+    /// the treasury's loan-to-value ratio would exceed the maximum
+    LtvExceeded = 542,
+
+    /// the queued withdrawal does not exist or has already been claimed
+    WithdrawalNotFound = 543,
+
+    /// an earlier withdrawal of the asset is still queued
+    WithdrawalNotNext = 544,
+
+    /// the blend pool does not have the liquidity to serve the withdrawal
+    InsufficientLiquidity = 545,
+
+    /// an accounting invariant of the treasury does not hold
+    InvariantViolation = 546,
+
+    /// the flash loan receiver is an account instead of a contract, or is not the registered pegkeeper
+    InvalidReceiver = 547,
+
+    /// automatic supply adjustment is not configured for the token
+    AutoSupplyDisabled = 548,
+
+    /// the supply was automatically adjusted less than the configured interval ago
+    AdjustmentTooSoon = 549,
+
+    /// the token is tracked by the treasury's accounting and cannot be swept
+    TokenNotSweepable = 550,
+
+    /// the timelock of the queued upgrade has passed and it can no longer be vetoed
+    UpgradeUnlockedError = 551,
+
+    /// no upgrade can be rolled back, or its rollback window has passed
+    RollbackUnavailable = 552,
+
+    /// the hash of the running contract wasm does not match the recorded hash
+    WasmHashMismatch = 553,
+
+    /// the storage was migrated since the last upgrade, so the previous wasm cannot read it
+    StorageMigrated = 554,
+}
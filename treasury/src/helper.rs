@@ -0,0 +1,1950 @@
+use sep_41_token::StellarAssetClient;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{log, panic_with_error, token::Client as TokenClient, vec, xdr::ToXdr, Address, Bytes, Env, IntoVal, InvokeError, Symbol, Val, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+
+use crate::dependencies::hook::{self, HookOp, HookStage};
+use crate::dependencies::oracle;
+use crate::dependencies::pair::Client as PairClient;
+use crate::dependencies::pool::{self, Client as PoolClient, Request};
+use crate::errors::TreasuryError;
+use crate::storage::{self, AutoSupplyConfig, BadDebt, BreakerConfig, BreakerState, DataKey, FeeModel, FeeTierConfig, FlashCollateralConfig, FlashLoanConfig, FlashLoanRecord, FlashMintConfig, LeverageConfig, MinterConfig, MultisigConfig, OracleConfig, Role, StabilityFee, Stream, SupplyLimit, SupplySnapshot, SupplyWindow, TreasuryConfig, Withdrawal};
+
+pub(crate) const SCALAR_BPS: i128 = 10_000;
+pub(crate) const SCALAR_7: i128 = 1_0000000;
+pub(crate) const SCALAR_9: i128 = 1_000_000_000;
+pub(crate) const SCALAR_18: i128 = 1_000_000_000_000_000_000;
+pub(crate) const MAX_FEE_BPS: u32 = 1_000; // 10%
+pub(crate) const MAX_HOOKS: u32 = 5;
+pub(crate) const MAX_BATCH_LOANS: u32 = 10;
+pub(crate) const MAX_LTV_BPS: u32 = 5_000; // 50%
+pub(crate) const MAX_FEE_TIERS: u32 = 10;
+
+// An XDR encoded `ScVal::Address` is the 4 byte `ScValType` tag followed by the 4 byte `ScAddressType`
+// tag, both big endian, so the address type is the last byte of the second tag
+const SC_ADDRESS_TYPE_BYTE: u32 = 7;
+const SC_ADDRESS_TYPE_CONTRACT: u8 = 1;
+
+/// Check if an account holds a role
+///
+/// The admin and accounts granted the `Admin` role hold every role.
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `account` - The Address of the account
+pub fn has_role(e: &Env, role: Role, account: &Address) -> bool {
+    *account == storage::get_admin(e)
+        || storage::has_role(e, Role::Admin, account)
+        || storage::has_role(e, role, account)
+}
+
+/// Require the admin to authorize the call, returning the admin
+///
+/// ### Panics
+/// If the admin did not authorize the call, or the treasury is in multisig mode, where admin
+/// functions are executed through proposals
+pub fn require_admin(e: &Env) -> Address {
+    if storage::get_multisig(e).is_some() {
+        panic_with_error!(e, TreasuryError::MultisigRequired);
+    }
+    let admin = storage::get_admin(e);
+    admin.require_auth();
+    admin
+}
+
+/// Require the bridge adapter to authorize the call
+///
+/// ### Panics
+/// If no bridge adapter is set, or the bridge adapter did not authorize the call
+pub fn require_bridge_adapter(e: &Env) {
+    match storage::get_bridge_adapter(e) {
+        Some(adapter) => adapter.require_auth(),
+        None => panic_with_error!(e, TreasuryError::UnauthorizedError),
+    }
+}
+
+/// Require a token to be untracked by the treasury's accounting, so it can be swept. Stablecoins, collaterals
+/// and flash loaned collaterals, the pair LP tokens of protocol-owned liquidity, and assets with a surplus buffer
+/// balance or queued withdrawals are tracked.
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+///
+/// ### Panics
+/// If the token is tracked by the treasury's accounting
+pub fn require_sweepable(e: &Env, token: &Address) {
+    let tokens = storage::get_index(e, &DataKey::TOKENS);
+    let tracked = tokens.contains(token)
+        || storage::get_collateral(e, token).is_some()
+        || storage::get_flash_collateral_config(e, token).is_some()
+        || storage::get_surplus(e, token) != 0
+        || storage::get_withdrawal_queue(e, token).queued != 0
+        || tokens.iter().any(|stablecoin| match storage::get_pol_position(e, &stablecoin) {
+            Some(position) => position.pair == *token,
+            None => false,
+        });
+    if tracked {
+        panic_with_error!(e, TreasuryError::TokenNotSweepable);
+    }
+}
+
+/// Require a multisig config to have a reachable threshold and no duplicate signers
+///
+/// ### Arguments
+/// * `config` - The multisig config
+///
+/// ### Panics
+/// If the threshold is zero or above the number of signers, or a signer is listed twice
+pub fn require_valid_multisig(e: &Env, config: &MultisigConfig) {
+    if config.threshold == 0 || config.threshold > config.signers.len() {
+        panic_with_error!(e, TreasuryError::InvalidMultisig);
+    }
+    for (i, signer) in config.signers.iter().enumerate() {
+        if config.signers.last_index_of(&signer) != Some(i as u32) {
+            panic_with_error!(e, TreasuryError::InvalidMultisig);
+        }
+    }
+}
+
+/// Require the caller to authorize the call and be a multisig signer
+///
+/// Returns the multisig config
+///
+/// ### Arguments
+/// * `signer` - The Address of the signer
+///
+/// ### Panics
+/// If the treasury is not in multisig mode or the caller is not a signer
+pub fn require_signer(e: &Env, signer: &Address) -> MultisigConfig {
+    signer.require_auth();
+    match storage::get_multisig(e) {
+        Some(config) if config.signers.contains(signer) => config,
+        _ => panic_with_error!(e, TreasuryError::NotSigner),
+    }
+}
+
+/// Require the caller to authorize the call and hold a role
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `caller` - The Address of the caller
+///
+/// ### Panics
+/// If the caller does not hold the role
+pub fn require_role(e: &Env, role: Role, caller: &Address) {
+    caller.require_auth();
+    if !has_role(e, role, caller) {
+        panic_with_error!(e, TreasuryError::UnauthorizedError);
+    }
+}
+
+/// Require the treasury to not be paused
+///
+/// ### Panics
+/// If the treasury is paused
+pub fn require_not_paused(e: &Env) {
+    if storage::is_paused(e) {
+        panic_with_error!(e, TreasuryError::PausedError);
+    }
+}
+
+/// Fetch the USD price of the currency a stablecoin is pegged to, scaled to 7 decimals
+///
+/// USD stablecoins are priced at 1. Other currencies are priced by the stablecoin's FX feed.
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+///
+/// ### Panics
+/// If the stablecoin is not pegged to USD and has no FX feed, or the FX price is missing or stale
+pub fn get_peg_price(e: &Env, token: &Address) -> i128 {
+    let currency = storage::get_currency(e, token);
+    if currency == Symbol::new(e, "USD") {
+        return SCALAR_7;
+    }
+    match storage::get_fx_feed(e, token) {
+        Some(feed) => oracle::get_fx_price(e, &feed, &currency),
+        None => panic_with_error!(e, TreasuryError::StaleOracleError),
+    }
+}
+
+/// Calculate the circuit breaker state of a token at the current oracle price, relative to the USD
+/// price of the currency it is pegged to
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `config` - The circuit breaker config
+///
+/// ### Panics
+/// If the oracle price is missing or stale, or the token is not pegged to USD and its FX price is unavailable
+pub fn calc_breaker_state(e: &Env, token: &Address, config: &BreakerConfig) -> BreakerState {
+    let price = oracle::get_price(e, &storage::get_oracle_config(e), token);
+    let target = get_peg_price(e, token);
+    let deviation_bps = (price - target).abs() * SCALAR_BPS / target;
+    let depeg_ledger = if deviation_bps > i128::from(config.threshold_bps) {
+        Some(storage::get_depeg_ledger(e, token).unwrap_or(e.ledger().sequence()))
+    } else {
+        None
+    };
+    let tripped = match depeg_ledger {
+        Some(ledger) => e.ledger().sequence() - ledger > config.delay,
+        None => false,
+    };
+    BreakerState { price, depeg_ledger, tripped }
+}
+
+/// Calculate and record the circuit breaker state of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `config` - The circuit breaker config
+///
+/// ### Panics
+/// If the oracle price is missing or stale
+pub fn update_breaker(e: &Env, token: &Address, config: &BreakerConfig) -> BreakerState {
+    let state = calc_breaker_state(e, token, config);
+    match state.depeg_ledger {
+        Some(ledger) => storage::set_depeg_ledger(e, token, ledger),
+        None => storage::del_depeg_ledger(e, token),
+    }
+    state
+}
+
+/// Require the circuit breaker of a token to not be tripped, recording its state.
+/// Tokens without a circuit breaker config are not checked.
+///
+/// The breaker trips once the oracle price has deviated from its peg price by more than the threshold for
+/// longer than the delay, and resets as soon as the price recovers.
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+///
+/// ### Panics
+/// If the breaker is tripped or the oracle price is missing or stale
+pub fn require_breaker_closed(e: &Env, token: &Address) {
+    if let Some(config) = storage::get_breaker_config(e, token) {
+        if update_breaker(e, token, &config).tripped {
+            panic_with_error!(e, TreasuryError::CircuitBreakerTripped);
+        }
+    }
+}
+
+/// Require an amount to be positive
+///
+/// ### Arguments
+/// * `amount` - The amount
+///
+/// ### Panics
+/// If the amount is zero or negative
+pub fn require_positive(e: &Env, amount: i128) {
+    if amount <= 0 {
+        panic_with_error!(e, TreasuryError::InvalidAmount);
+    }
+}
+
+/// Require the current ledger sequence to be at or before a deadline, if any
+///
+/// ### Arguments
+/// * `deadline` - The last ledger sequence the call can execute in, or None for no deadline
+///
+/// ### Panics
+/// If the current ledger sequence is past the deadline
+pub fn require_before_deadline(e: &Env, deadline: Option<u64>) {
+    if let Some(deadline) = deadline {
+        if u64::from(e.ledger().sequence()) > deadline {
+            panic_with_error!(e, TreasuryError::DeadlineExpired);
+        }
+    }
+}
+
+/// Require a fee rate to be at most `MAX_FEE_BPS`
+///
+/// ### Arguments
+/// * `fee_bps` - The fee rate in basis points
+///
+/// ### Panics
+/// If the fee rate is above `MAX_FEE_BPS`
+pub fn require_valid_fee_bps(e: &Env, fee_bps: u32) {
+    if fee_bps > MAX_FEE_BPS {
+        panic_with_error!(e, TreasuryError::InvalidAmount);
+    }
+}
+
+/// Unwrap the result of a checked arithmetic operation
+///
+/// ### Arguments
+/// * `value` - The result of the operation
+///
+/// ### Panics
+/// If the operation overflowed
+pub fn require_no_overflow(e: &Env, value: Option<i128>) -> i128 {
+    value.unwrap_or_else(|| panic_with_error!(e, TreasuryError::OverflowError))
+}
+
+/// Require an account not to be on the deny-list
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+///
+/// ### Panics
+/// If the account is denied
+pub fn require_not_denied(e: &Env, account: &Address) {
+    if storage::is_denied(e, account) {
+        panic_with_error!(e, TreasuryError::AccountDenied);
+    }
+}
+
+/// Add accounts to or remove them from the deny-list
+///
+/// ### Arguments
+/// * `accounts` - The Addresses of the accounts
+/// * `denied` - Whether the accounts are denied
+pub fn set_denied(e: &Env, accounts: &Vec<Address>, denied: bool) {
+    for account in accounts.iter() {
+        if denied {
+            storage::set_denied(e, &account);
+        } else {
+            storage::del_denied(e, &account);
+        }
+        e.events().publish(("Treasury", Symbol::new(e, "set_denied")), (account, denied));
+    }
+}
+
+/// Replace the hooks invoked around supply changes and flash loans
+///
+/// ### Arguments
+/// * `hooks` - The Addresses of the hook contracts
+///
+/// ### Panics
+/// If there are more than `MAX_HOOKS` hooks
+pub fn set_hooks(e: &Env, hooks: &Vec<Address>) {
+    if hooks.len() > MAX_HOOKS {
+        panic_with_error!(e, TreasuryError::TooManyHooks);
+    }
+    storage::set_hooks(e, hooks);
+    e.events().publish(("Treasury", Symbol::new(e, "set_hooks")), hooks.clone());
+}
+
+/// Calculate the fee owed for a flash loan
+///
+/// The receiver's fee rate is used if set, otherwise the treasury's default fee rate.
+/// The fee is never less than the treasury's minimum fee.
+///
+/// ### Arguments
+/// * `fee_model` - The treasury's fee model
+/// * `config` - The flash loan config of the receiver
+/// * `amount` - The amount borrowed
+pub fn calc_flash_loan_fee(e: &Env, fee_model: &FeeModel, config: &FlashLoanConfig, amount: i128) -> i128 {
+    let fee_bps = if config.fee_bps > 0 { config.fee_bps } else { fee_model.fee_bps };
+    let fee = require_no_overflow(e, amount.fixed_mul_ceil(i128::from(fee_bps), SCALAR_BPS));
+    fee.max(fee_model.min_fee)
+}
+
+/// Calculate the flash loan fee discount of a keeper, the largest discount of the fee tiers it
+/// qualifies for by cumulative volume or by staked balance
+///
+/// Returns the discount in basis points
+///
+/// ### Arguments
+/// * `keeper` - The Address of the keeper
+pub fn calc_fee_discount(e: &Env, keeper: &Address) -> u32 {
+    let config = match storage::get_fee_tiers(e) {
+        Some(config) => config,
+        None => return 0,
+    };
+    let volume = storage::get_keeper_volume(e, keeper);
+    let stake = config.stake_token.map(|stake_token| TokenClient::new(e, &stake_token).balance(keeper));
+
+    let mut discount_bps = 0;
+    for tier in config.tiers.iter() {
+        let staked = stake.is_some_and(|stake| stake >= tier.min_stake);
+        if (volume >= tier.min_volume || staked) && tier.discount_bps > discount_bps {
+            discount_bps = tier.discount_bps;
+        }
+    }
+    discount_bps
+}
+
+/// Apply a fee discount to a flash loan fee, rounding the discounted fee up
+///
+/// ### Arguments
+/// * `fee` - The flash loan fee
+/// * `discount_bps` - The discount in basis points
+pub fn apply_fee_discount(e: &Env, fee: i128, discount_bps: u32) -> i128 {
+    if discount_bps == 0 {
+        return fee;
+    }
+    require_no_overflow(e, fee.fixed_mul_ceil(SCALAR_BPS - i128::from(discount_bps), SCALAR_BPS))
+}
+
+/// Calculate the flash loan fee of a receiver, discounted by the fee tier of the keeper requesting the loan
+///
+/// ### Arguments
+/// * `fee_model` - The treasury's fee model
+/// * `config` - The flash loan config of the receiver
+/// * `keeper` - The Address of the keeper requesting the loan, if any
+/// * `amount` - The amount borrowed
+pub fn calc_keeper_fee(e: &Env, fee_model: &FeeModel, config: &FlashLoanConfig, keeper: Option<&Address>, amount: i128) -> i128 {
+    let fee = calc_flash_loan_fee(e, fee_model, config, amount);
+    match keeper {
+        Some(keeper) => apply_fee_discount(e, fee, calc_fee_discount(e, keeper)),
+        None => fee,
+    }
+}
+
+/// Add a flash loan amount to the cumulative volume of a keeper
+///
+/// ### Arguments
+/// * `keeper` - The Address of the keeper
+/// * `amount` - The amount borrowed
+pub fn record_keeper_volume(e: &Env, keeper: &Address, amount: i128) {
+    let volume = require_no_overflow(e, storage::get_keeper_volume(e, keeper).checked_add(amount));
+    storage::set_keeper_volume(e, keeper, volume);
+}
+
+/// Set the flash loan fee tiers of keepers
+///
+/// ### Arguments
+/// * `config` - The fee tier config
+///
+/// ### Panics
+/// If there are more than `MAX_FEE_TIERS` tiers, a threshold is not positive, or a discount is above 100%
+pub fn set_fee_tiers(e: &Env, config: &FeeTierConfig) {
+    if config.tiers.len() > MAX_FEE_TIERS {
+        panic_with_error!(e, TreasuryError::InvalidAmount);
+    }
+    for tier in config.tiers.iter() {
+        if tier.min_volume <= 0 || tier.min_stake <= 0 || i128::from(tier.discount_bps) > SCALAR_BPS {
+            panic_with_error!(e, TreasuryError::InvalidAmount);
+        }
+    }
+    storage::set_fee_tiers(e, config);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_fee_tiers")), config.clone());
+}
+
+/// Require a flash loan receiver to be an enabled whitelisted contract
+///
+/// The whitelist entry alone is not trusted: the receiver must also be a contract, so an account
+/// whose signer could authorize calls as the receiver can not take the keeper path.
+///
+/// Returns the flash loan config of the receiver
+///
+/// ### Arguments
+/// * `receiver` - The Address of the flash loan receiver
+///
+/// ### Panics
+/// If the receiver is not whitelisted or is disabled, or is not a contract
+pub fn require_flash_loaner(e: &Env, receiver: &Address) -> FlashLoanConfig {
+    let config = match storage::get_flash_loaner(e, receiver) {
+        Some(config) if config.enabled => config,
+        _ => panic_with_error!(e, TreasuryError::UnauthorizedError),
+    };
+    require_contract(e, receiver);
+    config
+}
+
+/// Require a flash loan requested by a keeper to be received by the registered pegkeeper, or by the
+/// keeper itself as a whitelisted contract
+///
+/// The keeper's authorization is required by the entrypoint, and a contract only authorizes calls it
+/// invokes directly, so a whitelisted contract can only take flash loans it requested itself. A keeper
+/// can not direct a loan, and its calldata, into another whitelisted receiver.
+///
+/// ### Arguments
+/// * `keeper` - The Address of the keeper requesting the loan
+/// * `receiver` - The Address of the flash loan receiver
+///
+/// ### Panics
+/// If the receiver is neither the registered pegkeeper nor the keeper
+pub fn require_keeper_receiver(e: &Env, keeper: &Address, receiver: &Address) {
+    if receiver != keeper && *receiver != storage::get_pegkeeper(e) {
+        panic_with_error!(e, TreasuryError::UnauthorizedError);
+    }
+}
+
+/// Require an address to be the registered pegkeeper
+///
+/// ### Arguments
+/// * `address` - The Address to check
+///
+/// ### Panics
+/// If the address is not the registered pegkeeper
+pub fn require_pegkeeper(e: &Env, address: &Address) {
+    if *address != storage::get_pegkeeper(e) {
+        panic_with_error!(e, TreasuryError::InvalidReceiver);
+    }
+}
+
+/// Require an address to be a contract rather than an account
+///
+/// ### Arguments
+/// * `address` - The Address to check
+///
+/// ### Panics
+/// If the address is an account
+pub fn require_contract(e: &Env, address: &Address) {
+    if address.clone().to_xdr(e).get(SC_ADDRESS_TYPE_BYTE) != Some(SC_ADDRESS_TYPE_CONTRACT) {
+        panic_with_error!(e, TreasuryError::InvalidReceiver);
+    }
+}
+
+/// Mint a flash loan to a whitelisted receiver, invoke it, and burn the repaid loan
+///
+/// The flash loan fee is appended to `args`. The receiver must approve the treasury to
+/// pull the loan plus the fee before returning.
+///
+/// ### Arguments
+/// * `receiver` - The Address of the flash loan receiver
+/// * `keeper` - The Address of the keeper requesting the loan, whose fee tier discounts the fee
+/// * `token` - The Address of the stablecoin to loan
+/// * `amount` - The amount to loan
+/// * `fn_name` - The receiver function to invoke
+/// * `args` - The arguments of the receiver function
+///
+/// ### Panics
+/// If the amount is zero or negative
+/// If the receiver is neither the registered pegkeeper nor the keeper
+/// If the receiver is denied, is not a whitelisted contract, is disabled, or the amount exceeds its maximum
+/// If a flash loan is already in progress or the loan is not repaid
+pub fn flash_loan(e: &Env, receiver: &Address, keeper: &Address, token: &Address, amount: i128, fn_name: Symbol, args: Vec<Val>) {
+    log!(e, "================================= Real: Treasury FlashLoan Function Start ============================");
+
+    if storage::is_locked(e) {
+        panic_with_error!(e, TreasuryError::ReentrancyError);
+    }
+    require_positive(e, amount);
+    require_keeper_receiver(e, keeper, receiver);
+    require_not_denied(e, receiver);
+    storage::set_lock(e);
+
+    let config = require_flash_loaner(e, receiver);
+    if amount > config.max_amount {
+        panic_with_error!(e, TreasuryError::FlashloanCapExceeded);
+    }
+    // the packed config is read once and passed down, instead of reading each config separately
+    let treasury_config = storage::get_config(e);
+    let fee = calc_keeper_fee(e, &treasury_config.fee_model, &config, Some(keeper), amount);
+
+    hook::call_hooks(e, HookOp::FlashLoan, HookStage::Before, token, amount);
+    mint_and_repay(e, &treasury_config, receiver, token, amount, fee, fn_name, args);
+    record_keeper_volume(e, keeper, amount);
+    hook::call_hooks(e, HookOp::FlashLoan, HookStage::After, token, amount);
+    storage::del_lock(e);
+    log!(e, "================================= Real: Treasury FlashLoan Function End ============================");
+}
+
+/// Mint flash loans to several whitelisted receivers, invoke their `fl_receive` functions, and burn the repaid loans
+///
+/// All loans are minted before any receiver is invoked, so receivers can pass funds between each
+/// other, and repayments are pulled once every receiver has returned. The lock, the treasury config
+/// and the stats are read and written once for the whole batch.
+///
+/// ### Arguments
+/// * `keeper` - The Address of the keeper requesting the loans, whose fee tier discounts the fees
+/// * `token` - The Address of the stablecoin to loan
+/// * `loans` - The receivers and the amounts loaned to them
+/// * `data` - The calldata passed to every receiver
+///
+/// ### Panics
+/// If the batch is empty or has more than `MAX_BATCH_LOANS` loans
+/// If an amount is zero or negative
+/// If a receiver is denied, is not a whitelisted contract, is disabled, or an amount exceeds its maximum
+/// If a flash loan is already in progress or a loan is not repaid
+pub fn flash_loan_batch(e: &Env, keeper: &Address, token: &Address, loans: &Vec<(Address, i128)>, data: &Bytes) {
+    if storage::is_locked(e) {
+        panic_with_error!(e, TreasuryError::ReentrancyError);
+    }
+    if loans.is_empty() || loans.len() > MAX_BATCH_LOANS {
+        panic_with_error!(e, TreasuryError::InvalidBatch);
+    }
+    storage::set_lock(e);
+
+    let treasury_config = storage::get_config(e);
+    let discount_bps = calc_fee_discount(e, keeper);
+    let mut fees: Vec<i128> = vec![e];
+    let mut total_amount = 0;
+    for (receiver, amount) in loans.iter() {
+        require_positive(e, amount);
+        require_not_denied(e, &receiver);
+        require_keeper_receiver(e, keeper, &receiver);
+        let config = require_flash_loaner(e, &receiver);
+        if amount > config.max_amount {
+            panic_with_error!(e, TreasuryError::FlashloanCapExceeded);
+        }
+        fees.push_back(apply_fee_discount(e, calc_flash_loan_fee(e, &treasury_config.fee_model, &config, amount), discount_bps));
+        total_amount += amount;
+    }
+
+    hook::call_hooks(e, HookOp::FlashLoan, HookStage::Before, token, total_amount);
+    let token_admin_client = StellarAssetClient::new(e, token);
+    for (receiver, amount) in loans.iter() {
+        token_admin_client.mint(&receiver, &amount);
+    }
+    let fn_name = Symbol::new(e, "fl_receive");
+    for (i, (receiver, amount)) in loans.iter().enumerate() {
+        let args: Vec<Val> = vec![e, token.into_val(e), amount.into_val(e), data.into_val(e), fees.get_unchecked(i as u32).into_val(e)];
+        e.invoke_contract::<Val>(&receiver, &fn_name, args);
+    }
+
+    let token_client = TokenClient::new(e, token);
+    let mut total_fee = 0;
+    for (i, (receiver, amount)) in loans.iter().enumerate() {
+        let fee = fees.get_unchecked(i as u32);
+        pull_repayment(e, &token_client, &receiver, amount + fee);
+        storage::push_flash_loan_history(e, &FlashLoanRecord {
+            receiver: receiver.clone(),
+            token: token.clone(),
+            amount,
+            fee,
+            ledger: e.ledger().sequence(),
+            success: true,
+        });
+        total_fee += fee;
+    }
+
+    // Burn the loaned tokens, the fees are kept by the treasury
+    token_client.burn(&e.current_contract_address(), &total_amount);
+    add_fee(e, &treasury_config, token, total_fee);
+    let mut stats = storage::get_stats(e);
+    stats.flash_loans += loans.len();
+    stats.flash_loan_volume += total_amount;
+    stats.fees_earned += total_fee;
+    storage::set_stats(e, &stats);
+    record_keeper_volume(e, keeper, total_amount);
+    hook::call_hooks(e, HookOp::FlashLoan, HookStage::After, token, total_amount);
+    storage::del_lock(e);
+}
+
+/// Flash mint stablecoins to any receiver, invoke its `fl_receive` function, and burn the repaid loan
+///
+/// Flash mints are limited by the token's flash mint cap and charged the flash mint fee, instead
+/// of the flash loan receiver whitelist and fee model.
+///
+/// ### Arguments
+/// * `receiver` - The Address of the receiver
+/// * `token` - The Address of the stablecoin to mint
+/// * `amount` - The amount to mint
+/// * `data` - The calldata passed to the receiver
+///
+/// ### Panics
+/// If the amount is zero or negative, the receiver is denied, flash minting is not enabled for the token, or the amount exceeds the cap
+/// If a flash loan is already in progress or the loan is not repaid
+pub fn flash_mint(e: &Env, receiver: &Address, token: &Address, amount: i128, data: Bytes) {
+    if storage::is_locked(e) {
+        panic_with_error!(e, TreasuryError::ReentrancyError);
+    }
+    require_positive(e, amount);
+    require_not_denied(e, receiver);
+    storage::set_lock(e);
+
+    let config = match storage::get_flash_mint_config(e, token) {
+        Some(config) => config,
+        None => panic_with_error!(e, TreasuryError::UnauthorizedError),
+    };
+    if amount > config.cap {
+        panic_with_error!(e, TreasuryError::FlashloanCapExceeded);
+    }
+    let fee = require_no_overflow(e, amount.fixed_mul_ceil(i128::from(config.fee_bps), SCALAR_BPS));
+
+    hook::call_hooks(e, HookOp::FlashMint, HookStage::Before, token, amount);
+    let args: Vec<Val> = vec![e, token.into_val(e), amount.into_val(e), data.into_val(e)];
+    mint_and_repay(e, &storage::get_config(e), receiver, token, amount, fee, Symbol::new(e, "fl_receive"), args);
+    hook::call_hooks(e, HookOp::FlashMint, HookStage::After, token, amount);
+    storage::del_lock(e);
+}
+
+/// Flash loan a collateral held by the treasury to any receiver and invoke its `fl_receive` function
+///
+/// The loan is taken from the treasury's idle balance of the collateral. If that does not cover the
+/// amount, the rest is withdrawn from the treasury's blend position in the configured pool and
+/// resupplied once the loan is repaid. The fee is split by `add_fee`. The receiver must
+/// approve the treasury to pull the loan plus the fee before returning.
+///
+/// ### Arguments
+/// * `receiver` - The Address of the receiver
+/// * `collateral` - The Address of the collateral to loan
+/// * `amount` - The amount to loan
+/// * `data` - The calldata passed to the receiver
+///
+/// ### Panics
+/// If the amount is zero or negative, the receiver is denied, flash loans of the collateral are not enabled, or the amount exceeds the cap
+/// If the idle balance and the blend position do not cover the amount
+/// If a flash loan is already in progress or the loan is not repaid
+pub fn flash_collateral(e: &Env, receiver: &Address, collateral: &Address, amount: i128, data: Bytes) {
+    if storage::is_locked(e) {
+        panic_with_error!(e, TreasuryError::ReentrancyError);
+    }
+    require_positive(e, amount);
+    require_not_denied(e, receiver);
+    storage::set_lock(e);
+
+    let config = match storage::get_flash_collateral_config(e, collateral) {
+        Some(config) => config,
+        None => panic_with_error!(e, TreasuryError::UnauthorizedError),
+    };
+    if amount > config.cap {
+        panic_with_error!(e, TreasuryError::FlashloanCapExceeded);
+    }
+    let fee = require_no_overflow(e, amount.fixed_mul_ceil(i128::from(config.fee_bps), SCALAR_BPS));
+    hook::call_hooks(e, HookOp::FlashCollateral, HookStage::Before, collateral, amount);
+
+    let token_client = TokenClient::new(e, collateral);
+    let self_address = e.current_contract_address();
+    let from_pool = (amount - token_client.balance(&self_address)).max(0);
+    let withdrawal = match (&config.blend_pool, from_pool > 0) {
+        (_, false) => None,
+        (Some(blend_pool), true) => Some((blend_pool.clone(), withdraw_from_pool(e, blend_pool, collateral, from_pool, &self_address))),
+        (None, true) => panic_with_error!(e, TreasuryError::BalanceError),
+    };
+
+    token_client.transfer(&self_address, receiver, &amount);
+    let args: Vec<Val> = vec![e, collateral.into_val(e), amount.into_val(e), data.into_val(e), fee.into_val(e)];
+    e.invoke_contract::<Val>(receiver, &Symbol::new(e, "fl_receive"), args);
+    pull_repayment(e, &token_client, receiver, amount + fee);
+
+    // return the withdrawn collateral to the same side of the blend position
+    if let Some((blend_pool, request_type)) = withdrawal {
+        let supply_type = if request_type == 3 { 2_u32 } else { 0_u32 }; // SUPPLY_COLLATERAL or SUPPLY RequestType
+        submit_supply(e, &blend_pool, collateral, from_pool, supply_type);
+    }
+    add_fee(e, &storage::get_config(e), collateral, fee);
+    record_flash_loan(e, amount, fee);
+    storage::push_flash_loan_history(e, &FlashLoanRecord {
+        receiver: receiver.clone(),
+        token: collateral.clone(),
+        amount,
+        fee,
+        ledger: e.ledger().sequence(),
+        success: true,
+    });
+    hook::call_hooks(e, HookOp::FlashCollateral, HookStage::After, collateral, amount);
+    storage::del_lock(e);
+}
+
+/// Mint a loan to a receiver, invoke it with the fee appended to `args`, pull the loan plus the fee,
+/// and burn the loan. The fee is split by `add_fee`.
+///
+/// ### Arguments
+/// * `config` - The treasury config
+/// * `receiver` - The Address of the receiver
+/// * `token` - The Address of the stablecoin to loan
+/// * `amount` - The amount to loan
+/// * `fee` - The fee owed on top of the loan
+/// * `fn_name` - The receiver function to invoke
+/// * `args` - The arguments of the receiver function
+///
+/// ### Panics
+/// If the loan plus the fee is not repaid
+fn mint_and_repay(e: &Env, config: &TreasuryConfig, receiver: &Address, token: &Address, amount: i128, fee: i128, fn_name: Symbol, mut args: Vec<Val>) {
+    StellarAssetClient::new(e, token).mint(receiver, &amount);
+
+    // Execute operation
+    args.push_back(fee.into_val(e));
+    e.invoke_contract::<Val>(receiver, &fn_name, args);
+    let token_client = TokenClient::new(e, token);
+    pull_repayment(e, &token_client, receiver, amount + fee);
+
+    // Burn the loaned tokens, the fee is kept by the treasury
+    token_client.burn(&e.current_contract_address(), &amount);
+    add_fee(e, config, token, fee);
+    record_flash_loan(e, amount, fee);
+    storage::push_flash_loan_history(e, &FlashLoanRecord {
+        receiver: receiver.clone(),
+        token: token.clone(),
+        amount,
+        fee,
+        ledger: e.ledger().sequence(),
+        success: true,
+    });
+}
+
+/// Pull the repayment of a loan from the receiver's allowance
+///
+/// The full repayment is moved by a single `transfer_from`, so a receiver can not keep part of it.
+///
+/// ### Arguments
+/// * `token_client` - The client of the loaned token
+/// * `receiver` - The Address of the receiver
+/// * `repayment` - The loan plus the fee
+///
+/// ### Panics
+/// If the receiver did not approve the treasury for the repayment or holds too little of the token
+fn pull_repayment(e: &Env, token_client: &TokenClient, receiver: &Address, repayment: i128) {
+    let self_address = e.current_contract_address();
+    if token_client.try_transfer_from(&self_address, receiver, &self_address, &repayment).is_err() {
+        panic_with_error!(e, TreasuryError::FlashloanNotRepaid);
+    }
+}
+
+/// Add tokens held by the treasury to the surplus buffer
+///
+/// If a surplus recipient is set, any surplus above the cap is forwarded to it.
+///
+/// ### Arguments
+/// * `config` - The treasury config
+/// * `token` - The Address of the token
+/// * `amount` - The amount to add to the surplus buffer
+pub fn add_surplus(e: &Env, config: &TreasuryConfig, token: &Address, amount: i128) {
+    let mut surplus = storage::get_surplus(e, token) + amount;
+    if let Some(surplus_config) = &config.surplus_config {
+        if surplus > surplus_config.cap {
+            let excess = surplus - surplus_config.cap;
+            TokenClient::new(e, token).transfer(&e.current_contract_address(), &surplus_config.recipient, &excess);
+            surplus = surplus_config.cap;
+            e.events().publish(("Treasury", Symbol::new(e, "forward_surplus")), (token.clone(), surplus_config.recipient.clone(), excess));
+        }
+    }
+    storage::set_surplus(e, token, surplus);
+}
+
+/// Split a fee held by the treasury between the fee recipient, the insurance fund and the surplus buffer
+///
+/// The fee recipient's share is taken first and the insurance fund's share out of the rest. If neither
+/// is set, the whole fee is added to the surplus buffer.
+///
+/// ### Arguments
+/// * `config` - The treasury config
+/// * `token` - The Address of the token
+/// * `fee` - The fee earned
+pub fn add_fee(e: &Env, config: &TreasuryConfig, token: &Address, fee: i128) {
+    let mut retained = fee;
+    if let Some(fee_recipient) = &config.fee_recipient {
+        let share = require_no_overflow(e, fee.fixed_mul_floor(i128::from(fee_recipient.split_bps), SCALAR_BPS));
+        if share > 0 {
+            TokenClient::new(e, token).transfer(&e.current_contract_address(), &fee_recipient.recipient, &share);
+            retained -= share;
+            e.events().publish(("Treasury", Symbol::new(e, "forward_fee")), (token.clone(), fee_recipient.recipient.clone(), share));
+        }
+    }
+    if let Some(insurance) = storage::get_insurance_config(e).filter(|insurance| insurance.token == *token) {
+        let share = require_no_overflow(e, retained.fixed_mul_floor(i128::from(insurance.split_bps), SCALAR_BPS));
+        if share > 0 {
+            TokenClient::new(e, token).transfer(&e.current_contract_address(), &insurance.fund, &share);
+            retained -= share;
+            e.events().publish(("Treasury", Symbol::new(e, "forward_insurance")), (token.clone(), insurance.fund, share));
+        }
+    }
+    add_surplus(e, config, token, retained);
+}
+
+/// Cover the outstanding bad debt of a token, first by burning its surplus buffer, then by burning
+/// payouts of the insurance fund if it holds the token. Any bad debt left stays outstanding.
+///
+/// Returns the bad debt after the cover
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn cover_bad_debt(e: &Env, token: &Address) -> BadDebt {
+    let mut bad_debt = storage::get_bad_debt(e, token);
+    if bad_debt.outstanding <= 0 {
+        return bad_debt;
+    }
+
+    let surplus = storage::get_surplus(e, token);
+    let from_surplus = surplus.min(bad_debt.outstanding).max(0);
+    if from_surplus > 0 {
+        storage::set_surplus(e, token, surplus - from_surplus);
+    }
+
+    let mut from_insurance = 0;
+    if let Some(insurance) = storage::get_insurance_config(e).filter(|insurance| insurance.token == *token) {
+        let remaining = bad_debt.outstanding - from_surplus;
+        if remaining > 0 {
+            from_insurance = e.invoke_contract::<i128>(&insurance.fund, &Symbol::new(e, "cover_shortfall"), vec![e, remaining.into_val(e)]);
+        }
+    }
+
+    let covered = from_surplus + from_insurance;
+    if covered > 0 {
+        TokenClient::new(e, token).burn(&e.current_contract_address(), &covered);
+        update_supply(e, token, -covered);
+        bad_debt.outstanding -= covered;
+        bad_debt.covered += covered;
+        storage::set_bad_debt(e, token, &bad_debt);
+    }
+
+    e.events().publish(("Treasury", Symbol::new(e, "cover_bad_debt")), (token.clone(), from_surplus, from_insurance, bad_debt.outstanding));
+    bad_debt
+}
+
+/// Withdraw an asset from the treasury's blend position, from its supply if it covers the amount,
+/// otherwise from its collateral
+///
+/// Returns the request type used for the withdrawal
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the asset
+/// * `amount` - The amount to withdraw
+/// * `to` - The Address receiving the asset
+///
+/// ### Panics
+/// If neither the supply nor the collateral of the position covers the amount
+/// If a collateral withdrawal leaves the loan-to-value ratio above the leverage config's maximum
+pub fn withdraw_from_pool(e: &Env, blend_pool: &Address, asset: &Address, amount: i128, to: &Address) -> u32 {
+    let reserve = pool::get_reserve(e, blend_pool, asset);
+    let pool_client = PoolClient::new(e, blend_pool);
+    let positions = pool_client.get_positions(&e.current_contract_address());
+
+    let to_underlying = |b_tokens: i128| require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+    let request_type = if to_underlying(positions.supply.get(reserve.index).unwrap_or(0)) >= amount {
+        1_u32 // WITHDRAW RequestType
+    } else if to_underlying(positions.collateral.get(reserve.index).unwrap_or(0)) >= amount {
+        3_u32 // WITHDRAW_COLLATERAL RequestType
+    } else {
+        panic_with_error!(e, TreasuryError::BalanceError)
+    };
+    pool_client.submit(&e.current_contract_address(), &e.current_contract_address(), to, &vec![
+        e,
+        Request {
+            request_type,
+            address: asset.clone(),
+            amount,
+        },
+    ]);
+    if request_type == 3 {
+        require_healthy(e, blend_pool);
+    }
+    request_type
+}
+
+/// Withdraw an asset from a blend pool as far as the pool's liquidity allows, queueing the rest as a
+/// withdrawal claimable once the pool has the liquidity. Withdrawals of an asset are served first in,
+/// first out, so nothing is withdrawn while earlier withdrawals of the asset are queued.
+///
+/// Returns the amount withdrawn and the id of the queued withdrawal, if any
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the asset
+/// * `amount` - The amount to withdraw
+/// * `recipient` - The Address receiving the asset
+/// * `burn` - Whether the asset is burnt on withdrawal, in which case the recipient is the treasury
+///
+/// ### Panics
+/// If the treasury's position, net of the queued withdrawals of the asset, does not cover the amount
+pub fn withdraw_or_queue(e: &Env, blend_pool: &Address, asset: &Address, amount: i128, recipient: &Address, burn: bool) -> (i128, Option<u32>) {
+    let mut queue = storage::get_withdrawal_queue(e, asset);
+    if calc_withdrawable(e, blend_pool, asset) - queue.queued < amount {
+        panic_with_error!(e, TreasuryError::BalanceError);
+    }
+
+    let withdrawn = if queue.head == queue.tail {
+        calc_available_liquidity(e, blend_pool, asset).min(amount)
+    } else {
+        0
+    };
+    if withdrawn > 0 {
+        withdraw_from_pool(e, blend_pool, asset, withdrawn, recipient);
+    }
+    if withdrawn == amount {
+        return (withdrawn, None);
+    }
+
+    let id = storage::next_withdrawal_id(e);
+    let withdrawal = Withdrawal {
+        asset: asset.clone(),
+        blend_pool: blend_pool.clone(),
+        recipient: recipient.clone(),
+        amount: amount - withdrawn,
+        burn,
+        position: queue.tail,
+    };
+    storage::set_withdrawal(e, id, &withdrawal);
+    queue.tail += 1;
+    queue.queued += withdrawal.amount;
+    storage::set_withdrawal_queue(e, asset, &queue);
+
+    e.events().publish(("Treasury", Symbol::new(e, "queue_withdrawal")), (id, asset.clone(), recipient.clone(), withdrawal.amount, withdrawal.position));
+    (withdrawn, Some(id))
+}
+
+/// Claim a queued withdrawal, withdrawing the asset from the blend pool to its recipient, or
+/// burning it if the withdrawal decreases the supply
+///
+/// ### Arguments
+/// * `id` - The id of the withdrawal
+///
+/// ### Panics
+/// If the withdrawal does not exist, an earlier withdrawal of the asset is still queued, or the
+/// pool does not have the liquidity to serve it
+pub fn claim_withdrawal(e: &Env, id: u32) -> Withdrawal {
+    let withdrawal = match storage::get_withdrawal(e, id) {
+        Some(withdrawal) => withdrawal,
+        None => panic_with_error!(e, TreasuryError::WithdrawalNotFound),
+    };
+    let mut queue = storage::get_withdrawal_queue(e, &withdrawal.asset);
+    if withdrawal.position != queue.head {
+        panic_with_error!(e, TreasuryError::WithdrawalNotNext);
+    }
+    if calc_available_liquidity(e, &withdrawal.blend_pool, &withdrawal.asset) < withdrawal.amount {
+        panic_with_error!(e, TreasuryError::InsufficientLiquidity);
+    }
+
+    storage::del_withdrawal(e, id);
+    queue.head += 1;
+    queue.queued -= withdrawal.amount;
+    storage::set_withdrawal_queue(e, &withdrawal.asset, &queue);
+
+    withdraw_from_pool(e, &withdrawal.blend_pool, &withdrawal.asset, withdrawal.amount, &withdrawal.recipient);
+    if withdrawal.burn {
+        hook::call_hooks(e, HookOp::DecreaseSupply, HookStage::Before, &withdrawal.asset, withdrawal.amount);
+        TokenClient::new(e, &withdrawal.asset).burn(&e.current_contract_address(), &withdrawal.amount);
+        update_supply(e, &withdrawal.asset, -withdrawal.amount);
+        hook::call_hooks(e, HookOp::DecreaseSupply, HookStage::After, &withdrawal.asset, withdrawal.amount);
+    }
+    withdrawal
+}
+
+/// Calculate the amount of an asset a blend pool holds and can pay out, net of the interest owed to its backstop
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the asset
+pub fn calc_available_liquidity(e: &Env, blend_pool: &Address, asset: &Address) -> i128 {
+    let reserve = pool::get_reserve(e, blend_pool, asset);
+    (TokenClient::new(e, asset).balance(blend_pool) - reserve.backstop_credit).max(0)
+}
+
+/// Calculate the largest amount of an asset the treasury can withdraw from its position in a
+/// blend pool in one request, from either its supply or its collateral
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the asset
+pub fn calc_withdrawable(e: &Env, blend_pool: &Address, asset: &Address) -> i128 {
+    let reserve = pool::get_reserve(e, blend_pool, asset);
+    let positions = PoolClient::new(e, blend_pool).get_positions(&e.current_contract_address());
+    let b_tokens = positions.supply.get(reserve.index).unwrap_or(0).max(positions.collateral.get(reserve.index).unwrap_or(0));
+    require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9))
+}
+
+/// Supply tokens held by the treasury to a blend pool
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the token
+/// * `amount` - The amount to supply
+pub fn supply_to_pool(e: &Env, blend_pool: &Address, token: &Address, amount: i128) {
+    submit_supply(e, blend_pool, token, amount, 0_u32); // SUPPLY RequestType
+}
+
+/// Repay the treasury's liabilities in a blend pool with tokens held by the treasury
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the token
+/// * `amount` - The amount to repay
+pub fn repay_to_pool(e: &Env, blend_pool: &Address, token: &Address, amount: i128) {
+    submit_supply(e, blend_pool, token, amount, 5_u32); // REPAY RequestType
+}
+
+/// Post tokens held by the treasury as collateral in a blend pool
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the token
+/// * `amount` - The amount to post
+pub fn post_collateral(e: &Env, blend_pool: &Address, token: &Address, amount: i128) {
+    require_positive(e, amount);
+    require_pool(e, token, blend_pool);
+    submit_supply(e, blend_pool, token, amount, 2_u32); // SUPPLY_COLLATERAL RequestType
+    e.events().publish(("Treasury", Symbol::new(e, "post_collateral")), (blend_pool.clone(), token.clone(), amount));
+}
+
+/// Borrow tokens from a blend pool against the treasury's collateral in it
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the token
+/// * `amount` - The amount to borrow
+///
+/// ### Panics
+/// If borrowing is not enabled, the amount is not positive, or the loan-to-value ratio would exceed the maximum
+pub fn borrow(e: &Env, blend_pool: &Address, token: &Address, amount: i128) {
+    match storage::get_leverage(e) {
+        Some(config) if config.enabled => (),
+        _ => panic_with_error!(e, TreasuryError::LeverageDisabled),
+    }
+    require_positive(e, amount);
+    PoolClient::new(e, blend_pool).submit(&e.current_contract_address(), &e.current_contract_address(), &e.current_contract_address(), &vec![
+        e,
+        Request {
+            request_type: 4_u32, // BORROW RequestType
+            address: token.clone(),
+            amount,
+        },
+    ]);
+    require_healthy(e, blend_pool);
+    e.events().publish(("Treasury", Symbol::new(e, "borrow")), (blend_pool.clone(), token.clone(), amount));
+}
+
+/// Calculate the loan-to-value ratio of the treasury's position in a blend pool, in basis points,
+/// from the USD value of its liabilities relative to its collateral. Assumes every reserve uses 7 decimals.
+///
+/// Returns 0 without liabilities, and `i128::MAX` with liabilities but without collateral
+///
+/// ### Arguments
+/// * `oracle_config` - The oracle config
+/// * `blend_pool` - The Address of the blend pool
+///
+/// ### Panics
+/// If the oracle has no price, or a stale price, for a reserve the treasury has collateral or liabilities in
+pub fn calc_ltv(e: &Env, oracle_config: &OracleConfig, blend_pool: &Address) -> i128 {
+    let positions = PoolClient::new(e, blend_pool).get_positions(&e.current_contract_address());
+    let mut collateral_value = 0;
+    let mut liabilities_value = 0;
+    for (index, asset) in pool::get_reserve_list(e, blend_pool).iter().enumerate() {
+        let index = index as u32;
+        let b_tokens = positions.collateral.get(index).unwrap_or(0);
+        let d_tokens = positions.liabilities.get(index).unwrap_or(0);
+        if b_tokens == 0 && d_tokens == 0 {
+            continue;
+        }
+        let reserve = pool::get_reserve(e, blend_pool, &asset);
+        let price = oracle::get_price(e, oracle_config, &asset);
+        let collateral = require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+        let liabilities = require_no_overflow(e, d_tokens.fixed_mul_ceil(reserve.d_rate, SCALAR_9));
+        collateral_value += require_no_overflow(e, collateral.fixed_mul_floor(price, SCALAR_7));
+        liabilities_value += require_no_overflow(e, liabilities.fixed_mul_ceil(price, SCALAR_7));
+    }
+    if liabilities_value == 0 {
+        0
+    } else if collateral_value == 0 {
+        i128::MAX
+    } else {
+        require_no_overflow(e, liabilities_value.fixed_mul_ceil(SCALAR_BPS, collateral_value))
+    }
+}
+
+/// Require the loan-to-value ratio of the treasury's position in a blend pool to be within the
+/// maximum of the leverage config. Always passes if no leverage config is set.
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+///
+/// ### Panics
+/// If the loan-to-value ratio exceeds the maximum
+pub fn require_healthy(e: &Env, blend_pool: &Address) {
+    let config = match storage::get_leverage(e) {
+        Some(config) => config,
+        None => return,
+    };
+    if calc_ltv(e, &storage::get_oracle_config(e), blend_pool) > i128::from(config.max_ltv_bps) {
+        panic_with_error!(e, TreasuryError::LtvExceeded);
+    }
+}
+
+/// Set the leverage config
+///
+/// ### Arguments
+/// * `config` - The leverage config
+///
+/// ### Panics
+/// If the maximum loan-to-value ratio is above `MAX_LTV_BPS`
+pub fn set_leverage(e: &Env, config: &LeverageConfig) {
+    if config.max_ltv_bps > MAX_LTV_BPS {
+        panic_with_error!(e, TreasuryError::InvalidAmount);
+    }
+    storage::set_leverage(e, config);
+    e.events().publish(("Treasury", Symbol::new(e, "set_leverage")), (config.enabled, config.max_ltv_bps));
+}
+
+/// Calculate the treasury's liabilities of a token in a blend pool, in the token
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the token
+pub fn calc_liabilities(e: &Env, blend_pool: &Address, token: &Address) -> i128 {
+    let reserve = pool::get_reserve(e, blend_pool, token);
+    let positions = PoolClient::new(e, blend_pool).get_positions(&e.current_contract_address());
+    let d_tokens = positions.liabilities.get(reserve.index).unwrap_or(0);
+    require_no_overflow(e, d_tokens.fixed_mul_ceil(reserve.d_rate, SCALAR_9))
+}
+
+/// Supply tokens held by the treasury to a blend pool with a supply, supply collateral or repay request
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `token` - The Address of the token
+/// * `amount` - The amount to supply
+/// * `request_type` - The blend request type
+fn submit_supply(e: &Env, blend_pool: &Address, token: &Address, amount: i128, request_type: u32) {
+    let args: Vec<Val> = vec![
+        e,
+        e.current_contract_address().into_val(e),
+        blend_pool.into_val(e),
+        amount.into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args,
+            },
+            sub_invocations: vec![e],
+        })
+    ]);
+    PoolClient::new(e, blend_pool).submit(&e.current_contract_address(), &e.current_contract_address(), &e.current_contract_address(), &vec![
+        e,
+        Request {
+            request_type,
+            address: token.clone(),
+            amount,
+        },
+    ]);
+}
+
+/// Swap an exact amount of a token held by the treasury directly through a soroswap pair
+///
+/// Returns the amount of the other pair asset received
+///
+/// ### Arguments
+/// * `pair` - The Address of the soroswap pair
+/// * `token_in` - The Address of the input token
+/// * `amount_in` - The amount of the input token to swap
+pub fn swap_in_pair(e: &Env, pair: &Address, token_in: &Address, amount_in: i128) -> i128 {
+    let pair_client = PairClient::new(e, pair);
+    let (reserve_0, reserve_1) = pair_client.get_reserves();
+    let is_token_0 = pair_client.token_0() == *token_in;
+    let (reserve_in, reserve_out) = if is_token_0 { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+
+    // constant product with the 0.3% soroswap fee
+    let amount_in_with_fee = require_no_overflow(e, amount_in.checked_mul(997));
+    let numerator = require_no_overflow(e, amount_in_with_fee.checked_mul(reserve_out));
+    let denominator = require_no_overflow(e, reserve_in.checked_mul(1_000)) + amount_in_with_fee;
+    let amount_out = numerator / denominator;
+
+    TokenClient::new(e, token_in).transfer(&e.current_contract_address(), pair, &amount_in);
+    if is_token_0 {
+        pair_client.swap(&0, &amount_out, &e.current_contract_address());
+    } else {
+        pair_client.swap(&amount_out, &0, &e.current_contract_address());
+    }
+    amount_out
+}
+
+/// Require a blend pool to be registered for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `blend_pool` - The Address of the blend pool
+///
+/// ### Panics
+/// If the blend pool is not registered for the token
+pub fn require_pool(e: &Env, token: &Address, blend_pool: &Address) {
+    if !storage::get_pools(e, token).contains(blend_pool) {
+        panic_with_error!(e, TreasuryError::PoolNotFoundError);
+    }
+}
+
+/// Calculate the underlying amount of a token the treasury supplies across its blend pools
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn calc_total_supplied(e: &Env, token: &Address) -> i128 {
+    let mut total = 0;
+    for blend_pool in storage::get_pools(e, token).iter() {
+        let reserve = pool::get_reserve(e, &blend_pool, token);
+        let positions = PoolClient::new(e, &blend_pool).get_positions(&e.current_contract_address());
+        let b_tokens = positions.supply.get(reserve.index).unwrap_or(0);
+        total += require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+    }
+    total
+}
+
+/// Mint stablecoins and supply them to the token's primary blend pool, increasing its supply
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `amount` - The amount to increase the supply by
+///
+/// ### Panics
+/// If the circuit breaker is tripped or the supply limit is exceeded
+pub fn issue_supply(e: &Env, token: &Address, amount: i128) {
+    require_breaker_closed(e, token);
+    check_supply_limit(e, token, amount);
+
+    hook::call_hooks(e, HookOp::IncreaseSupply, HookStage::Before, token, amount);
+    let blend = storage::get_blend_pool(e, token);
+    StellarAssetClient::new(e, token).mint(&e.current_contract_address(), &amount);
+    update_supply(e, token, amount);
+    supply_to_pool(e, &blend, token, amount);
+    hook::call_hooks(e, HookOp::IncreaseSupply, HookStage::After, token, amount);
+}
+
+/// Set the automatic supply adjustment config of a stablecoin
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `config` - The config, or None to disable automatic adjustments
+///
+/// ### Panics
+/// If the band is empty or above 100%, the target is outside the band, the step is not positive,
+/// or the interval is zero
+pub fn set_auto_supply(e: &Env, token: &Address, config: &Option<AutoSupplyConfig>) {
+    match config {
+        Some(config) => {
+            if config.min_util <= 0
+                || config.min_util > config.target_util
+                || config.target_util > config.max_util
+                || config.max_util > SCALAR_7
+                || config.max_step <= 0
+                || config.interval == 0
+            {
+                panic_with_error!(e, TreasuryError::InvalidAmount);
+            }
+            storage::set_auto_supply(e, token, config);
+        }
+        None => storage::del_auto_supply(e, token),
+    }
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_auto_supply")), (token.clone(), config.clone()));
+}
+
+/// A supply adjustment sampled from a stablecoin's primary blend pool reserve
+pub struct AutoAdjustment {
+    pub blend_pool: Address,   // primary blend pool of the stablecoin
+    pub utilization: i128,     // utilization of the reserve, in 7 decimals
+    pub target_supplied: i128, // total supplied to the reserve at the target utilization
+    pub amount: i128,          // amount to increase the supply by, or to decrease it by if negative
+}
+
+/// Calculate the supply adjustment moving the utilization of a stablecoin's primary blend pool reserve
+/// towards the target utilization, once it is outside the band. Decreases are limited to the
+/// treasury's supply and the pool's liquidity, and are skipped while withdrawals of the stablecoin are queued.
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `config` - The automatic supply adjustment config
+pub fn calc_auto_adjustment(e: &Env, token: &Address, config: &AutoSupplyConfig) -> AutoAdjustment {
+    let blend_pool = storage::get_blend_pool(e, token);
+    let reserve = pool::get_reserve(e, &blend_pool, token);
+    let total_supplied = require_no_overflow(e, reserve.b_supply.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+    let total_borrowed = require_no_overflow(e, reserve.d_supply.fixed_mul_ceil(reserve.d_rate, SCALAR_9));
+    let target_supplied = require_no_overflow(e, total_borrowed.fixed_div_ceil(config.target_util, SCALAR_7));
+    if total_supplied == 0 {
+        return AutoAdjustment { blend_pool, utilization: 0, target_supplied, amount: 0 };
+    }
+    let utilization = require_no_overflow(e, total_borrowed.fixed_div_ceil(total_supplied, SCALAR_7));
+
+    let amount = if utilization > config.max_util {
+        (target_supplied - total_supplied).clamp(0, config.max_step)
+    } else if utilization < config.min_util {
+        let queue = storage::get_withdrawal_queue(e, token);
+        if queue.head != queue.tail {
+            0
+        } else {
+            let decrease = (total_supplied - target_supplied)
+                .min(config.max_step)
+                .min(calc_withdrawable(e, &blend_pool, token))
+                .min(calc_available_liquidity(e, &blend_pool, token));
+            -decrease.max(0)
+        }
+    } else {
+        0
+    };
+    AutoAdjustment { blend_pool, utilization, target_supplied, amount }
+}
+
+/// Discover the reserve index of every asset of a blend pool, including the stablecoin and each
+/// collateral, and store them so reserve lookups do not fetch the pool's reserve list
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+pub fn refresh_reserve_indexes(e: &Env, blend_pool: &Address) {
+    let reserves = pool::get_reserve_list(e, blend_pool);
+    for (index, asset) in reserves.iter().enumerate() {
+        storage::set_reserve_index(e, blend_pool, &asset, index as u32);
+    }
+    e.events().publish(("Treasury", Symbol::new(e, "refresh_reserve_indexes")), (blend_pool.clone(), reserves.len()));
+}
+
+/// Calculate the stablecoins backing the outstanding supply of a token: the treasury's positions in
+/// the token's pools, the amounts minted by minters, the stablecoins seeded as protocol-owned liquidity,
+/// and the outstanding bad debt
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn calc_backing(e: &Env, token: &Address) -> i128 {
+    let mut backing = 0;
+    for blend_pool in storage::get_pools(e, token).iter() {
+        let reserve = pool::get_reserve(e, &blend_pool, token);
+        let positions = PoolClient::new(e, &blend_pool).get_positions(&e.current_contract_address());
+        let b_tokens = positions.supply.get(reserve.index).unwrap_or(0) + positions.collateral.get(reserve.index).unwrap_or(0);
+        backing += require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9));
+    }
+    for minter in storage::get_index(e, &DataKey::MINTERS).iter() {
+        if let Some(config) = storage::get_minter(e, &minter).filter(|config| config.token == *token) {
+            backing += config.minted;
+        }
+    }
+    if let Some(position) = storage::get_pol_position(e, token) {
+        backing += position.token_seeded;
+    }
+    backing + storage::get_bad_debt(e, token).outstanding
+}
+
+/// Check the core accounting invariants of a token:
+/// * the recorded supply does not exceed the total supply reported by the token, if it reports one
+/// * the recorded supply is backed, see `calc_backing`
+/// * the surplus buffer is not negative
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+///
+/// ### Panics
+/// If an invariant does not hold
+pub fn check_invariants(e: &Env, token: &Address) {
+    let supply = storage::get_supply(e, token);
+    if let Ok(Ok(total_supply)) = e.try_invoke_contract::<i128, InvokeError>(token, &Symbol::new(e, "total_supply"), vec![e]) {
+        if supply > total_supply {
+            log!(e, "invariant violated: recorded supply {} above total supply {}", supply, total_supply);
+            panic_with_error!(e, TreasuryError::InvariantViolation);
+        }
+    }
+    let backing = calc_backing(e, token);
+    if backing < supply {
+        log!(e, "invariant violated: backing {} below recorded supply {}", backing, supply);
+        panic_with_error!(e, TreasuryError::InvariantViolation);
+    }
+    let surplus = storage::get_surplus(e, token);
+    if surplus < 0 {
+        log!(e, "invariant violated: negative surplus {}", surplus);
+        panic_with_error!(e, TreasuryError::InvariantViolation);
+    }
+}
+
+/// Record a supply change of a token against the supply limit
+///
+/// Changes are counted within fixed windows of ledgers, and the limit is relative to the supply
+/// at the start of the window. The limit does not apply while the supply is zero.
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `amount` - The amount the supply is increased or decreased by
+///
+/// ### Panics
+/// If the change would exceed the supply limit
+pub fn check_supply_limit(e: &Env, token: &Address, amount: i128) {
+    let limit = match storage::get_supply_limit(e) {
+        Some(limit) => limit,
+        None => return,
+    };
+    let ledger = e.ledger().sequence();
+    let mut window = match storage::get_supply_window(e, token) {
+        Some(window) if ledger < window.start_ledger.saturating_add(limit.window) => window,
+        _ => SupplyWindow {
+            start_ledger: ledger,
+            base_supply: calc_total_supplied(e, token),
+            change: 0,
+        },
+    };
+    window.change += amount;
+    if window.base_supply > 0 {
+        let max_change = require_no_overflow(e, window.base_supply.fixed_mul_floor(i128::from(limit.max_change_bps), SCALAR_BPS));
+        if window.change > max_change {
+            panic_with_error!(e, TreasuryError::SupplyLimitExceeded);
+        }
+    }
+    storage::set_supply_window(e, token, &window);
+}
+
+/// Record a change in the outstanding supply of a token minted by the treasury. The stability
+/// fee is accrued on the supply before the change.
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `change` - The amount minted, or negative the amount burnt
+pub fn update_supply(e: &Env, token: &Address, change: i128) {
+    accrue_stability_fee(e, token);
+    set_supply(e, token, storage::get_supply(e, token) + change);
+
+    let mut stats = storage::get_stats(e);
+    if change > 0 {
+        stats.minted += change;
+    } else {
+        stats.burned -= change;
+    }
+    storage::set_stats(e, &stats);
+}
+
+/// Set the outstanding supply of a token minted by the treasury and record it in a snapshot for the
+/// current ledger, replacing the snapshot of an earlier change in the same ledger
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `supply` - The outstanding supply
+pub fn set_supply(e: &Env, token: &Address, supply: i128) {
+    storage::set_supply(e, token, supply);
+
+    let ledger = e.ledger().sequence();
+    let count = storage::get_snapshot_count(e, token);
+    let index = match count.checked_sub(1).and_then(|last| storage::get_snapshot(e, token, last)) {
+        Some(last) if last.ledger == ledger => count - 1,
+        _ => {
+            storage::set_snapshot_count(e, token, count + 1);
+            count
+        }
+    };
+    storage::set_snapshot(e, token, index, &SupplySnapshot { ledger, supply });
+}
+
+/// Calculate the outstanding supply of a token at the end of a ledger from its snapshots
+///
+/// Returns 0 if no snapshot was recorded at or before the ledger
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `ledger` - The ledger sequence
+pub fn calc_supply_at(e: &Env, token: &Address, ledger: u32) -> i128 {
+    // binary search for the last snapshot at or before the ledger
+    let mut low = 0;
+    let mut high = storage::get_snapshot_count(e, token);
+    let mut supply = 0;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match storage::get_snapshot(e, token, mid) {
+            Some(snapshot) if snapshot.ledger <= ledger => {
+                supply = snapshot.supply;
+                low = mid + 1;
+            }
+            _ => high = mid,
+        }
+    }
+    supply
+}
+
+/// Count a repaid flash loan in the lifetime counters
+///
+/// ### Arguments
+/// * `amount` - The amount loaned
+/// * `fee` - The fee earned
+pub fn record_flash_loan(e: &Env, amount: i128, fee: i128) {
+    let mut stats = storage::get_stats(e);
+    stats.flash_loans += 1;
+    stats.flash_loan_volume += amount;
+    stats.fees_earned += fee;
+    storage::set_stats(e, &stats);
+}
+
+/// Count a liquidation filled by the treasury in the lifetime counters
+pub fn record_liquidation(e: &Env) {
+    let mut stats = storage::get_stats(e);
+    stats.liquidations += 1;
+    storage::set_stats(e, &stats);
+}
+
+/// Create a payment stream, reserving its total out of the token's surplus buffer
+///
+/// Returns the id of the stream
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `recipient` - The Address receiving the payments
+/// * `total` - The amount paid out over the whole stream
+/// * `start` - The timestamp the payments start vesting at
+/// * `end` - The timestamp the whole amount is vested at
+///
+/// ### Panics
+/// If the total is not positive or exceeds the surplus, or the stream ends before it starts
+pub fn create_stream(e: &Env, token: &Address, recipient: &Address, total: i128, start: u64, end: u64) -> u32 {
+    require_positive(e, total);
+    if end <= start {
+        panic_with_error!(e, TreasuryError::InvalidStream);
+    }
+    let surplus = storage::get_surplus(e, token);
+    if total > surplus {
+        panic_with_error!(e, TreasuryError::BalanceError);
+    }
+    storage::set_surplus(e, token, surplus - total);
+
+    let id = storage::next_stream_id(e);
+    storage::set_stream(e, id, &Stream {
+        token: token.clone(),
+        recipient: recipient.clone(),
+        total,
+        start,
+        end,
+        withdrawn: 0,
+    });
+
+    e.events().publish(("Treasury", Symbol::new(e, "create_stream")), (id, token.clone(), recipient.clone(), total, start, end));
+    id
+}
+
+/// Cancel a payment stream, paying the vested amount not yet withdrawn to the recipient and
+/// returning the unvested amount to the surplus buffer
+///
+/// ### Arguments
+/// * `id` - The id of the stream
+///
+/// ### Panics
+/// If the stream does not exist
+pub fn cancel_stream(e: &Env, id: u32) {
+    let stream = require_stream(e, id);
+    let vested = calc_vested(e, &stream);
+    let owed = vested - stream.withdrawn;
+    if owed > 0 {
+        TokenClient::new(e, &stream.token).transfer(&e.current_contract_address(), &stream.recipient, &owed);
+    }
+    let unvested = stream.total - vested;
+    storage::set_surplus(e, &stream.token, storage::get_surplus(e, &stream.token) + unvested);
+    storage::del_stream(e, id);
+
+    e.events().publish(("Treasury", Symbol::new(e, "cancel_stream")), (id, owed, unvested));
+}
+
+/// Calculate the amount of a payment stream vested at the current timestamp
+///
+/// ### Arguments
+/// * `stream` - The payment stream
+pub fn calc_vested(e: &Env, stream: &Stream) -> i128 {
+    let now = e.ledger().timestamp();
+    if now <= stream.start {
+        0
+    } else if now >= stream.end {
+        stream.total
+    } else {
+        let elapsed = i128::from(now - stream.start);
+        let duration = i128::from(stream.end - stream.start);
+        require_no_overflow(e, stream.total.fixed_mul_floor(elapsed, duration))
+    }
+}
+
+/// Fetch a payment stream
+///
+/// ### Arguments
+/// * `id` - The id of the stream
+///
+/// ### Panics
+/// If the stream does not exist
+pub fn require_stream(e: &Env, id: u32) -> Stream {
+    match storage::get_stream(e, id) {
+        Some(stream) => stream,
+        None => panic_with_error!(e, TreasuryError::StreamNotFound),
+    }
+}
+
+/// Calculate the stability fee accumulator of a token accrued up to the current timestamp
+///
+/// ### Arguments
+/// * `fee` - The stability fee accumulator
+/// * `supply` - The outstanding supply since the last update
+pub fn calc_stability_fee(e: &Env, fee: &StabilityFee, supply: i128) -> StabilityFee {
+    let now = e.ledger().timestamp();
+    if now <= fee.last_update {
+        return fee.clone();
+    }
+    let accrued_rate = require_no_overflow(e, fee.rate.checked_mul(i128::from(now - fee.last_update)));
+    StabilityFee {
+        rate: fee.rate,
+        index: fee.index + require_no_overflow(e, fee.index.fixed_mul_floor(accrued_rate, SCALAR_18)),
+        last_update: now,
+        accrued: fee.accrued + require_no_overflow(e, supply.fixed_mul_floor(accrued_rate, SCALAR_18)),
+        collected: fee.collected,
+    }
+}
+
+/// Accrue the stability fee of a token on its outstanding supply, if a stability fee is set
+///
+/// Returns the total fees accrued
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn accrue_stability_fee(e: &Env, token: &Address) -> i128 {
+    match storage::get_stability_fee(e, token) {
+        Some(fee) => {
+            let fee = calc_stability_fee(e, &fee, storage::get_supply(e, token));
+            storage::set_stability_fee(e, token, &fee);
+            fee.accrued
+        }
+        None => 0,
+    }
+}
+
+/// Collect the stability fees accrued on a token into its surplus buffer
+///
+/// The fees are paid out of the interest the treasury earned on its position in the token's primary
+/// blend pool, so at most the backing above the outstanding supply is collected, and at most what the
+/// pool can pay out. Fees that can not be collected yet stay owed to later collections.
+///
+/// Returns the amount collected
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn collect_stability_fees(e: &Env, token: &Address) -> i128 {
+    accrue_stability_fee(e, token);
+    let mut fee = match storage::get_stability_fee(e, token) {
+        Some(fee) => fee,
+        None => return 0,
+    };
+    let blend_pool = storage::get_blend_pool(e, token);
+    let queued = storage::get_withdrawal_queue(e, token).queued;
+    // leave a margin for the rounding of the b-tokens burnt by the withdrawal
+    let excess = calc_backing(e, token) - storage::get_supply(e, token) - 2;
+    let amount = (fee.accrued - fee.collected)
+        .min(excess)
+        .min(calc_withdrawable(e, &blend_pool, token) - queued)
+        .min(calc_available_liquidity(e, &blend_pool, token));
+    if amount <= 0 {
+        return 0;
+    }
+
+    withdraw_from_pool(e, &blend_pool, token, amount, &e.current_contract_address());
+    add_surplus(e, &storage::get_config(e), token, amount);
+    fee.collected += amount;
+    storage::set_stability_fee(e, token, &fee);
+
+    e.events().publish(("Treasury", Symbol::new(e, "collect_stability_fees")), (token.clone(), amount));
+    amount
+}
+
+/// Calculate the USD value of the treasury's position in a blend pool net of its liabilities,
+/// in 7 decimals. Assumes every reserve uses 7 decimals.
+///
+/// ### Arguments
+/// * `oracle_config` - The oracle config
+/// * `blend_pool` - The Address of the blend pool
+///
+/// ### Panics
+/// If the oracle has no price, or a stale price, for a reserve the treasury has a position in
+pub fn calc_position_value(e: &Env, oracle_config: &OracleConfig, blend_pool: &Address) -> i128 {
+    let positions = PoolClient::new(e, blend_pool).get_positions(&e.current_contract_address());
+    let mut value = 0;
+    for (index, asset) in pool::get_reserve_list(e, blend_pool).iter().enumerate() {
+        let index = index as u32;
+        let b_tokens = positions.supply.get(index).unwrap_or(0) + positions.collateral.get(index).unwrap_or(0);
+        let d_tokens = positions.liabilities.get(index).unwrap_or(0);
+        if b_tokens == 0 && d_tokens == 0 {
+            continue;
+        }
+        let reserve = pool::get_reserve(e, blend_pool, &asset);
+        let balance = require_no_overflow(e, b_tokens.fixed_mul_floor(reserve.b_rate, SCALAR_9))
+            - require_no_overflow(e, d_tokens.fixed_mul_ceil(reserve.d_rate, SCALAR_9));
+        let price = oracle::get_price(e, oracle_config, &asset);
+        value += require_no_overflow(e, balance.fixed_mul_floor(price, SCALAR_7));
+    }
+    value
+}
+
+/// Set the flash mint config of a stablecoin
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `config` - The flash mint config
+///
+/// ### Panics
+/// If the cap is negative or the fee is above the maximum
+pub fn set_flash_mint(e: &Env, token: &Address, config: &FlashMintConfig) {
+    if config.cap < 0 {
+        panic_with_error!(e, TreasuryError::NegativeAmountError);
+    }
+    require_valid_fee_bps(e, config.fee_bps);
+    storage::set_flash_mint_config(e, token, config);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_flash_mint")), (token.clone(), config.cap, config.fee_bps));
+}
+
+/// Set the flash loan config of a collateral
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+/// * `config` - The flash loan config
+///
+/// ### Panics
+/// If the cap is negative, the fee is above the maximum, or the blend pool is not registered
+pub fn set_flash_collateral(e: &Env, collateral: &Address, config: &FlashCollateralConfig) {
+    if config.cap < 0 {
+        panic_with_error!(e, TreasuryError::NegativeAmountError);
+    }
+    require_valid_fee_bps(e, config.fee_bps);
+    if let Some(blend_pool) = &config.blend_pool {
+        let registered = storage::get_index(e, &DataKey::TOKENS)
+            .iter()
+            .any(|token| storage::get_pools(e, &token).contains(blend_pool));
+        if !registered {
+            panic_with_error!(e, TreasuryError::PoolNotFoundError);
+        }
+    }
+    storage::set_flash_collateral_config(e, collateral, config);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_flash_collateral")), (collateral.clone(), config.cap, config.fee_bps));
+}
+
+/// Whitelist a flash loan receiver
+///
+/// ### Arguments
+/// * `receiver` - The Address of the receiver
+/// * `config` - The flash loan config
+///
+/// ### Panics
+/// If the receiver is already whitelisted or is not a contract, the fee is above the maximum, or the max amount is not positive
+pub fn add_flash_loaner(e: &Env, receiver: &Address, config: &FlashLoanConfig) {
+    if storage::get_flash_loaner(e, receiver).is_some() {
+        panic_with_error!(e, TreasuryError::AlreadyInitializedError);
+    }
+    require_contract(e, receiver);
+    require_valid_fee_bps(e, config.fee_bps);
+    require_positive(e, config.max_amount);
+    storage::set_flash_loaner(e, receiver, config);
+
+    e.events().publish(("Treasury", Symbol::new(e, "add_flash_loaner")), (receiver.clone(), config.clone()));
+}
+
+/// Remove a flash loan receiver from the whitelist
+///
+/// ### Arguments
+/// * `receiver` - The Address of the receiver
+pub fn remove_flash_loaner(e: &Env, receiver: &Address) {
+    storage::del_flash_loaner(e, receiver);
+
+    e.events().publish(("Treasury", Symbol::new(e, "remove_flash_loaner")), receiver.clone());
+}
+
+/// Set the default flash loan fee
+///
+/// ### Arguments
+/// * `fee_bps` - The fee, in basis points
+///
+/// ### Panics
+/// If the fee is above the maximum
+pub fn set_fee_bps(e: &Env, fee_bps: u32) {
+    require_valid_fee_bps(e, fee_bps);
+    let mut fee_model = storage::get_fee_model(e);
+    fee_model.fee_bps = fee_bps;
+    storage::set_fee_model(e, &fee_model);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_fee_bps")), fee_bps);
+}
+
+/// Set the minimum flash loan fee
+///
+/// ### Arguments
+/// * `min_fee` - The minimum fee
+///
+/// ### Panics
+/// If the minimum fee is negative
+pub fn set_min_fee(e: &Env, min_fee: i128) {
+    if min_fee < 0 {
+        panic_with_error!(e, TreasuryError::NegativeAmountError);
+    }
+    let mut fee_model = storage::get_fee_model(e);
+    fee_model.min_fee = min_fee;
+    storage::set_fee_model(e, &fee_model);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_min_fee")), min_fee);
+}
+
+/// Set the mint caps of a minter, keeping the amounts it already minted
+///
+/// ### Arguments
+/// * `minter` - The Address of the minter
+/// * `token` - The Address of the stablecoin
+/// * `daily_cap` - The maximum amount the minter can mint in a day
+/// * `total_cap` - The maximum amount the minter can have outstanding
+///
+/// ### Panics
+/// If either cap is negative
+pub fn set_minter(e: &Env, minter: &Address, token: &Address, daily_cap: i128, total_cap: i128) {
+    if daily_cap < 0 || total_cap < 0 {
+        panic_with_error!(e, TreasuryError::NegativeAmountError);
+    }
+    let config = match storage::get_minter(e, minter) {
+        Some(config) => MinterConfig { token: token.clone(), daily_cap, total_cap, ..config },
+        None => MinterConfig { token: token.clone(), daily_cap, total_cap, minted: 0, day: 0, minted_today: 0 },
+    };
+    storage::set_minter(e, minter, &config);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_minter")), (minter.clone(), token.clone(), daily_cap, total_cap));
+}
+
+/// Calculate the amount a minter can still mint today, within both its daily and total caps
+///
+/// ### Arguments
+/// * `config` - The minter config
+pub fn calc_mint_allowance(e: &Env, config: &MinterConfig) -> i128 {
+    let minted_today = if config.day == e.ledger().timestamp() / 86400 { config.minted_today } else { 0 };
+    (config.daily_cap - minted_today).min(config.total_cap - config.minted).max(0)
+}
+
+/// Record a mint against the caps of a minter
+///
+/// Burns reduce the outstanding amount counted against the total cap, but not the amount minted
+/// during the day.
+///
+/// ### Arguments
+/// * `config` - The minter config
+/// * `amount` - The amount minted
+///
+/// ### Panics
+/// If the amount exceeds the minter's remaining daily or total cap
+pub fn record_mint(e: &Env, config: &mut MinterConfig, amount: i128) {
+    if amount > calc_mint_allowance(e, config) {
+        panic_with_error!(e, TreasuryError::MintCapExceeded);
+    }
+    let day = e.ledger().timestamp() / 86400;
+    if config.day != day {
+        config.day = day;
+        config.minted_today = 0;
+    }
+    config.minted_today += amount;
+    config.minted += amount;
+}
+
+/// Remove a minter
+///
+/// ### Arguments
+/// * `minter` - The Address of the minter
+pub fn remove_minter(e: &Env, minter: &Address) {
+    storage::del_minter(e, minter);
+
+    e.events().publish(("Treasury", Symbol::new(e, "remove_minter")), minter.clone());
+}
+
+/// Set the limit on supply changes within a window
+///
+/// ### Arguments
+/// * `limit` - The supply limit
+///
+/// ### Panics
+/// If the window is zero
+pub fn set_supply_limit(e: &Env, limit: &SupplyLimit) {
+    if limit.window == 0 {
+        panic_with_error!(e, TreasuryError::InvalidAmount);
+    }
+    storage::set_supply_limit(e, limit);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_supply_limit")), (limit.max_change_bps, limit.window));
+}
+
+/// Set the stability fee rate of a stablecoin, accruing the fee at the previous rate first
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `rate` - The rate
+///
+/// ### Panics
+/// If the rate is negative
+pub fn set_stability_fee(e: &Env, token: &Address, rate: i128) {
+    if rate < 0 {
+        panic_with_error!(e, TreasuryError::NegativeAmountError);
+    }
+    accrue_stability_fee(e, token);
+    let fee = match storage::get_stability_fee(e, token) {
+        Some(fee) => StabilityFee { rate, ..fee },
+        None => StabilityFee {
+            rate,
+            index: SCALAR_18,
+            last_update: e.ledger().timestamp(),
+            accrued: 0,
+            collected: 0,
+        },
+    };
+    storage::set_stability_fee(e, token, &fee);
+
+    e.events().publish(("Treasury", Symbol::new(e, "set_stability_fee")), (token.clone(), rate));
+}
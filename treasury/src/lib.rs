@@ -4,5 +4,6 @@ extern crate std;
 mod storage;
 mod contract;
 mod errors;
+mod helper;
 mod dependencies;
 pub use contract::*;
@@ -1,16 +1,394 @@
-use soroban_sdk::{Address, contracttype, Env};
-use soroban_sdk::unwrap::UnwrapOptimized;
+use sep_40_oracle::Asset;
+use soroban_sdk::{panic_with_error, vec, Address, BytesN, contracttype, Env, Symbol, Vec};
+
+use crate::contract::Proposal;
+use crate::errors::TreasuryError;
 
 pub(crate) const LEDGER_THRESHOLD_SHARED: u32 = 172800; // ~ 10 days
 pub(crate) const LEDGER_BUMP_SHARED: u32 = 241920; // ~ 14 days
 
+pub(crate) const LEDGER_THRESHOLD_USER: u32 = 518400; // ~ 30 days
+pub(crate) const LEDGER_BUMP_USER: u32 = 535670; // ~ 31 days
+
+pub(crate) const UPGRADE_TIMELOCK: u32 = 120960; // ~ 7 days
+pub(crate) const ROLLBACK_WINDOW: u32 = 17280; // ~ 1 day
+pub(crate) const PROPOSAL_DURATION: u32 = 120960; // ~ 7 days
+
+pub(crate) const FLASH_LOAN_HISTORY_SIZE: u32 = 20; // number of recent flash loans kept
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     ADMIN,
     BLENDPOOL(Address), // mapping token address to the blend pool addres
-    PEGKEEPER,
+    PEGKEEPER, // (legacy) packed into CONFIG
     BRIDGEORACLE,
+    VERSION,
+    UPGRADE,
+    FLASHLOANER(Address), // mapping flash loan receiver address to its flash loan config
+    LOCK,
+    FEE, // (legacy) packed into CONFIG
+    ORACLE,
+    SURPLUS(Address), // mapping token address to the surplus buffer balance
+    SUPPLY(Address), // mapping token address to the outstanding supply minted by the treasury
+    FLASHMINT(Address), // mapping token address to its flash mint config
+    FLASHCOLLATERAL(Address), // mapping collateral address to its flash loan config
+    BREAKER(Address), // mapping token address to its circuit breaker config
+    DEPEGLEDGER(Address), // mapping token address to the ledger its depeg started at
+    POL(Address), // mapping token address to its protocol-owned liquidity position
+    BACKSTOP(Address), // mapping token address to the backstop tokens deposited for its blend pool
+    BACKSTOPCONFIG,
+    STABILITYFEE(Address), // mapping token address to its stability fee accumulator
+    SURPLUSCONFIG, // (legacy) packed into CONFIG
+    FEERECIPIENT, // (legacy) packed into CONFIG
+    STABILITYPOOL(Address), // mapping token address to the stability pool address
+    SAVINGS(Address), // mapping token address to the savings vault address
+    MINTER(Address), // mapping minter address to its minter config
+    ROLE(RoleKey), // mapping role and account to whether the account holds the role
+    PAUSED,
+    REDEMPTIONFEE,
+    POOLS(Address), // mapping token address to the blend pools the treasury supplies it to
+    CURRENCY(Address), // mapping token address to the fiat currency it is pegged to
+    FXFEED(Address), // mapping token address to the FX oracle pricing its currency
+    FLASHLOANHISTORY,
+    STATS,
+    SUPPLYLIMIT,
+    SUPPLYWINDOW(Address), // mapping token address to its current supply change window
+    TOKENS,
+    FLASHLOANERS,
+    MINTERS,
+    COLLATERAL(Address), // mapping collateral address to its risk parameters
+    COLLATERALS,
+    TOKENADMIN(Address), // mapping token address to the pending admin of its stellar asset contract
+    CONFIG,
+    DENIED(Address), // mapping account address to whether governance denied it from flash loans and mints
+    HOOKS,
+    INSURANCE,
+    BADDEBT(Address), // mapping token address to its bad debt
+    MULTISIG,
+    PROPOSALID,
+    PROPOSAL(u32), // mapping proposal id to the multisig proposal
+    STREAMID,
+    STREAM(u32), // mapping stream id to the payment stream
+    LEVERAGE,
+    SNAPSHOTCOUNT(Address), // mapping token address to the number of supply snapshots recorded
+    SNAPSHOT(Address, u32), // mapping token address and index to a supply snapshot
+    WITHDRAWALID,
+    WITHDRAWAL(u32), // mapping withdrawal id to the queued withdrawal
+    QUEUE(Address), // mapping asset address to its withdrawal queue
+    RESERVEINDEX(Address, Address), // mapping blend pool and asset address to the asset's reserve index
+    AUTOSUPPLY(Address), // mapping token address to its automatic supply adjustment config
+    AUTOSUPPLYLEDGER(Address), // mapping token address to the ledger its supply was last automatically adjusted at
+    FEETIERS, // flash loan fee tiers of keepers
+    KEEPERVOLUME(Address), // mapping keeper address to its cumulative flash loan volume
+    WASMHASH,
+    ROLLBACK,
+    BRIDGEADAPTER,
+    BRIDGED(Address), // mapping token address to the supply locked in the bridge adapter for other chains
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[contracttype]
+pub enum Role {
+    Admin,      // holds every role
+    FeeManager, // manages flash loan fees and the surplus buffer
+    Pauser,     // pauses and unpauses the treasury
+    Keeper,     // manages the treasury's supply
+    Upgrader,   // queues and executes upgrades
+    Guardian,   // vetoes queued upgrades during their timelock
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct RoleKey {
+    pub role: Role,
+    pub account: Address,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct QueuedUpgrade {
+    pub wasm_hash: BytesN<32>, // hash of the queued contract wasm
+    pub unlock_ledger: u32,    // first ledger sequence the upgrade can be executed at
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Rollback {
+    pub wasm_hash: BytesN<32>,  // hash of the contract wasm running before the last upgrade
+    pub expiration_ledger: u32, // last ledger sequence the upgrade can be rolled back in
+    pub version: u32,           // storage layout version when the upgrade ran
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FlashLoanConfig {
+    pub max_amount: i128, // maximum amount that can be borrowed in a single flash loan
+    pub fee_bps: u32,     // fee charged on the borrowed amount, in basis points (0 uses the default fee)
+    pub enabled: bool,    // whether the receiver is currently allowed to take flash loans
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OracleConfig {
+    pub oracle: Address, // SEP-40 oracle reporting USD prices
+    pub max_age: u64,    // maximum age of a price, in seconds
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FxFeed {
+    pub oracle: Address, // SEP-40 oracle reporting the USD price of fiat currencies, e.g. Reflector FX
+    pub max_age: u64,    // maximum age of a price, in seconds
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct SurplusConfig {
+    pub recipient: Address, // governance controlled address receiving surplus
+    pub cap: i128,          // surplus above the cap is forwarded to the recipient
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FeeRecipientConfig {
+    pub recipient: Address, // external address receiving its share of fees, e.g. a DAO treasury or staking contract
+    pub split_bps: u32,     // share of each fee sent to the recipient, in basis points, the rest goes to the surplus buffer
+}
+
+/// The signer set executing admin functions through proposals, replacing the single admin
+#[derive(Clone)]
+#[contracttype]
+pub struct MultisigConfig {
+    pub signers: Vec<Address>, // addresses allowed to propose and approve
+    pub threshold: u32,        // approvals required to execute a proposal
+}
+
+/// The leveraged backstop mode, letting the treasury borrow from blend against its collateral
+#[derive(Clone)]
+#[contracttype]
+pub struct LeverageConfig {
+    pub enabled: bool,    // whether the treasury can borrow
+    pub max_ltv_bps: u32, // maximum value of the treasury's liabilities relative to its collateral in a pool, in basis points
+}
+
+/// A payment vesting linearly from the surplus buffer to a recipient
+#[derive(Clone)]
+#[contracttype]
+pub struct Stream {
+    pub token: Address,     // token paid out of its surplus buffer
+    pub recipient: Address, // address receiving the payments
+    pub total: i128,        // amount paid out over the whole stream
+    pub start: u64,         // timestamp the payments start vesting at
+    pub end: u64,           // timestamp the whole amount is vested at
+    pub withdrawn: i128,    // amount already withdrawn by the recipient
+}
+
+/// The outstanding supply of a token at the end of a ledger it changed in
+#[derive(Clone)]
+#[contracttype]
+pub struct SupplySnapshot {
+    pub ledger: u32,  // ledger sequence the supply changed in
+    pub supply: i128, // outstanding supply after the change
+}
+
+/// The utilization band a stablecoin's supply is automatically adjusted to. The supply is only
+/// adjusted once the utilization leaves the band, and then towards the target utilization.
+#[derive(Clone)]
+#[contracttype]
+pub struct AutoSupplyConfig {
+    pub min_util: i128,    // utilization below which the supply is decreased, in 7 decimals
+    pub max_util: i128,    // utilization above which the supply is increased, in 7 decimals
+    pub target_util: i128, // utilization the supply is adjusted towards, in 7 decimals
+    pub max_step: i128,    // maximum amount the supply is adjusted by in one adjustment
+    pub interval: u32,     // minimum number of ledgers between adjustments
+}
+
+/// A flash loan fee discount granted to keepers with enough cumulative volume or staked balance
+#[derive(Clone)]
+#[contracttype]
+pub struct FeeTier {
+    pub min_volume: i128,  // cumulative flash loan volume that qualifies for the tier
+    pub min_stake: i128,   // staked governance token balance that qualifies for the tier
+    pub discount_bps: u32, // discount on the flash loan fee, in basis points
+}
+
+/// The flash loan fee tiers of keepers. A keeper gets the largest discount of the tiers it qualifies for.
+#[derive(Clone)]
+#[contracttype]
+pub struct FeeTierConfig {
+    pub stake_token: Option<Address>, // governance token whose balance qualifies for the stake thresholds
+    pub tiers: Vec<FeeTier>,          // fee tiers
+}
+
+/// A withdrawal from a blend pool queued until the pool has the liquidity to serve it
+#[derive(Clone)]
+#[contracttype]
+pub struct Withdrawal {
+    pub asset: Address,      // asset withdrawn
+    pub blend_pool: Address, // blend pool the asset is withdrawn from
+    pub recipient: Address,  // address receiving the asset, or the treasury if it is burnt
+    pub amount: i128,        // amount withdrawn
+    pub burn: bool,          // whether the asset is burnt on withdrawal to decrease the supply
+    pub position: u32,       // position in the asset's withdrawal queue
+}
+
+/// The FIFO queue of withdrawals of an asset
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct WithdrawalQueue {
+    pub head: u32,    // position of the next withdrawal to claim
+    pub tail: u32,    // position assigned to the next queued withdrawal
+    pub queued: i128, // amount of the asset queued and not yet claimed
+}
+
+/// Stablecoins left unbacked by liquidations or shutdowns, in the stablecoin
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct BadDebt {
+    pub outstanding: i128, // bad debt not yet covered or written off
+    pub covered: i128,     // bad debt covered by burning surplus or insurance fund payouts
+    pub written_off: i128, // bad debt written off by governance and socialized across holders
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct InsuranceConfig {
+    pub fund: Address,  // insurance fund covering bad debt left by liquidations
+    pub token: Address, // token held by the fund, fees in other tokens are not shared with it
+    pub split_bps: u32, // share of the fees retained by the treasury sent to the fund, in basis points
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopConfig {
+    pub backstop: Address,   // blend backstop of the treasury's pools
+    pub allocation_bps: u32, // share of the treasury's backstop tokens that can be deposited, in basis points
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct StabilityFee {
+    pub rate: i128,       // fee accrued per second on the outstanding supply, in 18 decimals
+    pub index: i128,      // accumulated fee index since the fee was set, in 18 decimals
+    pub last_update: u64, // timestamp the fee was last accrued at
+    pub accrued: i128,    // total fees accrued, in the token
+    pub collected: i128,  // fees collected into the surplus buffer, in the token
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FlashMintConfig {
+    pub cap: i128,    // maximum amount of a single flash mint
+    pub fee_bps: u32, // fee charged on the minted amount, in basis points
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct CollateralConfig {
+    pub oracle_feed: Asset,     // asset the collateral is priced as by the treasury's oracle
+    pub ltv: u32,               // maximum debt against the collateral's value, in basis points
+    pub liquidation_bonus: u32, // discount on the collateral's value paid to liquidators, in basis points
+    pub cap: i128,              // maximum amount of the collateral paid out by a single redemption
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FlashCollateralConfig {
+    pub cap: i128,                   // maximum amount of a single flash loan
+    pub fee_bps: u32,                // fee charged on the loaned amount, in basis points
+    pub blend_pool: Option<Address>, // blend pool to withdraw from when the idle balance is too low
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct PolPosition {
+    pub pair: Address,        // soroswap pair of the stablecoin and the counter asset
+    pub counter: Address,     // counter asset of the pair
+    pub lp_tokens: i128,      // pair LP tokens held by the treasury
+    pub token_seeded: i128,   // stablecoins deposited into the pair
+    pub counter_seeded: i128, // counter assets deposited into the pair
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FeeModel {
+    pub fee_bps: u32,  // default fee charged on the borrowed amount, in basis points
+    pub min_fee: i128, // minimum fee charged on any flash loan
+}
+
+/// The config read by every flash loan, packed under a single key so it is read once per call
+#[derive(Clone)]
+#[contracttype]
+pub struct TreasuryConfig {
+    pub pegkeeper: Address,                        // pegkeeper contract
+    pub fee_model: FeeModel,                       // default flash loan fee
+    pub fee_recipient: Option<FeeRecipientConfig>, // recipient of a share of each fee
+    pub surplus_config: Option<SurplusConfig>,     // recipient of the surplus above a cap
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct MinterConfig {
+    pub token: Address,     // stablecoin the minter can mint
+    pub daily_cap: i128,    // maximum amount the minter can mint in a day
+    pub total_cap: i128,    // maximum amount the minter can have outstanding
+    pub minted: i128,       // amount minted and not yet burnt by the minter
+    pub day: u64,           // day of the last mint, in days since the unix epoch
+    pub minted_today: i128, // amount minted during the day of the last mint
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct SupplyLimit {
+    pub max_change_bps: u32, // maximum supply change within a window, in basis points of the supply at the window start
+    pub window: u32,         // length of a window, in ledgers
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct BreakerConfig {
+    pub threshold_bps: u32, // deviation of the oracle price from $1 that counts as a depeg, in basis points
+    pub delay: u32,         // ledgers a depeg must last before the breaker trips
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct BreakerState {
+    pub price: i128,               // oracle price of the token, in 7 decimals
+    pub depeg_ledger: Option<u32>, // ledger the current depeg started at, if the token is depegged
+    pub tripped: bool,             // whether supply increases and flash mints are disabled
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct SupplyWindow {
+    pub start_ledger: u32, // ledger sequence the window started at
+    pub base_supply: i128, // supply at the window start
+    pub change: i128,      // total supply increased and decreased within the window
+}
+
+/// Lifetime counters summed across every token, each in the units of the token counted
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct TreasuryStats {
+    pub flash_loans: u32,        // flash loans, flash mints and collateral flash loans repaid
+    pub flash_loan_volume: i128, // amount loaned by those flash loans
+    pub fees_earned: i128,       // flash loan and redemption fees earned
+    pub minted: i128,            // stablecoins minted
+    pub burned: i128,            // stablecoins burnt
+    pub liquidations: u32,       // liquidations filled by `liquidate` and `fill_auction`
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct FlashLoanRecord {
+    pub receiver: Address, // flash loan receiver
+    pub token: Address,    // token loaned
+    pub amount: i128,      // amount loaned
+    pub fee: i128,         // fee paid
+    pub ledger: u32,       // ledger sequence of the flash loan
+    pub success: bool,     // whether the loan was repaid, failed loans revert and are not recorded
 }
 
 /// Bump the instance rent for the contract
@@ -20,6 +398,73 @@ pub fn extend_instance(e: &Env) {
         .extend_ttl(LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Bump the rent of a persistent entry, if it exists
+///
+/// ### Arguments
+/// * `key` - The key of the entry
+fn extend_persistent(e: &Env, key: &DataKey) {
+    if e.storage().persistent().has(key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+}
+
+/// Fetch an index of addresses kept in instance storage
+///
+/// ### Arguments
+/// * `index` - The key of the index
+pub fn get_index(e: &Env, index: &DataKey) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(index)
+        .unwrap_or(vec![e])
+}
+
+fn add_to_index(e: &Env, index: &DataKey, address: &Address) {
+    let mut addresses = get_index(e, index);
+    if !addresses.contains(address) {
+        addresses.push_back(address.clone());
+        e.storage().instance().set(index, &addresses);
+    }
+}
+
+fn remove_from_index(e: &Env, index: &DataKey, address: &Address) {
+    let mut addresses = get_index(e, index);
+    if let Some(i) = addresses.first_index_of(address) {
+        addresses.remove(i);
+        e.storage().instance().set(index, &addresses);
+    }
+}
+
+/// Bump the rent of the instance and every persistent entry of the contract that can be enumerated:
+/// per-token surplus, supply, bridged supply, stability fees, protocol-owned liquidity, backstop deposits and supply windows, flash loan receivers, minters, and the flash loan history
+pub fn extend_all(e: &Env) {
+    extend_instance(e);
+    for token in get_index(e, &DataKey::TOKENS).iter() {
+        extend_persistent(e, &DataKey::SURPLUS(token.clone()));
+        extend_persistent(e, &DataKey::SUPPLY(token.clone()));
+        extend_persistent(e, &DataKey::BRIDGED(token.clone()));
+        extend_persistent(e, &DataKey::STABILITYFEE(token.clone()));
+        extend_persistent(e, &DataKey::POL(token.clone()));
+        extend_persistent(e, &DataKey::BACKSTOP(token.clone()));
+        extend_persistent(e, &DataKey::CURRENCY(token.clone()));
+        extend_persistent(e, &DataKey::FXFEED(token.clone()));
+        extend_persistent(e, &DataKey::SUPPLYWINDOW(token));
+    }
+    for receiver in get_index(e, &DataKey::FLASHLOANERS).iter() {
+        extend_persistent(e, &DataKey::FLASHLOANER(receiver));
+    }
+    for minter in get_index(e, &DataKey::MINTERS).iter() {
+        extend_persistent(e, &DataKey::MINTER(minter));
+    }
+    for collateral in get_index(e, &DataKey::COLLATERALS).iter() {
+        extend_persistent(e, &DataKey::COLLATERAL(collateral));
+    }
+    extend_persistent(e, &DataKey::FLASHLOANHISTORY);
+    extend_persistent(e, &DataKey::STATS);
+}
+
 /// Check if the contract has been initialized
 pub fn is_init(e: &Env) -> bool { e.storage().instance().has(&DataKey::ADMIN) }
 
@@ -31,7 +476,7 @@ pub fn get_admin(e: &Env) -> Address {
     e.storage()
         .instance()
         .get(&DataKey::ADMIN)
-        .unwrap_optimized()
+        .unwrap_or_else(|| panic_with_error!(e, TreasuryError::NotInitializedError))
 }
 
 /// Set a new admin
@@ -44,25 +489,33 @@ pub fn set_admin(e: &Env, new_admin: &Address) {
         .set(&DataKey::ADMIN, new_admin);
 }
 
-// Fetch the current admin Address
+/// Fetch the packed treasury config
 ///
 /// ### Panics
-/// If the admin does not exist
-pub fn get_pegkeeper(e: &Env) -> Address {
+/// If the config does not exist
+pub fn get_config(e: &Env) -> TreasuryConfig {
     e.storage()
         .instance()
-        .get(&DataKey::PEGKEEPER)
-        .unwrap_optimized()
+        .get(&DataKey::CONFIG)
+        .unwrap_or_else(|| panic_with_error!(e, TreasuryError::NotInitializedError))
 }
 
-/// Set a new admin
+/// Set the packed treasury config
 ///
 /// ### Arguments
-/// * `new_admin` - The Address for the admin
-pub fn set_pegkeeper(e: &Env, new_pegkeeper: &Address) {
+/// * `config` - The treasury config
+pub fn set_config(e: &Env, config: &TreasuryConfig) {
     e.storage()
         .instance()
-        .set(&DataKey::PEGKEEPER, new_pegkeeper);
+        .set(&DataKey::CONFIG, config);
+}
+
+/// Fetch the pegkeeper Address
+///
+/// ### Panics
+/// If the config does not exist
+pub fn get_pegkeeper(e: &Env) -> Address {
+    get_config(e).pegkeeper
 }
 
 /// Fetch the current treasury Address depending on token address
@@ -73,7 +526,7 @@ pub fn get_blend_pool(e: &Env, token_address: &Address) -> Address {
     e.storage()
         .instance()
         .get(&DataKey::BLENDPOOL(token_address.clone()))
-        .unwrap_optimized()
+        .unwrap_or_else(|| panic_with_error!(e, TreasuryError::PoolNotFoundError))
 }
 
 /// Set the treasury Address depending on token address
@@ -84,6 +537,50 @@ pub fn set_blend_pool(e: &Env, token_address: &Address, blend_pool: &Address) {
     e.storage()
         .instance()
         .set(&DataKey::BLENDPOOL(token_address.clone()), blend_pool);
+    add_to_index(e, &DataKey::TOKENS, token_address);
+}
+
+/// Fetch the blend pools the treasury supplies a token to. Defaults to the token's blend pool.
+///
+/// ### Arguments
+/// * `token_address` - The Address of the token
+///
+/// ### Panics
+/// If the token has no blend pool
+pub fn get_pools(e: &Env, token_address: &Address) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::POOLS(token_address.clone()))
+        .unwrap_or_else(|| vec![e, get_blend_pool(e, token_address)])
+}
+
+/// Set the blend pools the treasury supplies a token to
+///
+/// ### Arguments
+/// * `token_address` - The Address of the token
+/// * `pools` - The Addresses of the blend pools
+pub fn set_pools(e: &Env, token_address: &Address, pools: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::POOLS(token_address.clone()), pools);
+}
+
+/// Fetch the hooks invoked around supply changes and flash loans
+pub fn get_hooks(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::HOOKS)
+        .unwrap_or_else(|| vec![e])
+}
+
+/// Set the hooks invoked around supply changes and flash loans
+///
+/// ### Arguments
+/// * `hooks` - The Addresses of the hook contracts
+pub fn set_hooks(e: &Env, hooks: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::HOOKS, hooks);
 }
 
 /// Fetch the current bridge oracle
@@ -94,7 +591,7 @@ pub fn get_bridge_oracle(e: &Env) -> Address {
     e.storage()
         .instance()
         .get(&DataKey::BRIDGEORACLE)
-        .unwrap_optimized()
+        .unwrap_or_else(|| panic_with_error!(e, TreasuryError::NotInitializedError))
 }
 
 /// Set the bridge oracle
@@ -105,4 +602,1490 @@ pub fn set_bridge_oracle(e: &Env, bridge_oracle: &Address) {
     e.storage()
         .instance()
         .set(&DataKey::BRIDGEORACLE, bridge_oracle);
-}
\ No newline at end of file
+}
+
+/// Fetch the storage layout version
+///
+/// Returns 0 if no version has been recorded
+pub fn get_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::VERSION)
+        .unwrap_or(0)
+}
+
+/// Set the storage layout version
+///
+/// ### Arguments
+/// * `version` - The storage layout version
+pub fn set_version(e: &Env, version: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::VERSION, &version);
+}
+
+/// Fetch the queued upgrade, if any
+pub fn get_queued_upgrade(e: &Env) -> Option<QueuedUpgrade> {
+    e.storage()
+        .instance()
+        .get(&DataKey::UPGRADE)
+}
+
+/// Set the queued upgrade
+///
+/// ### Arguments
+/// * `upgrade` - The queued upgrade
+pub fn set_queued_upgrade(e: &Env, upgrade: &QueuedUpgrade) {
+    e.storage()
+        .instance()
+        .set(&DataKey::UPGRADE, upgrade);
+}
+
+/// Remove the queued upgrade
+pub fn del_queued_upgrade(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::UPGRADE);
+}
+
+/// Fetch the hash of the running contract wasm, if it was recorded by an upgrade
+pub fn get_wasm_hash(e: &Env) -> Option<BytesN<32>> {
+    e.storage()
+        .instance()
+        .get(&DataKey::WASMHASH)
+}
+
+/// Set the hash of the running contract wasm
+///
+/// ### Arguments
+/// * `wasm_hash` - The hash of the contract wasm
+pub fn set_wasm_hash(e: &Env, wasm_hash: &BytesN<32>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::WASMHASH, wasm_hash);
+}
+
+/// Fetch the rollback of the last upgrade, if any
+pub fn get_rollback(e: &Env) -> Option<Rollback> {
+    e.storage()
+        .instance()
+        .get(&DataKey::ROLLBACK)
+}
+
+/// Set the rollback of the last upgrade
+///
+/// ### Arguments
+/// * `rollback` - The rollback
+pub fn set_rollback(e: &Env, rollback: &Rollback) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ROLLBACK, rollback);
+}
+
+/// Remove the rollback of the last upgrade
+pub fn del_rollback(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::ROLLBACK);
+}
+
+/// Fetch the flash loan config for a receiver, if any
+///
+/// ### Arguments
+/// * `receiver` - The Address of the flash loan receiver
+pub fn get_flash_loaner(e: &Env, receiver: &Address) -> Option<FlashLoanConfig> {
+    let key = DataKey::FLASHLOANER(receiver.clone());
+    let config = e.storage().persistent().get::<DataKey, FlashLoanConfig>(&key);
+    if config.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    config
+}
+
+/// Set the flash loan config for a receiver
+///
+/// ### Arguments
+/// * `receiver` - The Address of the flash loan receiver
+/// * `config` - The flash loan config for the receiver
+pub fn set_flash_loaner(e: &Env, receiver: &Address, config: &FlashLoanConfig) {
+    let key = DataKey::FLASHLOANER(receiver.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, FlashLoanConfig>(&key, config);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    add_to_index(e, &DataKey::FLASHLOANERS, receiver);
+}
+
+/// Remove the flash loan config for a receiver
+///
+/// ### Arguments
+/// * `receiver` - The Address of the flash loan receiver
+pub fn del_flash_loaner(e: &Env, receiver: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::FLASHLOANER(receiver.clone()));
+    remove_from_index(e, &DataKey::FLASHLOANERS, receiver);
+}
+
+/// Check if the reentrancy lock is held
+pub fn is_locked(e: &Env) -> bool { e.storage().instance().has(&DataKey::LOCK) }
+
+/// Acquire the reentrancy lock
+pub fn set_lock(e: &Env) {
+    e.storage()
+        .instance()
+        .set(&DataKey::LOCK, &true);
+}
+
+/// Release the reentrancy lock
+pub fn del_lock(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::LOCK);
+}
+
+/// Check if the treasury is paused
+pub fn is_paused(e: &Env) -> bool { e.storage().instance().has(&DataKey::PAUSED) }
+
+/// Pause the treasury
+pub fn set_paused(e: &Env) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PAUSED, &true);
+}
+
+/// Unpause the treasury
+pub fn del_paused(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::PAUSED);
+}
+
+/// Fetch the redemption fee, in basis points
+pub fn get_redemption_fee(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::REDEMPTIONFEE)
+        .unwrap_or(0)
+}
+
+/// Set the redemption fee
+///
+/// ### Arguments
+/// * `fee_bps` - The redemption fee, in basis points
+pub fn set_redemption_fee(e: &Env, fee_bps: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::REDEMPTIONFEE, &fee_bps);
+}
+
+/// Check if an account was granted a role
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `account` - The Address of the account
+pub fn has_role(e: &Env, role: Role, account: &Address) -> bool {
+    let key = DataKey::ROLE(RoleKey { role, account: account.clone() });
+    let granted = e.storage().persistent().has(&key);
+    if granted {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    granted
+}
+
+/// Grant a role to an account
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `account` - The Address of the account
+pub fn set_role(e: &Env, role: Role, account: &Address) {
+    let key = DataKey::ROLE(RoleKey { role, account: account.clone() });
+    e.storage()
+        .persistent()
+        .set::<DataKey, bool>(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Revoke a role from an account
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `account` - The Address of the account
+pub fn del_role(e: &Env, role: Role, account: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::ROLE(RoleKey { role, account: account.clone() }));
+}
+
+/// Fetch the flash loan fee model
+///
+/// ### Panics
+/// If the config does not exist
+pub fn get_fee_model(e: &Env) -> FeeModel {
+    get_config(e).fee_model
+}
+
+/// Set the flash loan fee model
+///
+/// ### Arguments
+/// * `fee_model` - The flash loan fee model
+pub fn set_fee_model(e: &Env, fee_model: &FeeModel) {
+    let mut config = get_config(e);
+    config.fee_model = fee_model.clone();
+    set_config(e, &config);
+}
+
+/// Fetch the oracle config
+///
+/// ### Panics
+/// If the oracle config does not exist
+pub fn get_oracle_config(e: &Env) -> OracleConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::ORACLE)
+        .unwrap_or_else(|| panic_with_error!(e, TreasuryError::NotInitializedError))
+}
+
+/// Fetch the oracle config, if it is set
+pub fn find_oracle_config(e: &Env) -> Option<OracleConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::ORACLE)
+}
+
+/// Set the oracle config
+///
+/// ### Arguments
+/// * `config` - The oracle config
+pub fn set_oracle_config(e: &Env, config: &OracleConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ORACLE, config);
+}
+
+/// Fetch the surplus buffer balance for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_surplus(e: &Env, token: &Address) -> i128 {
+    let key = DataKey::SURPLUS(token.clone());
+    let surplus = e.storage().persistent().get::<DataKey, i128>(&key);
+    match surplus {
+        Some(surplus) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            surplus
+        }
+        None => 0,
+    }
+}
+
+/// Set the surplus buffer balance for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `surplus` - The surplus buffer balance
+pub fn set_surplus(e: &Env, token: &Address, surplus: i128) {
+    let key = DataKey::SURPLUS(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &surplus);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the bad debt of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_bad_debt(e: &Env, token: &Address) -> BadDebt {
+    let key = DataKey::BADDEBT(token.clone());
+    let bad_debt = e.storage().persistent().get::<DataKey, BadDebt>(&key);
+    match bad_debt {
+        Some(bad_debt) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            bad_debt
+        }
+        None => BadDebt::default(),
+    }
+}
+
+/// Set the bad debt of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `bad_debt` - The bad debt
+pub fn set_bad_debt(e: &Env, token: &Address, bad_debt: &BadDebt) {
+    let key = DataKey::BADDEBT(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, BadDebt>(&key, bad_debt);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the outstanding supply of a token minted by the treasury
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_supply(e: &Env, token: &Address) -> i128 {
+    let key = DataKey::SUPPLY(token.clone());
+    let supply = e.storage().persistent().get::<DataKey, i128>(&key);
+    match supply {
+        Some(supply) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            supply
+        }
+        None => 0,
+    }
+}
+
+/// Set the outstanding supply of a token minted by the treasury
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `supply` - The outstanding supply
+pub fn set_supply(e: &Env, token: &Address, supply: i128) {
+    let key = DataKey::SUPPLY(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &supply);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the supply of a token locked in the bridge adapter for other chains
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_bridged(e: &Env, token: &Address) -> i128 {
+    let key = DataKey::BRIDGED(token.clone());
+    let bridged = e.storage().persistent().get::<DataKey, i128>(&key);
+    match bridged {
+        Some(bridged) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            bridged
+        }
+        None => 0,
+    }
+}
+
+/// Set the supply of a token locked in the bridge adapter for other chains
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `bridged` - The supply locked
+pub fn set_bridged(e: &Env, token: &Address, bridged: i128) {
+    let key = DataKey::BRIDGED(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &bridged);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the number of supply snapshots recorded for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_snapshot_count(e: &Env, token: &Address) -> u32 {
+    let key = DataKey::SNAPSHOTCOUNT(token.clone());
+    match e.storage().persistent().get::<DataKey, u32>(&key) {
+        Some(count) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            count
+        }
+        None => 0,
+    }
+}
+
+/// Set the number of supply snapshots recorded for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `count` - The number of snapshots
+pub fn set_snapshot_count(e: &Env, token: &Address, count: u32) {
+    let key = DataKey::SNAPSHOTCOUNT(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, u32>(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch a supply snapshot of a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `index` - The index of the snapshot
+pub fn get_snapshot(e: &Env, token: &Address, index: u32) -> Option<SupplySnapshot> {
+    e.storage()
+        .persistent()
+        .get::<DataKey, SupplySnapshot>(&DataKey::SNAPSHOT(token.clone(), index))
+}
+
+/// Set a supply snapshot of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `index` - The index of the snapshot
+/// * `snapshot` - The snapshot
+pub fn set_snapshot(e: &Env, token: &Address, index: u32, snapshot: &SupplySnapshot) {
+    let key = DataKey::SNAPSHOT(token.clone(), index);
+    e.storage()
+        .persistent()
+        .set::<DataKey, SupplySnapshot>(&key, snapshot);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the fiat currency a token is pegged to, defaulting to USD
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_currency(e: &Env, token: &Address) -> Symbol {
+    let key = DataKey::CURRENCY(token.clone());
+    match e.storage().persistent().get::<DataKey, Symbol>(&key) {
+        Some(currency) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            currency
+        }
+        None => Symbol::new(e, "USD"),
+    }
+}
+
+/// Set the fiat currency a token is pegged to
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `currency` - The currency code, e.g. EUR
+pub fn set_currency(e: &Env, token: &Address, currency: &Symbol) {
+    let key = DataKey::CURRENCY(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, Symbol>(&key, currency);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the FX oracle pricing the currency of a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_fx_feed(e: &Env, token: &Address) -> Option<FxFeed> {
+    let key = DataKey::FXFEED(token.clone());
+    let feed = e.storage().persistent().get::<DataKey, FxFeed>(&key);
+    if feed.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    feed
+}
+
+/// Set the FX oracle pricing the currency of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `feed` - The FX feed
+pub fn set_fx_feed(e: &Env, token: &Address, feed: &FxFeed) {
+    let key = DataKey::FXFEED(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, FxFeed>(&key, feed);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove the FX oracle pricing the currency of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn del_fx_feed(e: &Env, token: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::FXFEED(token.clone()));
+}
+
+/// Fetch the pending admin of a token's stellar asset contract, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_pending_token_admin(e: &Env, token: &Address) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::TOKENADMIN(token.clone()))
+}
+
+/// Set the pending admin of a token's stellar asset contract
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `new_admin` - The Address of the pending admin
+pub fn set_pending_token_admin(e: &Env, token: &Address, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TOKENADMIN(token.clone()), new_admin);
+}
+
+/// Remove the pending admin of a token's stellar asset contract
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn del_pending_token_admin(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::TOKENADMIN(token.clone()));
+}
+
+/// Check if an account is on the deny-list
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+pub fn is_denied(e: &Env, account: &Address) -> bool {
+    let key = DataKey::DENIED(account.clone());
+    let denied = e.storage().persistent().has(&key);
+    if denied {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    denied
+}
+
+/// Add an account to the deny-list
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+pub fn set_denied(e: &Env, account: &Address) {
+    let key = DataKey::DENIED(account.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, bool>(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove an account from the deny-list
+///
+/// ### Arguments
+/// * `account` - The Address of the account
+pub fn del_denied(e: &Env, account: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::DENIED(account.clone()));
+}
+
+/// Fetch the protocol-owned liquidity position of a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_pol_position(e: &Env, token: &Address) -> Option<PolPosition> {
+    let key = DataKey::POL(token.clone());
+    let position = e.storage().persistent().get::<DataKey, PolPosition>(&key);
+    if position.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    position
+}
+
+/// Set the protocol-owned liquidity position of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `position` - The protocol-owned liquidity position
+pub fn set_pol_position(e: &Env, token: &Address, position: &PolPosition) {
+    let key = DataKey::POL(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, PolPosition>(&key, position);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the backstop tokens deposited for a token's blend pool
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_backstop_deposit(e: &Env, token: &Address) -> i128 {
+    let key = DataKey::BACKSTOP(token.clone());
+    let deposit = e.storage().persistent().get::<DataKey, i128>(&key);
+    match deposit {
+        Some(deposit) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            deposit
+        }
+        None => 0,
+    }
+}
+
+/// Set the backstop tokens deposited for a token's blend pool
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `deposit` - The backstop tokens deposited
+pub fn set_backstop_deposit(e: &Env, token: &Address, deposit: i128) {
+    let key = DataKey::BACKSTOP(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &deposit);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the backstop config
+///
+/// ### Panics
+/// If the backstop config is not set
+pub fn get_backstop_config(e: &Env) -> BackstopConfig {
+    e.storage()
+        .instance()
+        .get(&DataKey::BACKSTOPCONFIG)
+        .unwrap_or_else(|| panic_with_error!(e, TreasuryError::NotInitializedError))
+}
+
+/// Set the backstop config
+///
+/// ### Arguments
+/// * `config` - The backstop config
+pub fn set_backstop_config(e: &Env, config: &BackstopConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BACKSTOPCONFIG, config);
+}
+
+/// Fetch the surplus config, if any
+pub fn get_surplus_config(e: &Env) -> Option<SurplusConfig> {
+    get_config(e).surplus_config
+}
+
+/// Set the surplus config
+///
+/// ### Arguments
+/// * `surplus_config` - The surplus config
+pub fn set_surplus_config(e: &Env, surplus_config: &SurplusConfig) {
+    let mut config = get_config(e);
+    config.surplus_config = Some(surplus_config.clone());
+    set_config(e, &config);
+}
+
+/// Fetch the fee recipient config, if any
+pub fn get_fee_recipient(e: &Env) -> Option<FeeRecipientConfig> {
+    get_config(e).fee_recipient
+}
+
+/// Set the fee recipient config
+///
+/// ### Arguments
+/// * `fee_recipient` - The fee recipient config
+pub fn set_fee_recipient(e: &Env, fee_recipient: &FeeRecipientConfig) {
+    let mut config = get_config(e);
+    config.fee_recipient = Some(fee_recipient.clone());
+    set_config(e, &config);
+}
+
+/// Fetch the insurance fund config, if any
+pub fn get_insurance_config(e: &Env) -> Option<InsuranceConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::INSURANCE)
+}
+
+/// Set the insurance fund config
+///
+/// ### Arguments
+/// * `config` - The insurance fund config
+pub fn set_insurance_config(e: &Env, config: &InsuranceConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::INSURANCE, config);
+}
+
+/// Fetch the bridge adapter, if any
+pub fn get_bridge_adapter(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::BRIDGEADAPTER)
+}
+
+/// Set the bridge adapter
+///
+/// ### Arguments
+/// * `adapter` - The Address of the bridge adapter
+pub fn set_bridge_adapter(e: &Env, adapter: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BRIDGEADAPTER, adapter);
+}
+
+/// Migrate the configs stored under their own keys into the packed treasury config. Treasuries
+/// that predate the fee model get a zero fee model.
+///
+/// ### Panics
+/// If the pegkeeper does not exist
+pub fn migrate_config(e: &Env) {
+    let instance = e.storage().instance();
+    let config = TreasuryConfig {
+        pegkeeper: instance
+            .get(&DataKey::PEGKEEPER)
+            .unwrap_or_else(|| panic_with_error!(e, TreasuryError::NotInitializedError)),
+        fee_model: instance.get(&DataKey::FEE).unwrap_or(FeeModel {
+            fee_bps: 0,
+            min_fee: 0,
+        }),
+        fee_recipient: instance.get(&DataKey::FEERECIPIENT),
+        surplus_config: instance.get(&DataKey::SURPLUSCONFIG),
+    };
+    set_config(e, &config);
+    instance.remove(&DataKey::PEGKEEPER);
+    instance.remove(&DataKey::FEE);
+    instance.remove(&DataKey::FEERECIPIENT);
+    instance.remove(&DataKey::SURPLUSCONFIG);
+}
+
+/// Fetch the stability pool for a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_stability_pool(e: &Env, token: &Address) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::STABILITYPOOL(token.clone()))
+}
+
+/// Set the stability pool for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `stability_pool` - The Address of the stability pool
+pub fn set_stability_pool(e: &Env, token: &Address, stability_pool: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::STABILITYPOOL(token.clone()), stability_pool);
+}
+
+/// Fetch the flash mint config for a token, if flash minting is enabled
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_flash_mint_config(e: &Env, token: &Address) -> Option<FlashMintConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::FLASHMINT(token.clone()))
+}
+
+/// Set the flash mint config for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `config` - The flash mint config
+pub fn set_flash_mint_config(e: &Env, token: &Address, config: &FlashMintConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FLASHMINT(token.clone()), config);
+}
+
+/// Fetch the circuit breaker config for a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_breaker_config(e: &Env, token: &Address) -> Option<BreakerConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::BREAKER(token.clone()))
+}
+
+/// Set the circuit breaker config for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `config` - The circuit breaker config
+pub fn set_breaker_config(e: &Env, token: &Address, config: &BreakerConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BREAKER(token.clone()), config);
+}
+
+/// Remove the circuit breaker config for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn del_breaker_config(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::BREAKER(token.clone()));
+}
+
+/// Fetch the ledger the current depeg of a token started at, if it is depegged
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_depeg_ledger(e: &Env, token: &Address) -> Option<u32> {
+    e.storage()
+        .instance()
+        .get(&DataKey::DEPEGLEDGER(token.clone()))
+}
+
+/// Set the ledger the current depeg of a token started at
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `ledger` - The ledger sequence
+pub fn set_depeg_ledger(e: &Env, token: &Address, ledger: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::DEPEGLEDGER(token.clone()), &ledger);
+}
+
+/// Clear the depeg of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn del_depeg_ledger(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::DEPEGLEDGER(token.clone()));
+}
+
+/// Fetch the flash loan config for a collateral, if flash loans of it are enabled
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+pub fn get_flash_collateral_config(e: &Env, collateral: &Address) -> Option<FlashCollateralConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::FLASHCOLLATERAL(collateral.clone()))
+}
+
+/// Set the flash loan config for a collateral
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+/// * `config` - The flash loan config
+pub fn set_flash_collateral_config(e: &Env, collateral: &Address, config: &FlashCollateralConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FLASHCOLLATERAL(collateral.clone()), config);
+}
+
+/// Fetch the risk parameters of a collateral, if it is registered
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+pub fn get_collateral(e: &Env, collateral: &Address) -> Option<CollateralConfig> {
+    let key = DataKey::COLLATERAL(collateral.clone());
+    let config = e.storage().persistent().get::<DataKey, CollateralConfig>(&key);
+    if config.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    config
+}
+
+/// Register a collateral with its risk parameters
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+/// * `config` - The risk parameters of the collateral
+pub fn set_collateral(e: &Env, collateral: &Address, config: &CollateralConfig) {
+    let key = DataKey::COLLATERAL(collateral.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, CollateralConfig>(&key, config);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    add_to_index(e, &DataKey::COLLATERALS, collateral);
+}
+
+/// Remove a collateral from the registry
+///
+/// ### Arguments
+/// * `collateral` - The Address of the collateral
+pub fn del_collateral(e: &Env, collateral: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::COLLATERAL(collateral.clone()));
+    remove_from_index(e, &DataKey::COLLATERALS, collateral);
+}
+
+/// Fetch the savings vault for a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_savings_vault(e: &Env, token: &Address) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::SAVINGS(token.clone()))
+}
+
+/// Set the savings vault for a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `vault` - The Address of the savings vault
+pub fn set_savings_vault(e: &Env, token: &Address, vault: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SAVINGS(token.clone()), vault);
+}
+
+/// Fetch the minter config for a minter, if it is registered
+///
+/// ### Arguments
+/// * `minter` - The Address of the minter
+pub fn get_minter(e: &Env, minter: &Address) -> Option<MinterConfig> {
+    let key = DataKey::MINTER(minter.clone());
+    let config = e.storage().persistent().get::<DataKey, MinterConfig>(&key);
+    if config.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    config
+}
+
+/// Set the minter config for a minter
+///
+/// ### Arguments
+/// * `minter` - The Address of the minter
+/// * `config` - The minter config
+pub fn set_minter(e: &Env, minter: &Address, config: &MinterConfig) {
+    let key = DataKey::MINTER(minter.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, MinterConfig>(&key, config);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    add_to_index(e, &DataKey::MINTERS, minter);
+}
+
+/// Remove a minter
+///
+/// ### Arguments
+/// * `minter` - The Address of the minter
+pub fn del_minter(e: &Env, minter: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::MINTER(minter.clone()));
+    remove_from_index(e, &DataKey::MINTERS, minter);
+}
+
+/// Fetch the most recent flash loans, oldest first
+pub fn get_flash_loan_history(e: &Env) -> Vec<FlashLoanRecord> {
+    let key = DataKey::FLASHLOANHISTORY;
+    match e.storage().persistent().get::<DataKey, Vec<FlashLoanRecord>>(&key) {
+        Some(history) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            history
+        }
+        None => vec![e],
+    }
+}
+
+/// Record a flash loan, dropping the oldest record once the history is full
+///
+/// ### Arguments
+/// * `record` - The flash loan record
+pub fn push_flash_loan_history(e: &Env, record: &FlashLoanRecord) {
+    let key = DataKey::FLASHLOANHISTORY;
+    let mut history = get_flash_loan_history(e);
+    history.push_back(record.clone());
+    while history.len() > FLASH_LOAN_HISTORY_SIZE {
+        history.pop_front();
+    }
+    e.storage()
+        .persistent()
+        .set::<DataKey, Vec<FlashLoanRecord>>(&key, &history);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the lifetime counters of the treasury
+pub fn get_stats(e: &Env) -> TreasuryStats {
+    let key = DataKey::STATS;
+    match e.storage().persistent().get::<DataKey, TreasuryStats>(&key) {
+        Some(stats) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            stats
+        }
+        None => TreasuryStats::default(),
+    }
+}
+
+/// Set the lifetime counters of the treasury
+///
+/// ### Arguments
+/// * `stats` - The lifetime counters
+pub fn set_stats(e: &Env, stats: &TreasuryStats) {
+    let key = DataKey::STATS;
+    e.storage()
+        .persistent()
+        .set::<DataKey, TreasuryStats>(&key, stats);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the supply limit, if any
+pub fn get_supply_limit(e: &Env) -> Option<SupplyLimit> {
+    e.storage()
+        .instance()
+        .get(&DataKey::SUPPLYLIMIT)
+}
+
+/// Set the supply limit
+///
+/// ### Arguments
+/// * `limit` - The supply limit
+pub fn set_supply_limit(e: &Env, limit: &SupplyLimit) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SUPPLYLIMIT, limit);
+}
+
+/// Fetch the current supply change window of a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_supply_window(e: &Env, token: &Address) -> Option<SupplyWindow> {
+    let key = DataKey::SUPPLYWINDOW(token.clone());
+    let window = e.storage().persistent().get::<DataKey, SupplyWindow>(&key);
+    if window.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    window
+}
+
+/// Set the current supply change window of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `window` - The supply change window
+pub fn set_supply_window(e: &Env, token: &Address, window: &SupplyWindow) {
+    let key = DataKey::SUPPLYWINDOW(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, SupplyWindow>(&key, window);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the stability fee accumulator of a token, if a stability fee is set
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_stability_fee(e: &Env, token: &Address) -> Option<StabilityFee> {
+    let key = DataKey::STABILITYFEE(token.clone());
+    let fee = e.storage().persistent().get::<DataKey, StabilityFee>(&key);
+    if fee.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    fee
+}
+
+/// Set the stability fee accumulator of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `fee` - The stability fee accumulator
+pub fn set_stability_fee(e: &Env, token: &Address, fee: &StabilityFee) {
+    let key = DataKey::STABILITYFEE(token.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, StabilityFee>(&key, fee);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the multisig config, if the treasury is in multisig mode
+pub fn get_multisig(e: &Env) -> Option<MultisigConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::MULTISIG)
+}
+
+/// Set the multisig config, putting the treasury in multisig mode
+///
+/// ### Arguments
+/// * `config` - The multisig config
+pub fn set_multisig(e: &Env, config: &MultisigConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::MULTISIG, config);
+}
+
+/// Remove the multisig config, returning the treasury to single admin mode
+pub fn del_multisig(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::MULTISIG);
+}
+
+/// Fetch the next proposal id and increment it
+pub fn next_proposal_id(e: &Env) -> u32 {
+    let id = e.storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::PROPOSALID)
+        .unwrap_or(0);
+    e.storage()
+        .instance()
+        .set::<DataKey, u32>(&DataKey::PROPOSALID, &(id + 1));
+    id
+}
+
+/// Fetch a multisig proposal, if any
+///
+/// ### Arguments
+/// * `id` - The id of the proposal
+pub fn get_proposal(e: &Env, id: u32) -> Option<Proposal> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PROPOSAL(id))
+}
+
+/// Set a multisig proposal
+///
+/// ### Arguments
+/// * `id` - The id of the proposal
+/// * `proposal` - The proposal
+pub fn set_proposal(e: &Env, id: u32, proposal: &Proposal) {
+    let key = DataKey::PROPOSAL(id);
+    e.storage()
+        .persistent()
+        .set::<DataKey, Proposal>(&key, proposal);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the next payment stream id and increment it
+pub fn next_stream_id(e: &Env) -> u32 {
+    let id = e.storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::STREAMID)
+        .unwrap_or(0);
+    e.storage()
+        .instance()
+        .set::<DataKey, u32>(&DataKey::STREAMID, &(id + 1));
+    id
+}
+
+/// Fetch a payment stream, if any
+///
+/// ### Arguments
+/// * `id` - The id of the stream
+pub fn get_stream(e: &Env, id: u32) -> Option<Stream> {
+    let key = DataKey::STREAM(id);
+    let stream = e.storage().persistent().get::<DataKey, Stream>(&key);
+    if stream.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    stream
+}
+
+/// Set a payment stream
+///
+/// ### Arguments
+/// * `id` - The id of the stream
+/// * `stream` - The stream
+pub fn set_stream(e: &Env, id: u32, stream: &Stream) {
+    let key = DataKey::STREAM(id);
+    e.storage()
+        .persistent()
+        .set::<DataKey, Stream>(&key, stream);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove a payment stream
+///
+/// ### Arguments
+/// * `id` - The id of the stream
+pub fn del_stream(e: &Env, id: u32) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::STREAM(id));
+}
+
+/// Fetch the leverage config, if any
+pub fn get_leverage(e: &Env) -> Option<LeverageConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::LEVERAGE)
+}
+
+/// Set the leverage config
+///
+/// ### Arguments
+/// * `config` - The leverage config
+pub fn set_leverage(e: &Env, config: &LeverageConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::LEVERAGE, config);
+}
+
+/// Fetch the next withdrawal id and increment it
+pub fn next_withdrawal_id(e: &Env) -> u32 {
+    let id = e
+        .storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::WITHDRAWALID)
+        .unwrap_or(0);
+    e.storage()
+        .instance()
+        .set::<DataKey, u32>(&DataKey::WITHDRAWALID, &(id + 1));
+    id
+}
+
+/// Fetch a queued withdrawal, if any
+///
+/// ### Arguments
+/// * `id` - The id of the withdrawal
+pub fn get_withdrawal(e: &Env, id: u32) -> Option<Withdrawal> {
+    let key = DataKey::WITHDRAWAL(id);
+    let withdrawal = e.storage().persistent().get::<DataKey, Withdrawal>(&key);
+    if withdrawal.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    withdrawal
+}
+
+/// Set a queued withdrawal
+///
+/// ### Arguments
+/// * `id` - The id of the withdrawal
+/// * `withdrawal` - The withdrawal
+pub fn set_withdrawal(e: &Env, id: u32, withdrawal: &Withdrawal) {
+    let key = DataKey::WITHDRAWAL(id);
+    e.storage()
+        .persistent()
+        .set::<DataKey, Withdrawal>(&key, withdrawal);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove a queued withdrawal
+///
+/// ### Arguments
+/// * `id` - The id of the withdrawal
+pub fn del_withdrawal(e: &Env, id: u32) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::WITHDRAWAL(id));
+}
+
+/// Fetch the withdrawal queue of an asset
+///
+/// ### Arguments
+/// * `asset` - The Address of the asset
+pub fn get_withdrawal_queue(e: &Env, asset: &Address) -> WithdrawalQueue {
+    let key = DataKey::QUEUE(asset.clone());
+    let queue = e.storage().persistent().get::<DataKey, WithdrawalQueue>(&key);
+    match queue {
+        Some(queue) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+            queue
+        }
+        None => WithdrawalQueue::default(),
+    }
+}
+
+/// Set the withdrawal queue of an asset
+///
+/// ### Arguments
+/// * `asset` - The Address of the asset
+/// * `queue` - The withdrawal queue
+pub fn set_withdrawal_queue(e: &Env, asset: &Address, queue: &WithdrawalQueue) {
+    let key = DataKey::QUEUE(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, WithdrawalQueue>(&key, queue);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the reserve index of an asset in a blend pool, if discovered
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the asset
+pub fn get_reserve_index(e: &Env, blend_pool: &Address, asset: &Address) -> Option<u32> {
+    let key = DataKey::RESERVEINDEX(blend_pool.clone(), asset.clone());
+    let index = e.storage().persistent().get::<DataKey, u32>(&key);
+    if index.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+    index
+}
+
+/// Set the reserve index of an asset in a blend pool
+///
+/// ### Arguments
+/// * `blend_pool` - The Address of the blend pool
+/// * `asset` - The Address of the asset
+/// * `index` - The reserve index
+pub fn set_reserve_index(e: &Env, blend_pool: &Address, asset: &Address, index: u32) {
+    let key = DataKey::RESERVEINDEX(blend_pool.clone(), asset.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, u32>(&key, &index);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the automatic supply adjustment config of a token, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_auto_supply(e: &Env, token: &Address) -> Option<AutoSupplyConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::AUTOSUPPLY(token.clone()))
+}
+
+/// Set the automatic supply adjustment config of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `config` - The automatic supply adjustment config
+pub fn set_auto_supply(e: &Env, token: &Address, config: &AutoSupplyConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::AUTOSUPPLY(token.clone()), config);
+}
+
+/// Remove the automatic supply adjustment config of a token
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn del_auto_supply(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::AUTOSUPPLY(token.clone()));
+}
+
+/// Fetch the ledger the supply of a token was last automatically adjusted at, if ever
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+pub fn get_auto_supply_ledger(e: &Env, token: &Address) -> Option<u32> {
+    e.storage()
+        .instance()
+        .get(&DataKey::AUTOSUPPLYLEDGER(token.clone()))
+}
+
+/// Set the ledger the supply of a token was last automatically adjusted at
+///
+/// ### Arguments
+/// * `token` - The Address of the token
+/// * `ledger` - The ledger sequence
+pub fn set_auto_supply_ledger(e: &Env, token: &Address, ledger: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::AUTOSUPPLYLEDGER(token.clone()), &ledger);
+}
+
+/// Fetch the flash loan fee tiers of keepers, if any
+pub fn get_fee_tiers(e: &Env) -> Option<FeeTierConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::FEETIERS)
+}
+
+/// Set the flash loan fee tiers of keepers
+///
+/// ### Arguments
+/// * `config` - The fee tier config
+pub fn set_fee_tiers(e: &Env, config: &FeeTierConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FEETIERS, config);
+}
+
+/// Fetch the cumulative flash loan volume of a keeper
+///
+/// ### Arguments
+/// * `keeper` - The Address of the keeper
+pub fn get_keeper_volume(e: &Env, keeper: &Address) -> i128 {
+    let key = DataKey::KEEPERVOLUME(keeper.clone());
+    extend_persistent(e, &key);
+    e.storage()
+        .persistent()
+        .get::<DataKey, i128>(&key)
+        .unwrap_or(0)
+}
+
+/// Set the cumulative flash loan volume of a keeper
+///
+/// ### Arguments
+/// * `keeper` - The Address of the keeper
+/// * `volume` - The cumulative flash loan volume
+pub fn set_keeper_volume(e: &Env, keeper: &Address, volume: i128) {
+    let key = DataKey::KEEPERVOLUME(keeper.clone());
+    e.storage()
+        .persistent()
+        .set::<DataKey, i128>(&key, &volume);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
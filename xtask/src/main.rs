@@ -0,0 +1,143 @@
+//! Release tooling for the Orbit contracts, run with `cargo xtask <command>`
+//!
+//! Builds optimized contract wasms into `wasm/orbit`, checks them against their size budgets, and
+//! generates the contract spec JSON and TypeScript bindings integrators build clients from. The test
+//! suites import the contracts from `wasm/orbit`, so `cargo xtask test` rebuilds them before testing.
+
+mod spec;
+mod typescript;
+mod wasm;
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Parser, Subcommand};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// A contract released with typed clients
+pub struct Contract {
+    /// The cargo package of the contract
+    pub package: &'static str,
+    /// The maximum size of the optimized wasm, in bytes
+    pub size_budget: u64,
+}
+
+impl Contract {
+    /// The file name of the contract wasm
+    pub fn wasm_name(&self) -> String {
+        format!("{}.wasm", self.package.replace('-', "_"))
+    }
+}
+
+/// The released contracts. The network rejects wasms above 64 KiB.
+pub static CONTRACTS: [Contract; 13] = [
+    Contract { package: "treasury", size_budget: 64 * 1024 },
+    Contract { package: "pegkeeper", size_budget: 32 * 1024 },
+    Contract { package: "ousd-token", size_budget: 16 * 1024 },
+    Contract { package: "insurance-fund", size_budget: 16 * 1024 },
+    Contract { package: "collateral-auction", size_budget: 24 * 1024 },
+    Contract { package: "sentinel", size_budget: 16 * 1024 },
+    Contract { package: "bridge-adapter", size_budget: 16 * 1024 },
+    Contract { package: "stability-pool", size_budget: 24 * 1024 },
+    Contract { package: "psm", size_budget: 16 * 1024 },
+    Contract { package: "cdp", size_budget: 24 * 1024 },
+    Contract { package: "ousd-savings", size_budget: 16 * 1024 },
+    Contract { package: "governance", size_budget: 16 * 1024 },
+    Contract { package: "factory", size_budget: 16 * 1024 },
+];
+
+#[derive(Parser)]
+#[command(name = "xtask", about = "Build, check and generate bindings for the Orbit contracts")]
+struct Cli {
+    /// The contracts to act on, all released contracts if not set
+    #[arg(long = "contract", global = true)]
+    contracts: Vec<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build the contracts for wasm32 and optimize them into the wasm directory
+    Build,
+    /// Check the contract wasms against their size budgets
+    Size,
+    /// Generate the spec JSON and TypeScript bindings of the contract wasms
+    Spec {
+        /// The directory the bindings are written to, one subdirectory per contract
+        #[arg(long, default_value = "bindings")]
+        out_dir: PathBuf,
+    },
+    /// Build the contracts into the wasm directory, then run the workspace tests against them
+    Test,
+    /// Build the contracts, check their size budgets, and generate their bindings
+    Release {
+        /// The directory the bindings are written to, one subdirectory per contract
+        #[arg(long, default_value = "bindings")]
+        out_dir: PathBuf,
+    },
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let root = workspace_root();
+    let contracts = select_contracts(&cli.contracts)?;
+    match cli.command {
+        Command::Build => wasm::build(&root, &contracts),
+        Command::Size => wasm::check_sizes(&root, &contracts),
+        Command::Spec { out_dir } => spec::generate(&root, &contracts, &out_dir),
+        Command::Test => {
+            wasm::build(&root, &contracts)?;
+            test(&root)
+        }
+        Command::Release { out_dir } => {
+            wasm::build(&root, &contracts)?;
+            wasm::check_sizes(&root, &contracts)?;
+            spec::generate(&root, &contracts, &out_dir)
+        }
+    }
+}
+
+/// Run the workspace tests
+///
+/// ### Arguments
+/// * `root` - The root of the workspace
+fn test(root: &Path) -> Result<()> {
+    let status = process::Command::new(env!("CARGO")).current_dir(root).args(["test", "--workspace"]).status()?;
+    if !status.success() {
+        return Err("cargo test failed".into());
+    }
+    Ok(())
+}
+
+/// The root of the workspace, the parent of the xtask package
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Select the released contracts by package name
+///
+/// ### Arguments
+/// * `names` - The package names, or empty for every released contract
+fn select_contracts(names: &[String]) -> Result<Vec<&'static Contract>> {
+    if names.is_empty() {
+        return Ok(CONTRACTS.iter().collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            CONTRACTS
+                .iter()
+                .find(|contract| contract.package == name.as_str())
+                .ok_or_else(|| format!("unknown contract {name}").into())
+        })
+        .collect()
+}
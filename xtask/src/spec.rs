@@ -0,0 +1,87 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use stellar_xdr::curr::{Limited, Limits, ReadXdr, ScSpecEntry};
+
+use crate::{typescript, wasm, Contract, Result};
+
+/// The wasm custom section the soroban sdk writes the contract spec to
+const SPEC_SECTION: &str = "contractspecv0";
+
+/// Generate the spec JSON and TypeScript bindings of the contract wasms, writing
+/// `<out_dir>/<package>/spec.json` and `<out_dir>/<package>/index.ts`
+///
+/// ### Arguments
+/// * `root` - The root of the workspace
+/// * `contracts` - The contracts to generate bindings for
+/// * `out_dir` - The directory the bindings are written to
+pub fn generate(root: &Path, contracts: &[&Contract], out_dir: &Path) -> Result<()> {
+    for contract in contracts {
+        let path = wasm::wasm_dir(root).join(contract.wasm_name());
+        let code = fs::read(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let entries = read_spec(&code).map_err(|err| format!("{}: {err}", path.display()))?;
+
+        let dir = out_dir.join(contract.package);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("spec.json"), serde_json::to_string_pretty(&entries)?)?;
+        fs::write(dir.join("index.ts"), typescript::generate(&entries)?)?;
+        println!("{}: {} spec entries", dir.display(), entries.len());
+    }
+    Ok(())
+}
+
+/// Read the contract spec entries from the custom section of a contract wasm
+///
+/// ### Arguments
+/// * `code` - The contract wasm
+pub fn read_spec(code: &[u8]) -> Result<Vec<ScSpecEntry>> {
+    let section = custom_section(code, SPEC_SECTION)?.ok_or("the wasm has no contract spec")?;
+    let mut reader = Limited::new(Cursor::new(section), Limits::none());
+    let entries = ScSpecEntry::read_xdr_iter(&mut reader).collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(entries)
+}
+
+/// Find a custom section of a wasm module by name
+///
+/// Returns the payload of the section, or None if the module has no such section
+///
+/// ### Arguments
+/// * `code` - The wasm module
+/// * `name` - The name of the custom section
+fn custom_section<'a>(code: &'a [u8], name: &str) -> Result<Option<&'a [u8]>> {
+    if code.len() < 8 || &code[..4] != b"\0asm" {
+        return Err("not a wasm module".into());
+    }
+    let mut offset = 8;
+    while offset < code.len() {
+        let id = code[offset];
+        offset += 1;
+        let size = read_leb128(code, &mut offset)?;
+        let end = offset.checked_add(size).filter(|end| *end <= code.len()).ok_or("truncated wasm section")?;
+        if id == 0 {
+            let mut payload = offset;
+            let name_len = read_leb128(code, &mut payload)?;
+            let name_end = payload.checked_add(name_len).filter(|name_end| *name_end <= end).ok_or("truncated wasm section")?;
+            if &code[payload..name_end] == name.as_bytes() {
+                return Ok(Some(&code[name_end..end]));
+            }
+        }
+        offset = end;
+    }
+    Ok(None)
+}
+
+/// Read an unsigned LEB128 integer, advancing the offset past it
+fn read_leb128(code: &[u8], offset: &mut usize) -> Result<usize> {
+    let mut value: usize = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *code.get(*offset).ok_or("truncated wasm module")?;
+        *offset += 1;
+        value |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("invalid LEB128 integer".into())
+}
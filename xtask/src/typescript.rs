@@ -0,0 +1,161 @@
+use std::fmt::Write as _;
+
+use stellar_xdr::curr::{Limits, ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0, WriteXdr};
+
+use crate::Result;
+
+/// Generate TypeScript bindings for a contract spec
+///
+/// The bindings declare the contract's types and a typed `Client` built on the stellar sdk's
+/// contract client, which embeds the spec to encode arguments and decode results.
+///
+/// ### Arguments
+/// * `entries` - The contract spec entries
+pub fn generate(entries: &[ScSpecEntry]) -> Result<String> {
+    let mut ts = String::new();
+    writeln!(ts, "// Generated by `cargo xtask spec`, do not edit")?;
+    writeln!(ts, "import {{ Buffer }} from \"buffer\";")?;
+    writeln!(
+        ts,
+        "import {{ AssembledTransaction, Client as ContractClient, ClientOptions as ContractClientOptions, MethodOptions, Spec as ContractSpec }} from \"@stellar/stellar-sdk/contract\";"
+    )?;
+    writeln!(ts)?;
+
+    for entry in entries {
+        match entry {
+            ScSpecEntry::UdtStructV0(udt) => {
+                doc(&mut ts, "", &udt.doc.to_utf8_string_lossy())?;
+                let name = udt.name.to_utf8_string_lossy();
+                // tuple structs have numbered fields
+                if udt.fields.iter().all(|field| field.name.to_utf8_string_lossy().parse::<u32>().is_ok()) {
+                    let types: Vec<String> = udt.fields.iter().map(|field| type_def(&field.type_)).collect();
+                    writeln!(ts, "export type {name} = readonly [{}];", types.join(", "))?;
+                } else {
+                    writeln!(ts, "export interface {name} {{")?;
+                    for field in udt.fields.iter() {
+                        doc(&mut ts, "  ", &field.doc.to_utf8_string_lossy())?;
+                        writeln!(ts, "  {}: {};", field.name.to_utf8_string_lossy(), type_def(&field.type_))?;
+                    }
+                    writeln!(ts, "}}")?;
+                }
+            }
+            ScSpecEntry::UdtUnionV0(udt) => {
+                doc(&mut ts, "", &udt.doc.to_utf8_string_lossy())?;
+                let cases: Vec<String> = udt
+                    .cases
+                    .iter()
+                    .map(|case| match case {
+                        ScSpecUdtUnionCaseV0::VoidV0(case) => {
+                            format!("{{ tag: \"{}\"; values: void }}", case.name.to_utf8_string_lossy())
+                        }
+                        ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                            let types: Vec<String> = case.type_.iter().map(type_def).collect();
+                            format!("{{ tag: \"{}\"; values: readonly [{}] }}", case.name.to_utf8_string_lossy(), types.join(", "))
+                        }
+                    })
+                    .collect();
+                writeln!(ts, "export type {} = {};", udt.name.to_utf8_string_lossy(), cases.join(" | "))?;
+            }
+            ScSpecEntry::UdtEnumV0(udt) => {
+                doc(&mut ts, "", &udt.doc.to_utf8_string_lossy())?;
+                writeln!(ts, "export enum {} {{", udt.name.to_utf8_string_lossy())?;
+                for case in udt.cases.iter() {
+                    doc(&mut ts, "  ", &case.doc.to_utf8_string_lossy())?;
+                    writeln!(ts, "  {} = {},", case.name.to_utf8_string_lossy(), case.value)?;
+                }
+                writeln!(ts, "}}")?;
+            }
+            ScSpecEntry::UdtErrorEnumV0(udt) => {
+                doc(&mut ts, "", &udt.doc.to_utf8_string_lossy())?;
+                writeln!(ts, "export const {} = {{", udt.name.to_utf8_string_lossy())?;
+                for case in udt.cases.iter() {
+                    let message = case.doc.to_utf8_string_lossy();
+                    let message = if message.is_empty() { case.name.to_utf8_string_lossy() } else { message };
+                    writeln!(ts, "  {}: {{ message: {:?} }},", case.value, message)?;
+                }
+                writeln!(ts, "}};")?;
+            }
+            ScSpecEntry::FunctionV0(_) => continue,
+        }
+        writeln!(ts)?;
+    }
+
+    writeln!(ts, "export interface Client {{")?;
+    for entry in entries {
+        if let ScSpecEntry::FunctionV0(function) = entry {
+            let name = function.name.0.to_utf8_string_lossy();
+            // the constructor-like entrypoints are not exposed on the client
+            if name.starts_with("__") {
+                continue;
+            }
+            doc(&mut ts, "  ", &function.doc.to_utf8_string_lossy())?;
+            let output = function.outputs.first().map(type_def).unwrap_or_else(|| "void".to_string());
+            if function.inputs.is_empty() {
+                writeln!(ts, "  {name}(options?: MethodOptions): Promise<AssembledTransaction<{output}>>;")?;
+            } else {
+                let inputs: Vec<String> = function
+                    .inputs
+                    .iter()
+                    .map(|input| format!("{}: {}", input.name.to_utf8_string_lossy(), type_def(&input.type_)))
+                    .collect();
+                writeln!(ts, "  {name}(args: {{ {} }}, options?: MethodOptions): Promise<AssembledTransaction<{output}>>;", inputs.join("; "))?;
+            }
+        }
+    }
+    writeln!(ts, "}}")?;
+    writeln!(ts)?;
+
+    writeln!(ts, "export class Client extends ContractClient {{")?;
+    writeln!(ts, "  constructor(public readonly options: ContractClientOptions) {{")?;
+    writeln!(ts, "    super(new ContractSpec([")?;
+    for entry in entries {
+        writeln!(ts, "      \"{}\",", entry.to_xdr_base64(Limits::none())?)?;
+    }
+    writeln!(ts, "    ]), options);")?;
+    writeln!(ts, "  }}")?;
+    writeln!(ts, "}}")?;
+    Ok(ts)
+}
+
+/// Write a doc comment, if the doc is not empty
+fn doc(ts: &mut String, indent: &str, doc: &str) -> Result<()> {
+    if doc.is_empty() {
+        return Ok(());
+    }
+    writeln!(ts, "{indent}/**")?;
+    for line in doc.lines() {
+        writeln!(ts, "{indent} * {}", line.replace("*/", "*\\/"))?;
+    }
+    writeln!(ts, "{indent} */")?;
+    Ok(())
+}
+
+/// The TypeScript type of a contract spec type, following the stellar sdk's native conversions
+fn type_def(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Val => "any".to_string(),
+        ScSpecTypeDef::Bool => "boolean".to_string(),
+        ScSpecTypeDef::Void => "void".to_string(),
+        ScSpecTypeDef::Error => "Error".to_string(),
+        ScSpecTypeDef::U32 | ScSpecTypeDef::I32 => "number".to_string(),
+        ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::Timepoint
+        | ScSpecTypeDef::Duration
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128
+        | ScSpecTypeDef::U256
+        | ScSpecTypeDef::I256 => "bigint".to_string(),
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => "Buffer".to_string(),
+        ScSpecTypeDef::String | ScSpecTypeDef::Symbol | ScSpecTypeDef::Address => "string".to_string(),
+        ScSpecTypeDef::Option(option) => format!("{} | undefined", type_def(&option.value_type)),
+        ScSpecTypeDef::Result(result) => type_def(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("Array<{}>", type_def(&vec.element_type)),
+        ScSpecTypeDef::Map(map) => format!("Map<{}, {}>", type_def(&map.key_type), type_def(&map.value_type)),
+        ScSpecTypeDef::Tuple(tuple) => {
+            let types: Vec<String> = tuple.value_types.iter().map(type_def).collect();
+            format!("readonly [{}]", types.join(", "))
+        }
+        ScSpecTypeDef::Udt(udt) => udt.name.to_utf8_string_lossy(),
+    }
+}
@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{Contract, Result};
+
+/// The directory the released wasms are written to, which the test suites import them from
+pub fn wasm_dir(root: &Path) -> PathBuf {
+    root.join("wasm").join("orbit")
+}
+
+/// Build the contracts for wasm32 with the release profile and optimize them into the wasm directory
+///
+/// The wasms are optimized with `wasm-opt` if it is installed, and copied unoptimized otherwise.
+///
+/// ### Arguments
+/// * `root` - The root of the workspace
+/// * `contracts` - The contracts to build
+pub fn build(root: &Path, contracts: &[&Contract]) -> Result<()> {
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.current_dir(root).args(["build", "--release", "--target", "wasm32-unknown-unknown"]);
+    for contract in contracts {
+        cargo.args(["--package", contract.package]);
+    }
+    if !cargo.status()?.success() {
+        return Err("cargo build failed".into());
+    }
+
+    let target_dir = root.join("target").join("wasm32-unknown-unknown").join("release");
+    let out_dir = wasm_dir(root);
+    fs::create_dir_all(&out_dir)?;
+    for contract in contracts {
+        let input = target_dir.join(contract.wasm_name());
+        let output = out_dir.join(contract.wasm_name());
+        if !optimize(&input, &output)? {
+            println!("wasm-opt is not installed, copying {} unoptimized", contract.wasm_name());
+            fs::copy(&input, &output)?;
+        }
+        println!("{}: {} bytes", output.display(), fs::metadata(&output)?.len());
+    }
+    Ok(())
+}
+
+/// Optimize a wasm for size with `wasm-opt`
+///
+/// Returns false if `wasm-opt` is not installed
+///
+/// ### Arguments
+/// * `input` - The path of the wasm built by cargo
+/// * `output` - The path the optimized wasm is written to
+fn optimize(input: &Path, output: &Path) -> Result<bool> {
+    let status = Command::new("wasm-opt")
+        .arg(input)
+        .args(["-Oz", "--strip-debug", "--strip-producers", "-o"])
+        .arg(output)
+        .status();
+    match status {
+        Ok(status) if status.success() => Ok(true),
+        Ok(_) => Err(format!("wasm-opt failed on {}", input.display()).into()),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Check the contract wasms against their size budgets, printing the size of each
+///
+/// ### Arguments
+/// * `root` - The root of the workspace
+/// * `contracts` - The contracts to check
+///
+/// ### Errors
+/// If a wasm is missing or above its size budget
+pub fn check_sizes(root: &Path, contracts: &[&Contract]) -> Result<()> {
+    let mut over_budget = Vec::new();
+    for contract in contracts {
+        let path = wasm_dir(root).join(contract.wasm_name());
+        let size = fs::metadata(&path).map_err(|err| format!("{}: {err}", path.display()))?.len();
+        let status = if size > contract.size_budget { "OVER BUDGET" } else { "ok" };
+        println!("{:<18} {:>7} / {:>7} bytes  {status}", contract.package, size, contract.size_budget);
+        if size > contract.size_budget {
+            over_budget.push(contract.package);
+        }
+    }
+    if !over_budget.is_empty() {
+        return Err(format!("wasm size budget exceeded by {}", over_budget.join(", ")).into());
+    }
+    Ok(())
+}